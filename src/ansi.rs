@@ -0,0 +1,192 @@
+//! Rendered frames as ANSI escape sequences.
+//!
+//! Only the cells that changed since the previous frame are written, as
+//! cursor moves and truecolor escape sequences. Frames from elsewhere are
+//! [`sanitize`]d down to those before they reach the terminal.
+
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use std::fmt::Write as _;
+
+pub struct FrameEncoder {
+    previous: Buffer,
+}
+
+impl FrameEncoder {
+    /// An encoder for a screen that starts out blank.
+    pub fn new(width: u16, height: u16) -> Self {
+        FrameEncoder {
+            previous: Buffer::empty(Rect::new(0, 0, width, height)),
+        }
+    }
+
+    /// Escape sequences turning the last frame into `buffer`, or an empty
+    /// string if nothing changed.
+    pub fn encode(&mut self, buffer: &Buffer) -> String {
+        let mut data = String::new();
+        if buffer.area != self.previous.area {
+            // The screen was resized; start over from a blank one
+            data.push_str("\x1b[0m\x1b[2J");
+            self.previous = Buffer::empty(buffer.area);
+        }
+        let mut cursor = None;
+        let mut style = None;
+        for (x, y, cell) in self.previous.diff(buffer) {
+            if cursor != Some((x, y)) {
+                let _ = write!(data, "\x1b[{};{}H", y + 1, x + 1);
+            }
+            if style != Some((cell.fg, cell.bg)) {
+                push_style(&mut data, cell);
+                style = Some((cell.fg, cell.bg));
+            }
+            data.push_str(cell.symbol());
+            cursor = Some((x + 1, y));
+        }
+        self.previous = buffer.clone();
+        if !data.is_empty() {
+            data.push_str("\x1b[0m");
+        }
+        data
+    }
+}
+
+fn push_style(data: &mut String, cell: &Cell) {
+    data.push_str("\x1b[0");
+    push_color(data, cell.fg, 38);
+    push_color(data, cell.bg, 48);
+    data.push('m');
+}
+
+/// Append the SGR parameters selecting `color`; `base` is 38 for the
+/// foreground and 48 for the background.
+fn push_color(data: &mut String, color: Color, base: u8) {
+    let _ = match color {
+        Color::Rgb(r, g, b) => write!(data, ";{};2;{};{};{}", base, r, g, b),
+        Color::Indexed(i) => write!(data, ";{};5;{}", base, i),
+        named => match basic_index(named) {
+            Some(i) => write!(data, ";{};5;{}", base, i),
+            None => Ok(()),
+        },
+    };
+}
+
+/// Palette index of one of the 16 basic named colors.
+fn basic_index(color: Color) -> Option<u8> {
+    let index = match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        _ => return None,
+    };
+    Some(index)
+}
+
+// The final bytes of the control sequences frames are made of: cursor
+// moves, colors and clearing the screen
+#[cfg(feature = "network")]
+const ALLOWED_CSI: [char; 3] = ['H', 'm', 'J'];
+
+/// `data` with everything but printable text and the control sequences
+/// [`FrameEncoder`] writes taken out, so frames from an untrusted host can't
+/// set the clipboard or title, ask the terminal to answer back or change
+/// its modes.
+#[cfg(feature = "network")]
+pub fn sanitize(data: &str) -> String {
+    let mut clean = String::with_capacity(data.len());
+    let mut chars = data.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            if !c.is_control() {
+                clean.push(c);
+            }
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                let mut sequence = String::from("\x1b[");
+                let mut end = None;
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        end = Some(c);
+                        break;
+                    }
+                    sequence.push(c);
+                }
+                // Plain numeric parameters only, ending as the encoder's do
+                let plain = sequence[2..].chars().all(|c| c.is_ascii_digit() || c == ';');
+                if let Some(end) = end.filter(|end| plain && ALLOWED_CSI.contains(end)) {
+                    clean.push_str(&sequence);
+                    clean.push(end);
+                }
+            }
+            // Strings, like OSC's, run to a bell or a string terminator
+            Some(']' | 'P' | 'X' | '^' | '_') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            }
+            // Any other escape is two characters long
+            _ => {}
+        }
+    }
+    clean
+}
+
+#[cfg(all(test, feature = "network"))]
+mod tests {
+    use super::*;
+    use ratatui::style::Style;
+
+    #[test]
+    fn encoded_frames_pass_through_whole() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buffer.set_string(1, 0, "*·", Style::default().fg(Color::Rgb(255, 200, 0)));
+        buffer.set_string(3, 1, "◆", Style::default().fg(Color::Indexed(9)).bg(Color::Rgb(0, 0, 20)));
+        let data = FrameEncoder::new(4, 4).encode(&buffer);
+        assert!(data.contains("\x1b[2J"));
+        assert_eq!(sanitize(&data), data);
+    }
+
+    #[test]
+    fn strings_are_stripped_to_their_terminator() {
+        // Setting the clipboard, ended by a bell, and the title by ST
+        assert_eq!(sanitize("a\x1b]52;c;aGVsbG8=\x07b"), "ab");
+        assert_eq!(sanitize("a\x1b]0;owned\x1b\\b"), "ab");
+        // A terminfo query, and the other string kinds
+        assert_eq!(sanitize("\x1bP+q544e\x1b\\ok"), "ok");
+        assert_eq!(sanitize("\x1bXsos\x1b\\\x1b^pm\x07\x1b_apc\x1b\\ok"), "ok");
+    }
+
+    #[test]
+    fn other_escapes_and_controls_are_dropped() {
+        // A full reset, a bare ESC and one cut off at the end
+        assert_eq!(sanitize("a\x1bcb\x1b"), "ab");
+        // Mode changes, a report request and a window op aren't allowed
+        assert_eq!(sanitize("\x1b[?1049h\x1b[6n\x1b[8;1;1t\x1b[2;3H"), "\x1b[2;3H");
+        // Nor a color whose parameters aren't plain numbers
+        assert_eq!(sanitize("\x1b[38:2:1:2:3m*"), "*");
+        assert_eq!(sanitize("a\x07b\x08c\rd\x00e\x7f\u{9b}6n"), "abcde6n");
+    }
+
+    #[test]
+    fn cursor_moves_colors_and_clears_are_kept() {
+        let data = "\x1b[0m\x1b[2J\x1b[3;5Hab\x1b[0;38;2;1;2;3;48;5;17m*\x1b[0m";
+        assert_eq!(sanitize(data), data);
+    }
+}
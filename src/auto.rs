@@ -0,0 +1,239 @@
+//! The `--auto` scene: effects picked to suit the real season and weather
+//! outside, looked at again every hour, with rain, snow and wind following
+//! the weather as it's reported.
+
+use crate::cli::SkyOptions;
+use crate::simulation::density::DensityMap;
+use crate::simulation::environment::Sample;
+#[cfg(feature = "weather")]
+use crate::weather::{self, Conditions, Report};
+use chrono::{Datelike, Local};
+#[cfg(feature = "weather")]
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+#[cfg(feature = "weather")]
+use std::thread;
+use std::time::{Duration, Instant};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// The season in `month` (1 to 12), turned around south of the equator.
+    pub fn of(month: u32, latitude: Option<f32>) -> Season {
+        let north = match month {
+            3..=5 => Season::Spring,
+            6..=8 => Season::Summer,
+            9..=11 => Season::Autumn,
+            _ => Season::Winter,
+        };
+        if latitude.is_some_and(|lat| lat < 0.0) {
+            match north {
+                Season::Spring => Season::Autumn,
+                Season::Summer => Season::Winter,
+                Season::Autumn => Season::Spring,
+                Season::Winter => Season::Summer,
+            }
+        } else {
+            north
+        }
+    }
+
+    pub fn now(latitude: Option<f32>) -> Season {
+        Season::of(Local::now().month(), latitude)
+    }
+}
+
+/// The weather outside, as far as the sky's effects go.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "weather"), allow(dead_code))]
+pub enum Outside {
+    Clear,
+    /// Cloud, fog or snow, none of it falling as rain
+    Overcast,
+    Rain,
+}
+
+#[cfg(feature = "weather")]
+impl From<Conditions> for Outside {
+    fn from(conditions: Conditions) -> Self {
+        match conditions {
+            Conditions::Clear => Outside::Clear,
+            Conditions::PartlyCloudy | Conditions::Cloudy | Conditions::Fog | Conditions::Snow => {
+                Outside::Overcast
+            }
+            Conditions::Rain | Conditions::Storm => Outside::Rain,
+        }
+    }
+}
+
+// Millimetres of rain an hour that count as a downpour
+#[cfg(feature = "weather")]
+const DOWNPOUR_MM: f32 = 8.0;
+// Least intensity while rain or snow is reported, since light drizzle
+// often comes back as 0.0mm
+#[cfg(feature = "weather")]
+const DRIZZLE: f32 = 0.15;
+
+/// What `report` means for the sky's rain, snow and wind, seen from
+/// `latitude`.
+#[cfg(feature = "weather")]
+fn sample(report: &Report, latitude: Option<f32>) -> Sample {
+    let falling = matches!(
+        report.conditions,
+        Conditions::Rain | Conditions::Snow | Conditions::Storm
+    );
+    let mut precipitation = (report.precipitation / DOWNPOUR_MM).clamp(0.0, 1.0);
+    if falling {
+        precipitation = precipitation.max(DRIZZLE);
+    }
+    // The sky faces the equator, so east is on the left north of it and on
+    // the right south of it
+    let east = report.wind * report.wind_heading.to_radians().sin();
+    let facing_south = latitude.map_or(true, |lat| lat >= 0.0);
+    Sample {
+        precipitation,
+        snow: report.conditions == Conditions::Snow,
+        wind: if facing_south { -east } else { east },
+    }
+}
+
+/// Look up the weather outside. Blocks for up to a few seconds, and gives
+/// `None` when offline.
+#[cfg(feature = "weather")]
+pub fn outside_now() -> Option<Outside> {
+    weather::fetch(None).map(|report| Outside::from(report.conditions))
+}
+
+/// Without weather lookups, the weather outside is never known.
+#[cfg(not(feature = "weather"))]
+pub fn outside_now() -> Option<Outside> {
+    None
+}
+
+/// `options` with the effects suited to `season` and the weather `outside`,
+/// if known. Options given on the command line are left alone.
+pub fn choose(options: &SkyOptions, season: Season, outside: Option<Outside>) -> SkyOptions {
+    let free = |flag: &str| !options.explicit.iter().any(|given| given == flag);
+    let mut chosen = options.clone();
+
+    if free("--leaves") {
+        chosen.leaves = season == Season::Autumn;
+    }
+    if let Some(outside) = outside {
+        if free("--showers") {
+            chosen.showers = outside == Outside::Rain;
+        }
+        if free("--clouds") {
+            chosen.clouds = outside != Outside::Clear;
+        }
+    }
+    if free("--density") {
+        chosen.density = match (season, outside) {
+            // Haze and low cloud swallow most stars near the horizon
+            (_, Some(Outside::Overcast | Outside::Rain)) => DensityMap {
+                band: 0.5,
+                horizon: 0.9,
+                poles: 0.2,
+            },
+            // The bright core of the Milky Way is up on summer nights
+            (Season::Summer, _) => DensityMap {
+                band: 2.0,
+                horizon: 0.6,
+                poles: 0.6,
+            },
+            // Dry winter air keeps stars crisp down to the horizon, though
+            // the Milky Way is fainter this side of the sky
+            (Season::Winter, _) => DensityMap {
+                band: 1.0,
+                horizon: 0.3,
+                poles: 0.4,
+            },
+            _ => DensityMap {
+                band: 1.5,
+                horizon: 0.6,
+                poles: 0.5,
+            },
+        };
+    }
+    chosen
+}
+
+/// Keeps a live sky's effects matched to the conditions outside.
+pub struct AutoScene {
+    next_check: Instant,
+    /// Weather being looked up in the background, so the sky never waits
+    #[cfg(feature = "weather")]
+    pending: Option<Receiver<Option<Report>>>,
+    outside: Option<Outside>,
+    /// The last weather report that came through
+    #[cfg(feature = "weather")]
+    report: Option<Report>,
+}
+
+impl AutoScene {
+    /// Checks straight away, then hourly.
+    pub fn new() -> Self {
+        AutoScene {
+            next_check: Instant::now(),
+            #[cfg(feature = "weather")]
+            pending: None,
+            outside: None,
+            #[cfg(feature = "weather")]
+            report: None,
+        }
+    }
+
+    /// New options for the sky, whenever a check is due or the weather
+    /// arrives.
+    pub fn poll(&mut self, options: &SkyOptions) -> Option<SkyOptions> {
+        let mut due = false;
+        if Instant::now() >= self.next_check {
+            self.next_check = Instant::now() + CHECK_INTERVAL;
+            due = true;
+            #[cfg(feature = "weather")]
+            {
+                let (tx, rx) = mpsc::channel();
+                thread::spawn(move || {
+                    let _ = tx.send(weather::fetch(None));
+                });
+                self.pending = Some(rx);
+            }
+        }
+
+        #[cfg(feature = "weather")]
+        if let Some(pending) = &self.pending {
+            match pending.try_recv() {
+                Ok(report) => {
+                    // Keep the last known weather through a failed lookup
+                    self.report = report.or(self.report);
+                    self.outside = self.report.map(|report| Outside::from(report.conditions));
+                    self.pending = None;
+                    due = true;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.pending = None,
+            }
+        }
+
+        due.then(|| choose(options, Season::now(options.latitude), self.outside))
+    }
+
+    /// The weather outside for the sky's rain, snow and wind to follow, once
+    /// it's known, unless `--showers` was given.
+    pub fn weather(&self, options: &SkyOptions) -> Option<Sample> {
+        if options.explicit.iter().any(|given| given == "--showers") {
+            return None;
+        }
+        #[cfg(feature = "weather")]
+        return self.report.map(|report| sample(&report, options.latitude));
+        #[cfg(not(feature = "weather"))]
+        None
+    }
+}
@@ -0,0 +1,1090 @@
+//! Command-line parsing.
+
+use crate::config::{self, Profile};
+use crate::decoration::{self, FrameStyle};
+use crate::dusk::DuskMode;
+use crate::hooks::EventHook;
+use crate::input::QuitMode;
+#[cfg(feature = "network")]
+use crate::net;
+use crate::render::color::ColorDepth;
+use crate::render::light_pollution;
+use crate::render::scene::{self, Scene};
+use crate::render::theme::Theme;
+use crate::render::{RenderOptions, MAX_LIMITING_MAGNITUDE, MIN_LIMITING_MAGNITUDE};
+use crate::simulation::density::DensityMap;
+use crate::simulation::director::Pacing;
+#[cfg(feature = "astronomy")]
+use crate::simulation::meteor_showers::MeteorShower;
+use crate::simulation::sky_model::DEFAULT_ASPECT;
+use crate::simulation::zones::Zone;
+use crate::simulation::{SimulationOptions, DEFAULT_SEEING};
+use crate::wake;
+use chrono::NaiveTime;
+use ratatui::layout::Rect;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Frames per second with `--eink`, unless set with `--fps`.
+const EINK_FPS: f32 = 0.2;
+
+/// Rates, in hertz, accepted for `--tick-rate` and `--fps`.
+pub const RATES: RangeInclusive<f32> = 0.1..=1000.0;
+
+pub const USAGE: &str = "\
+Usage: nk [COMMAND] [OPTIONS]
+
+Commands:
+  statusline            Print a one-line sky summary for tmux/i3 status bars
+  check-theme [NAME]    Check themes for colors that look alike under color blindness
+  record <FILE>         Render the sky headlessly to an asciicast (.cast) file
+  replay <FILE>         Play a recording back; left/right seek, up/down change
+                        speed, space pauses
+  host [--listen ADDR]  Share this sky over the network (default 0.0.0.0:4477)
+  join <ADDR>           View a shared sky; the host's sky options apply
+  mirror <ADDR>         Display the frames a host draws, exactly as drawn
+  wake <HH:MM>          Keep a dark sky overnight, dawning over the half hour
+                        before the alarm
+
+Record options (plus any of the options below):
+  --duration <TIME>   Length of the recording, e.g. 30s or 2m (default 30s)
+  --size <WxH>        Recording size in cells (default 80x24)
+
+Statusline options:
+  --once              Print a single line and exit (for tmux #(...))
+  --interval <SECS>   Seconds between updates (default 60)
+  --location <LOC>    Weather location (default: derived from IP)
+  --no-weather        Skip the weather lookup
+  --latitude <DEG>    Where to predict the ISS's next pass for (default:
+  --longitude <DEG>   the config file's)
+  --no-iss            Skip the ISS's next pass
+
+Options:
+  --realism           Model atmospheric extinction and refraction
+  --latitude <DEG>    Your latitude, so meteor shower radiants sit as high
+                      as they really do (north positive)
+  --longitude <DEG>   Your longitude, for --catalog (east positive; default
+                      guessed from the time zone)
+  --catalog           The real bright stars and planets where they stand
+                      right now, rather than random ones
+  --iss               The real ISS going over, from its latest orbit (needs
+                      curl; set --latitude and --longitude)
+  --passes <X>        Real bright satellites' visible passes, labelled, by the
+                      real clock (1) or X times as fast (needs curl)
+  --day-cycle <X>     Run through dawn, day, dusk and night by the real
+                      clock (1) or X times as fast, with the sun by day
+  --density <MAP>     Star density: milky-way (default), uniform, or a mix
+                      like band=1.5,horizon=0.6,poles=0.5
+  --satellite-paths   With --realism, dot the rest of each satellite's pass
+  --theme <NAME>      Color theme: night (default), classic, deutan-safe,
+                      protan-safe, tritan-safe, high-contrast
+  --dither            Dither the sky gradient for 256-color terminals
+  --colors <DEPTH>    Colors the terminal can show: truecolor (default), 256,
+                      16 or mono
+  --ascii             Draw with plain ASCII characters only
+  --eink              For e-ink and serial displays: black and white, a
+                      frame every 5 seconds, nothing twinkling or flashing
+  --no-flashes        Never flash the whole sky (for photosensitive viewers)
+  --event-glow        Glow the screen border when a rare event happens
+  --leaves            Autumn leaves tumbling down in the foreground
+  --showers           Occasional passing rain showers
+  --moon              Show tonight's moon at its real phase
+  --eclipse           Eclipse a full moon over the first few minutes
+  --supernova         Blow up a star ten seconds in
+  --clouds            Thin high clouds now and then; over a bright moon
+                      they can form a halo
+  --aurora <X>        Auroras now and then, at this strength from 0 to 1
+                      (press a to call one up any time)
+  --avoid <X,Y,WxH>   Keep meteors, satellites, comets, rain and leaves out
+                      of this block of cells, like -20,0,20x3 for the top
+                      right corner behind a clock; repeatable
+  --rotation <X>      Turn the stars slowly about the celestial pole, X
+                      times as fast as the real sky (0 to 10)
+  --aspect <X>        How many times taller than wide the font's cells are
+                      (default 2), so the moon and halos come out round
+  --bortle <N>        Light pollution from 1 (a dark site, the default) to 9
+                      (a city sky): skyglow, fewer stars, no Milky Way
+  --limiting-magnitude <MAG>
+                      The faintest stars the naked eye sees, from 1 to 8
+                      (default 6.5; ] and [ change it)
+  --seeing <X>        How turbulent the air is, from 0 (rock-steady stars)
+                      to 1 (fast, deep twinkling); default 0.5
+  --meteor-shower <NAME>
+                      Run a meteor shower at its peak whatever the date
+                      (e.g. perseids, geminids)
+  --pacing <MODE>     Rare event pacing: realistic (default) or cinematic
+  --meteor-rate <X>   Multiply how often meteors appear (default 1, 0 to 10)
+  --satellite-rate <X>
+                      Multiply how often satellites pass (default 1, 0 to 10)
+  --scene <FILE>      Play a scene file's camera moves and captions
+  --tour              A looping guided tour of the moon, a planet, a
+                      constellation and a star cluster
+  --daily             The sky of the day: the same stars, and the same
+                      comet, aurora and Starlink trains at the same times,
+                      for everyone running nk today (UTC)
+  --auto              Pick leaves, rain, clouds and star density to suit the
+                      real season and weather, checked hourly
+  --auto-dim          Dim the palette late at night by the local clock
+  --on-exit <CMD>     Run a shell command after nk exits
+  --on-wake <CMD>     Run a shell command on the first input after being idle
+  --on-event <WHEN CMD>
+                      Run a shell command each time an event happens, like
+                      '@aurora notify-send Aurora' (@fireball, @bolide,
+                      @satellite, @iss, @pass, @moonbow, @halo, @aurora,
+                      @comet, @conjunction, @starlink, @supernova), or
+                      once the sky has been up a while, like '1h CMD';
+                      repeatable
+  --hook-log <FILE>   Append --on-event commands' output here (default:
+                      discard it)
+  --idle <SECS>       Seconds without input before nk counts as idle (default 300)
+  --quit <MODE>       single (q/Esc, default), double (press twice), or a chord like ctrl+q
+  --grace <SECS>      Ignore all input for the first SECS seconds
+  --tick-rate <HZ>    Simulation steps per second (default 20)
+  --fps <N>           Frames drawn per second (default 20, or 4 with --remote)
+  --mouse             Click the sky to launch a meteor (captures the mouse)
+  --no-mouse          Never capture the mouse, even with --mouse
+  --remote            Go easy on slow or multiplexed links: fewer frames,
+                      periodic full repaints
+  --no-alt-screen     Draw on the normal screen instead of the alternate one
+  --no-fade           Start and quit at once instead of fading in and out
+  --dusk <TIME>       Open at dusk and darken into night over TIME, e.g. 5m,
+                      or 'sunset' to follow the real sun (set --latitude)
+  --frame <STYLE>     Frame the sky with a border: rounded, plain, double or
+                      thick, titled and with tonight's moon in the corner
+  --frame-title <TEXT>
+                      The frame's title (default '· night sky ·', '' for none)
+  -h, --help          Show this help
+";
+
+pub enum Command {
+    Sky(SkyOptions),
+    Statusline(StatuslineOptions),
+    /// Check one theme, or all of them when `None`
+    CheckTheme(Option<Theme>),
+    Record(RecordOptions),
+    Replay(ReplayOptions),
+    #[cfg(feature = "network")]
+    Host(HostOptions),
+    #[cfg(feature = "network")]
+    Join(JoinOptions),
+    /// Follow a host's rendered frames; only local options like `--quit` apply
+    #[cfg(feature = "network")]
+    Mirror(JoinOptions),
+    Help,
+}
+
+#[derive(Clone)]
+pub struct SkyOptions {
+    pub realism: bool,
+    /// Observer's latitude in degrees, if known
+    pub latitude: Option<f32>,
+    pub longitude: Option<f32>,
+    pub satellite_paths: bool,
+    pub density: DensityMap,
+    pub theme: Theme,
+    pub auto_dim: bool,
+    pub dither: bool,
+    pub colors: ColorDepth,
+    pub ascii: bool,
+    /// Draw for e-ink and other slow-refreshing displays
+    pub eink: bool,
+    pub flashes: bool,
+    pub event_glow: bool,
+    pub leaves: bool,
+    pub showers: bool,
+    pub moon: bool,
+    /// Put on a lunar eclipse to show one off
+    pub eclipse: bool,
+    /// Set off a supernova early on to show one off
+    pub supernova: bool,
+    pub clouds: bool,
+    /// Strength of occasional auroras, 0 for none
+    pub aurora: f32,
+    /// Screen zones kept clear of moving things
+    pub avoid: Vec<Zone>,
+    /// How fast random stars turn about the pole, 0 for not at all
+    pub rotation: f32,
+    /// Height of the terminal's cells over their width
+    pub aspect: f32,
+    /// Light pollution on the Bortle scale
+    pub bortle: u8,
+    /// Faintest magnitude seen with the naked eye, if not the usual
+    pub limiting_magnitude: Option<f32>,
+    /// Air turbulence, from 0 for steady stars to 1
+    pub seeing: f32,
+    #[cfg(feature = "astronomy")]
+    pub meteor_shower: Option<&'static MeteorShower>,
+    /// Real bright stars rather than random ones
+    #[cfg(feature = "astronomy")]
+    pub catalog: bool,
+    /// Track the real ISS
+    #[cfg(feature = "astronomy")]
+    pub iss: bool,
+    /// How fast real satellites' passes come round, 0 for none
+    #[cfg(feature = "astronomy")]
+    pub passes: f32,
+    /// How fast the sky runs through day and night, 0 for always night
+    #[cfg(feature = "astronomy")]
+    pub day_cycle: f32,
+    pub pacing: Pacing,
+    /// Multipliers on how often meteors and satellites spawn
+    pub meteor_rate: f32,
+    pub satellite_rate: f32,
+    pub scene: Scene,
+    pub tour: bool,
+    /// Build the sky of the day and keep to its schedule of rare events
+    pub daily: bool,
+    /// Match effects to the real season and weather
+    pub auto: bool,
+    /// Alarm time for `nk wake`
+    pub wake: Option<NaiveTime>,
+    pub on_exit: Option<String>,
+    pub on_wake: Option<String>,
+    pub on_event: Vec<EventHook>,
+    /// Where --on-event commands' output goes
+    pub hook_log: Option<PathBuf>,
+    pub idle_timeout: Duration,
+    pub quit: QuitMode,
+    pub grace: Duration,
+    pub tick_rate: f32,
+    /// Frames per second, if set explicitly
+    pub fps: Option<f32>,
+    pub remote: bool,
+    pub alt_screen: bool,
+    /// Fade in on launch and out on quit
+    pub fade: bool,
+    /// Border to draw around the sky, if any
+    pub frame: Option<FrameStyle>,
+    /// Open in twilight and darken into night
+    pub dusk: Option<DuskMode>,
+    pub frame_title: String,
+    /// Click interactions were asked for
+    pub mouse: bool,
+    pub no_mouse: bool,
+    /// Size profiles from the config file
+    pub profiles: Vec<Profile>,
+    /// Flags given on the command line, which profiles leave alone
+    pub explicit: Vec<String>,
+}
+
+impl SkyOptions {
+    pub fn frame_rate(&self) -> f32 {
+        let default = if self.eink {
+            EINK_FPS
+        } else if self.remote {
+            4.0
+        } else {
+            20.0
+        };
+        self.fps.unwrap_or(default)
+    }
+
+    /// Whether the sky fades in and out. An e-ink panel would only show
+    /// the fade as a few slow redraws.
+    pub fn fades(&self) -> bool {
+        self.fade && !self.eink
+    }
+
+    /// The sky's part of a screen filling `area`: inside the frame, if
+    /// there is one.
+    pub fn sky_area(&self, area: Rect) -> Rect {
+        match self.frame {
+            Some(_) => decoration::inner(area),
+            None => area,
+        }
+    }
+
+    /// Capturing the mouse stops the terminal's own text selection, so only
+    /// do it when something actually responds to clicks.
+    pub fn mouse_capture(&self) -> bool {
+        self.mouse && !self.no_mouse
+    }
+
+    /// The options that shape the sky itself.
+    pub fn simulation(&self) -> SimulationOptions {
+        SimulationOptions {
+            density: self.density,
+            pacing: self.pacing,
+            #[cfg(feature = "astronomy")]
+            meteor_shower: self.meteor_shower,
+            #[cfg(feature = "astronomy")]
+            catalog: self.catalog,
+            #[cfg(feature = "astronomy")]
+            day_cycle: self.day_cycle,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            meteor_rate: self.meteor_rate,
+            satellite_rate: self.satellite_rate,
+            leaves: self.leaves,
+            showers: self.showers,
+            moon: self.moon,
+            eclipse: self.eclipse,
+            supernova: self.supernova,
+            clouds: self.clouds,
+            aurora: self.aurora,
+            tour: self.tour,
+            daily: self.daily,
+            avoid: self.avoid.clone(),
+            rotation: self.rotation,
+            aspect: self.aspect,
+        }
+    }
+
+    /// The options that shape how the sky is drawn.
+    pub fn rendering(&self) -> RenderOptions {
+        RenderOptions {
+            theme: self.theme,
+            realism: self.realism,
+            satellite_paths: self.satellite_paths,
+            dither: self.dither,
+            colors: if self.eink { ColorDepth::Mono } else { self.colors },
+            ascii: self.ascii,
+            // A full-screen flash is a full refresh on e-ink
+            flashes: self.flashes && !self.eink,
+            event_glow: self.event_glow,
+            auto_dim: self.auto_dim,
+            eink: self.eink,
+            bortle: self.bortle,
+            limiting_magnitude: self.limiting_magnitude,
+            seeing: self.seeing,
+            scene: self.scene.clone(),
+        }
+    }
+}
+
+impl Default for SkyOptions {
+    fn default() -> Self {
+        SkyOptions {
+            realism: false,
+            latitude: None,
+            longitude: None,
+            satellite_paths: false,
+            density: DensityMap::default(),
+            theme: Theme::default(),
+            auto_dim: false,
+            dither: false,
+            colors: ColorDepth::default(),
+            ascii: false,
+            eink: false,
+            flashes: true,
+            event_glow: false,
+            leaves: false,
+            showers: false,
+            moon: false,
+            eclipse: false,
+            supernova: false,
+            clouds: false,
+            aurora: 0.0,
+            avoid: Vec::new(),
+            rotation: 0.0,
+            aspect: DEFAULT_ASPECT,
+            bortle: light_pollution::DARKEST,
+            limiting_magnitude: None,
+            seeing: DEFAULT_SEEING,
+            #[cfg(feature = "astronomy")]
+            meteor_shower: None,
+            #[cfg(feature = "astronomy")]
+            catalog: false,
+            #[cfg(feature = "astronomy")]
+            iss: false,
+            #[cfg(feature = "astronomy")]
+            passes: 0.0,
+            #[cfg(feature = "astronomy")]
+            day_cycle: 0.0,
+            pacing: Pacing::default(),
+            meteor_rate: 1.0,
+            satellite_rate: 1.0,
+            scene: Scene::default(),
+            tour: false,
+            daily: false,
+            auto: false,
+            wake: None,
+            on_exit: None,
+            on_wake: None,
+            on_event: Vec::new(),
+            hook_log: None,
+            idle_timeout: Duration::from_secs(300),
+            quit: QuitMode::default(),
+            grace: Duration::ZERO,
+            tick_rate: 20.0,
+            fps: None,
+            remote: false,
+            alt_screen: true,
+            fade: true,
+            frame: None,
+            dusk: None,
+            frame_title: decoration::DEFAULT_TITLE.to_string(),
+            mouse: false,
+            no_mouse: false,
+            profiles: Vec::new(),
+            explicit: Vec::new(),
+        }
+    }
+}
+
+pub struct RecordOptions {
+    pub path: String,
+    /// Length of the recording in seconds
+    pub duration: f32,
+    pub width: u16,
+    pub height: u16,
+    pub sky: SkyOptions,
+}
+
+pub struct ReplayOptions {
+    pub path: String,
+    /// Local settings such as key bindings
+    pub sky: SkyOptions,
+}
+
+#[cfg(feature = "network")]
+pub struct HostOptions {
+    pub listen: String,
+    pub sky: SkyOptions,
+}
+
+#[cfg(feature = "network")]
+pub struct JoinOptions {
+    pub addr: String,
+    /// Local settings such as key bindings; the host's replace the rest
+    pub sky: SkyOptions,
+}
+
+pub struct StatuslineOptions {
+    pub once: bool,
+    pub interval: Duration,
+    #[cfg(feature = "weather")]
+    pub location: Option<String>,
+    #[cfg(feature = "weather")]
+    pub weather: bool,
+    /// Where the ISS's passes are predicted for
+    #[cfg(feature = "astronomy")]
+    pub latitude: Option<f32>,
+    #[cfg(feature = "astronomy")]
+    pub longitude: Option<f32>,
+    /// Show when the ISS next comes over
+    #[cfg(feature = "astronomy")]
+    pub iss: bool,
+}
+
+impl Default for StatuslineOptions {
+    fn default() -> Self {
+        StatuslineOptions {
+            once: false,
+            interval: Duration::from_secs(60),
+            #[cfg(feature = "weather")]
+            location: None,
+            #[cfg(feature = "weather")]
+            weather: true,
+            #[cfg(feature = "astronomy")]
+            latitude: None,
+            #[cfg(feature = "astronomy")]
+            longitude: None,
+            #[cfg(feature = "astronomy")]
+            iss: true,
+        }
+    }
+}
+
+pub fn parse<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.peekable();
+    match args.peek().map(String::as_str) {
+        Some("statusline") => {
+            args.next();
+            parse_statusline(args).map(Command::Statusline)
+        }
+        Some("check-theme") => {
+            args.next();
+            let theme = match args.next() {
+                Some(name) => Some(theme_by_name(&name)?),
+                None => None,
+            };
+            match args.next() {
+                Some(extra) => Err(format!("unexpected argument '{}'", extra)),
+                None => Ok(Command::CheckTheme(theme)),
+            }
+        }
+        Some("record") => {
+            args.next();
+            parse_record(args)
+        }
+        Some("replay") => {
+            args.next();
+            parse_replay(args)
+        }
+        Some("wake") => {
+            args.next();
+            parse_wake(args)
+        }
+        #[cfg(feature = "network")]
+        Some("host") => {
+            args.next();
+            parse_host(args)
+        }
+        #[cfg(feature = "network")]
+        Some("join") => {
+            args.next();
+            parse_join(args, "join").map(Command::Join)
+        }
+        #[cfg(feature = "network")]
+        Some("mirror") => {
+            args.next();
+            parse_join(args, "mirror").map(Command::Mirror)
+        }
+        #[cfg(not(feature = "network"))]
+        Some(command @ ("host" | "join" | "mirror")) => Err(format!(
+            "{} needs nk built with the 'network' feature",
+            command
+        )),
+        _ => parse_sky(args),
+    }
+}
+
+fn parse_sky<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut opts = SkyOptions::configured()?;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => parse_cli_arg(&mut opts, &arg, &mut args)?,
+        }
+    }
+    Ok(Command::Sky(opts))
+}
+
+fn parse_record<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let path = match args.next() {
+        Some(path) if !path.starts_with('-') => path,
+        _ => return Err("record requires an output file".to_string()),
+    };
+    let mut opts = RecordOptions {
+        path,
+        duration: 30.0,
+        width: 80,
+        height: 24,
+        sky: SkyOptions::configured()?,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--duration" => {
+                let raw: String = value(&mut args, &arg)?;
+                opts.duration = scene::parse_time(&raw)?;
+            }
+            "--size" => {
+                let raw: String = value(&mut args, &arg)?;
+                (opts.width, opts.height) = raw
+                    .split_once('x')
+                    .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+                    .filter(|&(w, h)| w > 0 && h > 0)
+                    .ok_or_else(|| format!("invalid size '{}', expected e.g. 80x24", raw))?;
+            }
+            _ => parse_cli_arg(&mut opts.sky, &arg, &mut args)?,
+        }
+    }
+    Ok(Command::Record(opts))
+}
+
+fn parse_replay<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let path = match args.next() {
+        Some(path) if !path.starts_with('-') => path,
+        _ => return Err("replay requires a recording".to_string()),
+    };
+    let mut sky = SkyOptions::configured()?;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => parse_cli_arg(&mut sky, &arg, &mut args)?,
+        }
+    }
+    Ok(Command::Replay(ReplayOptions { path, sky }))
+}
+
+fn parse_wake<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let at = match args.next() {
+        Some(at) if !at.starts_with('-') => wake::parse_time(&at)?,
+        _ => return Err("wake requires an alarm time, e.g. 06:45".to_string()),
+    };
+    let mut opts = SkyOptions::configured()?;
+    opts.wake = Some(at);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => parse_cli_arg(&mut opts, &arg, &mut args)?,
+        }
+    }
+    Ok(Command::Sky(opts))
+}
+
+#[cfg(feature = "network")]
+fn parse_host<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut opts = HostOptions {
+        listen: format!("0.0.0.0:{}", net::DEFAULT_PORT),
+        sky: SkyOptions::configured()?,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--listen" => opts.listen = value(&mut args, &arg)?,
+            _ => parse_cli_arg(&mut opts.sky, &arg, &mut args)?,
+        }
+    }
+    Ok(Command::Host(opts))
+}
+
+#[cfg(feature = "network")]
+fn parse_join<I: Iterator<Item = String>>(mut args: I, command: &str) -> Result<JoinOptions, String> {
+    let addr = match args.next() {
+        Some(addr) if !addr.starts_with('-') => addr,
+        _ => return Err(format!("{} requires a host address", command)),
+    };
+    let mut sky = SkyOptions::configured()?;
+    while let Some(arg) = args.next() {
+        parse_cli_arg(&mut sky, &arg, &mut args)?;
+    }
+    Ok(JoinOptions { addr, sky })
+}
+
+// Options that shape the simulation itself. Guests of a shared sky must use
+// the host's, or their skies drift apart.
+#[cfg(feature = "network")]
+const SHARED_FLAGS: &[&str] = &[
+    "--realism",
+    "--latitude",
+    "--longitude",
+    "--theme",
+    "--density",
+    "--leaves",
+    "--showers",
+    "--moon",
+    "--eclipse",
+    "--supernova",
+    "--clouds",
+    "--aurora",
+    "--avoid",
+    "--rotation",
+    "--aspect",
+    "--pacing",
+    "--meteor-rate",
+    "--satellite-rate",
+    "--meteor-shower",
+    "--catalog",
+    "--day-cycle",
+    "--tour",
+    "--daily",
+];
+
+impl SkyOptions {
+    /// The defaults with the config file's options applied. Size profiles
+    /// are checked here but only applied by [`SkyOptions::for_size`].
+    pub fn configured() -> Result<SkyOptions, String> {
+        let config = config::load()?;
+        let mut opts = SkyOptions::default();
+        apply_args(&mut opts, &config.args, &[]).map_err(|err| format!("config: {}", err))?;
+        for profile in &config.profiles {
+            apply_args(&mut opts.clone(), &profile.args, &[])
+                .map_err(|err| format!("config: {}", err))?;
+        }
+        opts.profiles = config.profiles;
+        Ok(opts)
+    }
+
+    /// Indices of the size profiles matching a `width` x `height` terminal.
+    pub fn matching_profiles(&self, width: u16, height: u16) -> Vec<usize> {
+        (0..self.profiles.len())
+            .filter(|&i| self.profiles[i].matches(width, height))
+            .collect()
+    }
+
+    /// These options with the size profiles matching a `width` x `height`
+    /// terminal applied, except for options given on the command line.
+    pub fn for_size(&self, width: u16, height: u16) -> SkyOptions {
+        let mut opts = self.clone();
+        for i in self.matching_profiles(width, height) {
+            // Already checked when the config was loaded
+            let _ = apply_args(&mut opts, &self.profiles[i].args, &self.explicit);
+        }
+        opts
+    }
+
+    /// The options in [`SHARED_FLAGS`], as arguments for a guest.
+    #[cfg(feature = "network")]
+    pub fn shared_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--theme".to_string(),
+            self.theme.name.to_string(),
+            "--density".to_string(),
+            self.density.to_string(),
+            "--pacing".to_string(),
+            self.pacing.name().to_string(),
+            "--meteor-rate".to_string(),
+            self.meteor_rate.to_string(),
+            "--satellite-rate".to_string(),
+            self.satellite_rate.to_string(),
+        ];
+        for (flag, on) in [
+            ("--realism", self.realism),
+            ("--leaves", self.leaves),
+            ("--showers", self.showers),
+            ("--moon", self.moon),
+            ("--eclipse", self.eclipse),
+            ("--supernova", self.supernova),
+            ("--clouds", self.clouds),
+            ("--tour", self.tour),
+            ("--daily", self.daily),
+        ] {
+            if on {
+                args.push(flag.to_string());
+            }
+        }
+        if let Some(latitude) = self.latitude {
+            args.extend(["--latitude".to_string(), latitude.to_string()]);
+        }
+        if let Some(longitude) = self.longitude {
+            args.extend(["--longitude".to_string(), longitude.to_string()]);
+        }
+        if self.aurora > 0.0 {
+            args.extend(["--aurora".to_string(), self.aurora.to_string()]);
+        }
+        for zone in &self.avoid {
+            args.extend(["--avoid".to_string(), zone.to_string()]);
+        }
+        if self.rotation > 0.0 {
+            args.extend(["--rotation".to_string(), self.rotation.to_string()]);
+        }
+        if self.aspect != DEFAULT_ASPECT {
+            args.extend(["--aspect".to_string(), self.aspect.to_string()]);
+        }
+        #[cfg(feature = "astronomy")]
+        if let Some(shower) = self.meteor_shower {
+            args.extend(["--meteor-shower".to_string(), shower.name.to_string()]);
+        }
+        #[cfg(feature = "astronomy")]
+        if self.catalog {
+            args.push("--catalog".to_string());
+        }
+        #[cfg(feature = "astronomy")]
+        if self.day_cycle > 0.0 {
+            args.extend(["--day-cycle".to_string(), self.day_cycle.to_string()]);
+        }
+        args
+    }
+
+    /// Take on a host's shared options. Anything else is refused, so a host
+    /// can't, say, set hook commands on its guests.
+    #[cfg(feature = "network")]
+    pub fn apply_shared(&mut self, args: &[String]) -> Result<(), String> {
+        let defaults = SkyOptions::default();
+        self.realism = defaults.realism;
+        self.latitude = defaults.latitude;
+        self.longitude = defaults.longitude;
+        self.leaves = defaults.leaves;
+        self.showers = defaults.showers;
+        self.moon = defaults.moon;
+        self.eclipse = defaults.eclipse;
+        self.supernova = defaults.supernova;
+        self.clouds = defaults.clouds;
+        self.aurora = defaults.aurora;
+        self.avoid = defaults.avoid;
+        self.rotation = defaults.rotation;
+        self.aspect = defaults.aspect;
+        #[cfg(feature = "astronomy")]
+        {
+            self.meteor_shower = defaults.meteor_shower;
+            self.catalog = defaults.catalog;
+            self.day_cycle = defaults.day_cycle;
+        }
+        self.tour = defaults.tour;
+        self.daily = defaults.daily;
+        let mut args = args.iter().cloned();
+        while let Some(arg) = args.next() {
+            if !SHARED_FLAGS.contains(&arg.as_str()) {
+                return Err(format!("host sent an option guests can't take: '{}'", arg));
+            }
+            parse_sky_arg(self, &arg, &mut args)?;
+        }
+        Ok(())
+    }
+}
+
+/// Apply a list of sky options, passing over any in `skip`.
+fn apply_args(opts: &mut SkyOptions, args: &[String], skip: &[String]) -> Result<(), String> {
+    let mut args = args.iter().cloned();
+    while let Some(arg) = args.next() {
+        if skip.contains(&arg) {
+            // Still consume the value so the next option lines up
+            parse_sky_arg(&mut SkyOptions::default(), &arg, &mut args)?;
+        } else {
+            parse_sky_arg(opts, &arg, &mut args)?;
+        }
+    }
+    Ok(())
+}
+
+/// Apply one sky option given on the command line.
+fn parse_cli_arg<I: Iterator<Item = String>>(
+    opts: &mut SkyOptions,
+    arg: &str,
+    args: &mut I,
+) -> Result<(), String> {
+    opts.explicit.push(arg.to_string());
+    parse_sky_arg(opts, arg, args)
+}
+
+/// Apply one sky option, consuming its value from `args` if it takes one.
+fn parse_sky_arg<I: Iterator<Item = String>>(
+    opts: &mut SkyOptions,
+    arg: &str,
+    args: &mut I,
+) -> Result<(), String> {
+    match arg {
+        "--realism" => opts.realism = true,
+        "--latitude" => {
+            let latitude: f32 = value(args, arg)?;
+            if !(-90.0..=90.0).contains(&latitude) {
+                return Err("--latitude must be between -90 and 90".to_string());
+            }
+            opts.latitude = Some(latitude);
+        }
+        "--longitude" => {
+            let longitude: f32 = value(args, arg)?;
+            if !(-180.0..=180.0).contains(&longitude) {
+                return Err("--longitude must be between -180 and 180".to_string());
+            }
+            opts.longitude = Some(longitude);
+        }
+        "--density" => {
+            let map: String = value(args, arg)?;
+            opts.density = map.parse()?;
+        }
+        "--satellite-paths" => opts.satellite_paths = true,
+        "--theme" => {
+            let name: String = value(args, arg)?;
+            opts.theme = theme_by_name(&name)?;
+        }
+        "--auto-dim" => opts.auto_dim = true,
+        "--dither" => opts.dither = true,
+        "--colors" => {
+            let depth: String = value(args, arg)?;
+            opts.colors = depth.parse()?;
+        }
+        "--ascii" => opts.ascii = true,
+        "--eink" => opts.eink = true,
+        "--no-flashes" => opts.flashes = false,
+        "--event-glow" => opts.event_glow = true,
+        "--leaves" => opts.leaves = true,
+        "--showers" => opts.showers = true,
+        "--moon" => opts.moon = true,
+        "--eclipse" => opts.eclipse = true,
+        "--supernova" => opts.supernova = true,
+        "--clouds" => opts.clouds = true,
+        "--aurora" => {
+            let intensity: f32 = value(args, arg)?;
+            if !(intensity.is_finite() && (0.0..=1.0).contains(&intensity)) {
+                return Err("--aurora must be between 0 and 1".to_string());
+            }
+            opts.aurora = intensity;
+        }
+        "--avoid" => {
+            let zone: String = value(args, arg)?;
+            opts.avoid.push(zone.parse()?);
+        }
+        "--rotation" => opts.rotation = multiplier(args, arg)?,
+        "--aspect" => {
+            let aspect: f32 = value(args, arg)?;
+            if !(aspect.is_finite() && (0.5..=4.0).contains(&aspect)) {
+                return Err(format!("{} must be between 0.5 and 4", arg));
+            }
+            opts.aspect = aspect;
+        }
+        "--bortle" => {
+            let bortle: u8 = value(args, arg)?;
+            if !(light_pollution::DARKEST..=light_pollution::BRIGHTEST).contains(&bortle) {
+                return Err(format!("{} must be between 1 and 9", arg));
+            }
+            opts.bortle = bortle;
+        }
+        "--limiting-magnitude" => {
+            let limit: f32 = value(args, arg)?;
+            if !(limit.is_finite() && (MIN_LIMITING_MAGNITUDE..=MAX_LIMITING_MAGNITUDE).contains(&limit)) {
+                return Err(format!("{} must be between 1 and 8", arg));
+            }
+            opts.limiting_magnitude = Some(limit);
+        }
+        "--seeing" => {
+            let seeing: f32 = value(args, arg)?;
+            if !(seeing.is_finite() && (0.0..=1.0).contains(&seeing)) {
+                return Err("--seeing must be between 0 and 1".to_string());
+            }
+            opts.seeing = seeing;
+        }
+        #[cfg(feature = "astronomy")]
+        "--meteor-shower" => {
+            let name: String = value(args, arg)?;
+            let shower = MeteorShower::by_name(&name)
+                .ok_or_else(|| format!("unknown meteor shower '{}'", name))?;
+            opts.meteor_shower = Some(shower);
+        }
+        #[cfg(not(feature = "astronomy"))]
+        "--meteor-shower" => {
+            return Err("--meteor-shower needs nk built with the 'astronomy' feature".to_string())
+        }
+        #[cfg(feature = "astronomy")]
+        "--catalog" => opts.catalog = true,
+        #[cfg(not(feature = "astronomy"))]
+        "--catalog" => {
+            return Err("--catalog needs nk built with the 'astronomy' feature".to_string())
+        }
+        #[cfg(feature = "astronomy")]
+        "--iss" => opts.iss = true,
+        #[cfg(not(feature = "astronomy"))]
+        "--iss" => return Err("--iss needs nk built with the 'astronomy' feature".to_string()),
+        #[cfg(feature = "astronomy")]
+        "--passes" => {
+            let speed: f32 = value(args, arg)?;
+            if !(speed.is_finite() && (1.0..=10_000.0).contains(&speed)) {
+                return Err(format!("{} must be between 1 and 10000", arg));
+            }
+            opts.passes = speed;
+        }
+        #[cfg(not(feature = "astronomy"))]
+        "--passes" => {
+            return Err("--passes needs nk built with the 'astronomy' feature".to_string())
+        }
+        #[cfg(feature = "astronomy")]
+        "--day-cycle" => {
+            let speed: f32 = value(args, arg)?;
+            if !(speed.is_finite() && (1.0..=10_000.0).contains(&speed)) {
+                return Err(format!("{} must be between 1 and 10000", arg));
+            }
+            opts.day_cycle = speed;
+        }
+        #[cfg(not(feature = "astronomy"))]
+        "--day-cycle" => {
+            return Err("--day-cycle needs nk built with the 'astronomy' feature".to_string())
+        }
+        "--pacing" => {
+            let mode: String = value(args, arg)?;
+            opts.pacing = mode.parse()?;
+        }
+        "--meteor-rate" => opts.meteor_rate = multiplier(args, arg)?,
+        "--satellite-rate" => opts.satellite_rate = multiplier(args, arg)?,
+        "--on-exit" => opts.on_exit = Some(value(args, arg)?),
+        "--on-wake" => opts.on_wake = Some(value(args, arg)?),
+        "--on-event" => {
+            let hook: String = value(args, arg)?;
+            opts.on_event.push(hook.parse()?);
+        }
+        "--hook-log" => opts.hook_log = Some(value(args, arg)?),
+        "--idle" => opts.idle_timeout = Duration::from_secs(value(args, arg)?),
+        "--quit" => {
+            let mode: String = value(args, arg)?;
+            opts.quit = mode.parse()?;
+        }
+        "--grace" => opts.grace = Duration::from_secs(value(args, arg)?),
+        "--tick-rate" => opts.tick_rate = rate(args, arg)?,
+        "--fps" => opts.fps = Some(rate(args, arg)?),
+        "--mouse" => opts.mouse = true,
+        "--no-mouse" => opts.no_mouse = true,
+        "--remote" => opts.remote = true,
+        "--no-alt-screen" => opts.alt_screen = false,
+        "--no-fade" => opts.fade = false,
+        "--frame" => opts.frame = Some(value(args, arg)?),
+        "--dusk" => {
+            let mode: String = value(args, arg)?;
+            opts.dusk = Some(mode.parse()?);
+        }
+        "--frame-title" => opts.frame_title = value(args, arg)?,
+        "--scene" => {
+            let path: String = value(args, arg)?;
+            opts.scene = Scene::load(&path)?;
+        }
+        "--tour" => opts.tour = true,
+        "--daily" => opts.daily = true,
+        "--auto" => opts.auto = true,
+        other => return Err(format!("unknown argument '{}'", other)),
+    }
+    Ok(())
+}
+
+fn parse_statusline<I: Iterator<Item = String>>(mut args: I) -> Result<StatuslineOptions, String> {
+    let mut opts = StatuslineOptions::default();
+    // The sky's own place unless told otherwise
+    #[cfg(feature = "astronomy")]
+    if let Ok(sky) = SkyOptions::configured() {
+        opts.latitude = sky.latitude;
+        opts.longitude = sky.longitude;
+    }
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--once" => opts.once = true,
+            "--interval" => {
+                let secs: u64 = value(&mut args, &arg)?;
+                opts.interval = Duration::from_secs(secs.max(1));
+            }
+            #[cfg(feature = "weather")]
+            "--location" => opts.location = Some(value(&mut args, &arg)?),
+            #[cfg(feature = "weather")]
+            "--no-weather" => opts.weather = false,
+            #[cfg(not(feature = "weather"))]
+            "--location" => {
+                return Err("--location needs nk built with the 'weather' feature".to_string())
+            }
+            // Nothing to skip
+            #[cfg(not(feature = "weather"))]
+            "--no-weather" => {}
+            #[cfg(feature = "astronomy")]
+            "--latitude" | "--longitude" => {
+                // Checked just as the sky's are
+                let mut place = SkyOptions::default();
+                parse_sky_arg(&mut place, &arg, &mut args)?;
+                opts.latitude = place.latitude.or(opts.latitude);
+                opts.longitude = place.longitude.or(opts.longitude);
+            }
+            #[cfg(feature = "astronomy")]
+            "--no-iss" => opts.iss = false,
+            #[cfg(not(feature = "astronomy"))]
+            "--latitude" | "--longitude" => {
+                return Err(format!("{} needs nk built with the 'astronomy' feature", arg))
+            }
+            #[cfg(not(feature = "astronomy"))]
+            "--no-iss" => {}
+            other => return Err(format!("unknown statusline argument '{}'", other)),
+        }
+    }
+    Ok(opts)
+}
+
+fn theme_by_name(name: &str) -> Result<Theme, String> {
+    Theme::by_name(name).ok_or_else(|| format!("unknown theme '{}'", name))
+}
+
+/// Parse a positive per-second rate following `flag`.
+fn rate<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<f32, String> {
+    let hz: f32 = value(args, flag)?;
+    if RATES.contains(&hz) {
+        Ok(hz)
+    } else {
+        Err(format!("{} must be between 0.1 and 1000", flag))
+    }
+}
+
+/// Parse a spawn rate multiplier following `flag`.
+fn multiplier<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<f32, String> {
+    let factor: f32 = value(args, flag)?;
+    if factor.is_finite() && (0.0..=10.0).contains(&factor) {
+        Ok(factor)
+    } else {
+        Err(format!("{} must be between 0 and 10", flag))
+    }
+}
+
+/// Parse the value following `flag`.
+fn value<T: std::str::FromStr, I: Iterator<Item = String>>(
+    args: &mut I,
+    flag: &str,
+) -> Result<T, String> {
+    let raw = args
+        .next()
+        .ok_or_else(|| format!("{} requires a value", flag))?;
+    raw.parse()
+        .map_err(|_| format!("invalid value '{}' for {}", raw, flag))
+}
@@ -0,0 +1,216 @@
+//! The config file: default sky options, one per line.
+//!
+//! Each line is a command-line option without its leading dashes, followed
+//! by its value if it takes one. Blank lines and `#` comments are ignored.
+//!
+//! A `[size ...]` header starts a profile: the lines after it apply only
+//! while the terminal size passes each of its comma-separated comparisons.
+//! Matching profiles apply in order after the lines for every size, and
+//! options given on the command line win over the whole file.
+//!
+//! ```text
+//! # ~/.config/nk/config
+//! theme night
+//! density milky-way
+//! leaves
+//!
+//! [size width >= 160]
+//! scene ~/.config/nk/ultrawide.scene
+//!
+//! [size width < 60, height < 20]
+//! density uniform
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+// Written at the top of a new config file
+const HEADER: &str = "# nk options, one per line, without the leading dashes\n";
+
+/// `$XDG_CONFIG_HOME/nk/config`, falling back to `~/.config/nk/config`.
+pub fn path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("nk").join("config"))
+}
+
+/// Split a config line into its key and value, skipping blanks and comments.
+fn entry(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    Some(match line.split_once(char::is_whitespace) {
+        Some((key, value)) => (key, value.trim()),
+        None => (line, ""),
+    })
+}
+
+/// How a size profile compares the terminal against a number of cells.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+/// One test on the terminal size, like `width >= 160`.
+#[derive(Clone, Debug, PartialEq)]
+struct Condition {
+    /// Whether the test is on the width rather than the height
+    width: bool,
+    comparison: Comparison,
+    cells: u16,
+}
+
+impl Condition {
+    fn parse(text: &str) -> Result<Condition, String> {
+        let text = text.trim();
+        let at = text
+            .find(|c: char| "<=>".contains(c))
+            .ok_or_else(|| format!("expected a comparison like 'width >= 160', got '{}'", text))?;
+        let (dimension, rest) = (text[..at].trim(), &text[at..]);
+        let width = match dimension {
+            "width" => true,
+            "height" => false,
+            other => return Err(format!("unknown dimension '{}'", other)),
+        };
+        let operators = [
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("<", Comparison::Less),
+            (">", Comparison::Greater),
+            ("=", Comparison::Equal),
+        ];
+        let (operator, comparison) = operators
+            .into_iter()
+            .find(|(op, _)| rest.starts_with(op))
+            .ok_or_else(|| format!("invalid comparison '{}'", text))?;
+        let number = rest[operator.len()..].trim();
+        let cells = number
+            .parse()
+            .map_err(|_| format!("invalid size '{}' in '{}'", number, text))?;
+        Ok(Condition {
+            width,
+            comparison,
+            cells,
+        })
+    }
+
+    fn matches(&self, width: u16, height: u16) -> bool {
+        let size = if self.width { width } else { height };
+        match self.comparison {
+            Comparison::Less => size < self.cells,
+            Comparison::LessOrEqual => size <= self.cells,
+            Comparison::Equal => size == self.cells,
+            Comparison::GreaterOrEqual => size >= self.cells,
+            Comparison::Greater => size > self.cells,
+        }
+    }
+}
+
+/// Options that apply only while the terminal size passes every condition.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    conditions: Vec<Condition>,
+    pub args: Vec<String>,
+}
+
+impl Profile {
+    pub fn matches(&self, width: u16, height: u16) -> bool {
+        self.conditions.iter().all(|c| c.matches(width, height))
+    }
+}
+
+/// The config file's contents as command-line arguments.
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    /// Options for every terminal size
+    pub args: Vec<String>,
+    pub profiles: Vec<Profile>,
+}
+
+/// Read the config file. A missing file is the same as an empty one.
+pub fn load() -> Result<Config, String> {
+    let Some(path) = path() else {
+        return Ok(Config::default());
+    };
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+        Err(err) => return Err(format!("can't read {}: {}", path.display(), err)),
+    };
+    let mut config = Config::default();
+    for (number, line) in text.lines().enumerate() {
+        let located = |err: String| format!("{} line {}: {}", path.display(), number + 1, err);
+        let Some((key, value)) = entry(line) else {
+            continue;
+        };
+        if let Some(section) = line.trim().strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let conditions = section
+                .trim()
+                .strip_prefix("size")
+                .ok_or_else(|| located(format!("unknown section '{}'", section)))?
+                .split(',')
+                .map(Condition::parse)
+                .collect::<Result<_, _>>()
+                .map_err(located)?;
+            config.profiles.push(Profile {
+                conditions,
+                args: Vec::new(),
+            });
+            continue;
+        }
+        let args = match config.profiles.last_mut() {
+            Some(profile) => &mut profile.args,
+            None => &mut config.args,
+        };
+        args.push(format!("--{}", key));
+        if !value.is_empty() {
+            args.push(value.to_string());
+        }
+    }
+    Ok(config)
+}
+
+/// Write `lines` to the config file in place of any existing lines for the
+/// `managed` keys. Other lines and comments, and all size profiles, are kept
+/// as they were.
+pub fn save(lines: &[String], managed: &[&str]) -> Result<PathBuf, String> {
+    let path = path().ok_or("can't find a config directory (is $HOME set?)")?;
+    let existing = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => HEADER.to_string(),
+        Err(err) => return Err(format!("can't read {}: {}", path.display(), err)),
+    };
+    // New lines go at the end of the options for every size, before any
+    // size profile starts
+    let profiles = existing
+        .lines()
+        .position(|line| line.trim().starts_with('['))
+        .unwrap_or(usize::MAX);
+    let mut text: String = existing
+        .lines()
+        .take(profiles)
+        .filter(|line| !entry(line).is_some_and(|(key, _)| managed.contains(&key)))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    for line in lines {
+        text.push_str(line);
+        text.push('\n');
+    }
+    for line in existing.lines().skip(profiles) {
+        text.push_str(line);
+        text.push('\n');
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| format!("can't create {}: {}", dir.display(), err))?;
+    }
+    fs::write(&path, text).map_err(|err| format!("can't write {}: {}", path.display(), err))?;
+    Ok(path)
+}
@@ -0,0 +1,72 @@
+//! An optional frame around the sky, for nk embedded in a scratchpad or a
+//! tiling window manager's pane: a border in the theme's colors, a title,
+//! and tonight's moon in the corner.
+
+use nightsky_tui::render::color::Grade;
+use nightsky_tui::render::theme::Theme;
+use nightsky_tui::simulation::astro::MoonPhase;
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType},
+    Frame,
+};
+use std::str::FromStr;
+
+/// The title a frame has unless given another.
+pub const DEFAULT_TITLE: &str = "· night sky ·";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameStyle {
+    Rounded,
+    Plain,
+    Double,
+    Thick,
+}
+
+impl FrameStyle {
+    fn border_type(self) -> BorderType {
+        match self {
+            FrameStyle::Rounded => BorderType::Rounded,
+            FrameStyle::Plain => BorderType::Plain,
+            FrameStyle::Double => BorderType::Double,
+            FrameStyle::Thick => BorderType::Thick,
+        }
+    }
+}
+
+impl FromStr for FrameStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rounded" => Ok(FrameStyle::Rounded),
+            "plain" => Ok(FrameStyle::Plain),
+            "double" => Ok(FrameStyle::Double),
+            "thick" => Ok(FrameStyle::Thick),
+            other => Err(format!("unknown frame style '{}'", other)),
+        }
+    }
+}
+
+/// The sky's part of `area` once a frame is drawn around it.
+pub fn inner(area: Rect) -> Rect {
+    Block::bordered().inner(area)
+}
+
+/// Frame `area` in `style`, with `title` centred along the top unless it's
+/// empty. Draw this before the sky inside it, which it would paint over.
+pub fn render(frame: &mut Frame, area: Rect, style: FrameStyle, title: &str, theme: &Theme, grade: Grade) {
+    let moon = format!(" {} ", MoonPhase::now().glyph());
+    let mut block = Block::bordered()
+        .border_type(style.border_type())
+        .border_style(Style::default().fg(grade.apply(theme.stars[1])))
+        .style(Style::default().bg(grade.apply(theme.background)))
+        .title_top(Line::from(moon).right_aligned());
+    if !title.is_empty() {
+        let title = Line::from(format!(" {} ", title)).style(Style::default().fg(grade.apply(theme.stars[2])));
+        block = block.title_top(title.centered());
+    }
+    frame.render_widget(block, area);
+}
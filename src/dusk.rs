@@ -0,0 +1,79 @@
+//! Opening at dusk: the sky starts out in twilight, orange and purple low
+//! down under a deepening blue, and darkens into night as the stars come
+//! out, over a set time or along with the real sun.
+
+use nightsky_tui::render::scene;
+#[cfg(feature = "astronomy")]
+use nightsky_tui::simulation::{astro, projection::Observer};
+use std::str::FromStr;
+#[cfg(feature = "astronomy")]
+use std::time::SystemTime;
+use std::time::Instant;
+
+/// How far into twilight a dusk starts, on the renderer's scale from 0 at
+/// night to 1 at sunrise: the sun just down and the horizon still orange.
+const START: f32 = 0.8;
+// Degrees the sun is below the horizon once twilight is over
+#[cfg(feature = "astronomy")]
+const NIGHTFALL: f64 = 18.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DuskMode {
+    /// Darkening over this many seconds from launch
+    Over(f32),
+    /// Following the real sun down at the observer, and up again at dawn
+    #[cfg(feature = "astronomy")]
+    Sunset,
+}
+
+impl FromStr for DuskMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            #[cfg(feature = "astronomy")]
+            "sunset" => Ok(DuskMode::Sunset),
+            #[cfg(not(feature = "astronomy"))]
+            "sunset" => Err("--dusk sunset needs nk built with the 'astronomy' feature".to_string()),
+            other => scene::parse_time(other).map(DuskMode::Over),
+        }
+    }
+}
+
+pub struct Dusk {
+    mode: DuskMode,
+    started: Instant,
+    #[cfg(feature = "astronomy")]
+    observer: Observer,
+}
+
+impl Dusk {
+    /// A dusk starting now, for an observer at `latitude` and `longitude`
+    /// where given.
+    #[cfg_attr(not(feature = "astronomy"), allow(unused_variables))]
+    pub fn new(mode: DuskMode, latitude: Option<f32>, longitude: Option<f32>) -> Self {
+        Dusk {
+            mode,
+            started: Instant::now(),
+            #[cfg(feature = "astronomy")]
+            observer: Observer::new(latitude, longitude),
+        }
+    }
+
+    /// How much twilight is left, from 0 once it's night. Day, following
+    /// the real sun, counts as 1.
+    pub fn twilight(&self) -> f32 {
+        match self.mode {
+            DuskMode::Over(length) => {
+                let progress = self.started.elapsed().as_secs_f32() / length.max(f32::EPSILON);
+                START * (1.0 - progress.min(1.0))
+            }
+            #[cfg(feature = "astronomy")]
+            DuskMode::Sunset => {
+                let jd = astro::julian_day(SystemTime::now());
+                let altitude = astro::sun_altitude(jd, self.observer.latitude, self.observer.longitude);
+                ((altitude + NIGHTFALL) / NIGHTFALL).clamp(0.0, 1.0) as f32
+            }
+        }
+    }
+}
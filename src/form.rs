@@ -0,0 +1,261 @@
+//! A small keyboard-driven form drawn as an overlay: a list of toggles,
+//! choices, sliders and text fields, one of them selected.
+
+use crate::render::color::Grade;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+    Frame,
+};
+
+const BORDER: Color = Color::Rgb(110, 120, 160);
+const TEXT: Color = Color::Rgb(210, 210, 230);
+const DIM: Color = Color::Rgb(130, 130, 160);
+const SELECTED: Color = Color::Rgb(255, 230, 170);
+
+// Cells given to a slider's bar
+const SLIDER_WIDTH: usize = 10;
+
+#[derive(Clone, Debug)]
+pub enum Field {
+    Toggle(bool),
+    Choice {
+        choices: Vec<&'static str>,
+        index: usize,
+    },
+    Slider {
+        value: f32,
+        min: f32,
+        max: f32,
+        step: f32,
+    },
+    /// Free text, typed in while selected
+    Text(String),
+}
+
+impl Field {
+    /// A choice starting on `current`, or the first choice if it isn't one.
+    pub fn choice(choices: Vec<&'static str>, current: &str) -> Field {
+        let index = choices.iter().position(|c| *c == current).unwrap_or(0);
+        Field::Choice { choices, index }
+    }
+
+    pub fn slider(value: f32, min: f32, max: f32, step: f32) -> Field {
+        Field::Slider {
+            value: value.clamp(min, max),
+            min,
+            max,
+            step,
+        }
+    }
+
+    /// Step the value forwards or backwards. Choices wrap; sliders stop at
+    /// their ends.
+    fn adjust(&mut self, forward: bool) {
+        match self {
+            Field::Toggle(on) => *on = !*on,
+            Field::Choice { choices, index } => {
+                let len = choices.len().max(1);
+                *index = if forward {
+                    (*index + 1) % len
+                } else {
+                    (*index + len - 1) % len
+                };
+            }
+            Field::Text(_) => {}
+            Field::Slider {
+                value,
+                min,
+                max,
+                step,
+            } => {
+                let delta = if forward { *step } else { -*step };
+                // Snap to the step grid so repeated presses don't drift
+                *value = ((*value + delta - *min) / *step).round() * *step + *min;
+                *value = value.clamp(*min, *max);
+            }
+        }
+    }
+
+    fn display(&self) -> String {
+        match self {
+            Field::Toggle(on) => if *on { "[x]" } else { "[ ]" }.to_string(),
+            Field::Text(text) => format!("{}_", text),
+            Field::Choice { choices, index } => {
+                format!("< {} >", choices.get(*index).copied().unwrap_or(""))
+            }
+            Field::Slider {
+                value, min, max, ..
+            } => {
+                let filled = ((value - min) / (max - min) * SLIDER_WIDTH as f32).round() as usize;
+                format!(
+                    "{}{} {:.2}",
+                    "█".repeat(filled),
+                    "░".repeat(SLIDER_WIDTH - filled.min(SLIDER_WIDTH)),
+                    value
+                )
+            }
+        }
+    }
+}
+
+pub struct Item {
+    /// Stable name the owner reads the value back by
+    pub key: &'static str,
+    pub label: &'static str,
+    pub field: Field,
+}
+
+/// What a key press did to the form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FormEvent {
+    /// The key was used but nothing changed
+    Moved,
+    /// The named item's value changed
+    Changed(&'static str),
+    Submit,
+    Close,
+}
+
+pub struct Form {
+    pub title: String,
+    pub items: Vec<Item>,
+    /// Hints shown along the bottom, one or more lines
+    pub footer: String,
+    selected: usize,
+}
+
+impl Form {
+    pub fn new(title: &str, items: Vec<Item>, footer: &str) -> Self {
+        Form {
+            title: title.to_string(),
+            items,
+            footer: footer.to_string(),
+            selected: 0,
+        }
+    }
+
+    fn field(&self, key: &str) -> Option<&Field> {
+        self.items.iter().find(|i| i.key == key).map(|i| &i.field)
+    }
+
+    pub fn toggle(&self, key: &str) -> bool {
+        matches!(self.field(key), Some(Field::Toggle(true)))
+    }
+
+    pub fn choice(&self, key: &str) -> &'static str {
+        match self.field(key) {
+            Some(Field::Choice { choices, index }) => choices.get(*index).copied().unwrap_or(""),
+            _ => "",
+        }
+    }
+
+    pub fn slider(&self, key: &str) -> f32 {
+        match self.field(key) {
+            Some(Field::Slider { value, .. }) => *value,
+            _ => 0.0,
+        }
+    }
+
+    pub fn text(&self, key: &str) -> &str {
+        match self.field(key) {
+            Some(Field::Text(text)) => text,
+            _ => "",
+        }
+    }
+
+    /// Navigate with the arrows (or `hjkl`), flip toggles with space, submit
+    /// with Enter and close with Esc. Text fields take typed characters
+    /// instead. Keys the form doesn't use give `None`.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Option<FormEvent> {
+        if let Some(Item {
+            key: name,
+            field: Field::Text(text),
+            ..
+        }) = self.items.get_mut(self.selected)
+        {
+            match key.code {
+                KeyCode::Char(c) => {
+                    text.push(c);
+                    return Some(FormEvent::Changed(name));
+                }
+                KeyCode::Backspace => {
+                    text.pop();
+                    return Some(FormEvent::Changed(name));
+                }
+                _ => {}
+            }
+        }
+        let count = self.items.len().max(1);
+        let forward = match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = (self.selected + count - 1) % count;
+                return Some(FormEvent::Moved);
+            }
+            KeyCode::Down | KeyCode::Char('j') | KeyCode::Tab => {
+                self.selected = (self.selected + 1) % count;
+                return Some(FormEvent::Moved);
+            }
+            KeyCode::Enter => return Some(FormEvent::Submit),
+            KeyCode::Esc => return Some(FormEvent::Close),
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => true,
+            KeyCode::Left | KeyCode::Char('h') => false,
+            _ => return None,
+        };
+        let item = self.items.get_mut(self.selected)?;
+        item.field.adjust(forward);
+        Some(FormEvent::Changed(item.key))
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, background: Color, grade: Grade) {
+        let label_width = self.items.iter().map(|i| i.label.chars().count()).max().unwrap_or(0);
+        let lines: Vec<Line> = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let style = if i == self.selected {
+                    Style::default()
+                        .fg(grade.apply(SELECTED))
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(grade.apply(TEXT))
+                };
+                let marker = if i == self.selected { "› " } else { "  " };
+                Line::from(vec![
+                    Span::styled(marker, style),
+                    Span::styled(format!("{:<w$}  ", item.label, w = label_width), style),
+                    Span::styled(item.field.display(), style),
+                ])
+            })
+            .chain([Line::default()])
+            .chain(
+                self.footer
+                    .lines()
+                    .map(|line| Line::styled(line, Style::default().fg(grade.apply(DIM)))),
+            )
+            .collect();
+
+        let content = lines.iter().map(Line::width).max().unwrap_or(0);
+        let width = (content.max(self.title.chars().count() + 2) as u16 + 4).min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let form_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", self.title))
+            .padding(Padding::horizontal(1))
+            .border_style(Style::default().fg(grade.apply(BORDER)))
+            .style(Style::default().bg(grade.apply(background)));
+        frame.render_widget(Clear, form_area);
+        frame.render_widget(Paragraph::new(lines).block(block), form_area);
+    }
+}
@@ -0,0 +1,91 @@
+//! Gamepads, for a sky on the TV across the room: the sticks steer the view
+//! and the buttons do what keys do.
+
+use crate::input::Action;
+use gilrs::{Axis, Button, EventType, Gilrs};
+
+// Stick travel ignored around the centre, where sticks rarely rest at zero
+const DEAD_ZONE: f32 = 0.15;
+
+/// Sky cells panned per second with a stick pushed all the way.
+pub const PAN_SPEED: f32 = 30.0;
+
+/// How many times over the view zooms per second with a stick pushed all the
+/// way.
+pub const ZOOM_SPEED: f32 = 2.0;
+
+/// How the sticks are held, each from -1 to 1.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Steer {
+    /// Rightwards
+    pub pan_x: f32,
+    /// Downwards
+    pub pan_y: f32,
+    /// Positive to zoom in
+    pub zoom: f32,
+}
+
+impl Steer {
+    pub fn is_idle(&self) -> bool {
+        self.pan_x == 0.0 && self.pan_y == 0.0 && self.zoom == 0.0
+    }
+}
+
+pub struct Gamepad {
+    gilrs: Gilrs,
+}
+
+impl Gamepad {
+    /// Start listening for gamepads, or `None` where the platform offers no
+    /// way to. Pads plugged in later are picked up as they connect.
+    pub fn new() -> Option<Self> {
+        Gilrs::new().ok().map(|gilrs| Gamepad { gilrs })
+    }
+
+    /// Actions for the buttons pressed since the last call. Call this
+    /// often, as it also keeps the sticks' positions up to date.
+    pub fn poll(&mut self) -> Vec<Action> {
+        let mut actions = Vec::new();
+        while let Some(event) = self.gilrs.next_event() {
+            if let EventType::ButtonPressed(button, _) = event.event {
+                actions.extend(action_for(button));
+            }
+        }
+        actions
+    }
+
+    /// How the sticks of the first connected pad are held.
+    pub fn steer(&self) -> Steer {
+        let Some((_, pad)) = self.gilrs.gamepads().next() else {
+            return Steer::default();
+        };
+        let axis = |axis| dead_zone(pad.value(axis));
+        Steer {
+            pan_x: axis(Axis::LeftStickX),
+            // Sticks read positive pushed up, and the sky's rows run down
+            pan_y: -axis(Axis::LeftStickY),
+            zoom: axis(Axis::RightStickY),
+        }
+    }
+}
+
+fn action_for(button: Button) -> Option<Action> {
+    match button {
+        Button::South => Some(Action::LaunchMeteor),
+        Button::East => Some(Action::CycleFov),
+        Button::North => Some(Action::ToggleConstellations),
+        Button::West | Button::RightTrigger => Some(Action::CycleTheme),
+        Button::Select => Some(Action::ToggleHeatmap),
+        Button::Start => Some(Action::ToggleMinimap),
+        _ => None,
+    }
+}
+
+/// `value` with the dead zone cut out, rescaled so full travel is still 1.
+fn dead_zone(value: f32) -> f32 {
+    if value.abs() < DEAD_ZONE {
+        0.0
+    } else {
+        (value - DEAD_ZONE * value.signum()) / (1.0 - DEAD_ZONE)
+    }
+}
@@ -0,0 +1,167 @@
+//! User-supplied shell commands run on lifecycle events and on what
+//! happens in the sky.
+
+use crate::render::scene;
+use crate::simulation::events::SkyEvent;
+use chrono::Local;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::PathBuf,
+    process::{Command, ExitStatus, Stdio},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+fn shell(cmd: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(cmd);
+    command
+}
+
+/// Run `cmd` in the background while the sky is still on screen. Its output
+/// is discarded so it can't scribble over the alternate screen.
+pub fn spawn_detached(cmd: &str) -> io::Result<()> {
+    let mut child = shell(cmd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // Reap the child so it doesn't linger as a zombie
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Run `cmd` to completion with the terminal's stdio. Only call this once the
+/// terminal has been restored.
+pub fn run(cmd: &str) -> io::Result<ExitStatus> {
+    shell(cmd).status()
+}
+
+/// What sets off an event hook.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Trigger {
+    /// Each time this happens in the sky
+    Event(SkyEvent),
+    /// Once, when the sky has been up this many seconds
+    After(f32),
+}
+
+/// A shell command to run when something happens, written like
+/// `@aurora notify-send Aurora` or `1h notify-send "an hour of stars"`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventHook {
+    trigger: Trigger,
+    command: String,
+}
+
+impl FromStr for EventHook {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (trigger, command) = s
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| format!("expected a trigger and a command, like '@aurora CMD', got '{}'", s))?;
+        let trigger = match trigger.strip_prefix('@') {
+            Some(event) => Trigger::Event(event.parse()?),
+            None => Trigger::After(scene::parse_time(trigger)?),
+        };
+        Ok(EventHook {
+            trigger,
+            command: command.trim().to_string(),
+        })
+    }
+}
+
+/// Runs event hooks in the background as their triggers come round. A hook
+/// still running from last time isn't started again, so a burst of events
+/// can't pile up processes.
+pub struct EventHooks {
+    hooks: Vec<EventHook>,
+    /// Whether each hook's last run is still going
+    running: Vec<Arc<AtomicBool>>,
+    /// Where hook output is appended; without one it's discarded
+    log: Option<PathBuf>,
+}
+
+impl EventHooks {
+    pub fn new(hooks: &[EventHook], log: Option<PathBuf>) -> Self {
+        EventHooks {
+            hooks: hooks.to_vec(),
+            running: hooks.iter().map(|_| Arc::default()).collect(),
+            log,
+        }
+    }
+
+    /// Run the hooks for the events the sky just reported.
+    pub fn notify(&mut self, events: &[SkyEvent]) {
+        for &event in events {
+            for i in 0..self.hooks.len() {
+                if self.hooks[i].trigger == Trigger::Event(event) {
+                    self.fire(i, event.name());
+                }
+            }
+        }
+    }
+
+    /// Run the hooks that fall due now the sky has been up `seconds`.
+    pub fn elapsed(&mut self, seconds: f32) {
+        for i in 0..self.hooks.len() {
+            if let Trigger::After(after) = self.hooks[i].trigger {
+                if seconds >= after {
+                    self.fire(i, "time");
+                    // Each runs once a session
+                    self.hooks[i].trigger = Trigger::After(f32::INFINITY);
+                }
+            }
+        }
+    }
+
+    /// Start hook `i`, telling it what set it off in `$NK_EVENT`. A failing
+    /// hook shouldn't take the sky down with it, so errors only go to the
+    /// log.
+    fn fire(&mut self, i: usize, event: &str) {
+        let running = &self.running[i];
+        if running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let command = &self.hooks[i].command;
+        let mut log = self.log.as_ref().and_then(|path| {
+            File::options().create(true).append(true).open(path).ok()
+        });
+        if let Some(log) = &mut log {
+            let _ = writeln!(log, "[{}] {}: {}", Local::now().format("%H:%M:%S"), event, command);
+        }
+        let output = |log: &Option<File>| match log.as_ref().map(File::try_clone) {
+            Some(Ok(file)) => Stdio::from(file),
+            _ => Stdio::null(),
+        };
+        let child = shell(command)
+            .env("NK_EVENT", event)
+            .stdin(Stdio::null())
+            .stdout(output(&log))
+            .stderr(output(&log))
+            .spawn();
+        match child {
+            Ok(mut child) => {
+                let running = Arc::clone(running);
+                // Reap it, and let the hook run again once it's finished
+                thread::spawn(move || {
+                    let _ = child.wait();
+                    running.store(false, Ordering::SeqCst);
+                });
+            }
+            Err(err) => {
+                if let Some(log) = &mut log {
+                    let _ = writeln!(log, "failed to start: {}", err);
+                }
+                running.store(false, Ordering::SeqCst);
+            }
+        }
+    }
+}
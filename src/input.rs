@@ -0,0 +1,191 @@
+//! Keybindings: translating key presses into sky actions. Other devices,
+//! like gamepads, produce the same actions.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+// How quickly the second press must follow the first in double-press mode
+const DOUBLE_PRESS_WINDOW: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    /// The first half of a double-press quit; waiting for confirmation
+    QuitPending,
+    CycleFov,
+    /// Zoom the view in or out about the middle of the screen
+    ZoomIn,
+    ZoomOut,
+    /// Show or hide the cursor that describes the star under it
+    ToggleCursor,
+    /// Move the star cursor by a cell, or pan the view while it's hidden
+    Move(i16, i16),
+    /// Show fainter stars, or fewer
+    RaiseMagnitudeLimit,
+    LowerMagnitudeLimit,
+    ToggleHeatmap,
+    ToggleConstellations,
+    ToggleZodiac,
+    /// Pick out the next zodiac constellation, in zodiac mode
+    NextZodiac,
+    ToggleMilkyWay,
+    ToggleMinimap,
+    #[cfg(feature = "astronomy")]
+    ToggleRadiant,
+    /// Show or hide the alt-az grid over a catalog sky
+    #[cfg(feature = "astronomy")]
+    ToggleGrid,
+    /// Show or hide the Messier objects over a catalog sky
+    #[cfg(feature = "astronomy")]
+    ToggleDeepSky,
+    OpenSettings,
+    /// Find a star or constellation by name
+    Search,
+    LaunchMeteor,
+    CycleTheme,
+    SummonAurora,
+    LaunchStarlink,
+}
+
+/// A key plus modifiers, written like `q`, `esc` or `ctrl+q`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeySpec {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeySpec {
+    fn matches(&self, key: &KeyEvent) -> bool {
+        key.code == self.code && key.modifiers == self.modifiers
+    }
+}
+
+impl FromStr for KeySpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut parts: Vec<&str> = s.split('+').collect();
+        let key = parts.pop().unwrap_or_default();
+        for part in parts {
+            modifiers |= match part.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("unknown modifier '{}'", other)),
+            };
+        }
+        let code = match key.to_lowercase().as_str() {
+            "esc" => KeyCode::Esc,
+            "enter" => KeyCode::Enter,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            _ if key.chars().count() == 1 => KeyCode::Char(key.chars().next().unwrap_or(' ')),
+            _ => return Err(format!("unknown key '{}'", key)),
+        };
+        Ok(KeySpec { code, modifiers })
+    }
+}
+
+/// What it takes to quit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QuitMode {
+    /// A single `q` or Esc
+    #[default]
+    Single,
+    /// `q` or Esc twice in quick succession
+    Double,
+    /// Only this exact key combination
+    Chord(KeySpec),
+}
+
+impl FromStr for QuitMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "single" => Ok(QuitMode::Single),
+            "double" => Ok(QuitMode::Double),
+            chord => chord.parse().map(QuitMode::Chord),
+        }
+    }
+}
+
+pub struct Keymap {
+    quit: QuitMode,
+    /// Input is ignored until this moment
+    grace_until: Instant,
+    last_quit_press: Option<Instant>,
+}
+
+impl Keymap {
+    pub fn new(quit: QuitMode, grace: Duration) -> Self {
+        Keymap {
+            quit,
+            grace_until: Instant::now() + grace,
+            last_quit_press: None,
+        }
+    }
+
+    /// Whether the startup grace period is over.
+    pub fn accepting_input(&self) -> bool {
+        Instant::now() >= self.grace_until
+    }
+
+    pub fn action_for(&mut self, key: &KeyEvent) -> Option<Action> {
+        let is_quit_key = matches!(key.code, KeyCode::Char('q') | KeyCode::Esc);
+        match self.quit {
+            QuitMode::Single if is_quit_key => return Some(Action::Quit),
+            QuitMode::Double if is_quit_key => {
+                let now = Instant::now();
+                let confirmed = self
+                    .last_quit_press
+                    .is_some_and(|t| now.duration_since(t) <= DOUBLE_PRESS_WINDOW);
+                self.last_quit_press = Some(now);
+                return Some(if confirmed {
+                    Action::Quit
+                } else {
+                    Action::QuitPending
+                });
+            }
+            QuitMode::Chord(spec) if spec.matches(key) => return Some(Action::Quit),
+            _ => {}
+        }
+
+        match key.code {
+            KeyCode::Char('v') => Some(Action::CycleFov),
+            KeyCode::Char('i') => Some(Action::ToggleCursor),
+            KeyCode::Left => Some(Action::Move(-1, 0)),
+            KeyCode::Right => Some(Action::Move(1, 0)),
+            KeyCode::Up => Some(Action::Move(0, -1)),
+            KeyCode::Down => Some(Action::Move(0, 1)),
+            KeyCode::Char('+') | KeyCode::Char('=') => Some(Action::ZoomIn),
+            KeyCode::Char('-') => Some(Action::ZoomOut),
+            KeyCode::Char(']') => Some(Action::RaiseMagnitudeLimit),
+            KeyCode::Char('[') => Some(Action::LowerMagnitudeLimit),
+            KeyCode::Char('h') => Some(Action::ToggleHeatmap),
+            KeyCode::Char('c') => Some(Action::ToggleConstellations),
+            KeyCode::Char('z') => Some(Action::ToggleZodiac),
+            KeyCode::Tab => Some(Action::NextZodiac),
+            KeyCode::Char('w') => Some(Action::ToggleMilkyWay),
+            KeyCode::Char('m') => Some(Action::ToggleMinimap),
+            #[cfg(feature = "astronomy")]
+            KeyCode::Char('r') => Some(Action::ToggleRadiant),
+            #[cfg(feature = "astronomy")]
+            KeyCode::Char('g') => Some(Action::ToggleGrid),
+            #[cfg(feature = "astronomy")]
+            KeyCode::Char('d') => Some(Action::ToggleDeepSky),
+            KeyCode::Char('o') => Some(Action::OpenSettings),
+            KeyCode::Char('/') => Some(Action::Search),
+            KeyCode::Char(' ') => Some(Action::LaunchMeteor),
+            KeyCode::Char('t') => Some(Action::CycleTheme),
+            KeyCode::Char('a') => Some(Action::SummonAurora),
+            KeyCode::Char('s') => Some(Action::LaunchStarlink),
+            _ => None,
+        }
+    }
+}
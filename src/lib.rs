@@ -0,0 +1,66 @@
+//! The night sky behind `nk`, as a library.
+//!
+//! [`Simulation`] steps the sky and exposes the state of everything in it,
+//! without any terminal code, so other front ends (a GUI, a game) can drive
+//! it and draw it their own way:
+//!
+//! ```
+//! use nightsky_tui::{Simulation, SimulationOptions};
+//!
+//! let mut sky = Simulation::new(120, 40, SimulationOptions::default(), 7);
+//! for _ in 0..100 {
+//!     for event in sky.update(1.0) {
+//!         println!("{:?}", event);
+//!     }
+//! }
+//! for star in sky.stars() {
+//!     let _ = (star.x, star.y, star.brightness);
+//! }
+//! ```
+//!
+//! Queries find things by place and brightness, for overlays and
+//! interactions of your own:
+//!
+//! ```
+//! use nightsky_tui::simulation::query::Region;
+//! use nightsky_tui::{Simulation, SimulationOptions};
+//!
+//! let sky = Simulation::new(120, 40, SimulationOptions::default(), 7);
+//! let corner = Region::new(0.0, 0.0, 30.0, 10.0);
+//! for star in sky.stars_in(corner) {
+//!     assert!(star.x < 30.0 && star.y < 10.0);
+//! }
+//! let brightest = sky.brightest_stars(5);
+//! assert!(brightest.windows(2).all(|pair| pair[0].magnitude <= pair[1].magnitude));
+//! let moving = sky.meteors_in(corner).count() + sky.satellites_in(corner).count();
+//! println!("{} things moving in the corner", moving);
+//! ```
+//!
+//! [`Renderer`] draws a simulation into a ratatui buffer, as `nk` does.
+//! Callbacks registered with [`Renderer::on_frame`] receive every finished
+//! frame, for recording, analysing or streaming it:
+//!
+//! ```
+//! use nightsky_tui::{Renderer, Simulation, SimulationOptions};
+//! use ratatui::{buffer::Buffer, layout::Rect};
+//!
+//! let mut sky = Simulation::new(80, 24, SimulationOptions::default(), 7);
+//! let mut renderer = Renderer::new(Default::default());
+//! renderer.on_frame(|frame: &Buffer| println!("{} cells", frame.content.len()));
+//!
+//! let area = Rect::new(0, 0, 80, 24);
+//! let mut buf = Buffer::empty(area);
+//! for _ in 0..100 {
+//!     let events = sky.update(1.0);
+//!     renderer.update(&sky, &events, 1.0);
+//!     renderer.render(&sky, &mut buf, area);
+//! }
+//! ```
+
+pub mod render;
+#[cfg(feature = "serde")]
+pub mod saved;
+pub mod simulation;
+
+pub use render::{RenderOptions, Renderer};
+pub use simulation::{Simulation, SimulationOptions};
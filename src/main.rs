@@ -1,260 +1,826 @@
+mod ansi;
+mod auto;
+mod cli;
+mod config;
+mod decoration;
+mod dusk;
+mod form;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+mod hooks;
+mod input;
+#[cfg(feature = "network")]
+mod net;
+mod popup;
+mod record;
+mod replay;
+mod search;
+mod settings;
+mod setup;
+mod statusline;
+mod theme_check;
+#[cfg(feature = "astronomy")]
+mod tle;
+mod wake;
+mod watchdog;
+#[cfg(feature = "weather")]
+mod weather;
+
+use nightsky_tui::render::{self, Renderer};
+use nightsky_tui::simulation::{self, star_info::StarInfo, Satellite, Simulation, TICKS_PER_SECOND};
+#[cfg(feature = "astronomy")]
+use nightsky_tui::simulation::{astro, deep_sky::DeepSkyObject, passes::{NextPass, Pass}, projection::{self, Observer}};
+#[cfg(feature = "astronomy")]
+use chrono::{DateTime, Local};
+#[cfg(feature = "astronomy")]
+use std::sync::mpsc::Receiver;
+use cli::{Command, RecordOptions, ReplayOptions, SkyOptions, StatuslineOptions};
+#[cfg(feature = "network")]
+use cli::JoinOptions;
+use form::{Form, FormEvent};
+use input::{Action, Keymap};
+use popup::Popup;
+use record::CastWriter;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, MouseButton,
+        MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use rand::Rng;
+#[cfg(feature = "network")]
+use crossterm::{
+    cursor,
+    style::ResetColor,
+    terminal::{Clear, ClearType},
+};
 use ratatui::{
-    backend::CrosstermBackend,
-    layout::Rect,
-    style::{Color, Style},
-    widgets::{Block, Paragraph},
+    backend::{CrosstermBackend, TestBackend},
+    layout::{Position, Rect},
+    style::{Color, Modifier, Style},
+    widgets::Paragraph,
     Frame, Terminal,
 };
-use std::{error::Error, io, time::Duration};
+use std::{
+    error::Error,
+    io::{self, IsTerminal},
+    time::{Duration, Instant, SystemTime},
+};
 
-struct Star {
-    x: u16,
-    y: u16,
-    brightness: u8,
-    twinkle_speed: f32,
-}
+// How far [ and ] move the limiting magnitude
+const MAGNITUDE_STEP: f32 = 0.5;
 
-struct ShootingStar {
-    x: f32,
-    y: f32,
-    speed: f32,
-    lifetime: u32,
-    max_lifetime: u32,
+// How much closer + brings the view, and - takes it back out
+const ZOOM_STEP: f32 = 1.5;
+
+// Screen cells an arrow key pans the view across and up or down
+const PAN_COLUMNS: f32 = 4.0;
+const PAN_ROWS: f32 = 2.0;
+
+/// A short message shown in the corner of the sky.
+struct Toast {
+    text: String,
+    ticks_left: f32,
 }
 
-impl ShootingStar {
-    fn new(width: u16, height: u16) -> Self {
-        let mut rng = rand::thread_rng();
-        ShootingStar {
-            x: rng.gen_range(0..width) as f32,
-            y: rng.gen_range(0..height / 2) as f32,
-            speed: rng.gen_range(2.0..4.0),
-            lifetime: 0,
-            max_lifetime: rng.gen_range(15..30),
-        }
+/// Info box describing a satellite, beside screen cell `anchor`, with when
+/// the real one next comes over by `next_pass`.
+fn satellite_popup(satellite: &Satellite, next_pass: String, anchor: (u16, u16)) -> Popup {
+    let entry = satellite.entry;
+    Popup {
+        title: entry.name.to_string(),
+        lines: vec![
+            format!("altitude   {:.0} km", entry.altitude_km),
+            format!("magnitude  {:.1}", satellite.magnitude),
+            format!("speed      {:.2} km/s", entry.speed_km_s()),
+            format!("period     {:.1} min", entry.period_minutes()),
+            format!("in view    {:.0} s more", satellite.seconds_remaining()),
+            format!("next pass  {}", next_pass),
+        ],
+        anchor,
+        ticks_left: 10.0 * TICKS_PER_SECOND as f32,
     }
+}
 
-    fn update(&mut self) {
-        self.x += self.speed;
-        self.y += self.speed * 0.5;
-        self.lifetime += 1;
+/// Info box describing a star under the cursor at `anchor`. It stays up
+/// while the cursor does.
+fn star_popup(info: &StarInfo, anchor: (u16, u16)) -> Popup {
+    let mut lines = Vec::new();
+    if let Some(constellation) = info.constellation {
+        lines.push(format!("in         {}", constellation));
     }
-
-    fn is_alive(&self) -> bool {
-        self.lifetime < self.max_lifetime
+    lines.push(format!("magnitude  {:.1}", info.magnitude));
+    lines.push(format!("class      {:?}", info.class));
+    if let Some(variability) = info.variability {
+        lines.push(format!(
+            "varies     {}, {:.1} magnitudes every {:.0} minutes",
+            variability.variation.name(),
+            variability.amplitude,
+            variability.period / 60.0
+        ));
+    }
+    if let Some(companion) = info.companion {
+        lines.push(format!(
+            "companion  magnitude {:.1}, class {:?}",
+            companion.magnitude, companion.class
+        ));
+    }
+    if let Some(distance) = info.distance {
+        lines.push(format!("distance   {} light years", distance));
+    }
+    Popup {
+        title: info.name.unwrap_or("star").to_string(),
+        lines,
+        anchor,
+        ticks_left: f32::INFINITY,
     }
 }
 
-struct Satellite {
-    x: f32,
-    y: f32,
-    speed: f32,
-    blink_phase: f32,
+/// Start working out the bright satellites' visible passes over the day
+/// from Julian day `from`, leaving out the ISS if it's followed already.
+#[cfg(feature = "astronomy")]
+fn look_up_passes(options: &SkyOptions, from: f64) -> Receiver<Option<Vec<Pass>>> {
+    let observer = Observer::new(options.latitude, options.longitude);
+    tle::predict_in_background(observer, from, options.iss)
 }
 
-impl Satellite {
-    fn new(_width: u16, height: u16) -> Self {
-        let mut rng = rand::thread_rng();
-        Satellite {
-            x: 0.0,
-            y: rng.gen_range(5..height.saturating_sub(5)) as f32,
-            speed: rng.gen_range(0.3..0.8),
-            blink_phase: rng.gen_range(0.0..6.28),
-        }
-    }
-
-    fn update(&mut self, width: u16) {
-        self.x += self.speed;
-        self.blink_phase += 0.1;
-        
-        // Reset when off screen
-        if self.x > width as f32 {
-            self.x = 0.0;
-        }
+/// A box describing `object`, by screen cell `anchor`, until the cursor
+/// moves off it.
+#[cfg(feature = "astronomy")]
+fn deep_sky_popup(object: &DeepSkyObject, anchor: (u16, u16)) -> Popup {
+    let distance = if object.distance >= 1_000_000.0 {
+        format!("{:.1} million light years", object.distance / 1_000_000.0)
+    } else {
+        format!("{} light years", object.distance)
+    };
+    Popup {
+        title: format!("M{} {}", object.messier, object.name),
+        lines: vec![
+            format!("kind       {}", object.kind.name()),
+            format!("in         {}", object.constellation),
+            format!("magnitude  {:.1}", object.magnitude),
+            format!("size       {} arcminutes", object.size),
+            format!("distance   {}", distance),
+        ],
+        anchor,
+        ticks_left: f32::INFINITY,
     }
 }
 
+/// The simulated sky and everything about how it's shown in the terminal.
 struct NightSky {
-    stars: Vec<Star>,
-    shooting_stars: Vec<ShootingStar>,
-    satellites: Vec<Satellite>,
-    frame_count: u32,
-    width: u16,
-    height: u16,
+    sim: Simulation,
+    renderer: Renderer,
+    toast: Option<Toast>,
+    popup: Option<Popup>,
+    /// The sky cell the star cursor is on, while it's showing
+    cursor: Option<(u16, u16)>,
+    options: SkyOptions,
 }
 
 impl NightSky {
-    fn new(width: u16, height: u16) -> Self {
-        let mut rng = rand::thread_rng();
-        let star_count = ((width as usize * height as usize) / 20).min(300);
-        
-        let stars: Vec<Star> = (0..star_count)
-            .map(|_| Star {
-                x: rng.gen_range(0..width),
-                y: rng.gen_range(0..height),
-                brightness: rng.gen_range(1..=5),
-                twinkle_speed: rng.gen_range(0.1..0.5),
-            })
-            .collect();
-
-        // Initialize satellites (start with none, spawn randomly)
-        let satellites: Vec<Satellite> = Vec::new();
+    /// A sky for a `width` x `height` screen, inside the frame if it has
+    /// one.
+    fn new(width: u16, height: u16, options: SkyOptions) -> Self {
+        let seed = new_seed(&options);
+        let sky = options.sky_area(Rect::new(0, 0, width, height));
+        Self::with_seed(sky.width, sky.height, options, seed)
+    }
+
+    /// A sky whose whole course is determined by `seed`, so two skies with
+    /// the same seed, size and options stay identical tick for tick.
+    fn with_seed(width: u16, height: u16, mut options: SkyOptions, seed: u64) -> Self {
+        let sim = Simulation::new(width, height, options.simulation(), seed);
+        if options.tour {
+            options.scene = render::tour::scene(sim.landmarks(), sim.sky_model().center());
+        }
 
         NightSky {
-            stars,
-            shooting_stars: Vec::new(),
-            satellites,
-            frame_count: 0,
-            width,
-            height,
+            sim,
+            renderer: Renderer::new(options.rendering()),
+            toast: None,
+            popup: None,
+            cursor: None,
+            options,
         }
     }
 
-    fn update(&mut self) {
-        self.frame_count += 1;
-        let mut rng = rand::thread_rng();
+    fn cycle_fov(&mut self) {
+        let fov = self.renderer.cycle_fov();
+        self.show_toast(format!("view: {}", fov.name()));
+    }
+
+    fn cycle_theme(&mut self) {
+        let themes = render::theme::THEMES;
+        let current = themes.iter().position(|t| t.name == self.options.theme.name);
+        let mut options = self.options.clone();
+        options.theme = themes[current.map_or(0, |i| (i + 1) % themes.len())];
+        self.show_toast(format!("theme: {}", options.theme.name));
+        self.set_options(options);
+    }
+
+    /// Pan and zoom the view as the sticks are held, over `seconds`.
+    #[cfg(feature = "gamepad")]
+    fn steer(&mut self, steer: gamepad::Steer, seconds: f32) {
+        let (width, height) = self.size();
+        let mut view = self.renderer.view();
+        view.zoom = (view.zoom * gamepad::ZOOM_SPEED.powf(steer.zoom * seconds)).clamp(1.0, render::MAX_VIEW_ZOOM);
+        // Slower when zoomed in, so the sky moves across the screen at the
+        // same pace
+        let pan = gamepad::PAN_SPEED * seconds / view.zoom;
+        view.pan_x = (view.pan_x + steer.pan_x * pan).rem_euclid(width.max(1) as f32);
+        let reach = height as f32 / 2.0;
+        view.pan_y = (view.pan_y + steer.pan_y * pan).clamp(-reach, reach);
+        self.renderer.set_view(view);
+    }
 
-        // Spawn shooting stars randomly
-        if rng.gen_range(0..100) < 2 {
-            self.shooting_stars.push(ShootingStar::new(self.width, self.height));
+    fn zoom(&mut self, factor: f32) {
+        let zoom = self.renderer.zoom_by(&self.sim, factor);
+        self.show_toast(format!("zoom: {:.1}×", zoom));
+    }
+
+    fn step_magnitude_limit(&mut self, step: f32) {
+        let mut options = self.options.clone();
+        let naked_eye = simulation::fov::Fov::NakedEye.magnitude_limit();
+        let limit = options.limiting_magnitude.unwrap_or(naked_eye) + step;
+        let limit = limit.clamp(render::MIN_LIMITING_MAGNITUDE, render::MAX_LIMITING_MAGNITUDE);
+        options.limiting_magnitude = Some(limit);
+        self.show_toast(format!("limiting magnitude: {:.1}", limit));
+        self.set_options(options);
+    }
+
+    fn toggle_cursor(&mut self) {
+        if self.cursor.take().is_some() {
+            self.popup = None;
+            self.show_toast("star cursor: off".to_string());
+            return;
         }
+        let (width, height) = self.size();
+        self.cursor = Some((width / 2, height / 2));
+        self.inspect();
+        self.show_toast("star cursor: on".to_string());
+    }
 
-        // Update and remove dead shooting stars
-        for star in &mut self.shooting_stars {
-            star.update();
+    /// Move the star cursor by `dx`, `dy` cells, or while it's hidden pan
+    /// the view that way.
+    fn nudge(&mut self, dx: i16, dy: i16) {
+        let Some((x, y)) = self.cursor else {
+            self.renderer.pan_by(&self.sim, dx as f32 * PAN_COLUMNS, dy as f32 * PAN_ROWS);
+            return;
+        };
+        let (width, height) = self.size();
+        let x = x.saturating_add_signed(dx).min(width.saturating_sub(1));
+        let y = y.saturating_add_signed(dy).min(height.saturating_sub(1));
+        self.cursor = Some((x, y));
+        self.inspect();
+    }
+
+    /// Describe the star or deep-sky object under the cursor, if there is
+    /// one.
+    fn inspect(&mut self) {
+        let Some((x, y)) = self.cursor else {
+            return;
+        };
+        let info = self
+            .renderer
+            .star_at(&self.sim, x, y)
+            .and_then(|star| self.sim.star_info(star));
+        self.popup = info.map(|info| star_popup(&info, (x, y)));
+        // Failing a star, whatever deep-sky object's patch the cursor is on
+        #[cfg(feature = "astronomy")]
+        if self.popup.is_none() {
+            let object = self.renderer.deep_sky_at(&self.sim, x, y);
+            self.popup = object.map(|i| deep_sky_popup(self.sim.deep_sky()[i].object, (x, y)));
         }
-        self.shooting_stars.retain(|s| s.is_alive() && s.x < self.width as f32);
+    }
 
-        // Spawn satellites rarely (1% chance per frame, max 1 satellite)
-        if self.satellites.is_empty() && rng.gen_range(0..300) < 1 {
-            self.satellites.push(Satellite::new(self.width, self.height));
+    /// Find the star or constellation called `name`: pan a catalog sky to
+    /// centre it, and pick it out with a marker.
+    fn go_to(&mut self, name: &str) {
+        if name.is_empty() {
+            return;
+        }
+        let Some(found) = self.sim.find(name) else {
+            self.show_toast(format!("no '{}' in this sky", name));
+            return;
+        };
+        let stars = self.sim.stars();
+        let up: Vec<usize> = found.stars.into_iter().filter(|&i| stars[i].altitude > 0.0).collect();
+        if up.is_empty() {
+            self.show_toast(format!("{} is below the horizon", found.name));
+            return;
+        }
+        #[cfg(feature = "astronomy")]
+        if self.sim.options().catalog {
+            // Averaged across the sky's wrap, from the first star
+            let span = self.sim.width().max(1) as f32;
+            let (x0, y0) = (stars[up[0]].x, stars[up[0]].y);
+            let (dx, dy) = up.iter().fold((0.0, 0.0), |(dx, dy), &i| {
+                let star = &stars[i];
+                (dx + (star.x - x0 + span / 2.0).rem_euclid(span) - span / 2.0, dy + star.y - y0)
+            });
+            let count = up.len() as f32;
+            self.renderer.center_on(&self.sim, x0 + dx / count, y0 + dy / count);
         }
+        self.renderer.mark(up);
+        self.show_toast(format!("found: {}", found.name));
+    }
+
+    fn toggle_heatmap(&mut self) {
+        let state = if self.renderer.toggle_heatmap() { "on" } else { "off" };
+        self.show_toast(format!("activity heatmap: {}", state));
+    }
+
+    fn toggle_constellations(&mut self) {
+        let state = if self.renderer.toggle_constellations() { "shown" } else { "hidden" };
+        self.show_toast(format!("constellations: {}", state));
+    }
 
-        // Update satellites and remove those that have crossed the screen
-        for satellite in &mut self.satellites {
-            satellite.update(self.width);
+    fn toggle_zodiac(&mut self) {
+        let Some(first) = self.sim.zodiac().next() else {
+            self.show_toast("no zodiac constellations in this sky".to_string());
+            return;
+        };
+        let name = first.constellation.name;
+        let state = if self.renderer.toggle_zodiac() { name } else { "off" };
+        self.show_toast(format!("zodiac: {}", state));
+    }
+
+    fn next_zodiac(&mut self) {
+        if let Some(constellation) = self.renderer.next_zodiac(&self.sim) {
+            self.show_toast(format!("zodiac: {}", constellation.name));
         }
-        self.satellites.retain(|s| s.x < self.width as f32);
+    }
 
+    fn summon_aurora(&mut self) {
+        if !self.sim.summon_aurora() {
+            self.show_toast("an aurora is already showing".to_string());
+        }
     }
 
-    fn render(&self, frame: &mut Frame, area: Rect) {
-        // Fill entire area with dark blue/black background using a block widget
-        // This is safer than direct buffer access
-        let block = Block::default()
-            .style(Style::default().bg(Color::Rgb(10, 10, 30)));
-        frame.render_widget(block, area);
-
-        // Render stars
-        for star in &self.stars {
-            if star.x < area.width && star.y < area.height {
-                // Create twinkling effect
-                let twinkle = ((self.frame_count as f32 * star.twinkle_speed).sin() + 1.0) / 2.0;
-                let brightness = (star.brightness as f32 * twinkle) as u8;
-                
-                let color = match brightness {
-                    0..=1 => Color::Rgb(100, 100, 120),
-                    2 => Color::Rgb(150, 150, 180),
-                    3 => Color::Rgb(200, 200, 220),
-                    4 => Color::Rgb(230, 230, 250),
-                    _ => Color::Rgb(255, 255, 255),
-                };
+    fn launch_starlink(&mut self) {
+        if !self.sim.launch_starlink() {
+            self.show_toast("a starlink train is already crossing".to_string());
+        }
+    }
 
-                let star_char = match brightness {
-                    0..=1 => "·",
-                    2..=3 => "•",
-                    _ => "✦",
-                };
+    fn toggle_milky_way(&mut self) {
+        let state = if self.renderer.toggle_milky_way() { "shown" } else { "hidden" };
+        self.show_toast(format!("milky way: {}", state));
+    }
 
-                let star_widget = Paragraph::new(star_char)
-                    .style(Style::default().fg(color));
-                
-                let star_area = Rect {
-                    x: area.x + star.x,
-                    y: area.y + star.y,
-                    width: 1,
-                    height: 1,
-                };
-                frame.render_widget(star_widget, star_area);
-            }
-        }
-
-        // Render shooting stars
-        for shooting_star in &self.shooting_stars {
-            let x = shooting_star.x as u16;
-            let y = shooting_star.y as u16;
-            
-            if x < area.width && y < area.height {
-                // Main shooting star
-                let star_widget = Paragraph::new("☄")
-                    .style(Style::default().fg(Color::Rgb(255, 200, 100)));
-                
-                let star_area = Rect {
-                    x: area.x + x,
-                    y: area.y + y,
-                    width: 1,
-                    height: 1,
+    fn toggle_minimap(&mut self) {
+        let state = if self.renderer.toggle_minimap() { "shown" } else { "hidden" };
+        self.show_toast(format!("minimap: {}", state));
+    }
+
+    #[cfg(feature = "astronomy")]
+    fn toggle_grid(&mut self) {
+        if self.sim.observer().is_none() {
+            self.show_toast("the grid needs a --catalog sky".to_string());
+            return;
+        }
+        let state = if self.renderer.toggle_grid() { "shown" } else { "hidden" };
+        self.show_toast(format!("alt-az grid: {}", state));
+    }
+
+    #[cfg(feature = "astronomy")]
+    fn toggle_deep_sky(&mut self) {
+        if self.sim.deep_sky().is_empty() {
+            self.show_toast("deep-sky objects need a --catalog sky".to_string());
+            return;
+        }
+        let state = if self.renderer.toggle_deep_sky() { "shown" } else { "hidden" };
+        self.show_toast(format!("deep-sky objects: {}", state));
+    }
+
+    #[cfg(feature = "astronomy")]
+    fn toggle_radiant(&mut self) {
+        let Some(shower) = self.sim.meteor_shower() else {
+            self.show_toast("no meteor shower tonight".to_string());
+            return;
+        };
+        let name = shower.shower.name;
+        let state = if self.renderer.toggle_radiant() { "shown" } else { "hidden" };
+        self.show_toast(format!("{} radiant: {}", name, state));
+    }
+
+    /// Brighten the sky towards sunrise and liven it up, `progress` from 0
+    /// overnight to 1 at the alarm.
+    fn set_dawn(&mut self, progress: f32) {
+        let mut options = self.options.simulation();
+        let activity = 1.0 + wake::DAWN_ACTIVITY * progress;
+        options.meteor_rate *= activity;
+        options.satellite_rate *= activity;
+        self.sim.set_options(options);
+        self.renderer.set_dawn(progress);
+    }
+
+    /// Respond to a left click on screen cell `(x, y)`: close an open
+    /// popup, describe a clicked satellite in realism mode, or otherwise
+    /// launch a meteor from there.
+    fn click(&mut self, x: u16, y: u16) {
+        if self.popup.take().is_some() {
+            return;
+        }
+        let (sx, sy) = self.renderer.sky_point(&self.sim, x, y);
+        if self.options.realism {
+            let hit = self
+                .sim
+                .satellites()
+                .iter()
+                .find(|s| (s.x - sx).abs() <= 1.5 && (s.y - sy).abs() <= 1.0);
+            if let Some(satellite) = hit {
+                #[cfg(feature = "astronomy")]
+                let next_pass = match self.sim.next_pass(satellite.entry.name) {
+                    NextPass::Unknown => "unknown (no TLE data)".to_string(),
+                    NextPass::NoneSoon => "none in the next day".to_string(),
+                    NextPass::At(jd) => DateTime::<Local>::from(astro::time_of(jd)).format("%a %H:%M").to_string(),
                 };
-                frame.render_widget(star_widget, star_area);
-                
-                // Trail
-                for i in 1..4 {
-                    let trail_x = (shooting_star.x - (i as f32 * 0.5)) as i32;
-                    let trail_y = (shooting_star.y - (i as f32 * 0.25)) as i32;
-                    
-                    if trail_x >= 0 && trail_y >= 0 && (trail_x as u16) < area.width && (trail_y as u16) < area.height {
-                        let trail_widget = Paragraph::new("·")
-                            .style(Style::default().fg(Color::Rgb(200, 150, 50)));
-                        
-                        let trail_area = Rect {
-                            x: area.x + trail_x as u16,
-                            y: area.y + trail_y as u16,
-                            width: 1,
-                            height: 1,
-                        };
-                        frame.render_widget(trail_widget, trail_area);
-                    }
+                #[cfg(not(feature = "astronomy"))]
+                let next_pass = "unknown (no TLE data)".to_string();
+                self.popup = Some(satellite_popup(satellite, next_pass, (x, y)));
+                return;
+            }
+        }
+        self.launch_meteor(sx as u16, sy as u16);
+    }
+
+    /// The sky cell showing in screen cell `(x, y)` of the sky's area.
+    fn sky_cell_at(&self, x: u16, y: u16) -> (u16, u16) {
+        let (x, y) = self.renderer.sky_point(&self.sim, x, y);
+        (x as u16, y.max(0.0) as u16)
+    }
+
+    /// Launch a meteor from a clicked cell.
+    fn launch_meteor(&mut self, x: u16, y: u16) {
+        self.sim.launch_meteor(x as f32, y as f32);
+    }
+
+    /// Take on changed options without rebuilding the sky. Star placement
+    /// only changes with a new sky.
+    fn set_options(&mut self, options: SkyOptions) {
+        self.sim.set_options(options.simulation());
+        self.renderer.set_options(options.rendering());
+        self.options = options;
+    }
+
+    /// The sky's size in cells.
+    fn size(&self) -> (u16, u16) {
+        (self.sim.width(), self.sim.sky_model().horizon_y)
+    }
+
+    /// The size of the screen the sky was built for, frame and all.
+    fn screen_size(&self) -> (u16, u16) {
+        let (width, height) = self.size();
+        match self.options.frame {
+            Some(_) => (width + 2, height + 2),
+            None => (width, height),
+        }
+    }
+
+    /// The sky cell under the screen cell at `column`, `row`, unless that's
+    /// on the frame.
+    fn sky_cell(&self, column: u16, row: u16) -> Option<(u16, u16)> {
+        let (width, height) = self.screen_size();
+        let sky = self.options.sky_area(Rect::new(0, 0, width, height));
+        sky.contains(Position::new(column, row))
+            .then(|| (column - sky.x, row - sky.y))
+    }
+
+    fn show_toast(&mut self, text: String) {
+        self.toast = Some(Toast {
+            text,
+            ticks_left: 2.0 * TICKS_PER_SECOND as f32,
+        });
+    }
+
+    /// Advance the simulation by `dt` ticks, returning what happened in
+    /// the sky.
+    fn update(&mut self, dt: f32) -> Vec<simulation::events::SkyEvent> {
+        if let Some(popup) = &mut self.popup {
+            popup.update(dt);
+            if popup.is_done() {
+                self.popup = None;
+            }
+        }
+
+        if let Some(toast) = &mut self.toast {
+            toast.ticks_left -= dt;
+            if toast.ticks_left <= 0.0 {
+                self.toast = None;
+            }
+        }
+
+        let events = self.sim.update(dt);
+        if events.contains(&simulation::events::SkyEvent::Conjunction) {
+            let names: Vec<_> = self
+                .sim
+                .satellites()
+                .iter()
+                .filter(|satellite| satellite.is_near_miss())
+                .map(|satellite| satellite.entry.name)
+                .collect();
+            match names[..] {
+                [first, second, ..] if first == second => {
+                    self.show_toast(format!("conjunction: two {} passes within a whisker", first));
+                }
+                [first, second, ..] => {
+                    self.show_toast(format!("conjunction: {} and {} pass within a whisker", first, second));
                 }
+                _ => {}
             }
         }
+        if events.contains(&simulation::events::SkyEvent::Supernova) {
+            self.show_toast("supernova: a star too faint to see flares up brighter than any other".to_string());
+        }
+        self.renderer.update(&self.sim, &events, dt);
+        // Stars move under a still cursor as the sky turns
+        self.inspect();
+        events
+    }
+
+    /// Draw the sky with the popup and toast over it, leaving the frame to
+    /// be finished.
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let background = self.options.theme.background;
+        let grade = self.renderer.grade();
+        if let Some(style) = self.options.frame {
+            let title = &self.options.frame_title;
+            decoration::render(frame, area, style, title, &self.options.theme, grade);
+        }
+        let area = self.options.sky_area(area);
+        self.renderer.draw(&self.sim, frame.buffer_mut(), area);
 
-        // Render satellites
-        for satellite in &self.satellites {
-            let x = satellite.x as u16;
-            let y = satellite.y as u16;
-            
-            if x < area.width && y < area.height {
-                // Blinking effect
-                let blink = (satellite.blink_phase.sin() + 1.0) / 2.0;
-                let brightness = (200.0 + blink * 55.0) as u8;
-                
-                let satellite_widget = Paragraph::new("◆")
-                    .style(Style::default().fg(Color::Rgb(brightness, brightness, brightness + 50)));
-                
-                let satellite_area = Rect {
-                    x: area.x + x,
-                    y: area.y + y,
-                    width: 1,
-                    height: 1,
-                };
-                frame.render_widget(satellite_widget, satellite_area);
+        if let Some((x, y)) = self.cursor {
+            if let Some(cell) = frame.buffer_mut().cell_mut((area.x + x, area.y + y)) {
+                cell.modifier.insert(Modifier::REVERSED);
             }
         }
 
+        if let Some(popup) = &self.popup {
+            popup.render(frame, area, background, grade);
+        }
+
+        if let Some(toast) = &self.toast {
+            let toast_widget = Paragraph::new(toast.text.as_str())
+                .style(Style::default().fg(grade.apply(Color::Rgb(150, 150, 180))));
+            let toast_area = Rect {
+                x: area.x + 1,
+                y: area.y + area.height.saturating_sub(1),
+                width: (toast.text.chars().count() as u16).min(area.width.saturating_sub(1)),
+                height: 1,
+            };
+            frame.render_widget(toast_widget, toast_area);
+        }
     }
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let command = match cli::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("nk: {}\n\n{}", err, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    // Drawing into a file or pipe would only fill it with escape codes
+    let command = if io::stdout().is_terminal() {
+        command
+    } else {
+        without_terminal(command)
+    };
+
+    match command {
+        Command::Sky(opts) => run_sky(opts, Session::Local),
+        Command::Statusline(opts) => Ok(statusline::run(&opts)?),
+        Command::CheckTheme(theme) => {
+            let themes = match theme {
+                Some(theme) => vec![theme],
+                None => render::theme::THEMES.to_vec(),
+            };
+            if !theme_check::run(&themes) {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Command::Record(opts) => Ok(run_record(opts)?),
+        Command::Replay(opts) => run_replay(opts),
+        #[cfg(feature = "network")]
+        Command::Host(opts) => {
+            let (width, height) = crossterm::terminal::size()?;
+            let sky = opts.sky.for_size(width, height);
+            // Guests are sent the sky's own size, inside any frame
+            let Rect { width, height, .. } = sky.sky_area(Rect::new(0, 0, width, height));
+            let seed = new_seed(&sky);
+            let hello = net::Message::Hello {
+                seed,
+                width,
+                height,
+                tick_rate: sky.tick_rate,
+                options: sky.shared_args(),
+            };
+            let host = match net::Host::bind(&opts.listen, hello) {
+                Ok(host) => host,
+                Err(err) => {
+                    eprintln!("nk: can't listen on {}: {}", opts.listen, err);
+                    std::process::exit(1);
+                }
+            };
+            let session = Session::Host {
+                host,
+                seed,
+                width,
+                height,
+            };
+            run_sky(sky, session)
+        }
+        #[cfg(feature = "network")]
+        Command::Mirror(opts) => run_mirror(opts),
+        #[cfg(feature = "network")]
+        Command::Join(mut opts) => {
+            let joined = net::Guest::connect(&opts.addr).and_then(|(guest, hello)| {
+                let net::Message::Hello {
+                    seed,
+                    width,
+                    height,
+                    tick_rate,
+                    options,
+                } = hello
+                else {
+                    unreachable!("connect only returns a greeting");
+                };
+                opts.sky.apply_shared(&options)?;
+                opts.sky.tick_rate = tick_rate;
+                Ok(Session::Guest {
+                    guest,
+                    seed,
+                    width,
+                    height,
+                })
+            });
+            match joined {
+                Ok(session) => run_sky(opts.sky, session),
+                Err(err) => {
+                    eprintln!("nk: {}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Help => {
+            print!("{}", cli::USAGE);
+            Ok(())
+        }
+    }
+}
+
+/// Which form is open over the sky.
+#[derive(Clone, Copy)]
+enum Overlay {
+    Settings,
+    /// First-run setup
+    Setup,
+    /// Finding a star or constellation by name
+    Search,
+}
+
+/// Pass a key to the open form, if any, acting on what it did. Returns
+/// whether the form used the key.
+fn overlay_key(night_sky: &mut NightSky, overlay: &mut Option<(Overlay, Form)>, key: &KeyEvent) -> bool {
+    let Some((kind, form)) = overlay else {
+        return false;
+    };
+    let Some(event) = form.handle_key(key) else {
+        return false;
+    };
+    let mut options = night_sky.options.clone();
+    let (width, height) = night_sky.screen_size();
+    match (*kind, event) {
+        (_, FormEvent::Moved) => {}
+        (Overlay::Settings, FormEvent::Changed(item)) => {
+            settings::apply(form, &mut options);
+            if settings::needs_rebuild(item) {
+                *night_sky = NightSky::new(width, height, options);
+            } else {
+                night_sky.set_options(options);
+            }
+        }
+        (Overlay::Settings, FormEvent::Submit) => {
+            *overlay = None;
+            match settings::save(&night_sky.options) {
+                Ok(path) => night_sky.show_toast(format!("saved to {}", path.display())),
+                Err(err) => night_sky.show_toast(err),
+            }
+        }
+        // A half-typed latitude isn't valid yet; it's checked on saving
+        (Overlay::Setup, FormEvent::Changed(_)) => {
+            if setup::apply(form, &mut options).is_ok() {
+                night_sky.set_options(options);
+            }
+        }
+        (Overlay::Setup, FormEvent::Submit) => {
+            match setup::apply(form, &mut options).and_then(|()| setup::save(&options)) {
+                Ok(path) => {
+                    *overlay = None;
+                    // The latitude moves shower radiants, so start afresh
+                    *night_sky = NightSky::new(width, height, options);
+                    night_sky.show_toast(format!("saved to {}", path.display()));
+                }
+                Err(err) => night_sky.show_toast(err),
+            }
+        }
+        (Overlay::Search, FormEvent::Changed(_)) => {}
+        (Overlay::Search, FormEvent::Submit) => {
+            let name = search::name(form).to_string();
+            *overlay = None;
+            night_sky.go_to(&name);
+        }
+        (Overlay::Settings | Overlay::Search, FormEvent::Close) => *overlay = None,
+        (Overlay::Setup, FormEvent::Close) => {
+            *overlay = None;
+            if let Err(err) = setup::skip() {
+                night_sky.show_toast(err);
+            }
+        }
+    }
+    true
+}
+
+/// The seed for a new sky: the day's with `--daily`, otherwise a random one.
+fn new_seed(options: &SkyOptions) -> u64 {
+    if options.daily {
+        simulation::daily::seed(simulation::astro::julian_day(SystemTime::now()))
+    } else {
+        rand::random()
+    }
+}
+
+/// Where the sky's simulation comes from.
+enum Session {
+    /// Simulated here, for this terminal only
+    Local,
+    /// Simulated here and streamed to guests
+    #[cfg(feature = "network")]
+    Host {
+        host: net::Host,
+        seed: u64,
+        width: u16,
+        height: u16,
+    },
+    /// Following a host's stream
+    #[cfg(feature = "network")]
+    Guest {
+        guest: net::Guest,
+        seed: u64,
+        width: u16,
+        height: u16,
+    },
+}
+
+impl Session {
+    /// Whether the sky steps on its own clock rather than a host's.
+    fn steps_locally(&self) -> bool {
+        match self {
+            #[cfg(feature = "network")]
+            Session::Guest { .. } => false,
+            _ => true,
+        }
+    }
+
+    /// Build the sky for a `width` x `height` terminal. Shared skies keep the
+    /// host's size so every copy simulates the same thing.
+    fn build_sky(&self, width: u16, height: u16, options: SkyOptions) -> NightSky {
+        match *self {
+            Session::Local => NightSky::new(width, height, options),
+            #[cfg(feature = "network")]
+            Session::Host {
+                seed,
+                width,
+                height,
+                ..
+            }
+            | Session::Guest {
+                seed,
+                width,
+                height,
+                ..
+            } => NightSky::with_seed(width, height, options, seed),
+        }
+    }
+}
+
+fn run_sky(options: SkyOptions, mut session: Session) -> Result<(), Box<dyn Error>> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    if options.alt_screen {
+        execute!(stdout, EnterAlternateScreen)?;
+    }
+    let mouse = options.mouse_capture();
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.hide_cursor()?;
@@ -262,52 +828,647 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     // Get initial terminal size
     let size = terminal.size()?;
-    let mut night_sky = NightSky::new(size.width, size.height);
+    let on_exit = options.on_exit.clone();
+    let alt_screen = options.alt_screen;
+    // Shared skies keep the options they started with
+    let base = options.clone();
+    let options = match session {
+        Session::Local => options.for_size(size.width, size.height),
+        #[cfg(feature = "network")]
+        _ => options,
+    };
+    let mut night_sky = session.build_sky(size.width, size.height, options);
 
-    let res = run_app(&mut terminal, &mut night_sky);
+    let res = run_app(&mut terminal, &mut night_sky, &mut session, &base);
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    if mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    if alt_screen {
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    } else {
+        // Don't leave the last frame behind in the shell
+        terminal.clear()?;
+    }
     terminal.show_cursor()?;
 
     if let Err(err) = res {
-        println!("Error: {:?}", err)
+        eprintln!("nk: {}", err)
+    }
+
+    // Hooks run only once the terminal is back to normal
+    if let Some(cmd) = on_exit {
+        if let Err(err) = hooks::run(&cmd) {
+            eprintln!("nk: failed to run --on-exit command: {}", err);
+        }
     }
 
     Ok(())
 }
 
-fn run_app<B: ratatui::backend::Backend>(
+/// What to run instead of `command` when stdout isn't a terminal. The sky
+/// gives way to a stream of status lines; the other live views have no text
+/// to fall back on, so nk exits.
+fn without_terminal(command: Command) -> Command {
+    let needs_terminal = || -> ! {
+        eprintln!("nk: stdout isn't a terminal (use nk record to render the sky to a file)");
+        std::process::exit(1);
+    };
+    match command {
+        Command::Sky(_) => {
+            eprintln!("nk: stdout isn't a terminal, streaming the status line instead");
+            Command::Statusline(StatuslineOptions::default())
+        }
+        Command::Replay(_) => needs_terminal(),
+        #[cfg(feature = "network")]
+        Command::Host(_) | Command::Join(_) | Command::Mirror(_) => needs_terminal(),
+        command => command,
+    }
+}
+
+/// Simulate `options.duration` seconds off-screen and write them to an
+/// asciicast file, as fast as the machine allows.
+fn run_record(options: RecordOptions) -> io::Result<()> {
+    let mut terminal = Terminal::new(TestBackend::new(options.width, options.height))?;
+    let mut cast = CastWriter::create(&options.path, options.width, options.height)?;
+    let mut sky = options.sky.for_size(options.width, options.height);
+    if sky.auto {
+        sky = auto::choose(&sky, auto::Season::now(sky.latitude), auto::outside_now());
+    }
+    let sky = &sky;
+    let tick_interval = 1.0 / sky.tick_rate;
+    let frame_interval = 1.0 / sky.frame_rate();
+    let dt = TICKS_PER_SECOND as f32 / sky.tick_rate;
+    let mut night_sky = NightSky::new(options.width, options.height, sky.clone());
+
+    let (mut next_tick, mut next_frame) = (0.0, 0.0);
+    while next_frame <= options.duration {
+        while next_tick <= next_frame {
+            night_sky.update(dt);
+            next_tick += tick_interval;
+        }
+        let completed = terminal.draw(|f| {
+            night_sky.render(f, f.area());
+            night_sky.renderer.finish(f.buffer_mut());
+        })?;
+        cast.frame(next_frame, completed.buffer)?;
+        next_frame += frame_interval;
+    }
+    cast.finish()
+}
+
+fn run_replay(options: ReplayOptions) -> Result<(), Box<dyn Error>> {
+    let mut replay = match replay::Replay::open(&options.path) {
+        Ok(replay) => replay,
+        Err(err) => {
+            eprintln!("nk: can't replay {}: {}", options.path, err);
+            std::process::exit(1);
+        }
+    };
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    let alt_screen = options.sky.alt_screen;
+    if alt_screen {
+        execute!(stdout, EnterAlternateScreen)?;
+    }
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+    terminal.hide_cursor()?;
+    terminal.clear()?;
+
+    let res = play_replay(&mut terminal, &mut replay, &options.sky);
+
+    disable_raw_mode()?;
+    if alt_screen {
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    } else {
+        terminal.clear()?;
+    }
+    terminal.show_cursor()?;
+
+    if let Err(err) = res {
+        eprintln!("nk: {}", err)
+    }
+    Ok(())
+}
+
+fn play_replay<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
-    night_sky: &mut NightSky,
+    replay: &mut replay::Replay,
+    options: &SkyOptions,
 ) -> io::Result<()> {
+    let mut keymap = Keymap::new(options.quit, options.grace);
+    let frame_interval = Duration::from_secs_f32(1.0 / options.frame_rate());
+    let mut last_frame = Instant::now();
     loop {
+        let now = Instant::now();
+        replay.advance(now.duration_since(last_frame).as_secs_f32());
+        last_frame = now;
         terminal.draw(|f| {
-            night_sky.render(f, f.area());
+            let area = f.area();
+            replay.render(f.buffer_mut(), area);
         })?;
 
-        // Handle events with timeout for animation
-        if event::poll(Duration::from_millis(50))? {
-            match event::read()? {
-                Event::Key(key) => {
-                    if key.code == KeyCode::Char('q') || key.code == KeyCode::Esc {
+        if !event::poll(frame_interval)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        match key.code {
+            KeyCode::Left => replay.seek(-replay::SEEK_STEP),
+            KeyCode::Right => replay.seek(replay::SEEK_STEP),
+            KeyCode::Home => replay.seek_to(0.0),
+            KeyCode::End => replay.seek_to(f32::INFINITY),
+            KeyCode::Up | KeyCode::Char('+') | KeyCode::Char('=') => replay.faster(),
+            KeyCode::Down | KeyCode::Char('-') => replay.slower(),
+            KeyCode::Char(' ') => replay.toggle_pause(),
+            _ if keymap.accepting_input() && keymap.action_for(&key) == Some(Action::Quit) => {
+                return Ok(())
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Show the frames a host streams until either side quits.
+#[cfg(feature = "network")]
+fn run_mirror(options: JoinOptions) -> Result<(), Box<dyn Error>> {
+    let mirror = match net::Mirror::connect(&options.addr) {
+        Ok(mirror) => mirror,
+        Err(err) => {
+            eprintln!("nk: {}", err);
+            std::process::exit(1);
+        }
+    };
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    let alt_screen = options.sky.alt_screen;
+    if alt_screen {
+        execute!(stdout, EnterAlternateScreen)?;
+    }
+    execute!(stdout, cursor::Hide, Clear(ClearType::All))?;
+
+    let res = show_mirror(&mut stdout, &mirror, &options.sky);
+
+    execute!(stdout, ResetColor, cursor::Show)?;
+    if alt_screen {
+        execute!(stdout, LeaveAlternateScreen)?;
+    } else {
+        execute!(stdout, Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+    }
+    disable_raw_mode()?;
+
+    if let Err(err) = res {
+        eprintln!("nk: {}", err)
+    }
+    Ok(())
+}
+
+#[cfg(feature = "network")]
+fn show_mirror(out: &mut impl io::Write, mirror: &net::Mirror, options: &SkyOptions) -> io::Result<()> {
+    let mut keymap = Keymap::new(options.quit, options.grace);
+    loop {
+        let Some(frames) = mirror.poll() else {
+            return Err(io::Error::other("the host ended the shared sky"));
+        };
+        for frame in frames {
+            // The host's frames are untrusted
+            out.write_all(ansi::sanitize(&frame).as_bytes())?;
+        }
+        out.flush()?;
+
+        if event::poll(Duration::from_millis(20))? {
+            if let Event::Key(key) = event::read()? {
+                if keymap.accepting_input() && keymap.action_for(&key) == Some(Action::Quit) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Carry out `action`, whichever device it came from. Returns whether to
+/// quit.
+fn perform(
+    action: Action,
+    night_sky: &mut NightSky,
+    overlay: &mut Option<(Overlay, Form)>,
+    session: &mut Session,
+) -> bool {
+    // Changing a shared sky's options would split it from the others
+    let shared = !matches!(session, Session::Local);
+    match action {
+        Action::Quit => return true,
+        Action::QuitPending => night_sky.show_toast("press again to quit".to_string()),
+        Action::CycleFov => night_sky.cycle_fov(),
+        Action::ZoomIn => night_sky.zoom(ZOOM_STEP),
+        Action::ZoomOut => night_sky.zoom(1.0 / ZOOM_STEP),
+        Action::RaiseMagnitudeLimit => night_sky.step_magnitude_limit(MAGNITUDE_STEP),
+        Action::LowerMagnitudeLimit => night_sky.step_magnitude_limit(-MAGNITUDE_STEP),
+        Action::ToggleCursor => night_sky.toggle_cursor(),
+        Action::Move(dx, dy) => night_sky.nudge(dx, dy),
+        Action::ToggleHeatmap => night_sky.toggle_heatmap(),
+        Action::ToggleConstellations => night_sky.toggle_constellations(),
+        Action::ToggleZodiac => night_sky.toggle_zodiac(),
+        Action::NextZodiac => night_sky.next_zodiac(),
+        Action::ToggleMilkyWay => night_sky.toggle_milky_way(),
+        Action::ToggleMinimap => night_sky.toggle_minimap(),
+        #[cfg(feature = "astronomy")]
+        Action::ToggleRadiant => night_sky.toggle_radiant(),
+        #[cfg(feature = "astronomy")]
+        Action::ToggleGrid => night_sky.toggle_grid(),
+        #[cfg(feature = "astronomy")]
+        Action::ToggleDeepSky => night_sky.toggle_deep_sky(),
+        Action::OpenSettings | Action::CycleTheme if shared => {
+            night_sky.show_toast("settings are fixed in a shared sky".to_string());
+        }
+        Action::OpenSettings => {
+            *overlay = match overlay.take() {
+                Some(_) => None,
+                None => Some((Overlay::Settings, settings::form(&night_sky.options))),
+            };
+        }
+        Action::Search => *overlay = Some((Overlay::Search, search::form())),
+        Action::CycleTheme => night_sky.cycle_theme(),
+        // Only the host's own spawns reach its guests
+        Action::SummonAurora if shared => {
+            night_sky.show_toast("auroras can't be called up in a shared sky".to_string());
+        }
+        Action::SummonAurora => night_sky.summon_aurora(),
+        Action::LaunchStarlink if shared => {
+            night_sky.show_toast("starlink trains can't be sent over a shared sky".to_string());
+        }
+        Action::LaunchStarlink => night_sky.launch_starlink(),
+        Action::LaunchMeteor => {
+            // Somewhere in the upper half, leaving it room to fall
+            let (width, height) = night_sky.size();
+            let x = rand::random::<f32>() * width as f32;
+            let y = rand::random::<f32>() * height as f32 / 2.0;
+            launch_meteor(night_sky, session, x as u16, y as u16);
+        }
+    }
+    false
+}
+
+/// Start quitting: fade the sky out if it fades, otherwise go straight
+/// away. Returns whether to go now, as on a second quit during the fade.
+fn leave(night_sky: &mut NightSky, leaving: &mut bool) -> bool {
+    if *leaving || !night_sky.options.fades() {
+        return true;
+    }
+    night_sky.renderer.fade_out();
+    *leaving = true;
+    false
+}
+
+/// Launch a meteor from sky cell `(x, y)`, in every sky sharing this one.
+fn launch_meteor(night_sky: &mut NightSky, session: &mut Session, x: u16, y: u16) {
+    match session {
+        Session::Local => night_sky.launch_meteor(x, y),
+        #[cfg(feature = "network")]
+        Session::Host { host, .. } => {
+            host.broadcast(&net::Message::Click { x, y });
+            night_sky.launch_meteor(x, y);
+        }
+        // The host echoes it back to everyone, us included
+        #[cfg(feature = "network")]
+        Session::Guest { guest, .. } => {
+            let _ = guest.send_click(x, y);
+        }
+    }
+}
+
+// How often remote mode repaints every cell, repairing any escape sequences
+// the link dropped
+const REMOTE_REPAINT_INTERVAL: Duration = Duration::from_secs(30);
+
+// Ticks to simulate at most before giving up on catching up after a stall
+const MAX_CATCH_UP_TICKS: u32 = 10;
+
+fn run_app<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    night_sky: &mut NightSky,
+    session: &mut Session,
+    base: &SkyOptions,
+) -> io::Result<()> {
+    let mut last_input = Instant::now();
+    let mut keymap = Keymap::new(night_sky.options.quit, night_sky.options.grace);
+
+    // The simulation steps at a fixed rate independent of how often we draw
+    let tick_rate = night_sky.options.tick_rate;
+    let tick_interval = Duration::from_secs_f32(1.0 / tick_rate);
+    let frame_interval = Duration::from_secs_f32(1.0 / night_sky.options.frame_rate());
+    let overnight_interval = Duration::from_secs_f32(1.0 / wake::OVERNIGHT_FPS).max(frame_interval);
+    let dt = TICKS_PER_SECOND as f32 / tick_rate;
+    let mut next_tick = Instant::now();
+    let mut next_frame = Instant::now();
+    let mut last_repaint = Instant::now();
+    let mut watchdog = watchdog::Watchdog::new(frame_interval);
+    let mut overlay = (matches!(session, Session::Local) && setup::is_first_run())
+        .then(|| (Overlay::Setup, setup::form(&night_sky.options)));
+    // A shared sky's options are the host's, so only a local one follows
+    // the weather
+    let mut auto = (matches!(session, Session::Local) && night_sky.options.auto)
+        .then(auto::AutoScene::new);
+    let alarm = night_sky.options.wake.map(wake::Alarm::new);
+    // When the bell next rings, once the alarm has gone off
+    let mut next_ring: Option<Instant> = None;
+    let started = Instant::now();
+    let options = &night_sky.options;
+    let mut hooks = hooks::EventHooks::new(&options.on_event, options.hook_log.clone());
+    #[cfg(feature = "gamepad")]
+    let mut gamepad = gamepad::Gamepad::new();
+    #[cfg(feature = "gamepad")]
+    let mut last_steer = Instant::now();
+    // The ISS's orbit, once it has been looked up
+    #[cfg(feature = "astronomy")]
+    let mut iss_lookup = night_sky.options.iss.then(tle::load_in_background);
+    #[cfg(feature = "astronomy")]
+    let mut iss_orbit = None;
+    // Real satellites' passes, once they've been worked out, and the day
+    // after's while those are looked up
+    #[cfg(feature = "astronomy")]
+    let mut pass_lookup = (night_sky.options.passes > 0.0).then(|| look_up_passes(&night_sky.options, projection::now()));
+    #[cfg(feature = "astronomy")]
+    let mut pass_list = None;
+    // When by the passes' clock the ones looked up run out
+    #[cfg(feature = "astronomy")]
+    let mut passes_until = projection::now() + tle::PASS_DAYS;
+    // Set on quitting, while the sky fades out
+    let mut leaving = false;
+    if night_sky.options.fades() {
+        night_sky.renderer.fade_in();
+    }
+    let options = &night_sky.options;
+    let dusk = options.dusk.map(|mode| dusk::Dusk::new(mode, options.latitude, options.longitude));
+
+    loop {
+        if leaving && !night_sky.renderer.is_fading_out() {
+            return Ok(());
+        }
+
+        #[cfg(feature = "astronomy")]
+        {
+            if let Some(found) = iss_lookup.as_ref().and_then(|lookup| lookup.try_recv().ok()) {
+                iss_lookup = None;
+                iss_orbit = found;
+                if iss_orbit.is_none() {
+                    night_sky.show_toast("ISS: couldn't fetch its orbit".to_string());
+                }
+            }
+            // A rebuilt sky has lost track of it
+            if let Some(orbit) = &iss_orbit {
+                if !night_sky.sim.tracks_iss() && !night_sky.sim.track_iss(orbit) {
+                    night_sky.show_toast("ISS: its orbit can't be followed".to_string());
+                    iss_orbit = None;
+                }
+            }
+
+            if let Some(found) = pass_lookup.as_ref().and_then(|lookup| lookup.try_recv().ok()) {
+                pass_lookup = None;
+                match found {
+                    None => night_sky.show_toast("passes: couldn't fetch satellite orbits".to_string()),
+                    Some(passes) if passes.is_empty() && pass_list.is_none() => {
+                        night_sky.show_toast("passes: none visible from here in the next day".to_string());
+                    }
+                    Some(passes) => {
+                        night_sky.sim.fly_passes(passes.clone(), night_sky.options.passes);
+                        pass_list = Some(passes);
+                    }
+                }
+            }
+            if let Some(passes) = &pass_list {
+                match night_sky.sim.passes() {
+                    // A rebuilt sky has lost them
+                    None => night_sky.sim.fly_passes(passes.clone(), night_sky.options.passes),
+                    // Before the day's passes run out, work out the next day's
+                    Some(flying) if flying.jd(night_sky.sim.seconds()) >= passes_until && pass_lookup.is_none() => {
+                        pass_lookup = Some(look_up_passes(&night_sky.options, passes_until));
+                        passes_until += tle::PASS_DAYS;
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        if let Some(options) = auto.as_mut().and_then(|auto| auto.poll(&night_sky.options)) {
+            let (width, height) = night_sky.screen_size();
+            if options.density != night_sky.options.density {
+                // Stars are only placed when a sky is built
+                *night_sky = NightSky::new(width, height, options);
+            } else {
+                night_sky.set_options(options);
+            }
+            if let Some(sample) = auto.as_ref().and_then(|auto| auto.weather(&night_sky.options)) {
+                night_sky.sim.report_weather(sample);
+            }
+        } else if let Some(auto) = &auto {
+            // A rebuilt sky has forgotten the weather
+            if !night_sky.sim.follows_weather() {
+                if let Some(sample) = auto.weather(&night_sky.options) {
+                    night_sky.sim.report_weather(sample);
+                }
+            }
+        }
+
+        let now = Instant::now();
+        if let Some(alarm) = &alarm {
+            if alarm.is_due() && next_ring.map_or(true, |at| now >= at) {
+                wake::ring()?;
+                if night_sky.popup.is_none() {
+                    let (width, height) = night_sky.size();
+                    night_sky.popup = Some(alarm.greeting(width, height));
+                }
+                next_ring = Some(now + wake::RING_INTERVAL);
+            }
+        }
+
+        if now >= next_frame {
+            let dawn = alarm.as_ref().map_or(0.0, wake::Alarm::dawn);
+            if alarm.is_some() {
+                night_sky.set_dawn(dawn);
+            } else if let Some(dusk) = &dusk {
+                night_sky.renderer.set_dawn(dusk.twilight());
+            }
+            if night_sky.options.remote && last_repaint.elapsed() >= REMOTE_REPAINT_INTERVAL {
+                terminal.clear()?;
+                last_repaint = now;
+            }
+            let drawing = Instant::now();
+            #[cfg_attr(not(feature = "network"), allow(unused_variables))]
+            let completed = terminal.draw(|f| {
+                night_sky.render(f, f.area());
+                if let Some((_, form)) = &overlay {
+                    form.render(f, f.area(), night_sky.options.theme.background, night_sky.renderer.grade());
+                }
+                night_sky.renderer.finish(f.buffer_mut());
+            })?;
+            #[cfg(feature = "network")]
+            if let Session::Host { host, .. } = session {
+                host.send_frame(completed.buffer);
+            }
+            // A terminal that can't keep up gets fewer frames with less
+            // changing in them, and the time it stalled for is skipped
+            // rather than simulated in a rush
+            if let Some(overrun) = watchdog.record(drawing.elapsed()) {
+                next_tick += overrun;
+            }
+            night_sky.renderer.set_steady(watchdog.is_struggling());
+            // Overnight the sky is dark and barely changes
+            let interval = if alarm.is_some() && dawn == 0.0 {
+                overnight_interval
+            } else {
+                watchdog.interval()
+            };
+            next_frame = (next_frame + interval).max(now);
+        }
+
+        #[cfg(feature = "network")]
+        if let Session::Guest { guest, .. } = session {
+            // Guests step only when the host says so
+            let Some(messages) = guest.poll() else {
+                return Err(io::Error::other("the host ended the shared sky"));
+            };
+            for message in messages {
+                match message {
+                    net::Message::Tick => hooks.notify(&night_sky.update(dt)),
+                    net::Message::Click { x, y } => night_sky.launch_meteor(x, y),
+                    _ => {}
+                }
+            }
+            while Instant::now() >= next_tick {
+                next_tick += tick_interval;
+            }
+        }
+
+        let mut steps = 0;
+        while session.steps_locally() && Instant::now() >= next_tick {
+            if steps == MAX_CATCH_UP_TICKS {
+                // Too far behind; drop the backlog rather than fast-forwarding
+                next_tick = Instant::now() + tick_interval;
+                break;
+            }
+            #[cfg(feature = "network")]
+            if let Session::Host { host, .. } = session {
+                if host.accept() > 0 {
+                    night_sky.show_toast(format!("watching: {}", host.follower_count()));
+                }
+                for (x, y) in host.clicks() {
+                    host.broadcast(&net::Message::Click { x, y });
+                    night_sky.launch_meteor(x, y);
+                }
+            }
+            hooks.notify(&night_sky.update(dt));
+            #[cfg(feature = "network")]
+            if let Session::Host { host, .. } = session {
+                host.broadcast(&net::Message::Tick);
+            }
+            next_tick += tick_interval;
+            steps += 1;
+        }
+
+        hooks.elapsed(started.elapsed().as_secs_f32());
+
+        #[cfg(feature = "gamepad")]
+        if let Some(pad) = &mut gamepad {
+            let actions = pad.poll();
+            if !actions.is_empty() && keymap.accepting_input() {
+                last_input = Instant::now();
+                for action in actions {
+                    if perform(action, night_sky, &mut overlay, session) && leave(night_sky, &mut leaving) {
                         return Ok(());
                     }
                 }
-                Event::Resize(width, height) => {
-                    // Recreate night sky with new dimensions
-                    *night_sky = NightSky::new(width, height);
+            }
+            let now = Instant::now();
+            let steer = pad.steer();
+            if !steer.is_idle() {
+                night_sky.steer(steer, now.duration_since(last_steer).as_secs_f32());
+            }
+            last_steer = now;
+        }
+
+        // Wait for input until the next tick or frame is due
+        let timeout = next_tick.min(next_frame).saturating_duration_since(Instant::now());
+        if !event::poll(timeout)? {
+            continue;
+        }
+
+        let event = event::read()?;
+        let is_input = matches!(event, Event::Key(_) | Event::Mouse(_));
+        if is_input && !keymap.accepting_input() {
+            continue;
+        }
+        if is_input {
+            let options = &night_sky.options;
+            if let Some(cmd) = &options.on_wake {
+                if last_input.elapsed() >= options.idle_timeout {
+                    // A failing hook shouldn't take the sky down with it
+                    let _ = hooks::spawn_detached(cmd);
                 }
-                _ => {}
+            }
+            last_input = Instant::now();
+        }
+
+        if next_ring.is_some() && matches!(event, Event::Key(_)) {
+            return Ok(());
+        }
+
+        if let Event::Key(key) = &event {
+            if overlay_key(night_sky, &mut overlay, key) {
+                continue;
             }
         }
 
-        night_sky.update();
+        match event {
+            Event::Key(key) => {
+                if let Some(action) = keymap.action_for(&key) {
+                    if perform(action, night_sky, &mut overlay, session) && leave(night_sky, &mut leaving) {
+                        return Ok(());
+                    }
+                }
+            }
+            Event::Mouse(mouse) if night_sky.options.mouse_capture() => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    if let Some((x, y)) = night_sky.sky_cell(mouse.column, mouse.row) {
+                        if matches!(session, Session::Local) {
+                            night_sky.click(x, y);
+                        } else {
+                            let (x, y) = night_sky.sky_cell_at(x, y);
+                            launch_meteor(night_sky, session, x, y);
+                        }
+                    }
+                }
+                // The star cursor follows the mouse
+                MouseEventKind::Moved if night_sky.cursor.is_some() => {
+                    if let Some(cell) = night_sky.sky_cell(mouse.column, mouse.row) {
+                        night_sky.cursor = Some(cell);
+                        night_sky.inspect();
+                    }
+                }
+                _ => {}
+            },
+            // A shared sky keeps the host's size; the terminal just shows
+            // more or less of it
+            Event::Resize(width, height) if matches!(session, Session::Local) => {
+                // Switch size profiles if the new size calls for it, but
+                // otherwise keep any settings changed since
+                let (old_width, old_height) = night_sky.screen_size();
+                let options = if base.matching_profiles(width, height)
+                    != base.matching_profiles(old_width, old_height)
+                {
+                    base.for_size(width, height)
+                } else {
+                    night_sky.options.clone()
+                };
+                // Recreate night sky with new dimensions
+                *night_sky = NightSky::new(width, height, options);
+            }
+            _ => {}
+        }
     }
 }
-
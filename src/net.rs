@@ -0,0 +1,475 @@
+//! Shared skies over TCP.
+//!
+//! One `nk host` owns the simulation and any number of `nk join` guests
+//! follow it in lockstep. On connecting, a guest is sent the seed, size and
+//! options the host's sky was built from, so it can build the identical sky;
+//! from then on the host announces every simulation step and every clicked
+//! meteor, in order, and guests replay them. Guests send their own clicks to
+//! the host, which folds them into the stream for everyone.
+//!
+//! A connection can instead ask to mirror the host: it is then sent the
+//! host's rendered frames, as ANSI escape sequences, and simply displays
+//! them. Mirrors need no simulation of their own, so they work whatever
+//! their size or version of nk, at the cost of more bandwidth.
+//!
+//! Messages are single lines of tab-separated fields. The connecting side
+//! speaks first, saying which role it wants.
+
+use crate::ansi::FrameEncoder;
+use crate::cli;
+use ratatui::buffer::Buffer;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+pub const DEFAULT_PORT: u16 = 4477;
+const PROTOCOL_VERSION: u32 = 2;
+// A guest that can't keep up is dropped rather than stalling everyone
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+// How long a new connection has to say what it wants
+const ROLE_TIMEOUT: Duration = Duration::from_secs(5);
+// The longest message read from a peer; a whole mirrored frame of a big
+// screen fits with room to spare
+const MAX_LINE: u64 = 8 * 1024 * 1024;
+// The widest or tallest sky a guest will build, beyond any real terminal
+const MAX_SIZE: u16 = 2000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    /// A guest asking to follow the simulation
+    Join,
+    /// A follower asking for rendered frames
+    Mirror,
+    /// Everything a guest needs to rebuild the host's sky
+    Hello {
+        seed: u64,
+        width: u16,
+        height: u16,
+        tick_rate: f32,
+        /// Sky options, as command-line arguments
+        options: Vec<String>,
+    },
+    /// The host advanced the simulation by one step
+    Tick,
+    /// Someone clicked to launch a meteor from a cell
+    Click { x: u16, y: u16 },
+    /// Escape sequences that bring a mirror's screen up to date
+    Frame(String),
+}
+
+impl Message {
+    fn encode(&self) -> String {
+        let fields = match self {
+            Message::Hello {
+                seed,
+                width,
+                height,
+                tick_rate,
+                options,
+            } => {
+                let mut fields = vec![
+                    "hello".to_string(),
+                    PROTOCOL_VERSION.to_string(),
+                    seed.to_string(),
+                    width.to_string(),
+                    height.to_string(),
+                    tick_rate.to_string(),
+                ];
+                fields.extend(options.iter().cloned());
+                fields
+            }
+            Message::Join => vec!["join".to_string()],
+            Message::Mirror => vec!["mirror".to_string()],
+            Message::Tick => vec!["tick".to_string()],
+            Message::Frame(data) => vec!["frame".to_string(), data.clone()],
+            Message::Click { x, y } => vec!["click".to_string(), x.to_string(), y.to_string()],
+        };
+        let mut line = fields.join("\t");
+        line.push('\n');
+        line
+    }
+
+    fn decode(line: &str) -> Result<Message, String> {
+        let mut fields = line.trim_end_matches(['\r', '\n']).split('\t');
+        let mut next = |name: &str| {
+            fields
+                .next()
+                .ok_or_else(|| format!("message is missing its {}", name))
+        };
+        match next("kind")? {
+            "hello" => {
+                let version: u32 = number(next("version")?, "version")?;
+                if version != PROTOCOL_VERSION {
+                    return Err(format!(
+                        "host speaks protocol version {}, this nk speaks {}",
+                        version, PROTOCOL_VERSION
+                    ));
+                }
+                let seed = number(next("seed")?, "seed")?;
+                let width = number(next("width")?, "width")?;
+                let height = number(next("height")?, "height")?;
+                if !(1..=MAX_SIZE).contains(&width) || !(1..=MAX_SIZE).contains(&height) {
+                    return Err(format!("host's sky of {}x{} is empty or too big", width, height));
+                }
+                let tick_rate = number(next("tick rate")?, "tick rate")?;
+                if !cli::RATES.contains(&tick_rate) {
+                    return Err(format!("host's tick rate {} is out of range", tick_rate));
+                }
+                Ok(Message::Hello {
+                    seed,
+                    width,
+                    height,
+                    tick_rate,
+                    options: fields.map(str::to_string).collect(),
+                })
+            }
+            "join" => Ok(Message::Join),
+            "mirror" => Ok(Message::Mirror),
+            "tick" => Ok(Message::Tick),
+            "frame" => Ok(Message::Frame(fields.collect::<Vec<_>>().join("\t"))),
+            "click" => {
+                let x = number(next("x")?, "x")?;
+                let y = number(next("y")?, "y")?;
+                Ok(Message::Click { x, y })
+            }
+            other => Err(format!("unknown message '{}'", other)),
+        }
+    }
+}
+
+fn number<T: std::str::FromStr>(raw: &str, name: &str) -> Result<T, String> {
+    raw.parse()
+        .map_err(|_| format!("invalid {} '{}'", name, raw))
+}
+
+/// Read one line from `reader` into `line`, like [`BufRead::read_line`], but
+/// failing on one longer than [`MAX_LINE`], so a peer that never ends its
+/// lines can't use up all our memory.
+fn read_line<R: BufRead>(reader: &mut R, line: &mut String) -> io::Result<usize> {
+    let read = reader.by_ref().take(MAX_LINE).read_line(line)?;
+    if read as u64 == MAX_LINE && !line.ends_with('\n') {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "message too long"));
+    }
+    Ok(read)
+}
+
+fn send(stream: &mut TcpStream, message: &Message) -> io::Result<()> {
+    stream.write_all(message.encode().as_bytes())
+}
+
+/// Forward each decodable line from `reader` into `sender` until either end
+/// goes away. A peer that sends a line too long to be a message is cut off.
+fn forward_messages(mut reader: BufReader<TcpStream>, sender: Sender<Message>) {
+    thread::spawn(move || {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if !matches!(read_line(&mut reader, &mut line), Ok(read) if read > 0) {
+                break;
+            }
+            // Ignore garbage rather than tearing the session down
+            if let Ok(message) = Message::decode(&line) {
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        }
+        // Writes to it fail from now on too, so it's dropped everywhere
+        let _ = reader.get_ref().shutdown(Shutdown::Both);
+    });
+}
+
+pub struct Host {
+    listener: TcpListener,
+    guests: Vec<TcpStream>,
+    mirrors: Vec<(TcpStream, FrameEncoder)>,
+    hello: Message,
+    /// Messages from guests
+    inbox: Receiver<Message>,
+    sender: Sender<Message>,
+    /// New connections that have said which role they want
+    arrivals: Receiver<(Message, TcpStream)>,
+    arrival_sender: Sender<(Message, TcpStream)>,
+}
+
+impl Host {
+    pub fn bind<A: ToSocketAddrs>(addr: A, hello: Message) -> io::Result<Host> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let (sender, inbox) = mpsc::channel();
+        let (arrival_sender, arrivals) = mpsc::channel();
+        Ok(Host {
+            listener,
+            guests: Vec::new(),
+            mirrors: Vec::new(),
+            hello,
+            inbox,
+            sender,
+            arrivals,
+            arrival_sender,
+        })
+    }
+
+    /// Guests and mirrors currently connected.
+    pub fn follower_count(&self) -> usize {
+        self.guests.len() + self.mirrors.len()
+    }
+
+    /// Greet any guests and mirrors waiting to join. Returns how many
+    /// arrived.
+    pub fn accept(&mut self) -> usize {
+        while let Ok((stream, _)) = self.listener.accept() {
+            let configured = stream.set_nonblocking(false).is_ok()
+                && stream.set_nodelay(true).is_ok()
+                && stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_ok()
+                && stream.set_read_timeout(Some(ROLE_TIMEOUT)).is_ok();
+            if configured {
+                self.await_role(stream);
+            }
+        }
+
+        let mut joined = 0;
+        for (role, mut stream) in self.arrivals.try_iter() {
+            match role {
+                Message::Join if send(&mut stream, &self.hello).is_ok() => {
+                    self.guests.push(stream);
+                    joined += 1;
+                }
+                Message::Mirror => {
+                    // An empty previous frame makes the first one complete
+                    self.mirrors.push((stream, FrameEncoder::new(0, 0)));
+                    joined += 1;
+                }
+                _ => {}
+            }
+        }
+        joined
+    }
+
+    /// Read a new connection's role off the main thread, then keep
+    /// forwarding a guest's messages.
+    fn await_role(&self, stream: TcpStream) {
+        let arrivals = self.arrival_sender.clone();
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let Ok(reader) = stream.try_clone() else {
+                return;
+            };
+            let mut reader = BufReader::new(reader);
+            let mut line = String::new();
+            if read_line(&mut reader, &mut line).is_err() {
+                return;
+            }
+            let role = match Message::decode(&line) {
+                Ok(role @ (Message::Join | Message::Mirror)) => role,
+                _ => return,
+            };
+            let is_guest = role == Message::Join;
+            if stream.set_read_timeout(None).is_err() || arrivals.send((role, stream)).is_err() {
+                return;
+            }
+            if is_guest {
+                forward_messages(reader, sender);
+            }
+        });
+    }
+
+    /// Clicks guests have sent since the last call.
+    pub fn clicks(&self) -> Vec<(u16, u16)> {
+        self.inbox
+            .try_iter()
+            .filter_map(|message| match message {
+                Message::Click { x, y } => Some((x, y)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Send `message` to every guest, dropping any that have gone away.
+    pub fn broadcast(&mut self, message: &Message) {
+        self.guests.retain_mut(|guest| send(guest, message).is_ok());
+    }
+
+    /// Bring every mirror up to date with a freshly drawn frame.
+    pub fn send_frame(&mut self, buffer: &Buffer) {
+        self.mirrors.retain_mut(|(stream, encoder)| {
+            let data = encoder.encode(buffer);
+            data.is_empty() || send(stream, &Message::Frame(data)).is_ok()
+        });
+    }
+}
+
+/// Connect to a host at `addr`, which defaults to the standard port.
+fn connect(addr: &str) -> Result<TcpStream, String> {
+    let addr = if addr.contains(':') {
+        addr.to_string()
+    } else {
+        format!("{}:{}", addr, DEFAULT_PORT)
+    };
+    let stream =
+        TcpStream::connect(&addr).map_err(|err| format!("can't connect to {}: {}", addr, err))?;
+    let _ = stream.set_nodelay(true);
+    Ok(stream)
+}
+
+/// Messages received since the last call, or `None` once the other end has
+/// gone.
+fn drain(inbox: &Receiver<Message>) -> Option<Vec<Message>> {
+    let mut messages = Vec::new();
+    loop {
+        match inbox.try_recv() {
+            Ok(message) => messages.push(message),
+            Err(TryRecvError::Empty) => return Some(messages),
+            Err(TryRecvError::Disconnected) if messages.is_empty() => return None,
+            Err(TryRecvError::Disconnected) => return Some(messages),
+        }
+    }
+}
+
+pub struct Guest {
+    stream: TcpStream,
+    inbox: Receiver<Message>,
+}
+
+impl Guest {
+    /// Connect to a host, returning the session and the host's greeting.
+    pub fn connect(addr: &str) -> Result<(Guest, Message), String> {
+        let mut stream = connect(addr)?;
+        let lost = |err: io::Error| format!("lost connection to {}: {}", addr, err);
+        send(&mut stream, &Message::Join).map_err(lost)?;
+        let mut reader = BufReader::new(stream.try_clone().map_err(lost)?);
+        let mut line = String::new();
+        read_line(&mut reader, &mut line).map_err(lost)?;
+        let hello = Message::decode(&line)?;
+        if !matches!(hello, Message::Hello { .. }) {
+            return Err(format!("{} didn't greet us like an nk host", addr));
+        }
+
+        let (sender, inbox) = mpsc::channel();
+        // Keep the reader: it may already hold the first ticks
+        forward_messages(reader, sender);
+        Ok((Guest { stream, inbox }, hello))
+    }
+
+    /// Messages from the host since the last call, or `None` once the host
+    /// has gone.
+    pub fn poll(&self) -> Option<Vec<Message>> {
+        drain(&self.inbox)
+    }
+
+    pub fn send_click(&mut self, x: u16, y: u16) -> io::Result<()> {
+        send(&mut self.stream, &Message::Click { x, y })
+    }
+}
+
+pub struct Mirror {
+    inbox: Receiver<Message>,
+}
+
+impl Mirror {
+    pub fn connect(addr: &str) -> Result<Mirror, String> {
+        let mut stream = connect(addr)?;
+        send(&mut stream, &Message::Mirror)
+            .map_err(|err| format!("lost connection to {}: {}", addr, err))?;
+        let (sender, inbox) = mpsc::channel();
+        forward_messages(BufReader::new(stream), sender);
+        Ok(Mirror { inbox })
+    }
+
+    /// Frames from the host since the last call, or `None` once the host
+    /// has gone.
+    pub fn poll(&self) -> Option<Vec<String>> {
+        let messages = drain(&self.inbox)?;
+        Some(
+            messages
+                .into_iter()
+                .filter_map(|message| match message {
+                    Message::Frame(data) => Some(data),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn hello(width: u16, height: u16, tick_rate: f32) -> Message {
+        Message::Hello {
+            seed: u64::MAX,
+            width,
+            height,
+            tick_rate,
+            options: vec!["--theme".to_string(), "aurora".to_string(), "--moon".to_string()],
+        }
+    }
+
+    #[test]
+    fn messages_round_trip() {
+        for message in [
+            hello(120, 40, 30.0),
+            hello(1, MAX_SIZE, 0.1),
+            Message::Tick,
+            Message::Click { x: 0, y: u16::MAX },
+            Message::Join,
+            Message::Mirror,
+            Message::Frame("\x1b[1;1Ha\tb".to_string()),
+        ] {
+            assert_eq!(Message::decode(&message.encode()), Ok(message));
+        }
+    }
+
+    #[test]
+    fn malformed_fields_are_refused() {
+        for line in [
+            "",
+            "wave\n",
+            "click\t3\n",
+            "click\tx\t3\n",
+            "click\t-1\t3\n",
+            "click\t70000\t3\n",
+            "hello\t2\tseed\t80\t24\t30\n",
+            "hello\t2\t1\t80\t24\n",
+            "hello\t1\t1\t80\t24\t30\n",
+        ] {
+            assert!(Message::decode(line).is_err(), "{:?} was accepted", line);
+        }
+    }
+
+    #[test]
+    fn unusable_greetings_are_refused() {
+        for (width, height, tick_rate) in [
+            (80, 24, 0.0),
+            (80, 24, -30.0),
+            (80, 24, f32::NAN),
+            (80, 24, f32::INFINITY),
+            (80, 24, 1001.0),
+            (0, 24, 30.0),
+            (80, 0, 30.0),
+            (u16::MAX, u16::MAX, 30.0),
+            (MAX_SIZE + 1, 24, 30.0),
+        ] {
+            let line = hello(width, height, tick_rate).encode();
+            assert!(Message::decode(&line).is_err(), "{:?} was accepted", line);
+        }
+    }
+
+    #[test]
+    fn lines_too_long_are_refused() {
+        let mut long = vec![b'a'; MAX_LINE as usize + 10];
+        long.push(b'\n');
+        let mut reader = Cursor::new(long);
+        let err = read_line(&mut reader, &mut String::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut reader = Cursor::new(b"tick\nclick\t1\t2\n".to_vec());
+        let mut line = String::new();
+        assert_eq!(read_line(&mut reader, &mut line).unwrap(), 5);
+        assert_eq!(Message::decode(&line), Ok(Message::Tick));
+    }
+}
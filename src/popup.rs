@@ -0,0 +1,69 @@
+//! Small bordered info boxes anchored next to something on screen.
+
+use crate::render::color::Grade;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+    Frame,
+};
+
+const BORDER: Color = Color::Rgb(110, 120, 160);
+const TEXT: Color = Color::Rgb(210, 210, 230);
+
+pub struct Popup {
+    pub title: String,
+    pub lines: Vec<String>,
+    /// Cell the popup points at
+    pub anchor: (u16, u16),
+    pub ticks_left: f32,
+}
+
+impl Popup {
+    pub fn update(&mut self, dt: f32) {
+        self.ticks_left -= dt;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.ticks_left <= 0.0
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, background: Color, grade: Grade) {
+        let content = self
+            .lines
+            .iter()
+            .map(|l| l.chars().count())
+            .chain([self.title.chars().count()])
+            .max()
+            .unwrap_or(0);
+        let width = (content as u16 + 4).min(area.width);
+        let height = (self.lines.len() as u16 + 2).min(area.height);
+
+        // Sit beside the anchor, flipping to the other side near the edges
+        let (ax, ay) = self.anchor;
+        let x = if ax + 2 + width <= area.width {
+            ax + 2
+        } else {
+            ax.saturating_sub(width + 1)
+        };
+        let y = ay.min(area.height.saturating_sub(height));
+        let popup_area = Rect {
+            x: area.x + x,
+            y: area.y + y,
+            width,
+            height,
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", self.title))
+            .padding(Padding::horizontal(1))
+            .border_style(Style::default().fg(grade.apply(BORDER)))
+            .style(Style::default().bg(grade.apply(background)));
+        let text = Paragraph::new(self.lines.join("\n"))
+            .style(Style::default().fg(grade.apply(TEXT)))
+            .block(block);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(text, popup_area);
+    }
+}
@@ -0,0 +1,59 @@
+//! Headless recording to asciicast v2.
+
+use crate::ansi::FrameEncoder;
+use ratatui::buffer::Buffer;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+pub struct CastWriter {
+    out: BufWriter<File>,
+    encoder: FrameEncoder,
+}
+impl CastWriter {
+    pub fn create(path: &str, width: u16, height: u16) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(
+            out,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"env\": {{\"TERM\": \"xterm-256color\"}}}}",
+            width, height
+        )?;
+        // Start from a clear screen with the cursor hidden
+        writeln!(out, "[0.000, \"o\", {}]", json_string("\x1b[?25l\x1b[2J"))?;
+        Ok(CastWriter {
+            out,
+            encoder: FrameEncoder::new(width, height),
+        })
+    }
+
+    /// Write the cells of `buffer` that changed since the last frame.
+    pub fn frame(&mut self, seconds: f32, buffer: &Buffer) -> io::Result<()> {
+        let data = self.encoder.encode(buffer);
+        if data.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.out, "[{:.3}, \"o\", {}]", seconds, json_string(&data))
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+fn json_string(raw: &str) -> String {
+    let mut escaped = String::with_capacity(raw.len() + 2);
+    escaped.push('"');
+    for c in raw.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
@@ -0,0 +1,1185 @@
+//! Drawing a [`Simulation`] into a ratatui [`Buffer`]: the colors and
+//! glyphs of everything in the sky, the camera a scene steers, captions, and
+//! the effects that only exist on screen, like flashes and border glows.
+
+pub mod ascii;
+pub mod atmosphere;
+pub mod aurora;
+pub mod background;
+pub mod bolide;
+pub mod border_glow;
+pub mod camera;
+pub mod captions;
+pub mod clock;
+pub mod clouds;
+pub mod color;
+#[cfg(feature = "astronomy")]
+pub mod compass;
+pub mod comet;
+pub mod compositor;
+pub mod constellations;
+pub mod cvd;
+#[cfg(feature = "astronomy")]
+pub mod deep_sky;
+pub mod easing;
+pub mod fade;
+#[cfg(feature = "astronomy")]
+pub mod grid;
+pub mod halo;
+pub mod heatmap;
+pub mod illumination;
+pub mod light_pollution;
+pub mod magnitude;
+pub mod marker;
+pub mod milky_way;
+pub mod minimap;
+pub mod moon;
+pub mod moonbow;
+pub mod moonlight;
+pub mod particles;
+#[cfg(feature = "astronomy")]
+pub mod radiant;
+pub mod scene;
+pub mod shade;
+pub mod sparks;
+#[cfg(feature = "astronomy")]
+pub mod sun;
+pub mod supernova;
+pub mod theme;
+pub mod tour;
+pub mod trails;
+
+use crate::simulation::events::SkyEvent;
+use crate::simulation::fov::Fov;
+use crate::simulation::landmarks::Kind;
+use crate::simulation::constellation::{Constellation, Figure};
+use crate::simulation::{Simulation, Star, DEFAULT_SEEING, TICKS_PER_SECOND};
+use border_glow::BorderGlow;
+use camera::Camera;
+use captions::CaptionTrack;
+use color::{ColorDepth, Grade};
+use compositor::{Compositor, Paint};
+use fade::Fade;
+use illumination::Illumination;
+use marker::Marker;
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+use scene::Scene;
+use theme::Theme;
+
+/// Tint blended into cluster member stars' colors.
+const CLUSTER_TINT: Color = Color::Rgb(170, 195, 255);
+
+/// Where in their twinkle stars are held when nothing should change
+/// between frames.
+const STEADY_TWINKLE: f32 = 0.85;
+
+/// How many stars have their twinkle worked out afresh each tick. A bigger
+/// sky's stars take turns, twinkling a little less smoothly rather than
+/// costing more to keep up.
+const TWINKLE_BATCH: usize = 2000;
+
+/// The lowest the naked eye's limiting magnitude can be set, leaving only
+/// the brightest stars.
+pub const MIN_LIMITING_MAGNITUDE: f32 = 1.0;
+
+/// The highest the naked eye's limiting magnitude can be set, for the
+/// keenest eyes at the darkest site.
+pub const MAX_LIMITING_MAGNITUDE: f32 = 8.0;
+
+/// How strongly the brightest planets light the cells beside them.
+#[cfg(feature = "astronomy")]
+const PLANET_GLOW: f32 = 0.25;
+
+/// Magnitudes of starlight lost to the brightening sky by sunrise.
+const DAWN_MAGNITUDES: f32 = 7.0;
+
+/// Magnitudes short of the limit that stars start out at as the sky fades
+/// in, so the brightest come out first.
+const FADE_MAGNITUDES: f32 = 8.0;
+
+/// The furthest the view zooms in, on top of the field of view.
+pub const MAX_VIEW_ZOOM: f32 = 4.0;
+
+/// How many rows from a screen cell a star can be and still count as
+/// pointed at.
+pub const STAR_REACH: f32 = 1.5;
+
+/// How much of the Milky Way the light of a full moon washes out.
+const MOONLIT_MILKY_WAY: f32 = 0.7;
+
+/// What satellites flash up to in a near miss.
+const NEAR_MISS: Color = Color::Rgb(255, 110, 90);
+
+/// What a flaring satellite brightens to.
+const FLARE: Color = Color::Rgb(255, 255, 255);
+
+/// How far into a flare the cells around a satellite begin to glow.
+const FLARE_GLOW: f32 = 0.6;
+
+/// How bright the ISS shows in the Earth's shadow, where it's really dark:
+/// just enough to follow it by.
+#[cfg(feature = "astronomy")]
+const ISS_SHADOWED: f32 = 0.3;
+
+/// Letters of a passing satellite's name shown beside it.
+#[cfg(feature = "astronomy")]
+const PASS_LABEL: usize = 12;
+
+/// How the sky is drawn.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenderOptions {
+    pub theme: Theme,
+    /// Dim and redden objects near the horizon and apply refraction lift
+    pub realism: bool,
+    /// With realism, dot the rest of each satellite's predicted pass
+    pub satellite_paths: bool,
+    pub dither: bool,
+    pub colors: ColorDepth,
+    /// Keep to plain ASCII characters
+    pub ascii: bool,
+    /// Light the whole sky for bright events
+    pub flashes: bool,
+    /// Light the screen border when a rare event happens
+    pub event_glow: bool,
+    /// Dim with the local clock through the small hours
+    pub auto_dim: bool,
+    /// Hold still between frames for slow-refreshing displays: stars don't
+    /// twinkle and satellites don't blink
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub eink: bool,
+    /// Light pollution on the Bortle scale, 1 to 9
+    #[cfg_attr(feature = "serde", serde(default = "light_pollution::default_bortle"))]
+    pub bortle: u8,
+    /// The faintest magnitude seen with the naked eye, if not the usual;
+    /// optics see as much deeper as ever
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub limiting_magnitude: Option<f32>,
+    /// How turbulent the air is, from 0 (rock-steady) to 1, setting how
+    /// deeply and quickly stars twinkle
+    #[cfg_attr(feature = "serde", serde(default = "default_seeing"))]
+    pub seeing: f32,
+    pub scene: Scene,
+}
+
+#[cfg(feature = "serde")]
+fn default_seeing() -> f32 {
+    DEFAULT_SEEING
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            theme: Theme::default(),
+            realism: false,
+            satellite_paths: false,
+            dither: false,
+            colors: ColorDepth::default(),
+            ascii: false,
+            flashes: true,
+            event_glow: false,
+            auto_dim: false,
+            eink: false,
+            bortle: light_pollution::DARKEST,
+            limiting_magnitude: None,
+            seeing: DEFAULT_SEEING,
+            scene: Scene::default(),
+        }
+    }
+}
+
+/// Called with each finished frame.
+pub type FrameHook = Box<dyn FnMut(&Buffer)>;
+
+/// Draws a simulation frame by frame, keeping the state that only matters
+/// on screen.
+pub struct Renderer {
+    options: RenderOptions,
+    illumination: Illumination,
+    border_glow: BorderGlow,
+    fov: Fov,
+    /// Where the viewer has steered, on top of any scene's camera
+    view: Camera,
+    show_heatmap: bool,
+    show_constellations: bool,
+    /// In zodiac mode, which of the sky's zodiac constellations is picked
+    /// out
+    zodiac: Option<usize>,
+    show_milky_way: bool,
+    show_minimap: bool,
+    #[cfg(feature = "astronomy")]
+    show_radiant: bool,
+    #[cfg(feature = "astronomy")]
+    show_grid: bool,
+    #[cfg(feature = "astronomy")]
+    show_deep_sky: bool,
+    captions: CaptionTrack,
+    grade: Grade,
+    /// Seconds until the grade is next recomputed
+    grade_timer: f32,
+    /// How far the sky has dawned, from 0 at night to 1 at sunrise
+    dawn: f32,
+    /// How far into the day it is once the sun is up, from 0 to 1
+    daylight: f32,
+    /// Fading in from black or out to it
+    fade: Option<Fade>,
+    /// Each star's twinkle, as of its last turn to be refreshed
+    twinkles: Vec<f32>,
+    /// The next star due a refresh
+    next_twinkle: usize,
+    /// Holding twinkles and blinks still, so frames change less
+    steady: bool,
+    /// Stars picked out by a search
+    marker: Option<Marker>,
+    /// Handed every finished frame. Usually empty, so finishing a frame
+    /// costs nothing extra
+    hooks: Vec<FrameHook>,
+}
+
+impl Renderer {
+    pub fn new(options: RenderOptions) -> Self {
+        Renderer {
+            illumination: Illumination::new(options.flashes),
+            border_glow: BorderGlow::new(),
+            fov: Fov::default(),
+            view: Camera::default(),
+            show_heatmap: false,
+            show_constellations: false,
+            zodiac: None,
+            show_milky_way: true,
+            show_minimap: false,
+            #[cfg(feature = "astronomy")]
+            show_radiant: false,
+            #[cfg(feature = "astronomy")]
+            show_grid: false,
+            #[cfg(feature = "astronomy")]
+            show_deep_sky: false,
+            captions: CaptionTrack::new(options.scene.captions.clone(), options.scene.period),
+            grade: Self::grade_for(&options),
+            grade_timer: 1.0,
+            dawn: 0.0,
+            daylight: 0.0,
+            fade: None,
+            twinkles: Vec::new(),
+            next_twinkle: 0,
+            steady: false,
+            marker: None,
+            options,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Color adjustments derived from the options and the current time.
+    fn grade_for(options: &RenderOptions) -> Grade {
+        let mut grade = Grade::default();
+        if options.auto_dim {
+            grade.brightness = clock::night_dimming(clock::local_hour());
+        }
+        grade
+    }
+
+    pub fn options(&self) -> &RenderOptions {
+        &self.options
+    }
+
+    /// Take on changed options. The scene and its captions carry on as they
+    /// were.
+    pub fn set_options(&mut self, options: RenderOptions) {
+        self.illumination = Illumination::new(options.flashes);
+        self.options = options;
+        self.regrade();
+    }
+
+    /// Recompute the grade, dimmed by any fade.
+    fn regrade(&mut self) {
+        self.grade = Self::grade_for(&self.options);
+        if let Some(fade) = self.fade {
+            self.grade.brightness *= fade.level();
+        }
+    }
+
+    /// Color adjustments currently applied, for drawing on top of the sky.
+    pub fn grade(&self) -> Grade {
+        self.grade
+    }
+
+    pub fn fov(&self) -> Fov {
+        self.fov
+    }
+
+    /// Switch to the next field of view.
+    pub fn cycle_fov(&mut self) -> Fov {
+        self.fov = self.fov.next();
+        self.fov
+    }
+
+    pub fn view(&self) -> Camera {
+        self.view
+    }
+
+    /// Steer the view, panning and zooming on top of any scene's camera.
+    pub fn set_view(&mut self, view: Camera) {
+        self.view = view;
+    }
+
+    /// Show or hide the heatmap, returning whether it's now shown.
+    pub fn toggle_heatmap(&mut self) -> bool {
+        self.show_heatmap = !self.show_heatmap;
+        self.show_heatmap
+    }
+
+    /// Show or hide the constellation figures, returning whether they're now
+    /// shown.
+    pub fn toggle_constellations(&mut self) -> bool {
+        self.show_constellations = !self.show_constellations;
+        self.show_constellations
+    }
+
+    /// Show only the zodiac constellations, picking out the first, or go
+    /// back to the usual figures.
+    pub fn toggle_zodiac(&mut self) -> bool {
+        self.zodiac = match self.zodiac {
+            Some(_) => None,
+            None => Some(0),
+        };
+        self.zodiac.is_some()
+    }
+
+    /// In zodiac mode, pick out the next of `sim`'s zodiac constellations
+    /// and return it.
+    pub fn next_zodiac(&mut self, sim: &Simulation) -> Option<&'static Constellation> {
+        let count = sim.zodiac().count();
+        let pick = (self.zodiac? + 1) % count.max(1);
+        self.zodiac = Some(pick);
+        sim.zodiac().nth(pick).map(|figure| figure.constellation)
+    }
+
+    /// The faintest magnitude showing in the current field of view, before
+    /// anything washes stars out.
+    fn magnitude_limit(&self) -> f32 {
+        let limit = self.fov.magnitude_limit();
+        match self.options.limiting_magnitude {
+            Some(naked_eye) => naked_eye + limit - Fov::NakedEye.magnitude_limit(),
+            None => limit,
+        }
+    }
+
+    /// The point of sky showing in screen cell `(x, y)` of the sky's area.
+    pub fn sky_point(&self, sim: &Simulation, x: u16, y: u16) -> (f32, f32) {
+        let (camera, zoom) = self.camera(sim);
+        self.unproject(sim, x, y, camera, zoom)
+    }
+
+    /// The screen cell the sky point `(x, y)` shows in, if it's not off the
+    /// top or left of the screen.
+    pub fn screen_cell(&self, sim: &Simulation, x: f32, y: f32) -> Option<(u16, u16)> {
+        let (camera, zoom) = self.camera(sim);
+        self.project(sim, x, y, camera, zoom)
+    }
+
+    /// Pan the view by `dx`, `dy` screen cells, however far that is across
+    /// the sky at the current zoom.
+    pub fn pan_by(&mut self, sim: &Simulation, dx: f32, dy: f32) {
+        let (_, zoom) = self.camera(sim);
+        let span = sim.width().max(1) as f32;
+        self.view.pan_x = (self.view.pan_x + dx / zoom).rem_euclid(span);
+        self.view.pan_y += dy / zoom;
+        self.keep_in_sky(sim);
+    }
+
+    /// Pick out the sky's `stars` with a pulsing marker for a while.
+    pub fn mark(&mut self, stars: Vec<usize>) {
+        self.marker = Some(Marker::new(stars));
+    }
+
+    /// Pan the view to put the sky point `(x, y)` in the middle of the
+    /// screen, or as near as it goes without looking past the top of the
+    /// sky or below the horizon.
+    pub fn center_on(&mut self, sim: &Simulation, x: f32, y: f32) {
+        let (cx, cy) = sim.sky_model().center();
+        let (camera, _) = self.camera(sim);
+        let span = sim.width().max(1) as f32;
+        self.view.pan_x = (self.view.pan_x + x - cx - camera.pan_x).rem_euclid(span);
+        self.view.pan_y += y - cy - camera.pan_y;
+        self.keep_in_sky(sim);
+    }
+
+    /// Zoom the view by `factor` about the middle of the screen, between
+    /// the whole sky and [`MAX_VIEW_ZOOM`] times closer. Returns the zoom
+    /// it comes to.
+    pub fn zoom_by(&mut self, sim: &Simulation, factor: f32) -> f32 {
+        self.view.zoom = (self.view.zoom * factor).clamp(1.0, MAX_VIEW_ZOOM);
+        self.keep_in_sky(sim);
+        self.view.zoom
+    }
+
+    /// Pull the view's pan back up or down until it doesn't look past the
+    /// top of the sky or below the horizon.
+    fn keep_in_sky(&mut self, sim: &Simulation) {
+        let (_, cy) = sim.sky_model().center();
+        let (_, zoom) = self.camera(sim);
+        let reach = (cy - cy / zoom).max(0.0);
+        self.view.pan_y = self.view.pan_y.clamp(-reach, reach);
+    }
+
+    /// Hold the stars' twinkling and satellites' blinking still, as on
+    /// e-ink, so less of each frame changes for a terminal to catch up on.
+    pub fn set_steady(&mut self, steady: bool) {
+        self.steady = steady;
+    }
+
+    /// Show or hide the Milky Way, returning whether it's now shown.
+    pub fn toggle_milky_way(&mut self) -> bool {
+        self.show_milky_way = !self.show_milky_way;
+        self.show_milky_way
+    }
+
+    /// Show or hide the map of the whole sky, returning whether it's now
+    /// shown.
+    pub fn toggle_minimap(&mut self) -> bool {
+        self.show_minimap = !self.show_minimap;
+        self.show_minimap
+    }
+
+    /// Show or hide the meteor shower's radiant, returning whether it's now
+    /// shown.
+    #[cfg(feature = "astronomy")]
+    pub fn toggle_radiant(&mut self) -> bool {
+        self.show_radiant = !self.show_radiant;
+        self.show_radiant
+    }
+
+    /// Show or hide the alt-az grid, returning whether it's now shown.
+    #[cfg(feature = "astronomy")]
+    pub fn toggle_grid(&mut self) -> bool {
+        self.show_grid = !self.show_grid;
+        self.show_grid
+    }
+
+    /// Show or hide the deep-sky objects, returning whether they're now
+    /// shown.
+    #[cfg(feature = "astronomy")]
+    pub fn toggle_deep_sky(&mut self) -> bool {
+        self.show_deep_sky = !self.show_deep_sky;
+        self.show_deep_sky
+    }
+
+    /// Brighten the sky towards sunrise, `progress` from 0 (night) to 1.
+    /// Stars fade out as it does.
+    pub fn set_dawn(&mut self, progress: f32) {
+        self.dawn = progress.clamp(0.0, 1.0);
+    }
+
+    /// Bring the sky up from black, the brightest stars first.
+    pub fn fade_in(&mut self) {
+        self.fade = Some(Fade::fade_in());
+        self.regrade();
+    }
+
+    /// Take the sky down to black, to leave.
+    pub fn fade_out(&mut self) {
+        self.fade = Some(Fade::fade_out(self.fade));
+        self.regrade();
+    }
+
+    /// Whether a fade out is still on its way to black.
+    pub fn is_fading_out(&self) -> bool {
+        self.fade.is_some_and(|fade| fade.is_out() && !fade.is_done())
+    }
+
+    /// Register `hook` to be handed each finished frame, for recording,
+    /// analysing or streaming what's drawn.
+    pub fn on_frame(&mut self, hook: impl FnMut(&Buffer) + 'static) {
+        self.hooks.push(Box::new(hook));
+    }
+
+    /// Advance on-screen effects by `dt` ticks, showing the `events` the
+    /// simulation's last step reported.
+    pub fn update(&mut self, sim: &Simulation, events: &[SkyEvent], dt: f32) {
+        // The clock moves slowly; re-grade once a second, or every tick
+        // while fading
+        self.grade_timer -= dt / TICKS_PER_SECOND as f32;
+        if let Some(fade) = &mut self.fade {
+            fade.update(dt);
+        }
+        if self.grade_timer <= 0.0 || self.fade.is_some() {
+            self.regrade();
+            self.grade_timer = 1.0;
+        }
+        if self.fade.is_some_and(|fade| fade.is_done() && !fade.is_out()) {
+            self.fade = None;
+        }
+        if let Some(marker) = &mut self.marker {
+            marker.update(dt);
+            if marker.is_done() {
+                self.marker = None;
+            }
+        }
+        // The stars twinkle a batch at a time, all of them afresh when the
+        // sky changes
+        let stars = sim.stars();
+        if self.twinkles.len() != stars.len() {
+            self.twinkles = stars.iter().map(|star| star.twinkle(sim.time(), self.options.seeing)).collect();
+            self.next_twinkle = 0;
+        } else {
+            for _ in 0..TWINKLE_BATCH.min(stars.len()) {
+                let i = self.next_twinkle;
+                self.twinkles[i] = stars[i].twinkle(sim.time(), self.options.seeing);
+                self.next_twinkle = (i + 1) % stars.len();
+            }
+        }
+        // A sky running through the day dawns and darkens with its sun
+        #[cfg(feature = "astronomy")]
+        if let Some(sun) = sim.sun() {
+            self.dawn = sun.twilight();
+            self.daylight = sun.daylight();
+        }
+
+        for &event in events {
+            // A new bright meteor faintly lights the sky as it flares, and a
+            // bolide bursting lights the whole of it
+            match event {
+                SkyEvent::Fireball => self.illumination.flash(0.08, 6.0),
+                SkyEvent::Bolide => self.illumination.flash(0.4, 1.5 * TICKS_PER_SECOND as f32),
+                _ => {}
+            }
+            self.captions.notify(event, sim.seconds());
+            if event.is_rare() && self.options.event_glow {
+                self.border_glow
+                    .trigger(border_glow::event_color(event), 3.0 * TICKS_PER_SECOND as f32);
+            }
+        }
+        self.illumination.update(dt);
+        self.border_glow.update(dt);
+    }
+
+    /// Screen cell for a point in the sky, seen through `camera` at `zoom`.
+    /// The sky wraps horizontally so the camera can pan indefinitely.
+    fn project(
+        &self,
+        sim: &Simulation,
+        x: f32,
+        y: f32,
+        camera: Camera,
+        zoom: f32,
+    ) -> Option<(u16, u16)> {
+        let (cx, cy) = sim.sky_model().center();
+        let span = sim.width().max(1) as f32;
+        let x = (x - camera.pan_x - cx).rem_euclid(span) + cx;
+        let x = if x >= cx + span / 2.0 { x - span } else { x };
+        let sx = cx + (x - cx) * zoom;
+        let sy = cy + (y - camera.pan_y - cy) * zoom;
+        if sx < 0.0 || sy < 0.0 {
+            return None;
+        }
+        Some((sx as u16, sy as u16))
+    }
+
+    /// The point of sky shown in screen cell `(x, y)`; the reverse of
+    /// [`Renderer::project`].
+    fn unproject(&self, sim: &Simulation, x: u16, y: u16, camera: Camera, zoom: f32) -> (f32, f32) {
+        let (cx, cy) = sim.sky_model().center();
+        let span = sim.width().max(1) as f32;
+        let sx = cx + (x as f32 + 0.5 - cx) / zoom + camera.pan_x;
+        let sy = cy + (y as f32 + 0.5 - cy) / zoom + camera.pan_y;
+        (sx.rem_euclid(span), sy)
+    }
+
+    /// The view, magnified around the centre: the scene's camera moved by
+    /// the viewer's, and the zoom it comes to in the field of view.
+    fn camera(&self, sim: &Simulation) -> (Camera, f32) {
+        let scene = &self.options.scene;
+        let scripted = scene.camera.at(scene.clock(sim.seconds()));
+        let camera = Camera {
+            pan_x: scripted.pan_x + self.view.pan_x,
+            pan_y: scripted.pan_y + self.view.pan_y,
+            zoom: scripted.zoom * self.view.zoom,
+        };
+        (camera, self.fov.zoom() * camera.zoom)
+    }
+
+    /// The faintest magnitude showing anywhere in the sky once city lights,
+    /// the dawn and any fade have washed stars out, before the moon's glare.
+    fn star_limit(&self) -> f32 {
+        let fading = self.fade.map_or(0.0, |fade| 1.0 - fade.level());
+        self.magnitude_limit()
+            - light_pollution::magnitude_loss(self.options.bortle)
+            - (self.dawn + self.daylight) * DAWN_MAGNITUDES
+            - fading * FADE_MAGNITUDES
+    }
+
+    /// The row `star` shows in, lifted by refraction in realism mode.
+    fn apparent_y(&self, sim: &Simulation, star: &Star) -> f32 {
+        if self.options.realism {
+            let apparent = star.altitude + atmosphere::refraction(star.altitude);
+            sim.sky_model().row_for_altitude(apparent)
+        } else {
+            star.y
+        }
+    }
+
+    /// The index of the brightest star showing within [`STAR_REACH`] cells
+    /// of screen cell `(x, y)`, as last drawn.
+    pub fn star_at(&self, sim: &Simulation, x: u16, y: u16) -> Option<usize> {
+        let (camera, zoom) = self.camera(sim);
+        let limit = self.star_limit();
+        let aspect = sim.sky_model().aspect;
+        sim.stars()
+            .iter()
+            .enumerate()
+            .filter(|(_, star)| {
+                let glare = sim.moon().map_or(0.0, |moon| moon.glare(star.x, star.y));
+                star.altitude > 0.0 && star.presence > 0.0 && star.magnitude_at(sim.seconds()) <= limit - glare
+            })
+            .filter(|(_, star)| {
+                let Some((sx, sy)) = self.project(sim, star.x, self.apparent_y(sim, star), camera, zoom) else {
+                    return false;
+                };
+                let dx = (sx as f32 - x as f32) / aspect;
+                let dy = sy as f32 - y as f32;
+                dx.hypot(dy) <= STAR_REACH
+            })
+            .min_by(|(_, a), (_, b)| a.magnitude.total_cmp(&b.magnitude))
+            .map(|(i, _)| i)
+    }
+
+    /// The index of the deep-sky object whose patch covers screen cell
+    /// `(x, y)`, the nearest if several do, while they're shown.
+    #[cfg(feature = "astronomy")]
+    pub fn deep_sky_at(&self, sim: &Simulation, x: u16, y: u16) -> Option<usize> {
+        if !self.show_deep_sky {
+            return None;
+        }
+        let (camera, zoom) = self.camera(sim);
+        sim.deep_sky()
+            .iter()
+            .enumerate()
+            .filter(|(_, object)| object.altitude > 0.0)
+            .filter_map(|(i, object)| {
+                let (sx, sy) = self.project(sim, object.x, object.y, camera, zoom)?;
+                let (columns, rows) = deep_sky::reach(object, sim.sky_model(), zoom);
+                let dx = (sx as f32 - x as f32) / columns;
+                let dy = (sy as f32 - y as f32) / rows;
+                let d = dx.hypot(dy);
+                (d < 1.0).then_some((i, d))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i)
+    }
+
+    /// Draw a whole frame of `sim` into `area` and finish it.
+    pub fn render(&mut self, sim: &Simulation, buf: &mut Buffer, area: Rect) {
+        self.draw(sim, buf, area);
+        self.finish(buf);
+    }
+
+    /// Fit a drawn frame to what the terminal can show, then hand it to
+    /// the frame hooks. Anything drawn over the sky should be drawn first.
+    pub fn finish(&mut self, buf: &mut Buffer) {
+        if self.options.ascii {
+            ascii::convert(buf);
+        }
+        self.options.colors.apply(buf);
+        for hook in &mut self.hooks {
+            hook(buf);
+        }
+    }
+
+    /// Draw the sky into `area` without finishing the frame.
+    pub fn draw(&self, sim: &Simulation, buf: &mut Buffer, area: Rect) {
+        let theme = &self.options.theme;
+        let zenith = self.illumination.light(theme.background);
+        let horizon = self
+            .illumination
+            .light(background::horizon_color(theme.background, theme.stars[0]));
+        let (zenith, horizon) = light_pollution::skyglow(zenith, horizon, self.options.bortle);
+        let (zenith, horizon) = background::dawn(zenith, horizon, self.dawn);
+        let (zenith, horizon) = background::day(zenith, horizon, self.daylight);
+        background::render(
+            buf,
+            area,
+            zenith,
+            horizon,
+            self.options.dither,
+            self.grade,
+        );
+
+        // Everything over the background is blended into it layer by layer
+        let canvas = &mut Compositor::new(buf, area);
+
+        let (camera, zoom) = self.camera(sim);
+        let sky_point = |x, y| self.unproject(sim, x, y, camera, zoom);
+
+        // A bright moon lights up the sky around it
+        if let Some(moon) = sim.moon() {
+            moonlight::render(moon, canvas, sky_point, self.grade);
+        }
+
+        // The Milky Way is the faintest thing in the sky, lost first to
+        // moonlight, light pollution and the dawn, and deep-sky objects with
+        // it
+        let moon = sim.moon();
+        let moonlight = moon.map_or(0.0, |moon| moon.brightness()) * MOONLIT_MILKY_WAY;
+        let strength = (1.0 - moonlight) * light_pollution::milky_way(self.options.bortle) * (1.0 - self.dawn);
+        if self.show_milky_way {
+            let glow = |x, y| {
+                let glare = moon.map_or(0.0, |moon| moon.glare(x, y));
+                sim.milky_way(x, y) * strength * (-glare).exp()
+            };
+            milky_way::render(canvas, sky_point, glow, zoom, self.grade);
+        }
+
+        // Aurorae hang far above the stars, which shine through them
+        if let Some(aurora) = sim.aurora() {
+            let strength = aurora.strength() * (1.0 - self.dawn);
+            aurora::render(aurora, canvas, strength, self.grade);
+        }
+
+        if self.show_heatmap {
+            heatmap::render(sim.heatmap(), canvas, self.grade);
+        }
+
+        if let Some(moonbow) = sim.moonbow() {
+            moonbow::render(moonbow, canvas, sim.sky_model().aspect, self.grade);
+        }
+
+        // Moving things stay out of the avoided zones
+        let avoided = |x: u16, y: u16| sim.is_avoided(x as f32 + 0.5, y as f32 + 0.5);
+
+        // Smoke trains sit behind everything else
+        canvas.mask(avoided);
+        trails::render(sim.trails(), canvas, self.grade, |x, y| self.project(sim, x, y, camera, zoom));
+        canvas.unmask();
+
+        #[cfg(feature = "astronomy")]
+        if self.show_radiant {
+            if let Some(shower) = sim.meteor_shower() {
+                radiant::render(shower, canvas, sim.sky_model().aspect, self.grade);
+            }
+        }
+
+        // Faint stars near a bright moon are lost in its glare, under city
+        // lights, and all of them as the sky brightens at dawn. Fading, only
+        // the brightest show
+        let limit = self.star_limit();
+        let limit_at = |x, y| limit - sim.moon().map_or(0.0, |moon| moon.glare(x, y));
+        // Real stars that have set wait beneath the horizon to rise again,
+        // and variable stars drop out of sight at their faintest
+        let seconds = sim.seconds();
+        let visible = |star: &&Star| {
+            star.altitude > 0.0 && star.presence > 0.0 && star.magnitude_at(seconds) <= limit_at(star.x, star.y)
+        };
+        let apparent_y = |star: &Star| self.apparent_y(sim, star);
+
+        // The grid is drawn behind everything in the sky itself
+        #[cfg(feature = "astronomy")]
+        if let Some(observer) = sim.observer().filter(|_| self.show_grid) {
+            let ascii = self.options.ascii;
+            grid::render(canvas, sim.sky_model(), observer, zoom, sky_point, ascii, self.grade);
+        }
+
+        #[cfg(feature = "astronomy")]
+        if self.show_deep_sky {
+            deep_sky::render(sim.deep_sky(), canvas, sim.sky_model(), zoom, strength, self.grade, |x, y| {
+                self.project(sim, x, y, camera, zoom)
+            });
+        }
+
+        // Constellation lines run between stars that can be seen, behind
+        // them. In zodiac mode only the zodiac's show, and brighter
+        let figures: Vec<&Figure> = if self.zodiac.is_some() {
+            sim.zodiac().collect()
+        } else if self.show_constellations {
+            sim.constellations().iter().collect()
+        } else {
+            Vec::new()
+        };
+        let span = sim.width().max(1) as f32;
+        if !figures.is_empty() {
+            let stars = sim.stars();
+            let lines: Vec<_> = figures
+                .iter()
+                .flat_map(|figure| {
+                    figure.constellation.lines.iter().map(|&(a, b)| {
+                        (&stars[figure.stars[a]], &stars[figure.stars[b]])
+                    })
+                })
+                .filter(|(a, b)| visible(a) && visible(b))
+                .map(|(a, b)| {
+                    // Go the short way round where a figure straddles the
+                    // sky's wrap
+                    let dx = (b.x - a.x + span / 2.0).rem_euclid(span) - span / 2.0;
+                    ((a.x, apparent_y(a)), (a.x + dx, apparent_y(b)))
+                })
+                .collect();
+            let aspect = sim.sky_model().aspect;
+            let strength = if self.zodiac.is_some() { constellations::BRIGHT } else { constellations::DIM };
+            constellations::render(&lines, canvas, zoom, aspect, strength, self.grade, |x, y| {
+                self.project(sim, x, y, camera, zoom)
+            });
+        }
+        // The zodiac constellation picked out is named over its top star
+        if let Some(pick) = self.zodiac.filter(|_| !figures.is_empty()) {
+            let figure = figures[pick % figures.len()];
+            let stars = figure.stars.iter().map(|&i| &sim.stars()[i]);
+            let first = stars.clone().next().map_or(0.0, |star| star.x);
+            // Measured from its first star the short way round the sky
+            let across: f32 = stars
+                .clone()
+                .map(|star| (star.x - first + span / 2.0).rem_euclid(span) - span / 2.0)
+                .sum::<f32>()
+                / figure.stars.len().max(1) as f32;
+            let top = stars.map(apparent_y).fold(f32::INFINITY, f32::min);
+            if let Some((x, y)) = self.project(sim, first + across, top - 1.0, camera, zoom) {
+                let sign = figure.constellation.zodiac_sign().filter(|_| !self.options.ascii);
+                let name = figure.constellation.name;
+                constellations::label(canvas, (x, y), sign, name, self.grade);
+            }
+        }
+
+        // A catalog sky's brightest stars are named beneath them, fainter
+        // ones too the narrower the field of view
+        #[cfg(feature = "astronomy")]
+        if sim.options().catalog {
+            let cutoff = self.fov.label_limit();
+            let height = canvas.area().height;
+            for (i, star) in sim.stars().iter().enumerate() {
+                if star.magnitude > cutoff || !visible(&star) {
+                    continue;
+                }
+                let Some(name) = sim.star_info(i).and_then(|info| info.name) else {
+                    continue;
+                };
+                if let Some((x, y)) = self.project(sim, star.x, apparent_y(star), camera, zoom) {
+                    let row = if y + 1 < height { y + 1 } else { y.saturating_sub(1) };
+                    constellations::label(canvas, (x, row), None, name, self.grade);
+                }
+            }
+        }
+
+        // A supernova's light spreads out behind the stars
+        if let Some(supernova) = sim.supernova() {
+            let star = &sim.stars()[supernova.star];
+            if let Some((x, y)) = self.project(sim, star.x, apparent_y(star), camera, zoom) {
+                canvas.mask(avoided);
+                let center = (x as f32 + 0.5, y as f32 + 0.5);
+                supernova::render(supernova, canvas, center, zoom, sim.sky_model().aspect, self.grade);
+                canvas.unmask();
+            }
+        }
+
+        // The real sky's stars look as bright as their magnitudes, where
+        // random ones each have a brightness of their own
+        #[cfg(feature = "astronomy")]
+        let by_magnitude = sim.options().catalog;
+        #[cfg(not(feature = "astronomy"))]
+        let by_magnitude = false;
+        let aspect = sim.sky_model().aspect;
+        for (i, star) in sim.stars().iter().enumerate().filter(|(_, star)| visible(star)) {
+            let y = apparent_y(star);
+            let Some((x, y)) = self.project(sim, star.x, y, camera, zoom) else {
+                continue;
+            };
+
+            if x < area.width && y < area.height {
+                // Create twinkling effect
+                let twinkle = if self.options.eink || self.steady {
+                    STEADY_TWINKLE
+                } else {
+                    self.twinkles.get(i).copied().unwrap_or_else(|| star.twinkle(sim.time(), self.options.seeing))
+                };
+
+                // The theme sets how bright a star looks, its class what
+                // color it burns
+                let palette = &self.options.theme.stars;
+                let limit = limit_at(star.x, star.y);
+                let look = |magnitude: f32, brightness: f32| {
+                    if by_magnitude {
+                        let light = magnitude::light(magnitude, twinkle);
+                        let level = palette[(light * (palette.len() - 1) as f32).round() as usize];
+                        (level, magnitude::glyph(magnitude), magnitude::reveal(magnitude, limit))
+                    } else {
+                        let brightness = (brightness * twinkle) as u8;
+                        let level = palette[(brightness.max(1) as usize - 1).min(palette.len() - 1)];
+                        let star_char = match brightness {
+                            0..=1 => "·",
+                            2..=3 => "•",
+                            _ => "✦",
+                        };
+                        // Dimmed, not just dropped, as the moon's glare or
+                        // the dawn closes in on them
+                        (level, star_char, magnitude::reveal(magnitude, limit))
+                    }
+                };
+                let paint = |level, tint, coverage| {
+                    let color = color::multiply(level, tint);
+                    let color = if star.cluster.is_some() {
+                        color::lerp(color, CLUSTER_TINT, 0.35)
+                    } else {
+                        color
+                    };
+                    let color = if self.options.realism {
+                        atmosphere::redden(color, atmosphere::transmission(star.altitude))
+                    } else {
+                        color
+                    };
+                    Paint::new(self.grade.apply(color), star.presence * coverage)
+                };
+
+                // A variable star's own cycle dims it on top of its twinkle
+                let magnitude = star.magnitude_at(seconds);
+                let dimming = 10f32.powf(-0.4 * (magnitude - star.magnitude));
+                let (level, star_char, coverage) = look(magnitude, star.brightness as f32 * dimming);
+                let tint = color::rgb(star.class.color());
+
+                // A double star's companion splits off beside it once the
+                // view is close enough; until then their colors mix in the
+                // one point
+                let split = star.companion.map(|companion| (companion, companion.offset(zoom, aspect)));
+                let tint = match split {
+                    Some((companion, None)) => {
+                        color::lerp(tint, color::rgb(companion.class.color()), companion.share(magnitude))
+                    }
+                    _ => tint,
+                };
+                canvas.glyph(x, y, star_char, paint(level, tint, coverage));
+
+                if let Some((companion, Some((dx, dy)))) = split.filter(|(companion, _)| companion.magnitude <= limit) {
+                    let (cx, cy) = (x as i32 + dx, y as i32 + dy);
+                    if cx >= 0 && cy >= 0 {
+                        let brightness = (6.5 - companion.magnitude).clamp(1.0, 5.0);
+                        let (level, star_char, coverage) = look(companion.magnitude, brightness);
+                        let tint = color::rgb(companion.class.color());
+                        canvas.glyph(cx as u16, cy as u16, star_char, paint(level, tint, coverage));
+                    }
+                }
+            }
+        }
+
+        if let Some(marker) = &self.marker {
+            let stars = marker.stars.iter().filter_map(|&i| sim.stars().get(i));
+            for star in stars.filter(|star| star.altitude > 0.0) {
+                if let Some(cell) = self.project(sim, star.x, apparent_y(star), camera, zoom) {
+                    marker::render(canvas, cell, marker.strength(), self.grade);
+                }
+            }
+        }
+
+        // Planets show as steady discs over the stars
+        for landmark in sim.landmarks() {
+            let Kind::Planet(planet) = landmark.kind else {
+                continue;
+            };
+            let Some((x, y)) = self.project(sim, landmark.x, landmark.y, camera, zoom) else {
+                continue;
+            };
+            canvas.glyph(x, y, "●", Paint::solid(self.grade.apply(color::rgb(planet.color))));
+        }
+
+        // Real planets shine steadily, the brightest with a glow either side
+        #[cfg(feature = "astronomy")]
+        for planet in sim.planets() {
+            if planet.altitude <= 0.0 || planet.magnitude > limit_at(planet.x, planet.y) {
+                continue;
+            }
+            let y = if self.options.realism {
+                let apparent = planet.altitude + atmosphere::refraction(planet.altitude);
+                sim.sky_model().row_for_altitude(apparent)
+            } else {
+                planet.y
+            };
+            let Some((x, y)) = self.project(sim, planet.x, y, camera, zoom) else {
+                continue;
+            };
+            let color = self.grade.apply(color::rgb(planet.planet.color));
+            let glow = (-planet.magnitude / 4.0).clamp(0.0, 1.0) * PLANET_GLOW;
+            if glow > 0.0 {
+                canvas.wash(x.wrapping_sub(1), y, Paint::new(color, glow));
+                canvas.wash(x + 1, y, Paint::new(color, glow));
+            }
+            canvas.glyph(x, y, "●", Paint::solid(color));
+        }
+
+        // Comets hang among the stars, fading with the dawn
+        if let Some(comet) = sim.comet() {
+            let strength = comet.strength() * (1.0 - self.dawn);
+            canvas.mask(avoided);
+            comet::render(comet, canvas, strength, self.grade, |x, y| {
+                self.project(sim, x, y, camera, zoom)
+            });
+            canvas.unmask();
+        }
+
+        // The sun is behind the clouds and a daytime moon
+        #[cfg(feature = "astronomy")]
+        if let Some(sun) = sim.sun() {
+            if let Some((x, y)) = self.project(sim, sun.x, sun.y, camera, zoom) {
+                let center = (x as f32 + 0.5, y as f32 + 0.5);
+                sun::render(sun, canvas, center, zoom, sim.sky_model().aspect, self.grade);
+            }
+        }
+
+        // Thin cloud hazes the stars behind it and spreads the moon's light
+        clouds::render(canvas, sim.veils(), sky_point, self.grade);
+        if let Some(moon) = sim.moon() {
+            if let Some((x, y)) = self.project(sim, moon.x, moon.y, camera, zoom) {
+                let horizon = sim.sky_model().horizon_y as f32;
+                let radius = halo::RADIUS_DEGREES / 90.0 * horizon * zoom;
+                let center = (x as f32 + 0.5, y as f32 + 0.5);
+                let strength = sim.halo_strength();
+                let aspect = sim.sky_model().aspect;
+                halo::render(canvas, center, radius, strength, aspect, self.grade);
+                moon::render(moon, canvas.buffer_mut(), area, center, zoom, aspect, self.grade);
+            }
+        }
+
+        canvas.mask(avoided);
+        // Render shooting stars over the sparks they shed
+        sparks::render(sim.sparks(), canvas, &self.options.theme, self.grade, |x, y| {
+            self.project(sim, x, y, camera, zoom)
+        });
+        for shooting_star in sim.shooting_stars() {
+            let Some((x, y)) = self.project(sim, shooting_star.x, shooting_star.y, camera, zoom) else {
+                continue;
+            };
+            if shooting_star.bolide {
+                let aspect = sim.sky_model().aspect;
+                bolide::render(canvas, (x, y), shooting_star.progress(), aspect, self.grade);
+            } else {
+                let tint = self.options.theme.meteor(shooting_star.tint);
+                let paint = Paint::new(self.grade.apply(tint.head), shooting_star.brightness());
+                canvas.glyph(x, y, "☄", paint);
+            }
+        }
+
+        // Faint dotted line ahead of each satellite, fading with distance
+        if self.options.realism && self.options.satellite_paths {
+            let color = self.grade.apply(self.options.theme.satellite);
+            for satellite in sim.satellites() {
+                let dots: Vec<_> = satellite.path_ahead().collect();
+                for (i, &&(x, y)) in dots.iter().enumerate() {
+                    let fade = 1.0 - i as f32 / dots.len() as f32;
+                    if let Some((x, y)) = self.project(sim, x, y, camera, zoom) {
+                        canvas.glyph(x, y, "·", Paint::new(color, 0.25 * fade));
+                    }
+                }
+            }
+        }
+
+        // Render satellites
+        for satellite in sim.satellites() {
+            let Some((x, y)) = self.project(sim, satellite.x, satellite.y, camera, zoom) else {
+                continue;
+            };
+            
+            if x < area.width && y < area.height {
+                // Blinking effect
+                let blink = if self.options.eink || self.steady {
+                    1.0
+                } else {
+                    (satellite.blink_phase.sin() + 1.0) / 2.0
+                };
+                let brightness = (0.8 + blink * 0.2) * (0.45 + 0.55 * magnitude::light(satellite.magnitude, 1.0));
+                
+                let mut satellite_color = color::scale(self.options.theme.satellite, brightness);
+                if satellite.is_near_miss() {
+                    // A near miss flashes between dim and a warning red
+                    satellite_color = color::lerp(color::scale(satellite_color, 0.4), NEAR_MISS, blink);
+                }
+                // A flare builds to white, lighting the cells around it at
+                // its peak
+                let flare = satellite.flare();
+                satellite_color = color::lerp(satellite_color, FLARE, flare);
+                let glow = (flare - FLARE_GLOW) / (1.0 - FLARE_GLOW);
+                if glow > 0.0 {
+                    let paint = |coverage: f32| Paint::new(self.grade.apply(FLARE), coverage * glow);
+                    for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)] {
+                        let (gx, gy) = (x as i32 + dx, y as i32 + dy);
+                        if gx >= 0 && gy >= 0 {
+                            let coverage = if dx == 0 || dy == 0 { 0.3 } else { 0.15 };
+                            canvas.wash(gx as u16, gy as u16, paint(coverage));
+                        }
+                    }
+                }
+                canvas.glyph(x, y, "◆", Paint::solid(self.grade.apply(satellite_color)));
+            }
+        }
+
+        // A Starlink train, a string of steady lights
+        if let Some(train) = sim.starlink() {
+            let color = self.grade.apply(color::scale(self.options.theme.satellite, 0.9));
+            for member in train.visible(sim.width(), sim.sky_model().horizon_y) {
+                if let Some((x, y)) = self.project(sim, member.x, member.y, camera, zoom) {
+                    canvas.glyph(x, y, "•", Paint::solid(color));
+                }
+            }
+        }
+
+        // The real ISS, labelled as it passes
+        #[cfg(feature = "astronomy")]
+        if let Some(iss) = sim.iss().filter(|iss| iss.altitude > 0.0) {
+            if let Some((x, y)) = self.project(sim, iss.x, iss.y, camera, zoom) {
+                let brightness = if iss.sunlit { 1.0 } else { ISS_SHADOWED };
+                let color = self.grade.apply(color::scale(self.options.theme.satellite, brightness));
+                canvas.glyph(x, y, "◆", Paint::solid(color));
+                // On whichever side has room
+                let label = if x + 5 < area.width { x + 2 } else { x.saturating_sub(4) };
+                for (i, letter) in ["I", "S", "S"].into_iter().enumerate() {
+                    canvas.glyph(label + i as u16, y, letter, Paint::new(color, 0.7));
+                }
+            }
+        }
+
+        // Real satellites on their predicted passes, each labelled with its name
+        #[cfg(feature = "astronomy")]
+        for passing in sim.passes().map_or(&[][..], |passes| passes.passing()) {
+            if let Some((x, y)) = self.project(sim, passing.x, passing.y, camera, zoom) {
+                let brightness = if passing.sunlit { 1.0 } else { ISS_SHADOWED };
+                let color = self.grade.apply(color::scale(self.options.theme.satellite, brightness));
+                canvas.glyph(x, y, "◆", Paint::solid(color));
+                let name: Vec<String> = passing.name.chars().take(PASS_LABEL).map(String::from).collect();
+                let width = name.len() as u16;
+                let label = if x + width + 2 < area.width { x + 2 } else { x.saturating_sub(width + 1) };
+                for (i, letter) in name.iter().enumerate() {
+                    canvas.glyph(label + i as u16, y, letter, Paint::new(color, 0.7));
+                }
+            }
+        }
+
+        // Foreground particles pass in front of the sky
+        let (raindrops, leaves) = (sim.raindrops(), sim.leaves());
+        particles::render(raindrops, particles::RAINDROP, canvas, self.grade);
+        particles::render(sim.snowflakes(), particles::SNOWFLAKE, canvas, self.grade);
+        particles::render(leaves, particles::LEAF, canvas, self.grade);
+        canvas.unmask();
+
+        // Compass points along the bottom, over everything in the sky
+        #[cfg(feature = "astronomy")]
+        if let Some(observer) = sim.observer() {
+            compass::render(canvas, sim.sky_model(), observer, zoom, sky_point, self.grade);
+        }
+
+        let buf = canvas.buffer_mut();
+        if self.show_minimap {
+            // The screen's corners, back in the sky
+            let (cx, cy) = sim.sky_model().center();
+            let viewport = minimap::Viewport {
+                x: cx - cx / zoom + camera.pan_x,
+                y: cy - cy / zoom + camera.pan_y,
+                width: area.width as f32 / zoom,
+                height: area.height as f32 / zoom,
+            };
+            minimap::render(sim, buf, area, viewport, self.grade);
+        }
+        self.border_glow
+            .render(buf, area, self.options.theme.background, self.grade);
+
+        self.captions.render(
+            buf,
+            area,
+            sim.seconds(),
+            self.options.theme.background,
+            self.grade,
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! Plain ASCII output for terminals and fonts without the symbols nk draws.
+//!
+//! Layers draw with their usual glyphs; a finished frame is then rewritten
+//! cell by cell, so nothing else needs to know about ASCII mode.
+
+use ratatui::buffer::Buffer;
+
+fn replacement(symbol: &str) -> &'static str {
+    match symbol {
+        "·" => ".",
+        "•" | "☄" | "✦" => "*",
+        "●" | "🌕" => "O",
+        "🌑" => "o",
+        "🌒" | "🌓" | "🌔" => ")",
+        "🌖" | "🌗" | "🌘" => "(",
+        "◆" => "#",
+        "❦" | "❧" | "☙" => "&",
+        "│" | "┃" | "║" => "|",
+        "─" | "━" | "═" => "-",
+        "┼" => "+",
+        "╱" => "/",
+        "╲" => "\\",
+        "┌" | "┐" | "└" | "┘" | "╭" | "╮" | "╰" | "╯" | "┏" | "┓" | "┗" | "┛" | "╔" | "╗" | "╚" | "╝" => "+",
+        "░" => ".",
+        "▒" => ":",
+        "▓" => "%",
+        "█" => "#",
+        "›" => ">",
+        "←" => "<",
+        "→" => ">",
+        "↑" => "^",
+        "↓" => "v",
+        _ => "?",
+    }
+}
+
+/// Swap every non-ASCII symbol in `buf` for an ASCII stand-in.
+pub fn convert(buf: &mut Buffer) {
+    for cell in &mut buf.content {
+        if !cell.symbol().is_ascii() {
+            let symbol = replacement(cell.symbol());
+            cell.set_symbol(symbol);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Atmospheric effects on objects near the horizon.
+
+use ratatui::style::Color;
+
+// Extinction coefficient in magnitudes per airmass (typical clear site, V band)
+const EXTINCTION_COEFF: f32 = 0.2;
+
+/// Relative path length through the atmosphere (1.0 at the zenith).
+pub fn airmass(altitude: f32) -> f32 {
+    // Kasten & Young approximation, well-behaved down to the horizon
+    let alt = altitude.max(0.0);
+    1.0 / (alt.to_radians().sin() + 0.50572 * (alt + 6.07995).powf(-1.6364))
+}
+
+/// Fraction of light surviving the trip through the atmosphere.
+pub fn transmission(altitude: f32) -> f32 {
+    10f32.powf(-0.4 * EXTINCTION_COEFF * (airmass(altitude) - 1.0))
+}
+
+/// Apparent lift in degrees caused by refraction (Sæmundsson's formula).
+pub fn refraction(altitude: f32) -> f32 {
+    let alt = altitude.max(-1.0);
+    let arcmin = 1.02 / (alt + 10.3 / (alt + 5.11)).to_radians().tan();
+    arcmin.max(0.0) / 60.0
+}
+
+/// Dim `color` by `transmission`, losing blue faster than red.
+pub fn redden(color: Color, transmission: f32) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => {
+            let t = transmission.clamp(0.0, 1.0);
+            Color::Rgb(
+                (r as f32 * t) as u8,
+                (g as f32 * t.powf(1.3)) as u8,
+                (b as f32 * t.powf(1.8)) as u8,
+            )
+        }
+        other => other,
+    }
+}
@@ -0,0 +1,45 @@
+//! Drawing for the aurora's curtains across the top of the sky.
+
+use crate::render::color::{self, Grade};
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::aurora::Aurora;
+use ratatui::style::Color;
+
+// Top of the curtains to their lower edge
+const CROWN: Color = Color::Rgb(150, 70, 190);
+const HEM: Color = Color::Rgb(80, 230, 140);
+// How much of a cell the curtains cover at their brightest
+const MAX_COVERAGE: f32 = 0.55;
+// Brightness past which the folds show as rays
+const RAYS: f32 = 0.35;
+
+/// Curtains hanging in the top third of the sky, green along their lower
+/// edge and purple above, over `strength` from 0 to 1.
+pub fn render(aurora: &Aurora, canvas: &mut Compositor, strength: f32, grade: Grade) {
+    if strength <= 0.01 {
+        return;
+    }
+    let area = canvas.area();
+    let height = (area.height / 3).max(1) as f32;
+    for x in 0..area.width {
+        let u = (x as f32 + 0.5) / area.width as f32;
+        let brightness = aurora.brightness(u) * strength;
+        if brightness <= 0.01 {
+            continue;
+        }
+        let hem = aurora.hem(u) * height;
+        for y in 0..height as u16 {
+            // Brightest along the lower edge, fading out towards the top
+            let t = (y as f32 + 0.5) / hem;
+            if t > 1.0 {
+                break;
+            }
+            let level = brightness * t.powf(1.5);
+            let tint = grade.apply(color::lerp(CROWN, HEM, t));
+            canvas.wash(x, y, Paint::new(tint, level * MAX_COVERAGE));
+            if level > RAYS {
+                canvas.glyph(x, y, "│", Paint::new(tint, level * 0.5));
+            }
+        }
+    }
+}
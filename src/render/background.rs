@@ -0,0 +1,92 @@
+//! The sky backdrop: a vertical gradient brightening slightly towards the
+//! horizon, optionally dithered.
+//!
+//! Terminals limited to 256 colors snap a smooth gradient to a handful of
+//! palette entries, leaving visible bands. With dithering on, each cell is
+//! snapped to the xterm color cube here instead, choosing between the two
+//! nearest levels with a 4x4 Bayer matrix so the bands blend into each other.
+
+use crate::render::color::{self, Grade, CUBE_LEVELS};
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+
+// How far the horizon glow shifts the background towards the faintest star
+// color
+const HORIZON_GLOW: f32 = 0.2;
+
+
+
+// Sky colors through dawn as (progress, zenith, horizon): astronomical,
+// nautical and civil twilight, then sunrise
+const DAWN: &[(f32, Color, Color)] = &[
+    (0.25, Color::Rgb(12, 16, 40), Color::Rgb(35, 35, 75)),
+    (0.5, Color::Rgb(30, 40, 90), Color::Rgb(125, 85, 125)),
+    (0.75, Color::Rgb(60, 90, 150), Color::Rgb(230, 130, 90)),
+    (1.0, Color::Rgb(110, 160, 215), Color::Rgb(255, 200, 120)),
+];
+
+// Sky colors in full day, at the zenith and the horizon
+const DAY_ZENITH: Color = Color::Rgb(60, 120, 210);
+const DAY_HORIZON: Color = Color::Rgb(165, 195, 230);
+
+/// The horizon end of the gradient for a sky whose zenith is `background`.
+pub fn horizon_color(background: Color, faintest_star: Color) -> Color {
+    color::lerp(background, faintest_star, HORIZON_GLOW)
+}
+
+/// The gradient's ends `progress` of the way through dawn, from the night's
+/// `zenith` and `horizon` at 0 to sunrise at 1.
+pub fn dawn(zenith: Color, horizon: Color, progress: f32) -> (Color, Color) {
+    let mut from = (0.0, zenith, horizon);
+    for &(at, z, h) in DAWN {
+        if progress <= at {
+            let t = (progress - from.0) / (at - from.0);
+            return (color::lerp(from.1, z, t), color::lerp(from.2, h, t));
+        }
+        from = (at, z, h);
+    }
+    (from.1, from.2)
+}
+
+/// The gradient's ends `progress` of the way from sunrise's `zenith` and
+/// `horizon` at 0 to full day at 1.
+pub fn day(zenith: Color, horizon: Color, progress: f32) -> (Color, Color) {
+    (
+        color::lerp(zenith, DAY_ZENITH, progress),
+        color::lerp(horizon, DAY_HORIZON, progress),
+    )
+}
+
+pub fn render(buf: &mut Buffer, area: Rect, zenith: Color, horizon: Color, dither: bool, grade: Grade) {
+    for y in 0..area.height {
+        let t = y as f32 / area.height.saturating_sub(1).max(1) as f32;
+        let row = grade.apply(color::lerp(zenith, horizon, t));
+        for x in 0..area.width {
+            let color = if dither { dithered(row, x, y) } else { row };
+            if let Some(cell) = buf.cell_mut((area.x + x, area.y + y)) {
+                cell.set_bg(color);
+            }
+        }
+    }
+}
+
+/// Snap `color` to the color cube, dithering by the cell's position.
+fn dithered(color: Color, x: u16, y: u16) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    let threshold = color::bayer(x, y);
+    let level = |v: u8| {
+        let upper = CUBE_LEVELS.iter().position(|&l| l >= v).unwrap_or(5);
+        if upper == 0 {
+            return 0;
+        }
+        let (lo, hi) = (CUBE_LEVELS[upper - 1], CUBE_LEVELS[upper]);
+        let frac = (v - lo) as f32 / (hi - lo) as f32;
+        if frac > threshold {
+            upper as u8
+        } else {
+            upper as u8 - 1
+        }
+    };
+    Color::Indexed(16 + 36 * level(r) + 6 * level(g) + level(b))
+}
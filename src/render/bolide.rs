@@ -0,0 +1,45 @@
+//! A bolide's head: a fireball too big for one cell, burning green as it
+//! comes in and orange as it breaks up, in a glow of its own light.
+
+use crate::render::color::{self, Grade};
+use crate::render::compositor::{Compositor, Paint};
+use ratatui::style::Color;
+
+// The head as it comes in, and as it breaks up
+const GREEN: Color = Color::Rgb(160, 255, 150);
+const ORANGE: Color = Color::Rgb(255, 150, 60);
+// Rows the glow reaches around the head, and how much it covers beside it
+const GLOW_ROWS: f32 = 2.5;
+const GLOW: f32 = 0.5;
+
+/// A bolide's color `progress` through its flight.
+pub fn tint(progress: f32) -> Color {
+    color::lerp(GREEN, ORANGE, progress)
+}
+
+/// Draw a bolide's head at the cell `(x, y)`, `progress` through its
+/// flight, round for rows `aspect` columns tall.
+pub fn render(canvas: &mut Compositor, (x, y): (u16, u16), progress: f32, aspect: f32, grade: Grade) {
+    let tint = grade.apply(tint(progress));
+    let rows = GLOW_ROWS.ceil() as i32;
+    let columns = (GLOW_ROWS * aspect).ceil() as i32;
+    for dy in -rows..=rows {
+        for dx in -columns..=columns {
+            let d = (dx as f32 / aspect).hypot(dy as f32);
+            let (Some(cx), Some(cy)) = (x.checked_add_signed(dx as i16), y.checked_add_signed(dy as i16)) else {
+                continue;
+            };
+            if d < GLOW_ROWS {
+                let falloff = 1.0 - d / GLOW_ROWS;
+                canvas.wash(cx, cy, Paint::new(tint, GLOW * falloff * falloff));
+            }
+        }
+    }
+    // A bright core with the head spilling into the cells around it
+    for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+        if let (Some(cx), Some(cy)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
+            canvas.glyph(cx, cy, "•", Paint::new(tint, 0.8));
+        }
+    }
+    canvas.glyph(x, y, "●", Paint::solid(tint));
+}
@@ -0,0 +1,90 @@
+//! Overlay that softly lights the screen edge to announce rare events.
+
+use crate::render::color::{self, Grade};
+use crate::simulation::events::SkyEvent;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Widget},
+};
+
+/// Tint used when `event` is announced with a border glow.
+pub fn event_color(event: SkyEvent) -> Color {
+    match event {
+        SkyEvent::Fireball => Color::Rgb(255, 190, 90),
+        SkyEvent::Bolide => Color::Rgb(255, 160, 70),
+        SkyEvent::SatellitePass => Color::Rgb(170, 190, 255),
+        SkyEvent::IssPass => Color::Rgb(190, 205, 255),
+        SkyEvent::PredictedPass => Color::Rgb(180, 200, 255),
+        SkyEvent::Moonbow => Color::Rgb(190, 170, 230),
+        SkyEvent::MoonHalo => Color::Rgb(220, 225, 245),
+        SkyEvent::Aurora => Color::Rgb(90, 220, 150),
+        SkyEvent::Comet => Color::Rgb(200, 220, 240),
+        SkyEvent::Conjunction => Color::Rgb(255, 120, 100),
+        SkyEvent::StarlinkTrain => Color::Rgb(200, 210, 255),
+        SkyEvent::Supernova => Color::Rgb(235, 240, 255),
+    }
+}
+
+pub struct BorderGlow {
+    color: Color,
+    /// Ticks remaining and total duration of the current glow
+    remaining: f32,
+    duration: f32,
+}
+
+impl Default for BorderGlow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BorderGlow {
+    pub fn new() -> Self {
+        BorderGlow {
+            color: Color::Reset,
+            remaining: 0.0,
+            duration: 1.0,
+        }
+    }
+
+    pub fn trigger(&mut self, color: Color, ticks: f32) {
+        self.color = color;
+        self.remaining = ticks;
+        self.duration = ticks.max(1.0);
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.remaining = (self.remaining - dt).max(0.0);
+    }
+
+    /// Tint the outermost ring of cells, pulsing gently as it fades.
+    pub fn render(&self, buf: &mut Buffer, area: Rect, background: Color, grade: Grade) {
+        if self.remaining <= 0.0 || area.width == 0 || area.height == 0 {
+            return;
+        }
+        let fade = self.remaining / self.duration;
+        let pulse = 0.75 + 0.25 * (self.remaining * 0.3).sin();
+        let tint = color::lerp(background, self.color, (fade * pulse).clamp(0.0, 1.0) * 0.6);
+        let style = Style::default().bg(grade.apply(tint));
+
+        let edges = [
+            Rect { height: 1, ..area },
+            Rect {
+                y: area.y + area.height - 1,
+                height: 1,
+                ..area
+            },
+            Rect { width: 1, ..area },
+            Rect {
+                x: area.x + area.width - 1,
+                width: 1,
+                ..area
+            },
+        ];
+        for edge in edges {
+            Block::default().style(style).render(edge, buf);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Viewport onto the star field and scripted moves between viewports.
+
+use crate::render::easing::Easing;
+
+/// Where the view is looking. Pans are in sky cells relative to the centre.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Camera {
+    pub pan_x: f32,
+    pub pan_y: f32,
+    pub zoom: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera {
+            pan_x: 0.0,
+            pan_y: 0.0,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl Camera {
+    fn lerp(self, to: Camera, t: f32) -> Camera {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Camera {
+            pan_x: mix(self.pan_x, to.pan_x),
+            pan_y: mix(self.pan_y, to.pan_y),
+            // Interpolate zoom geometrically so zooming feels even
+            zoom: (self.zoom.ln() + (to.zoom.ln() - self.zoom.ln()) * t).exp(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyframe {
+    /// Seconds from the start of the session
+    pub time: f32,
+    pub camera: Camera,
+    /// Curve used when moving from the previous keyframe to this one
+    pub easing: Easing,
+}
+
+/// A sequence of keyframes, held at the last one once it is reached.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CameraPath {
+    keyframes: Vec<Keyframe>,
+}
+
+impl CameraPath {
+    pub fn new(mut keyframes: Vec<Keyframe>) -> Self {
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        CameraPath { keyframes }
+    }
+
+    /// The camera `seconds` into the session.
+    pub fn at(&self, seconds: f32) -> Camera {
+        let Some(first) = self.keyframes.first() else {
+            return Camera::default();
+        };
+        if seconds <= first.time {
+            return first.camera;
+        }
+        for pair in self.keyframes.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            if seconds < to.time {
+                let span = (to.time - from.time).max(f32::EPSILON);
+                let t = to.easing.apply((seconds - from.time) / span);
+                return from.camera.lerp(to.camera, t);
+            }
+        }
+        self.keyframes[self.keyframes.len() - 1].camera
+    }
+}
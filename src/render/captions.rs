@@ -0,0 +1,127 @@
+//! Timed captions overlaid on the sky.
+
+use crate::render::color::{self, Grade};
+use crate::simulation::events::SkyEvent;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Paragraph, Widget},
+};
+use std::str::FromStr;
+
+const TEXT: Color = Color::Rgb(220, 220, 235);
+// Seconds spent fading in and out
+const FADE: f32 = 0.6;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Position {
+    Top,
+    Center,
+    #[default]
+    Bottom,
+}
+
+impl FromStr for Position {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "top" => Ok(Position::Top),
+            "center" => Ok(Position::Center),
+            "bottom" => Ok(Position::Bottom),
+            other => Err(format!("unknown caption position '{}'", other)),
+        }
+    }
+}
+
+/// What starts a caption.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cue {
+    /// Seconds from the start of the session
+    At(f32),
+    /// Every time the event happens
+    On(SkyEvent),
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Caption {
+    pub cue: Cue,
+    /// Seconds on screen
+    pub duration: f32,
+    pub position: Position,
+    pub text: String,
+}
+
+/// Schedules a scene's captions against the clock and the sky's events.
+pub struct CaptionTrack {
+    captions: Vec<Caption>,
+    /// When each event-cued caption last fired
+    fired: Vec<Option<f32>>,
+    /// Length of a looping scene, after which timed captions come round again
+    period: Option<f32>,
+}
+
+impl CaptionTrack {
+    pub fn new(captions: Vec<Caption>, period: Option<f32>) -> Self {
+        let fired = vec![None; captions.len()];
+        CaptionTrack {
+            captions,
+            fired,
+            period,
+        }
+    }
+
+    /// Start the captions cued on `event`, restarting any already showing.
+    pub fn notify(&mut self, event: SkyEvent, now: f32) {
+        for (caption, fired) in self.captions.iter().zip(&mut self.fired) {
+            if caption.cue == Cue::On(event) {
+                *fired = Some(now);
+            }
+        }
+    }
+
+    /// Seconds since caption `index` last started at time `now`, if it has.
+    fn elapsed(&self, index: usize, now: f32) -> Option<f32> {
+        match self.captions[index].cue {
+            Cue::At(time) => Some(self.period.map_or(now, |p| now % p) - time),
+            Cue::On(_) => self.fired[index].map(|fired| now - fired),
+        }
+    }
+
+    pub fn render(&self, buf: &mut Buffer, area: Rect, now: f32, background: Color, grade: Grade) {
+        if area.height == 0 {
+            return;
+        }
+        for (index, caption) in self.captions.iter().enumerate() {
+            let Some(elapsed) = self.elapsed(index, now) else {
+                continue;
+            };
+            let remaining = caption.duration - elapsed;
+            if elapsed < 0.0 || remaining <= 0.0 {
+                continue;
+            }
+            let opacity = (elapsed.min(remaining) / FADE).min(1.0);
+
+            let row = match caption.position {
+                Position::Top => 1,
+                Position::Center => area.height / 2,
+                // Leave the last row free for toasts
+                Position::Bottom => area.height.saturating_sub(3),
+            };
+            let width = (caption.text.chars().count() as u16).min(area.width);
+            let caption_area = Rect {
+                x: area.x + (area.width - width) / 2,
+                y: area.y + row.min(area.height - 1),
+                width,
+                height: 1,
+            };
+            let color = grade.apply(color::lerp(background, TEXT, opacity));
+            let widget = Paragraph::new(caption.text.as_str()).style(Style::default().fg(color));
+            widget.render(caption_area, buf);
+        }
+    }
+}
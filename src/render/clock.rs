@@ -0,0 +1,33 @@
+//! Wall-clock helpers.
+
+use chrono::{Local, Timelike};
+
+/// Current local time as fractional hours since midnight.
+pub fn local_hour() -> f32 {
+    let now = Local::now();
+    now.hour() as f32 + now.minute() as f32 / 60.0 + now.second() as f32 / 3600.0
+}
+
+// (hour, brightness) keyframes: full brightness in the evening, easing down
+// towards the small hours and back up by morning
+const DIMMING_CURVE: &[(f32, f32)] = &[
+    (0.0, 0.65),
+    (2.0, 0.55),
+    (5.0, 0.6),
+    (7.0, 1.0),
+    (20.0, 1.0),
+    (23.0, 0.75),
+    (24.0, 0.65),
+];
+
+/// Palette brightness multiplier for a local `hour` in `0.0..24.0`.
+pub fn night_dimming(hour: f32) -> f32 {
+    let hour = hour.rem_euclid(24.0);
+    for pair in DIMMING_CURVE.windows(2) {
+        let ((h0, b0), (h1, b1)) = (pair[0], pair[1]);
+        if hour <= h1 {
+            return b0 + (b1 - b0) * (hour - h0) / (h1 - h0);
+        }
+    }
+    1.0
+}
@@ -0,0 +1,35 @@
+//! Drawing for the thin cloud veils drifting across the sky.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::clouds::{self, Veil};
+use ratatui::style::Color;
+
+const VEIL: Color = Color::Rgb(65, 68, 88);
+// How much of a fully covered cell the veil hides
+const OPACITY: f32 = 0.5;
+
+/// Haze the cells under the veils. `sky_point` maps a screen cell to the
+/// point of sky it shows, so the veils follow the camera.
+pub fn render<F: Fn(u16, u16) -> (f32, f32)>(
+    canvas: &mut Compositor,
+    veils: &[Veil],
+    sky_point: F,
+    grade: Grade,
+) {
+    if veils.is_empty() {
+        return;
+    }
+    let veil = grade.apply(VEIL);
+    let area = canvas.area();
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let (sx, sy) = sky_point(x, y);
+            let cover = clouds::coverage(veils, sx, sy);
+            if cover <= 0.01 {
+                continue;
+            }
+            canvas.wash(x, y, Paint::new(veil, cover * OPACITY));
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Color math shared by the effect layers.
+
+use crate::simulation::Rgb;
+use ratatui::{buffer::Buffer, style::Color};
+
+/// A simulation color as a terminal one.
+pub fn rgb((r, g, b): Rgb) -> Color {
+    Color::Rgb(r, g, b)
+}
+
+/// Blend from `a` to `b` by `t` in `0.0..=1.0`. Non-RGB colors snap at the midpoint.
+pub fn lerp(a: Color, b: Color, t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    match (a, b) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let mix = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round() as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        _ if t < 0.5 => a,
+        _ => b,
+    }
+}
+
+/// `color` seen through colored glass: each channel scaled by `filter`'s.
+pub fn multiply(color: Color, filter: Color) -> Color {
+    match (color, filter) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let m = |x: u8, y: u8| (x as u16 * y as u16 / 255) as u8;
+            Color::Rgb(m(r1, r2), m(g1, g2), m(b1, b2))
+        }
+        _ => color,
+    }
+}
+
+/// Scale an RGB color's brightness by `factor`.
+pub fn scale(color: Color, factor: f32) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => {
+            let s = |c: u8| (c as f32 * factor).round().clamp(0.0, 255.0) as u8;
+            Color::Rgb(s(r), s(g), s(b))
+        }
+        other => other,
+    }
+}
+
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Ordered-dither threshold in `0.0..1.0` for the cell at `(x, y)`, from a
+/// 4x4 Bayer matrix. Drawing a cell only where a level beats its threshold
+/// spreads that level evenly over the area.
+pub fn bayer(x: u16, y: u16) -> f32 {
+    (BAYER[y as usize % 4][x as usize % 4] as f32 + 0.5) / 16.0
+}
+
+/// Adjustments applied to every color on its way to the screen.
+#[derive(Clone, Copy, Debug)]
+pub struct Grade {
+    /// Overall brightness multiplier
+    pub brightness: f32,
+}
+
+impl Grade {
+    pub fn apply(&self, color: Color) -> Color {
+        scale(color, self.brightness)
+    }
+}
+
+impl Default for Grade {
+    fn default() -> Self {
+        Grade { brightness: 1.0 }
+    }
+}
+
+/// How many colors the terminal can show.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorDepth {
+    #[default]
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    /// Pure black and white, dithered, for e-ink and serial displays
+    Mono,
+}
+
+impl ColorDepth {
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorDepth::TrueColor => "truecolor",
+            ColorDepth::Ansi256 => "256",
+            ColorDepth::Ansi16 => "16",
+            ColorDepth::Mono => "mono",
+        }
+    }
+
+    /// Best guess from the environment, the way most terminal programs guess.
+    pub fn detect() -> Self {
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        let term = std::env::var("TERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorDepth::TrueColor
+        } else if term.contains("256") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// Bring every cell of `buf` within this depth.
+    pub fn apply(self, buf: &mut Buffer) {
+        if self == ColorDepth::TrueColor {
+            return;
+        }
+        if self == ColorDepth::Mono {
+            return mono(buf);
+        }
+        for cell in &mut buf.content {
+            cell.fg = self.reduce(cell.fg);
+            cell.bg = self.reduce(cell.bg);
+        }
+    }
+
+    /// The closest color this depth can show.
+    pub fn reduce(self, color: Color) -> Color {
+        let Color::Rgb(r, g, b) = color else {
+            return color;
+        };
+        match self {
+            ColorDepth::TrueColor => color,
+            ColorDepth::Ansi256 => nearest_256(r, g, b),
+            ColorDepth::Ansi16 => nearest(ANSI_16.iter().copied(), (r, g, b)),
+            ColorDepth::Mono if luminance(color) >= 0.2 => Color::White,
+            ColorDepth::Mono => Color::Black,
+        }
+    }
+}
+
+impl std::str::FromStr for ColorDepth {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "truecolor" | "24bit" => Ok(ColorDepth::TrueColor),
+            "256" => Ok(ColorDepth::Ansi256),
+            "16" => Ok(ColorDepth::Ansi16),
+            "mono" => Ok(ColorDepth::Mono),
+            other => Err(format!("unknown color depth '{}'", other)),
+        }
+    }
+}
+
+/// Relative luminance of an RGB color in linear light, from 0 for black to
+/// 1 for white. Other colors count as black.
+fn luminance(color: Color) -> f32 {
+    let Color::Rgb(r, g, b) = color else {
+        return 0.0;
+    };
+    let encoded = (0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32) / 255.0;
+    encoded.powf(2.2)
+}
+
+/// Dither every cell of `buf` to black or white. Backgrounds dither by their
+/// brightness, and each glyph is drawn in whichever of the two stands out
+/// from its background, or dropped where it was too faint to stand out at
+/// all.
+fn mono(buf: &mut Buffer) {
+    let area = buf.area;
+    for (i, cell) in buf.content.iter_mut().enumerate() {
+        let x = area.x + (i % area.width.max(1) as usize) as u16;
+        let y = area.y + (i / area.width.max(1) as usize) as u16;
+        let threshold = bayer(x, y);
+        let background = luminance(cell.bg);
+        let white = background > threshold;
+        let contrast = (luminance(cell.fg) - background).abs();
+        if cell.symbol() != " " && contrast < threshold * 0.5 {
+            cell.set_symbol(" ");
+        }
+        (cell.fg, cell.bg) = if white {
+            (Color::Black, Color::White)
+        } else {
+            (Color::White, Color::Black)
+        };
+    }
+}
+
+// Typical RGB values of the 16 basic terminal colors
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel levels of the xterm 6x6x6 color cube.
+pub const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn nearest<I: Iterator<Item = (Color, (u8, u8, u8))>>(palette: I, (r, g, b): (u8, u8, u8)) -> Color {
+    let distance = |(pr, pg, pb): (u8, u8, u8)| {
+        let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2);
+        d(r, pr) + d(g, pg) + d(b, pb)
+    };
+    palette
+        .min_by_key(|&(_, rgb)| distance(rgb))
+        .map_or(Color::Reset, |(color, _)| color)
+}
+
+/// The closest xterm 256-color entry from the color cube or the gray ramp.
+fn nearest_256(r: u8, g: u8, b: u8) -> Color {
+    let cube = (0..216u8).map(|i| {
+        let rgb = (
+            CUBE_LEVELS[(i / 36) as usize],
+            CUBE_LEVELS[(i / 6 % 6) as usize],
+            CUBE_LEVELS[(i % 6) as usize],
+        );
+        (Color::Indexed(16 + i), rgb)
+    });
+    let grays = (0..24u8).map(|i| {
+        let v = 8 + 10 * i;
+        (Color::Indexed(232 + i), (v, v, v))
+    });
+    nearest(cube.chain(grays), (r, g, b))
+}
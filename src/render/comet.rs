@@ -0,0 +1,47 @@
+//! Drawing for comets: a bright head and a tail that fades as it widens.
+
+use crate::render::color::{self, Grade};
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::comet::Comet;
+use ratatui::style::Color;
+
+// Near the head the tail is pale dust, further out the blue of its gas
+const HEAD: Color = Color::Rgb(240, 240, 225);
+const DUST: Color = Color::Rgb(225, 215, 180);
+const GAS: Color = Color::Rgb(120, 160, 220);
+// How much of a cell the tail covers next to the head
+const MAX_COVERAGE: f32 = 0.6;
+
+/// Draw `comet` over `strength` from 0 to 1, placing points of sky on
+/// screen with `project`.
+pub fn render(
+    comet: &Comet,
+    canvas: &mut Compositor,
+    strength: f32,
+    grade: Grade,
+    project: impl Fn(f32, f32) -> Option<(u16, u16)>,
+) {
+    if strength <= 0.01 {
+        return;
+    }
+    for (x, y, t) in comet.tail() {
+        let Some((x, y)) = project(x, y) else {
+            continue;
+        };
+        let level = strength * (1.0 - t).powf(1.5);
+        let tint = grade.apply(color::lerp(DUST, GAS, t));
+        canvas.wash(x, y, Paint::new(tint, level * MAX_COVERAGE));
+        // Out along the tail it spreads a little either side
+        if t > 0.3 {
+            let spread = Paint::new(tint, level * MAX_COVERAGE * 0.4);
+            canvas.wash(x.wrapping_sub(1), y, spread);
+            canvas.wash(x + 1, y, spread);
+        }
+        if t < 0.4 {
+            canvas.glyph(x, y, "·", Paint::new(tint, level));
+        }
+    }
+    if let Some((x, y)) = project(comet.x, comet.y) {
+        canvas.glyph(x, y, "✺", Paint::new(grade.apply(HEAD), strength));
+    }
+}
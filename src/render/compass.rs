@@ -0,0 +1,45 @@
+//! Compass points along the bottom of a catalog sky, under the directions
+//! they name, so the view can be matched to the real sky outside.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use crate::render::grid;
+use crate::simulation::projection::Observer;
+use crate::simulation::sky_model::SkyModel;
+use ratatui::style::Color;
+
+const MARK: Color = Color::Rgb(160, 175, 210);
+const COVERAGE: f32 = 0.8;
+// The points, a step of azimuth apart from north
+const POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+const STEP: f64 = 45.0;
+
+/// Mark the compass points for `observer` along the bottom row, each screen
+/// cell showing the sky point `sky_point` gives for it at `zoom`.
+pub fn render(
+    canvas: &mut Compositor,
+    sky: &SkyModel,
+    observer: &Observer,
+    zoom: f32,
+    sky_point: impl Fn(u16, u16) -> (f32, f32),
+    grade: Grade,
+) {
+    let area = canvas.area();
+    let Some(y) = area.height.checked_sub(1) else {
+        return;
+    };
+    let paint = Paint::new(grade.apply(MARK), COVERAGE);
+    let cell_azimuth = 360.0 / sky.width.max(1) as f64 / zoom as f64;
+    for x in 0..area.width {
+        let (sx, sy) = sky_point(x, y);
+        let (_, azimuth) = observer.direction(sky, sx, sy);
+        let Some(point) = grid::azimuth_line(azimuth, cell_azimuth, STEP) else {
+            continue;
+        };
+        let name = POINTS[(point / STEP) as usize % POINTS.len()];
+        // Starting under the direction and reading on to the right
+        for i in 0..name.len() {
+            canvas.glyph(x + i as u16, y, &name[i..=i], paint);
+        }
+    }
+}
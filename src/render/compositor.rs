@@ -0,0 +1,114 @@
+//! Blending the sky's layers cell by cell.
+//!
+//! Each layer paints a color along with how much of a cell it covers, and
+//! is composited over whatever the layers beneath left there, so an aurora
+//! behind thin cloud or a moonbow over the dawn gradient come out as mixed
+//! colors instead of whichever was drawn last winning the cell.
+
+use crate::render::color;
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+
+/// What a layer puts into one cell.
+#[derive(Clone, Copy, Debug)]
+pub struct Paint {
+    pub color: Color,
+    /// How much of the cell it covers, from 0 (none) to 1 (all of it)
+    pub coverage: f32,
+}
+
+impl Paint {
+    pub fn new(color: Color, coverage: f32) -> Self {
+        Paint {
+            color,
+            coverage: coverage.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Paint covering the whole cell.
+    pub fn solid(color: Color) -> Self {
+        Paint::new(color, 1.0)
+    }
+}
+
+/// Composites layers into a buffer. Positions are cells within the sky's
+/// area; anything outside it is dropped.
+pub struct Compositor<'a> {
+    buf: &'a mut Buffer,
+    area: Rect,
+    /// How strongly each cell's glyph shows, from 0 where there is none
+    glyphs: Vec<f32>,
+    /// Cells left alone while masked, or empty when nothing is
+    masked: Vec<bool>,
+}
+
+impl<'a> Compositor<'a> {
+    pub fn new(buf: &'a mut Buffer, area: Rect) -> Self {
+        Compositor {
+            buf,
+            area,
+            glyphs: vec![0.0; area.area() as usize],
+            masked: Vec::new(),
+        }
+    }
+
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    /// The buffer underneath, for layers that draw whole cells themselves.
+    pub fn buffer_mut(&mut self) -> &mut Buffer {
+        self.buf
+    }
+
+    /// Leave the cells where `masked` holds alone until [`unmask`], so
+    /// the layers drawn in between stay out of them.
+    ///
+    /// [`unmask`]: Compositor::unmask
+    pub fn mask(&mut self, masked: impl Fn(u16, u16) -> bool) {
+        let width = self.area.width.max(1) as usize;
+        self.masked = (0..self.glyphs.len())
+            .map(|i| masked((i % width) as u16, (i / width) as u16))
+            .collect();
+    }
+
+    pub fn unmask(&mut self) {
+        self.masked.clear();
+    }
+
+    /// A translucent layer over the cell: the background and any glyph on
+    /// it both take on the paint's color by its coverage.
+    pub fn wash(&mut self, x: u16, y: u16, paint: Paint) {
+        let Some(i) = self.index(x, y) else {
+            return;
+        };
+        if let Some(cell) = self.buf.cell_mut((self.area.x + x, self.area.y + y)) {
+            cell.set_bg(color::lerp(cell.bg, paint.color, paint.coverage));
+            cell.set_fg(color::lerp(cell.fg, paint.color, paint.coverage));
+            self.glyphs[i] *= 1.0 - paint.coverage;
+        }
+    }
+
+    /// Draw `symbol` in the cell. The glyph shows over the background by the
+    /// paint's coverage; over a glyph that shows more strongly it can't take
+    /// the cell, and only tints that one instead.
+    pub fn glyph(&mut self, x: u16, y: u16, symbol: &str, paint: Paint) {
+        let Some(i) = self.index(x, y) else {
+            return;
+        };
+        if let Some(cell) = self.buf.cell_mut((self.area.x + x, self.area.y + y)) {
+            if paint.coverage >= self.glyphs[i] {
+                let fg = color::lerp(cell.bg, paint.color, paint.coverage);
+                cell.set_symbol(symbol).set_fg(fg);
+                self.glyphs[i] = paint.coverage;
+            } else {
+                cell.set_fg(color::lerp(cell.fg, paint.color, paint.coverage));
+            }
+        }
+    }
+
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        (x < self.area.width && y < self.area.height)
+            .then(|| y as usize * self.area.width as usize + x as usize)
+            .filter(|&i| !self.masked.get(i).copied().unwrap_or(false))
+    }
+}
@@ -0,0 +1,81 @@
+//! Dim stick figures joining the stars of each constellation, and labels
+//! naming them.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use ratatui::{style::Color, text::Span};
+
+const LINE: Color = Color::Rgb(120, 140, 190);
+const LABEL: Color = Color::Rgb(200, 210, 235);
+/// How far lines usually stand out from the sky behind them.
+pub const DIM: f32 = 0.3;
+/// How far the lines of figures picked out stand out.
+pub const BRIGHT: f32 = 0.75;
+// How far labels stand out
+const LABEL_STRENGTH: f32 = 0.8;
+
+/// A line's ends as sky points.
+pub type Line = ((f32, f32), (f32, f32));
+
+/// Glyph for a line running `dx` cells across and `dy` rows down, allowing
+/// for rows being `aspect` columns tall.
+fn glyph(dx: f32, dy: f32, aspect: f32) -> &'static str {
+    let angle = (dy * aspect).atan2(dx).to_degrees().rem_euclid(180.0);
+    match angle {
+        a if !(22.5..157.5).contains(&a) => "─",
+        a if a < 67.5 => "╲",
+        a if a < 112.5 => "│",
+        _ => "╱",
+    }
+}
+
+/// Draw each line, given as its ends in the sky, as strongly as
+/// `strength`. `project` finds a sky point's screen cell, `zoom` is how much
+/// the view is magnified, and rows are `aspect` columns tall.
+pub fn render(
+    lines: &[Line],
+    canvas: &mut Compositor,
+    zoom: f32,
+    aspect: f32,
+    strength: f32,
+    grade: Grade,
+    project: impl Fn(f32, f32) -> Option<(u16, u16)>,
+) {
+    let paint = Paint::new(grade.apply(LINE), strength);
+    for &(from, to) in lines {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let symbol = glyph(dx, dy, aspect);
+        // A couple of samples per cell crossed, so the line has no gaps
+        let steps = ((dx.abs().max(dy.abs()) * zoom * 2.0).ceil() as usize).max(1);
+        for i in 1..steps {
+            let t = i as f32 / steps as f32;
+            let Some((x, y)) = project(from.0 + dx * t, from.1 + dy * t) else {
+                continue;
+            };
+            canvas.glyph(x, y, symbol, paint);
+        }
+    }
+}
+
+/// Write `name`, after `sign` if there is one, centred on screen cell
+/// `(x, y)` and kept on screen. Terminals often draw signs two cells wide.
+pub fn label(canvas: &mut Compositor, (x, y): (u16, u16), sign: Option<&str>, name: &str, grade: Grade) {
+    let paint = Paint::new(grade.apply(LABEL), LABEL_STRENGTH);
+    let sign_width = sign.map_or(0, |sign| Span::raw(sign).width() as u16 + 1);
+    let width = sign_width + name.chars().count() as u16;
+    let mut column = x
+        .saturating_sub(width / 2)
+        .min(canvas.area().width.saturating_sub(width));
+    if let Some(sign) = sign {
+        canvas.glyph(column, y, sign, paint);
+        // Blank behind a wide sign, and the space after it
+        for gap in 1..sign_width {
+            canvas.glyph(column + gap, y, " ", paint);
+        }
+        column += sign_width;
+    }
+    for letter in name.chars() {
+        canvas.glyph(column, y, letter.encode_utf8(&mut [0; 4]), paint);
+        column += 1;
+    }
+}
@@ -0,0 +1,158 @@
+//! Color-vision deficiency simulation and perceptual color distance, and the
+//! check `nk check-theme` makes of each theme with them.
+//!
+//! Simulation uses the Machado, Oliveira & Fernandes (2009) matrices at full
+//! severity, applied in linear RGB.
+
+use crate::render::theme::Theme;
+use ratatui::style::Color;
+
+// Pairs closer than this (ΔE) are hard to tell apart at a glance on a dark
+// background with single-cell glyphs
+const MIN_DISTINCT: f32 = 25.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Deficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl Deficiency {
+    pub const ALL: [Deficiency; 3] = [
+        Deficiency::Protanopia,
+        Deficiency::Deuteranopia,
+        Deficiency::Tritanopia,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Deficiency::Protanopia => "protanopia",
+            Deficiency::Deuteranopia => "deuteranopia",
+            Deficiency::Tritanopia => "tritanopia",
+        }
+    }
+
+    fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            Deficiency::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Deficiency::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Deficiency::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+
+    /// How `color` appears to someone with this deficiency.
+    pub fn simulate(self, color: Color) -> Color {
+        let Some(rgb) = to_linear(color) else {
+            return color;
+        };
+        let m = self.matrix();
+        let row = |r: [f32; 3]| r[0] * rgb[0] + r[1] * rgb[1] + r[2] * rgb[2];
+        from_linear([row(m[0]), row(m[1]), row(m[2])])
+    }
+}
+
+fn decode(c: u8) -> f32 {
+    let c = c as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn encode(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let c = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (c * 255.0).round() as u8
+}
+
+fn to_linear(color: Color) -> Option<[f32; 3]> {
+    match color {
+        Color::Rgb(r, g, b) => Some([decode(r), decode(g), decode(b)]),
+        _ => None,
+    }
+}
+
+fn from_linear(rgb: [f32; 3]) -> Color {
+    Color::Rgb(encode(rgb[0]), encode(rgb[1]), encode(rgb[2]))
+}
+
+/// CIE L*a*b* coordinates (D65 white).
+fn to_lab(color: Color) -> Option<[f32; 3]> {
+    let [r, g, b] = to_linear(color)?;
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+    let f = |t: f32| {
+        if t > 0.008856 {
+            t.cbrt()
+        } else {
+            7.787 * t + 16.0 / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    Some([116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)])
+}
+
+/// Perceptual difference between two colors (CIE76 ΔE). Around 2.3 is just
+/// noticeable; below ~10 colors are easily confused at a glance.
+pub fn delta_e(a: Color, b: Color) -> Option<f32> {
+    let (a, b) = (to_lab(a)?, to_lab(b)?);
+    Some(((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt())
+}
+
+/// The colors of objects a viewer should be able to tell apart.
+fn object_colors(theme: &Theme) -> Vec<(String, Color)> {
+    let mut colors: Vec<(String, Color)> = theme
+        .meteors
+        .iter()
+        .map(|t| (format!("{} meteor", t.name), t.head))
+        .collect();
+    colors.push(("satellite".to_string(), theme.satellite));
+    colors
+}
+
+/// Warnings for `theme`, one per pair of its object colors that someone
+/// with a deficiency would confuse.
+pub fn check(theme: &Theme) -> Vec<String> {
+    let colors = object_colors(theme);
+    let mut warnings = Vec::new();
+
+    for deficiency in Deficiency::ALL {
+        for (i, (name_a, a)) in colors.iter().enumerate() {
+            for (name_b, b) in &colors[i + 1..] {
+                let normal = delta_e(*a, *b).unwrap_or(f32::MAX);
+                let seen = delta_e(deficiency.simulate(*a), deficiency.simulate(*b)).unwrap_or(f32::MAX);
+                // Only flag pairs that were meant to be distinct
+                if normal >= MIN_DISTINCT && seen < MIN_DISTINCT {
+                    warnings.push(format!(
+                        "{}: {} and {} look alike (ΔE {:.1}, normally {:.1})",
+                        deficiency.name(),
+                        name_a,
+                        name_b,
+                        seen,
+                        normal
+                    ));
+                }
+            }
+        }
+    }
+    warnings
+}
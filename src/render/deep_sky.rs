@@ -0,0 +1,75 @@
+//! Deep-sky objects as faint, fuzzy patches behind the stars, tinted by
+//! kind: warm galaxies, pink nebulae, blue-white clusters.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::deep_sky::{DeepSkyKind, SkyObject};
+use crate::simulation::sky_model::SkyModel;
+use ratatui::style::Color;
+
+// The smallest patch's radius in rows, so even the Ring Nebula spans a few
+// cells
+const MIN_RADIUS: f32 = 0.6;
+// Rows of fuzz the patch fades out over beyond its own size
+const FUZZ: f32 = 1.0;
+// How much the brightest object covers at its centre, and the faintest
+const BRIGHTEST: f32 = 0.45;
+const FAINTEST: f32 = 0.2;
+// Magnitudes the coverage runs between
+const BRIGHT_MAGNITUDE: f32 = 1.5;
+const FAINT_MAGNITUDE: f32 = 9.0;
+
+fn tint(kind: DeepSkyKind) -> Color {
+    match kind {
+        DeepSkyKind::Galaxy => Color::Rgb(235, 215, 180),
+        DeepSkyKind::Nebula => Color::Rgb(235, 125, 165),
+        DeepSkyKind::PlanetaryNebula => Color::Rgb(120, 215, 200),
+        DeepSkyKind::OpenCluster => Color::Rgb(165, 195, 255),
+        DeepSkyKind::GlobularCluster => Color::Rgb(250, 235, 190),
+    }
+}
+
+/// How far a patch for `object` reaches from its centre at `zoom`, in
+/// columns and rows, fuzz included.
+pub fn reach(object: &SkyObject, sky: &SkyModel, zoom: f32) -> (f32, f32) {
+    let degrees = object.object.size / 60.0 / 2.0;
+    let rows = (degrees * sky.horizon_y as f32 / 90.0 * zoom).max(MIN_RADIUS) + FUZZ;
+    let columns = (degrees * sky.width as f32 / 360.0 * zoom).max(MIN_RADIUS * sky.aspect) + FUZZ * sky.aspect;
+    (columns, rows)
+}
+
+/// Draw each of `objects` above the horizon where `project` puts it at
+/// `zoom`, as faint as `strength` lets them show.
+pub fn render(
+    objects: &[SkyObject],
+    canvas: &mut Compositor,
+    sky: &SkyModel,
+    zoom: f32,
+    strength: f32,
+    grade: Grade,
+    project: impl Fn(f32, f32) -> Option<(u16, u16)>,
+) {
+    let area = canvas.area();
+    for object in objects.iter().filter(|object| object.altitude > 0.0) {
+        let Some((cx, cy)) = project(object.x, object.y) else {
+            continue;
+        };
+        let (cx, cy) = (cx as f32 + 0.5, cy as f32 + 0.5);
+        let (columns, rows) = reach(object, sky, zoom);
+        let brightness = (FAINT_MAGNITUDE - object.object.magnitude) / (FAINT_MAGNITUDE - BRIGHT_MAGNITUDE);
+        let peak = (FAINTEST + (BRIGHTEST - FAINTEST) * brightness.clamp(0.0, 1.0)) * strength;
+        let color = grade.apply(tint(object.object.kind));
+        let ys = (cy - rows).max(0.0) as u16..((cy + rows).ceil() as u16).min(area.height);
+        let xs = (cx - columns).max(0.0) as u16..((cx + columns).ceil() as u16).min(area.width);
+        for y in ys {
+            for x in xs.clone() {
+                let dx = (x as f32 + 0.5 - cx) / columns;
+                let dy = (y as f32 + 0.5 - cy) / rows;
+                let d = dx.hypot(dy);
+                if d < 1.0 {
+                    canvas.wash(x, y, Paint::new(color, peak * (1.0 - d) * (1.0 - d)));
+                }
+            }
+        }
+    }
+}
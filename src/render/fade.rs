@@ -0,0 +1,67 @@
+//! The sky fading up from black on launch and back down on quit.
+
+use crate::render::easing::Easing;
+use crate::simulation::TICKS_PER_SECOND;
+
+// Seconds to fade in and out; leaving shouldn't keep anyone waiting
+const FADE_IN: f32 = 2.5;
+const FADE_OUT: f32 = 0.8;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Fade {
+    /// Ticks gone and in total
+    elapsed: f32,
+    duration: f32,
+    /// Coming up from black rather than going down to it
+    rising: bool,
+}
+
+impl Fade {
+    pub fn fade_in() -> Self {
+        Fade {
+            elapsed: 0.0,
+            duration: FADE_IN * TICKS_PER_SECOND as f32,
+            rising: true,
+        }
+    }
+
+    /// Down to black from wherever `from` had got to, or from full when
+    /// there was no fade.
+    pub fn fade_out(from: Option<Fade>) -> Self {
+        let duration = FADE_OUT * TICKS_PER_SECOND as f32;
+        let shown = from.map_or(1.0, |fade| fade.progress());
+        Fade {
+            elapsed: (1.0 - shown) * duration,
+            duration,
+            rising: false,
+        }
+    }
+
+    /// How much of the sky is up, from 0 in black to 1.
+    fn progress(&self) -> f32 {
+        let t = (self.elapsed / self.duration).min(1.0);
+        if self.rising {
+            t
+        } else {
+            1.0 - t
+        }
+    }
+
+    /// How much of the sky is up, eased, from 0 in black to 1.
+    pub fn level(&self) -> f32 {
+        Easing::InOut.apply(self.progress())
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.elapsed += dt;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Whether this fade ends in black.
+    pub fn is_out(&self) -> bool {
+        !self.rising
+    }
+}
@@ -0,0 +1,83 @@
+//! A grid of altitude and azimuth over a catalog sky: a circle of altitude
+//! every 15° and a line of azimuth every 30°, labelled in degrees and drawn
+//! faintly behind the stars.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::projection::Observer;
+use crate::simulation::sky_model::SkyModel;
+use ratatui::style::Color;
+
+const LINE: Color = Color::Rgb(90, 115, 160);
+const COVERAGE: f32 = 0.3;
+const LABEL_COVERAGE: f32 = 0.6;
+// Degrees between circles of altitude, and between lines of azimuth
+const ALTITUDE_STEP: f64 = 15.0;
+const AZIMUTH_STEP: f64 = 30.0;
+
+/// Draw the grid for `observer`, each screen cell showing the sky point
+/// `sky_point` gives for it at `zoom`. Altitudes are labelled down the left
+/// edge and azimuths along the bottom, above the compass points, without
+/// the degree sign in `ascii`.
+pub fn render(
+    canvas: &mut Compositor,
+    sky: &SkyModel,
+    observer: &Observer,
+    zoom: f32,
+    sky_point: impl Fn(u16, u16) -> (f32, f32),
+    ascii: bool,
+    grade: Grade,
+) {
+    let area = canvas.area();
+    let paint = Paint::new(grade.apply(LINE), COVERAGE);
+    let label = Paint::new(grade.apply(LINE), LABEL_COVERAGE);
+    // Degrees of altitude and azimuth a screen cell spans
+    let cell_altitude = 90.0 / sky.horizon_y.max(1) as f64 / zoom as f64;
+    let cell_azimuth = 360.0 / sky.width.max(1) as f64 / zoom as f64;
+    let degrees = if ascii { "" } else { "°" };
+    let mut labels = Vec::new();
+
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let (sx, sy) = sky_point(x, y);
+            let (altitude, azimuth) = observer.direction(sky, sx, sy);
+            if altitude < 0.0 {
+                continue;
+            }
+            // A line belongs to the cell whose top or left edge it's on or
+            // just past, so it's drawn once
+            let top = altitude + cell_altitude / 2.0;
+            let circle = (top / ALTITUDE_STEP).floor() * ALTITUDE_STEP;
+            let circle = (circle > top - cell_altitude && circle > 0.0 && circle < 90.0).then_some(circle);
+            let line = azimuth_line(azimuth, cell_azimuth, AZIMUTH_STEP);
+            let glyph = match (circle, line) {
+                (Some(_), Some(_)) => "┼",
+                (Some(_), None) => "─",
+                (None, Some(_)) => "│",
+                (None, None) => continue,
+            };
+            canvas.glyph(x, y, glyph, paint);
+            if let (Some(altitude), 0) = (circle, x) {
+                labels.push((x, y, format!("{}{}", altitude, degrees)));
+            }
+            if let (Some(azimuth), true) = (line, y + 2 == area.height) {
+                labels.push((x + 1, y, format!("{}{}", azimuth, degrees)));
+            }
+        }
+    }
+    for (x, y, text) in labels {
+        for (i, letter) in text.chars().enumerate() {
+            let mut buf = [0; 4];
+            canvas.glyph(x + i as u16, y, letter.encode_utf8(&mut buf), label);
+        }
+    }
+}
+
+/// The multiple of `step` degrees of azimuth in a cell centred on
+/// `azimuth` and `span` degrees wide, if one falls in it. Each falls in just
+/// one of a row of cells.
+pub(crate) fn azimuth_line(azimuth: f64, span: f64, step: f64) -> Option<f64> {
+    let left = azimuth - span / 2.0;
+    let line = (left / step).ceil() * step;
+    (line < left + span).then_some(line.rem_euclid(360.0))
+}
@@ -0,0 +1,61 @@
+//! Atmospheric optics around a bright moon seen through thin cloud: the
+//! 22-degree halo ring, and a corona glowing close around the disc.
+//!
+//! Both are drawn as ordered-dither patterns over the background, so they
+//! read as a soft sparkle of light rather than solid rings.
+
+use crate::render::color::{self, Grade};
+use crate::render::compositor::{Compositor, Paint};
+use ratatui::style::Color;
+
+/// Angular radius of the halo ring.
+pub const RADIUS_DEGREES: f32 = 22.0;
+
+// Ice crystals bend red light least, so the ring's inner edge is reddish
+const RING_INNER: Color = Color::Rgb(200, 150, 130);
+const RING_OUTER: Color = Color::Rgb(190, 200, 230);
+const CORONA: Color = Color::Rgb(225, 220, 200);
+// How much of each lit cell the halo colors cover; the dither pattern, not
+// the tint, carries the brightness
+const TINT: f32 = 0.35;
+
+/// Draw a halo of `strength` in `0.0..=1.0` around the screen point
+/// `center`, with a ring `radius` rows across, round for rows `aspect`
+/// columns tall.
+pub fn render(
+    canvas: &mut Compositor,
+    center: (f32, f32),
+    radius: f32,
+    strength: f32,
+    aspect: f32,
+    grade: Grade,
+) {
+    if strength <= 0.0 || radius <= 0.0 {
+        return;
+    }
+    let area = canvas.area();
+    let (cx, cy) = center;
+    let ring_width = (radius * 0.12).max(0.6);
+    let corona_radius = (radius * 0.25).max(1.0);
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let dx = (x as f32 + 0.5 - cx) / aspect;
+            let dy = y as f32 + 0.5 - cy;
+            let d = dx.hypot(dy);
+            let ring = (-((d - radius) / ring_width).powi(2)).exp();
+            let corona = (-(d / corona_radius).powi(2)).exp();
+            let level = strength * (ring * 0.8 + corona);
+            if level <= color::bayer(x, y) {
+                continue;
+            }
+            let tint = if corona > ring {
+                CORONA
+            } else if d < radius {
+                RING_INNER
+            } else {
+                RING_OUTER
+            };
+            canvas.wash(x, y, Paint::new(grade.apply(tint), TINT));
+        }
+    }
+}
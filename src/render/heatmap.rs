@@ -0,0 +1,41 @@
+//! Drawing for the record of where meteors and satellites have been.
+
+use crate::render::color::{self, Grade};
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::heatmap::Heatmap;
+use ratatui::style::Color;
+
+// Dark violet through magenta and orange to pale yellow
+const COLORMAP: &[Color] = &[
+    Color::Rgb(30, 12, 60),
+    Color::Rgb(90, 20, 100),
+    Color::Rgb(170, 40, 90),
+    Color::Rgb(230, 110, 40),
+    Color::Rgb(250, 220, 120),
+];
+
+fn color_for(count: u32, max: u32) -> Color {
+    // Log scale so a single busy cell doesn't wash out the rest
+    let t = (count as f32).ln_1p() / (max as f32).ln_1p();
+    let scaled = t * (COLORMAP.len() - 1) as f32;
+    let i = (scaled.floor() as usize).min(COLORMAP.len() - 2);
+    color::lerp(COLORMAP[i], COLORMAP[i + 1], scaled - i as f32)
+}
+
+/// Tint the background of every visited cell.
+pub fn render(heatmap: &Heatmap, canvas: &mut Compositor, grade: Grade) {
+    if heatmap.max() == 0 {
+        return;
+    }
+    let area = canvas.area();
+    for y in 0..heatmap.height().min(area.height) {
+        for x in 0..heatmap.width().min(area.width) {
+            let count = heatmap.count(x, y);
+            if count == 0 {
+                continue;
+            }
+            let tint = color_for(count, heatmap.max());
+            canvas.wash(x, y, Paint::solid(grade.apply(tint)));
+        }
+    }
+}
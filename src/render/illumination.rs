@@ -0,0 +1,50 @@
+//! Transient sky illumination: flashes that briefly light up the whole
+//! background (bright meteors, and later lightning and bolides).
+//!
+//! Every effect that lights the sky goes through here so `--no-flashes`
+//! can suppress the flash while the event itself still happens.
+
+use crate::render::color;
+use ratatui::style::Color;
+
+// Color the sky is pushed towards at full intensity
+const FLASH_COLOR: Color = Color::Rgb(170, 180, 210);
+
+pub struct Illumination {
+    enabled: bool,
+    /// Current flash strength in `0.0..=1.0`
+    level: f32,
+    /// Fraction of the level lost per tick
+    decay: f32,
+}
+
+impl Illumination {
+    pub fn new(enabled: bool) -> Self {
+        Illumination {
+            enabled,
+            level: 0.0,
+            decay: 0.0,
+        }
+    }
+
+    /// Light the sky at `strength`, fading out over roughly `ticks`.
+    pub fn flash(&mut self, strength: f32, ticks: f32) {
+        if !self.enabled || strength <= self.level {
+            return;
+        }
+        self.level = strength.min(1.0);
+        self.decay = 1.0 / ticks.max(1.0);
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.level = (self.level - self.decay * dt).max(0.0);
+    }
+
+    /// The sky background with any active flash applied.
+    pub fn light(&self, background: Color) -> Color {
+        if self.level <= 0.0 {
+            return background;
+        }
+        color::lerp(background, FLASH_COLOR, self.level)
+    }
+}
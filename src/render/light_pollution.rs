@@ -0,0 +1,57 @@
+//! Light pollution on the Bortle scale, from 1, a truly dark site, to 9, an
+//! inner-city sky: the glow it casts over the sky, and the faint stars and
+//! Milky Way it drowns out.
+
+use crate::render::color;
+use ratatui::style::Color;
+
+/// A pristine dark site, as the sky is drawn by default.
+pub const DARKEST: u8 = 1;
+/// An inner-city sky.
+pub const BRIGHTEST: u8 = 9;
+
+// Streetlight scattered back down by the air
+const SKYGLOW: Color = Color::Rgb(110, 90, 75);
+// By class from 1: the faintest magnitude the eye can see, how far the sky
+// overhead is washed towards the glow, and how much of the Milky Way is left
+const CLASSES: [(f32, f32, f32); 9] = [
+    (7.8, 0.0, 1.0),
+    (7.3, 0.02, 0.9),
+    (6.8, 0.05, 0.75),
+    (6.3, 0.09, 0.5),
+    (5.8, 0.14, 0.3),
+    (5.3, 0.2, 0.12),
+    (4.8, 0.27, 0.0),
+    (4.3, 0.35, 0.0),
+    (4.0, 0.45, 0.0),
+];
+// How much stronger the glow is low down, towards the lights
+const HORIZON_GLOW: f32 = 1.6;
+
+#[cfg(feature = "serde")]
+pub(crate) fn default_bortle() -> u8 {
+    DARKEST
+}
+
+fn class(bortle: u8) -> (f32, f32, f32) {
+    CLASSES[(bortle.clamp(DARKEST, BRIGHTEST) - DARKEST) as usize]
+}
+
+/// Magnitudes a `bortle` sky takes off the faintest stars that show.
+pub fn magnitude_loss(bortle: u8) -> f32 {
+    CLASSES[0].0 - class(bortle).0
+}
+
+/// How much of the Milky Way shows through a `bortle` sky, from 0 to 1.
+pub fn milky_way(bortle: u8) -> f32 {
+    class(bortle).2
+}
+
+/// The sky gradient's `zenith` and `horizon` under a `bortle` sky's glow.
+pub fn skyglow(zenith: Color, horizon: Color, bortle: u8) -> (Color, Color) {
+    let glow = class(bortle).1;
+    (
+        color::lerp(zenith, SKYGLOW, glow),
+        color::lerp(horizon, SKYGLOW, (glow * HORIZON_GLOW).min(1.0)),
+    )
+}
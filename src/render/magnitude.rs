@@ -0,0 +1,34 @@
+//! Stars drawn by their apparent magnitude, as in the real sky: the
+//! brighter the star, the bigger its glyph and the brighter its color. Stars
+//! just inside the limiting magnitude only just show, so raising the limit
+//! brings the fainter ones out gradually.
+
+// The magnitudes the palette spans, from Sirius down to the naked eye's limit
+const BRIGHTEST: f32 = -1.5;
+const FAINTEST: f32 = 6.5;
+// Magnitudes inside the limit a star takes to show fully
+const REVEAL: f32 = 1.0;
+// How much of its light a star loses at the bottom of its twinkle
+const TWINKLE_DEPTH: f32 = 0.35;
+
+/// How bright a star of `magnitude` looks at `twinkle`, from 0 at the faint
+/// end of the palette to 1 at the bright end.
+pub fn light(magnitude: f32, twinkle: f32) -> f32 {
+    let light = ((FAINTEST - magnitude) / (FAINTEST - BRIGHTEST)).clamp(0.0, 1.0);
+    light * (1.0 - TWINKLE_DEPTH * (1.0 - twinkle))
+}
+
+/// The glyph for a star of `magnitude`.
+pub fn glyph(magnitude: f32) -> &'static str {
+    match magnitude {
+        m if m < 1.5 => "✦",
+        m if m < 3.5 => "•",
+        _ => "·",
+    }
+}
+
+/// How much of a star of `magnitude` shows with stars down to `limit`
+/// visible, from 0 at the limit to 1.
+pub fn reveal(magnitude: f32, limit: f32) -> f32 {
+    ((limit - magnitude) / REVEAL).clamp(0.0, 1.0)
+}
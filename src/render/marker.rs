@@ -0,0 +1,54 @@
+//! A pulsing marker around stars found by name, so they stand out from the
+//! rest for a while: brackets either side of each.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::TICKS_PER_SECOND;
+use ratatui::style::Color;
+use std::f32::consts::TAU;
+
+const MARK: Color = Color::Rgb(255, 220, 140);
+// How long a marker shows, and how long it takes to fade at the end
+const SHOW_TICKS: f32 = 10.0 * TICKS_PER_SECOND as f32;
+const FADE_TICKS: f32 = 2.0 * TICKS_PER_SECOND as f32;
+// Ticks per pulse
+const PULSE_TICKS: f32 = TICKS_PER_SECOND as f32;
+
+pub struct Marker {
+    /// Indices into the sky's stars
+    pub stars: Vec<usize>,
+    ticks_left: f32,
+}
+
+impl Marker {
+    pub fn new(stars: Vec<usize>) -> Self {
+        Marker {
+            stars,
+            ticks_left: SHOW_TICKS,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.ticks_left -= dt;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.ticks_left <= 0.0
+    }
+
+    /// How strongly the marker shows right now, pulsing between faint and
+    /// full and fading out at the end.
+    pub fn strength(&self) -> f32 {
+        let pulse = ((self.ticks_left / PULSE_TICKS * TAU).sin() + 1.0) / 2.0;
+        (0.35 + 0.65 * pulse) * (self.ticks_left / FADE_TICKS).clamp(0.0, 1.0)
+    }
+}
+
+/// Bracket the star in screen cell `(x, y)` at `strength`.
+pub fn render(canvas: &mut Compositor, (x, y): (u16, u16), strength: f32, grade: Grade) {
+    let paint = Paint::new(grade.apply(MARK), strength);
+    if let Some(left) = x.checked_sub(1) {
+        canvas.glyph(left, y, "(", paint);
+    }
+    canvas.glyph(x.saturating_add(1), y, ")", paint);
+}
@@ -0,0 +1,66 @@
+//! Drawing for the Milky Way: a mottled haze across the sky, thick with
+//! stars too faint to pick out one by one.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use ratatui::style::Color;
+
+const HAZE: Color = Color::Rgb(150, 160, 190);
+const DUST: Color = Color::Rgb(190, 195, 220);
+// How much the haze covers a cell in the brightest part of the band
+const HAZE_OPACITY: f32 = 0.16;
+// Chance of a faint star in a cell there
+const DUST_DENSITY: f32 = 0.35;
+// How strongly those stars show
+const DUST_COVERAGE: f32 = 0.45;
+// Sky cells across each clump of the haze's mottling
+const CLUMP: f32 = 3.0;
+
+/// Haze the sky where the Milky Way runs and scatter its faint stars.
+/// `sky_point` maps a screen cell to the point of sky it shows, so the band
+/// follows the camera, and `glow` gives how strongly it shows there.
+pub fn render<F, G>(canvas: &mut Compositor, sky_point: F, glow: G, zoom: f32, grade: Grade)
+where
+    F: Fn(u16, u16) -> (f32, f32),
+    G: Fn(f32, f32) -> f32,
+{
+    let (haze, dust) = (grade.apply(HAZE), grade.apply(DUST));
+    let area = canvas.area();
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let (sx, sy) = sky_point(x, y);
+            let strength = glow(sx, sy);
+            if strength <= 0.01 {
+                continue;
+            }
+            let mottle = 0.4 + 0.6 * noise(sx / CLUMP, sy / CLUMP);
+            canvas.wash(x, y, Paint::new(haze, strength * mottle * HAZE_OPACITY));
+
+            // Each faint star keeps to its own patch of sky
+            let (cx, cy) = ((sx * zoom).floor() as i32, (sy * zoom).floor() as i32);
+            if hash(cx, cy) < strength * mottle * DUST_DENSITY {
+                canvas.glyph(x, y, "·", Paint::new(dust, strength * DUST_COVERAGE));
+            }
+        }
+    }
+}
+
+/// A fixed pseudo-random value in `0..1` for the lattice point `(x, y)`.
+fn hash(x: i32, y: i32) -> f32 {
+    let mut h = (x as u32).wrapping_mul(0x9e37_79b1) ^ (y as u32).wrapping_mul(0x85eb_ca77);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2c1b_3c6d);
+    h ^= h >> 12;
+    (h & 0xff_ffff) as f32 / 0x100_0000 as f32
+}
+
+/// Smooth value noise in `0..1`, varying over about one unit.
+fn noise(x: f32, y: f32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (x - x0, y - y0);
+    let (tx, ty) = (tx * tx * (3.0 - 2.0 * tx), ty * ty * (3.0 - 2.0 * ty));
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let top = hash(ix, iy) + (hash(ix + 1, iy) - hash(ix, iy)) * tx;
+    let bottom = hash(ix, iy + 1) + (hash(ix + 1, iy + 1) - hash(ix, iy + 1)) * tx;
+    top + (bottom - top) * ty
+}
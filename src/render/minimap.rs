@@ -0,0 +1,156 @@
+//! A small map of the whole sky in the top right corner, with the part on
+//! screen marked out, for keeping your bearings when panned or zoomed in.
+
+use crate::render::color::{self, Grade};
+use crate::simulation::landmarks::Kind;
+use crate::simulation::Simulation;
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+
+// Widest the map gets, in cells; it never takes more than a third of the
+// screen either way
+const MAX_WIDTH: u16 = 24;
+const BACKGROUND: Color = Color::Rgb(6, 6, 16);
+const STARLIGHT: Color = Color::Rgb(60, 60, 90);
+const VIEWPORT: Color = Color::Rgb(30, 34, 60);
+const EDGE: Color = Color::Rgb(70, 80, 130);
+const FRAME: Color = Color::Rgb(80, 80, 110);
+const MOON: Color = Color::Rgb(235, 235, 215);
+const COMET: Color = Color::Rgb(200, 220, 240);
+const SATELLITE: Color = Color::Rgb(170, 190, 255);
+
+/// The part of the sky on screen, in sky cells. The left edge may lie
+/// outside the sky, which wraps.
+#[derive(Clone, Copy, Debug)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Draw the map of `sim` in the top right corner of `area`, with `viewport`
+/// marked out on it. Skipped when the screen is too small to spare room.
+pub fn render(sim: &Simulation, buf: &mut Buffer, area: Rect, viewport: Viewport, grade: Grade) {
+    let sky_width = sim.width() as f32;
+    let sky_height = sim.sky_model().horizon_y as f32;
+    let width = MAX_WIDTH.min(area.width / 3);
+    if sky_width < 1.0 || sky_height < 1.0 || width < 6 {
+        return;
+    }
+    let height = ((width as f32 * sky_height / sky_width).round() as u16).min(area.height / 3);
+    if height < 2 {
+        return;
+    }
+    let map = Rect {
+        x: area.right() - width - 2,
+        y: area.y + 2,
+        width,
+        height,
+    };
+    let scale = (width as f32 / sky_width, height as f32 / sky_height);
+    let cell = |x: f32, y: f32| -> Option<(u16, u16)> {
+        if !(0.0..sky_height).contains(&y) {
+            return None;
+        }
+        let mx = (x.rem_euclid(sky_width) * scale.0) as u16;
+        let my = (y * scale.1) as u16;
+        (mx < width && my < height).then_some((map.x + mx, map.y + my))
+    };
+
+    // The stars, summed into the map's cells
+    let mut light = vec![0.0f32; width as usize * height as usize];
+    for star in sim.stars().iter().filter(|star| star.altitude > 0.0) {
+        if let Some((x, y)) = cell(star.x, star.y) {
+            light[(y - map.y) as usize * width as usize + (x - map.x) as usize] += star.brightness as f32 * star.presence;
+        }
+    }
+    let brightest = light.iter().copied().fold(0.0, f32::max).max(1.0);
+
+    let inside = |mx: i32, my: i32| {
+        if my < 0 || my >= height as i32 {
+            return false;
+        }
+        let x = (mx as f32 + 0.5) / scale.0;
+        let y = (my as f32 + 0.5) / scale.1;
+        (x - viewport.x).rem_euclid(sky_width) < viewport.width
+            && (viewport.y..viewport.y + viewport.height).contains(&y)
+    };
+    for my in 0..height {
+        for mx in 0..width {
+            let (mx, my) = (mx as i32, my as i32);
+            let starlight = light[my as usize * width as usize + mx as usize] / brightest;
+            let mut bg = color::lerp(BACKGROUND, STARLIGHT, starlight.sqrt());
+            if inside(mx, my) {
+                // The viewport's edge stands out from its inside, except
+                // where the map wraps round under it
+                let wraps = |dx: i32| (mx + dx).rem_euclid(width as i32);
+                let edge = !inside(wraps(-1), my)
+                    || !inside(wraps(1), my)
+                    || !inside(mx, my - 1)
+                    || !inside(mx, my + 1);
+                bg = color::lerp(bg, if edge { EDGE } else { VIEWPORT }, 0.7);
+            }
+            if let Some(c) = buf.cell_mut((map.x + mx as u16, map.y + my as u16)) {
+                c.set_symbol(" ").set_bg(grade.apply(bg));
+            }
+        }
+    }
+
+    // Notable things as dots over the map
+    let mut dot = |x: f32, y: f32, symbol: &str, color: Color| {
+        if let Some(position) = cell(x, y) {
+            if let Some(c) = buf.cell_mut(position) {
+                c.set_symbol(symbol).set_fg(grade.apply(color));
+            }
+        }
+    };
+    for landmark in sim.landmarks() {
+        if let Kind::Planet(planet) = landmark.kind {
+            dot(landmark.x, landmark.y, "•", color::rgb(planet.color));
+        }
+    }
+    #[cfg(feature = "astronomy")]
+    for planet in sim.planets().iter().filter(|planet| planet.altitude > 0.0) {
+        dot(planet.x, planet.y, "•", color::rgb(planet.planet.color));
+    }
+    for satellite in sim.satellites() {
+        dot(satellite.x, satellite.y, "+", SATELLITE);
+    }
+    if let Some(train) = sim.starlink() {
+        let (width, height) = (sim.sky_model().width, sim.sky_model().horizon_y);
+        for member in train.visible(width, height) {
+            dot(member.x, member.y, "+", SATELLITE);
+        }
+    }
+    #[cfg(feature = "astronomy")]
+    if let Some(iss) = sim.iss().filter(|iss| iss.altitude > 0.0) {
+        dot(iss.x, iss.y, "+", SATELLITE);
+    }
+    if let Some(comet) = sim.comet() {
+        dot(comet.x, comet.y, "*", COMET);
+    }
+    if let Some(moon) = sim.moon() {
+        dot(moon.x, moon.y, "o", MOON);
+    }
+
+    // A thin frame around it all
+    let (left, right, top, bottom) = (map.x - 1, map.right(), map.y - 1, map.bottom());
+    let (fg, bg) = (grade.apply(FRAME), grade.apply(BACKGROUND));
+    let mut frame = |x, y, symbol| {
+        if let Some(c) = buf.cell_mut((x, y)) {
+            c.set_symbol(symbol).set_fg(fg).set_bg(bg);
+        }
+    };
+    for x in map.left()..map.right() {
+        frame(x, top, "─");
+        frame(x, bottom, "─");
+    }
+    for y in map.top()..map.bottom() {
+        frame(left, y, "│");
+        frame(right, y, "│");
+    }
+    frame(left, top, "┌");
+    frame(right, top, "┐");
+    frame(left, bottom, "└");
+    frame(right, bottom, "┘");
+}
@@ -0,0 +1,148 @@
+//! Drawing for the moon.
+//!
+//! The moon is a shaded disc a few cells across, growing as it's magnified:
+//! the terminator cuts it to the right phase on the side facing the sun, the
+//! maria show as darker patches, and during crescent phases earthshine
+//! faintly lights the rest of the disc. In an eclipse the Earth's shadow
+//! turns whatever it covers copper red.
+
+use crate::render::color::{self, Grade};
+use crate::simulation::eclipse::{Eclipse, UMBRA_RADIUS};
+use crate::simulation::moon::Moon;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Color,
+};
+
+pub const COLOR: Color = Color::Rgb(245, 240, 210);
+const MARE: Color = Color::Rgb(150, 150, 145);
+const EARTHSHINE: Color = Color::Rgb(70, 80, 105);
+// Only sunlight bent through the Earth's air reaches the moon in its
+// shadow, reddened like a sunset, and least of it at the shadow's heart
+const COPPER: Color = Color::Rgb(190, 95, 55);
+const COPPER_DEEP: Color = Color::Rgb(105, 38, 22);
+// The shadow's edge is soft over this much of the disc's radius
+const UMBRA_EDGE: f32 = 0.25;
+// How much the Earth's fainter outer shadow dims the moon by totality
+const PENUMBRA_DIMMING: f32 = 0.25;
+
+// Disc radius in rows at a zoom of 1. Far larger than the real moon, so
+// that its phase reads at a glance even to the naked eye
+const DISC_RADIUS: f32 = 1.2;
+
+// Points sampled across and down each cell, so the terminator and rim fall
+// between cells smoothly
+const SAMPLES: (usize, usize) = (4, 2);
+
+// Crescents thinner than this show earthshine
+const EARTHSHINE_BELOW: f32 = 0.35;
+
+// The larger maria as (across, down, radius) on a disc of radius 1, as seen
+// from the northern hemisphere
+const MARIA: [(f32, f32, f32); 6] = [
+    (-0.35, -0.45, 0.3),  // Imbrium
+    (0.1, -0.4, 0.2),     // Serenitatis
+    (0.3, -0.05, 0.25),   // Tranquillitatis
+    (0.7, -0.25, 0.13),   // Crisium
+    (-0.6, 0.05, 0.35),   // Procellarum
+    (-0.25, 0.45, 0.2),   // Nubium
+];
+
+/// Disc radius in rows when magnified by `zoom`.
+pub fn radius(zoom: f32) -> f32 {
+    DISC_RADIUS * zoom
+}
+
+/// Color of the disc at `(u, v)`, measured across and down from its
+/// centre with the rim at 1, or `None` for a dark part of the disc.
+/// Earthshine is given as a color and how strongly to blend it in.
+fn surface(moon: &Moon, u: f32, v: f32) -> Option<(Color, f32)> {
+    // From the south the whole disc is turned half around
+    let (u, v) = if moon.southern { (-u, -v) } else { (u, v) };
+    let fraction = moon.phase.fraction() as f32;
+    // The terminator is half an ellipse across the disc
+    let rim = (1.0 - v * v).max(0.0).sqrt();
+    let terminator = (fraction * std::f32::consts::TAU).cos() * rim;
+    let side = if fraction < 0.5 { u } else { -u };
+    let mare = MARIA
+        .iter()
+        .map(|&(mu, mv, r)| (1.0 - ((u - mu).hypot(v - mv) / r).powi(2)).max(0.0))
+        .fold(0.0, f32::max);
+    if side > terminator {
+        // Slightly darker towards the limb
+        let limb = 1.0 - 0.25 * (u * u + v * v);
+        let color = color::scale(color::lerp(COLOR, MARE, mare), limb);
+        let color = match moon.eclipse {
+            Some(eclipse) => shadowed(color, &eclipse, u, v, mare),
+            None => color,
+        };
+        return Some((color, 1.0));
+    }
+    let earthshine = (1.0 - moon.brightness() / EARTHSHINE_BELOW).clamp(0.0, 1.0) * 0.5;
+    (earthshine > 0.0).then_some((EARTHSHINE, earthshine * (1.0 - 0.4 * mare)))
+}
+
+/// The lit `color` at `(u, v)` on the disc, in the shadow of `eclipse`.
+fn shadowed(color: Color, eclipse: &Eclipse, u: f32, v: f32, mare: f32) -> Color {
+    let color = color::scale(color, 1.0 - PENUMBRA_DIMMING * eclipse.coverage());
+    let inside = ((UMBRA_RADIUS - (u - eclipse.shadow()).hypot(v)) / UMBRA_EDGE).clamp(0.0, 1.0);
+    let copper = color::lerp(COPPER, COPPER_DEEP, eclipse.depth());
+    color::lerp(color, color::scale(copper, 1.0 - 0.3 * mare), inside)
+}
+
+/// Draw the moon centred on the screen point `center`, round for rows
+/// `aspect` columns tall.
+pub fn render(
+    moon: &Moon,
+    buf: &mut Buffer,
+    area: Rect,
+    center: (f32, f32),
+    zoom: f32,
+    aspect: f32,
+    grade: Grade,
+) {
+    let (cx, cy) = center;
+    let radius = radius(zoom);
+    let (half_w, half_h) = (radius * aspect, radius);
+    let left = (cx - half_w).floor().max(0.0) as u16;
+    let top = (cy - half_h).floor().max(0.0) as u16;
+    let right = ((cx + half_w).ceil().max(0.0) as u16).min(area.width);
+    let bottom = ((cy + half_h).ceil().max(0.0) as u16).min(area.height);
+    let (across, down) = SAMPLES;
+    for y in top..bottom {
+        for x in left..right {
+            let Some(cell) = buf.cell_mut((area.x + x, area.y + y)) else {
+                continue;
+            };
+            let sky = cell.bg;
+            // Average the disc over the cell, the sky showing where it's missed
+            let (mut inside, mut disc) = (0, sky);
+            for i in 0..across {
+                for j in 0..down {
+                    let sx = x as f32 + (i as f32 + 0.5) / across as f32;
+                    let sy = y as f32 + (j as f32 + 0.5) / down as f32;
+                    let (u, v) = ((sx - cx) / half_w, (sy - cy) / half_h);
+                    if u.hypot(v) > 1.0 {
+                        continue;
+                    }
+                    let color = match surface(moon, u, v) {
+                        Some((color, strength)) => color::lerp(sky, grade.apply(color), strength),
+                        None => sky,
+                    };
+                    inside += 1;
+                    disc = color::lerp(disc, color, 1.0 / inside as f32);
+                }
+            }
+            if inside == 0 {
+                continue;
+            }
+            let cover = inside as f32 / (across * down) as f32;
+            // The disc hides the stars behind it, lit or not
+            if cover > 0.5 {
+                cell.set_symbol(" ");
+            }
+            cell.set_bg(color::lerp(sky, disc, cover));
+        }
+    }
+}
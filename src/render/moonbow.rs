@@ -0,0 +1,44 @@
+//! Drawing for the faint lunar rainbow that can follow a shower.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use crate::render::shade;
+use crate::simulation::moonbow::Moonbow;
+use ratatui::style::Color;
+
+// Outer to inner band
+const BANDS: [Color; 6] = [
+    Color::Rgb(200, 80, 80),
+    Color::Rgb(210, 150, 70),
+    Color::Rgb(200, 200, 90),
+    Color::Rgb(90, 180, 100),
+    Color::Rgb(80, 120, 200),
+    Color::Rgb(130, 90, 190),
+];
+
+/// Arc centred on the bottom of the sky, spanning most of its width, round
+/// for rows `aspect` columns tall.
+pub fn render(moonbow: &Moonbow, canvas: &mut Compositor, aspect: f32, grade: Grade) {
+    let strength = moonbow.strength();
+    let Some(glyph) = shade::glyph(strength) else {
+        return;
+    };
+    let area = canvas.area();
+    let cx = area.width as f32 / 2.0;
+    let base = area.height as f32;
+    let outer = area.width as f32 * 0.4;
+    let band_width = (outer * 0.04).max(1.0);
+
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let dx = x as f32 + 0.5 - cx;
+            let dy = (base - y as f32 - 0.5) * aspect;
+            let depth = (outer - dx.hypot(dy)) / band_width;
+            if !(0.0..BANDS.len() as f32).contains(&depth) {
+                continue;
+            }
+            let band = grade.apply(BANDS[depth as usize]);
+            canvas.glyph(x, y, glyph, Paint::new(band, strength));
+        }
+    }
+}
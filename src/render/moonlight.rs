@@ -0,0 +1,27 @@
+//! The sky lit by a bright moon: a pale glow over the background around
+//! it, strongest beside a full moon and gone for a crescent.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::moon::Moon;
+use ratatui::style::Color;
+
+const LIGHT: Color = Color::Rgb(120, 135, 170);
+// How much of the background the glow covers right beside a full moon
+const COVERAGE: f32 = 0.3;
+
+/// Brighten the background around `moon`, each screen cell showing the sky
+/// point `sky_point` gives for it.
+pub fn render(moon: &Moon, canvas: &mut Compositor, sky_point: impl Fn(u16, u16) -> (f32, f32), grade: Grade) {
+    let area = canvas.area();
+    let light = grade.apply(LIGHT);
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let (sx, sy) = sky_point(x, y);
+            let level = moon.skylight(sx, sy);
+            if level > 0.0 {
+                canvas.wash(x, y, Paint::new(light, COVERAGE * level));
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Drawing for the particle engine's foreground effects.
+
+use crate::render::color::{self, Grade};
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::particles::ParticleSystem;
+
+// Rotating through these reads as a leaf turning over as it falls
+pub const LEAF: &[&str] = &["❦", "❧", "☙", "❧"];
+pub const RAINDROP: &[&str] = &["│"];
+pub const SNOWFLAKE: &[&str] = &["*"];
+
+/// Draw each particle with `glyphs` as its animation frames.
+pub fn render(system: &ParticleSystem, glyphs: &[&str], canvas: &mut Compositor, grade: Grade) {
+    let area = canvas.area();
+    for p in system.iter() {
+        if p.x < 0.0 || p.y < 0.0 {
+            continue;
+        }
+        let (x, y) = (p.x as u16, p.y as u16);
+        if x >= area.width || y >= area.height {
+            continue;
+        }
+        let glyph = glyphs[p.frame() % glyphs.len()];
+        let paint = Paint::new(grade.apply(color::rgb(p.color)), 1.0 - p.fade());
+        canvas.glyph(x, y, glyph, paint);
+    }
+}
@@ -0,0 +1,37 @@
+//! Guides drawn over an active meteor shower's radiant.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::meteor_showers::ActiveShower;
+use ratatui::style::Color;
+
+const GUIDE: Color = Color::Rgb(150, 170, 220);
+
+/// Mark the radiant and draw faint guides along the lines meteors follow,
+/// with rows `aspect` columns tall.
+pub fn render(shower: &ActiveShower, canvas: &mut Compositor, aspect: f32, grade: Grade) {
+    const GUIDES: usize = 12;
+    let (rx, ry) = shower.radiant;
+    let area = canvas.area();
+    let guide = grade.apply(GUIDE);
+    let mut put = |x: f32, y: f32, glyph: &'static str, coverage: f32| {
+        if x < 0.0 || y < 0.0 {
+            return;
+        }
+        canvas.glyph(x as u16, y as u16, glyph, Paint::new(guide, coverage));
+    };
+
+    let reach = (area.width as f32).max(area.height as f32 * aspect);
+    for i in 0..GUIDES {
+        let angle = i as f32 / GUIDES as f32 * std::f32::consts::TAU;
+        let (dx, dy) = (angle.cos(), angle.sin() / aspect);
+        // Dots every few cells, fading away from the radiant
+        let mut distance = 3.0;
+        while distance < reach {
+            let fade = 1.0 - distance / reach;
+            put(rx + dx * distance, ry + dy * distance, "·", 0.3 * fade);
+            distance += 3.0;
+        }
+    }
+    put(rx, ry, "+", 0.8);
+}
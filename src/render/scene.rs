@@ -0,0 +1,171 @@
+//! Scene files: scripted direction for recordings and kiosk loops.
+//!
+//! A scene is a plain text file split into `[sections]`. Blank lines and
+//! lines starting with `#` are ignored.
+//!
+//! ```text
+//! [scene]
+//! loop 40s
+//!
+//! [camera]
+//! # time  pan (cells)   zoom      easing into this keyframe
+//! 0s      x=0 y=0       zoom=1
+//! 20s     x=60          zoom=1.5  ease=in-out
+//!
+//! [captions]
+//! # start     duration  position  text
+//! 2s          5s        top       The Perseids peak tonight
+//! @fireball   4s                  A fireball!
+//! ```
+//!
+//! Camera keyframes leave out any field that doesn't change; it carries over
+//! from the previous keyframe. A caption starts either at a time or, written
+//! as `@event`, each time that sky event happens. The position is optional
+//! and defaults to `bottom`. A `loop` time restarts the camera and timed
+//! captions from the top once it is reached.
+
+use crate::render::camera::{Camera, CameraPath, Keyframe};
+use crate::render::captions::{Caption, Cue, Position};
+use crate::render::easing::Easing;
+use std::fs;
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scene {
+    pub camera: CameraPath,
+    pub captions: Vec<Caption>,
+    /// Restart the scene after this many seconds
+    pub period: Option<f32>,
+}
+
+impl Scene {
+    pub fn load(path: &str) -> Result<Scene, String> {
+        let text =
+            fs::read_to_string(path).map_err(|err| format!("can't read scene '{}': {}", path, err))?;
+        Scene::parse(&text).map_err(|err| format!("{}: {}", path, err))
+    }
+
+    /// Position within the scene `seconds` into the session.
+    pub fn clock(&self, seconds: f32) -> f32 {
+        self.period.map_or(seconds, |period| seconds % period)
+    }
+
+    pub fn parse(text: &str) -> Result<Scene, String> {
+        let mut section = None;
+        let mut period = None;
+        let mut keyframes: Vec<Keyframe> = Vec::new();
+        let mut captions = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let located = |err: String| format!("line {}: {}", number + 1, err);
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = Some(name.trim().to_string());
+                continue;
+            }
+            match section.as_deref() {
+                Some("camera") => {
+                    let previous = keyframes.last().map(|k| k.camera).unwrap_or_default();
+                    keyframes.push(parse_keyframe(line, previous).map_err(located)?);
+                }
+                Some("captions") => captions.push(parse_caption(line).map_err(located)?),
+                Some("scene") => match split_word(line) {
+                    ("loop", time) => period = Some(parse_time(time).map_err(located)?),
+                    (other, _) => return Err(located(format!("unknown scene setting '{}'", other))),
+                },
+                Some(other) => return Err(located(format!("unknown section '{}'", other))),
+                None => return Err(located("expected a [section] header".to_string())),
+            }
+        }
+        Ok(Scene {
+            camera: CameraPath::new(keyframes),
+            captions,
+            period: period.filter(|&p| p > 0.0),
+        })
+    }
+}
+
+/// Parse a time like `12`, `12s`, `1.5m` or `1h` into seconds.
+pub fn parse_time(raw: &str) -> Result<f32, String> {
+    let (number, scale) = if let Some(hours) = raw.strip_suffix('h') {
+        (hours, 3600.0)
+    } else if let Some(minutes) = raw.strip_suffix('m') {
+        (minutes, 60.0)
+    } else {
+        (raw.strip_suffix('s').unwrap_or(raw), 1.0)
+    };
+    match number.parse::<f32>() {
+        Ok(value) if value.is_finite() && value >= 0.0 => Ok(value * scale),
+        _ => Err(format!("invalid time '{}'", raw)),
+    }
+}
+
+fn parse_keyframe(line: &str, previous: Camera) -> Result<Keyframe, String> {
+    let mut fields = line.split_whitespace();
+    let time = parse_time(fields.next().unwrap_or_default())?;
+    let mut camera = previous;
+    let mut easing = Easing::default();
+    for field in fields {
+        let (key, value) = field
+            .split_once('=')
+            .ok_or_else(|| format!("expected key=value, got '{}'", field))?;
+        let number = || {
+            value
+                .parse::<f32>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| format!("invalid value '{}' for {}", value, key))
+        };
+        match key {
+            "x" => camera.pan_x = number()?,
+            "y" => camera.pan_y = number()?,
+            "zoom" => {
+                camera.zoom = number()?;
+                if camera.zoom <= 0.0 {
+                    return Err("zoom must be positive".to_string());
+                }
+            }
+            "ease" => easing = value.parse()?,
+            other => return Err(format!("unknown camera field '{}'", other)),
+        }
+    }
+    Ok(Keyframe {
+        time,
+        camera,
+        easing,
+    })
+}
+
+fn parse_caption(line: &str) -> Result<Caption, String> {
+    let (cue, rest) = split_word(line);
+    let cue = match cue.strip_prefix('@') {
+        Some(event) => Cue::On(event.parse()?),
+        None => Cue::At(parse_time(cue)?),
+    };
+    let (duration, rest) = split_word(rest);
+    let duration = parse_time(duration)?;
+    let (word, text) = split_word(rest);
+    let (position, text) = match word.parse::<Position>() {
+        Ok(position) => (position, text),
+        Err(_) => (Position::default(), rest),
+    };
+    if text.is_empty() {
+        return Err("caption has no text".to_string());
+    }
+    Ok(Caption {
+        cue,
+        duration,
+        position,
+        text: text.to_string(),
+    })
+}
+
+/// Split off the first whitespace-separated word.
+fn split_word(s: &str) -> (&str, &str) {
+    match s.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim_start()),
+        None => (s, ""),
+    }
+}
@@ -0,0 +1,13 @@
+//! Block-shading helpers for soft, low-intensity fills.
+
+const SHADES: [&str; 4] = ["░", "▒", "▓", "█"];
+
+/// Shade glyph for a coverage level in `0.0..=1.0`, or `None` when too faint
+/// to draw at all.
+pub fn glyph(level: f32) -> Option<&'static str> {
+    if level <= 0.05 {
+        return None;
+    }
+    let i = (level.clamp(0.0, 1.0) * SHADES.len() as f32) as usize;
+    Some(SHADES[i.min(SHADES.len() - 1)])
+}
@@ -0,0 +1,34 @@
+//! Drawing for the sparks meteors shed.
+
+use crate::render::bolide;
+use crate::render::color::{self, Grade};
+use crate::render::compositor::{Compositor, Paint};
+use crate::render::theme::Theme;
+use crate::simulation::sparks::Sparks;
+
+/// Draw `sparks` in `theme`'s meteor tints, `project`ing each to the screen
+/// cell it shows in.
+pub fn render(
+    sparks: &Sparks,
+    canvas: &mut Compositor,
+    theme: &Theme,
+    grade: Grade,
+    project: impl Fn(f32, f32) -> Option<(u16, u16)>,
+) {
+    for spark in sparks.iter() {
+        let Some((x, y)) = project(spark.x, spark.y) else {
+            continue;
+        };
+        let fade = spark.fade();
+        // Struck off hot in the head's color, cooling to the trail's
+        let tint = match spark.burn {
+            Some(burn) => bolide::tint(burn),
+            None => {
+                let tint = theme.meteor(spark.tint);
+                color::lerp(tint.head, tint.trail, 2.0 * fade)
+            }
+        };
+        let glyph = if fade < 0.3 { "•" } else { "·" };
+        canvas.glyph(x, y, glyph, Paint::new(grade.apply(tint), spark.brightness * (1.0 - fade)));
+    }
+}
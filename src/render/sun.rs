@@ -0,0 +1,45 @@
+//! The sun by day: a bright disc the size of the moon's, yellowing and
+//! reddening as it sinks, in a glow that washes out the sky around it.
+
+use crate::render::color::{self, Grade};
+use crate::render::compositor::{Compositor, Paint};
+use crate::render::moon;
+use crate::simulation::sun::Sun;
+use ratatui::style::Color;
+
+// The disc high in the sky, and down at the horizon
+const DISC: Color = Color::Rgb(255, 250, 225);
+const LOW: Color = Color::Rgb(255, 150, 70);
+// Degrees above the horizon where the disc is at its whitest
+const HIGH: f32 = 10.0;
+// How many disc radii the glow reaches, and how much it covers at the rim
+const GLOW_RADII: f32 = 3.0;
+const GLOW: f32 = 0.5;
+
+/// Draw `sun` at the screen point `center` at `zoom`, round for rows
+/// `aspect` columns tall. Nothing shows once it's set.
+pub fn render(sun: &Sun, canvas: &mut Compositor, center: (f32, f32), zoom: f32, aspect: f32, grade: Grade) {
+    if sun.altitude < 0.0 {
+        return;
+    }
+    let tint = grade.apply(color::lerp(LOW, DISC, sun.altitude / HIGH));
+    let radius = moon::radius(zoom).max(0.5);
+    let reach = radius * GLOW_RADII;
+    let area = canvas.area();
+    let (cx, cy) = center;
+    let rows = (cy - reach).max(0.0) as u16..((cy + reach).ceil() as u16).min(area.height);
+    let columns = (cx - reach * aspect).max(0.0) as u16..((cx + reach * aspect).ceil() as u16).min(area.width);
+    for y in rows {
+        for x in columns.clone() {
+            let dx = (x as f32 + 0.5 - cx) / aspect;
+            let dy = y as f32 + 0.5 - cy;
+            let d = dx.hypot(dy);
+            if d <= radius {
+                canvas.wash(x, y, Paint::solid(tint));
+            } else if d < reach {
+                let falloff = 1.0 - (d - radius) / (reach - radius);
+                canvas.wash(x, y, Paint::new(tint, GLOW * falloff * falloff));
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! A supernova's ring of light, racing out from the star as it flares and
+//! thinning to nothing over half a minute, with a glow around the star
+//! while it's brighter than any other.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::supernova::Supernova;
+use ratatui::style::Color;
+
+const LIGHT: Color = Color::Rgb(205, 220, 255);
+// Rows the ring has spread by when it vanishes
+const REACH: f32 = 8.0;
+// Columns between dots around the ring
+const SPACING: f32 = 1.5;
+// Rows the glow reaches, and how much it covers by the star at its peak
+const GLOW_RADIUS: f32 = 2.0;
+const GLOW: f32 = 0.5;
+// Magnitudes over which the glow comes up, from 0 to the peak
+const GLOW_MAGNITUDES: f32 = 4.0;
+
+/// Draw `supernova`'s ring and glow about the screen point `center` at
+/// `zoom`, round for rows `aspect` columns tall.
+pub fn render(supernova: &Supernova, canvas: &mut Compositor, center: (f32, f32), zoom: f32, aspect: f32, grade: Grade) {
+    let light = grade.apply(LIGHT);
+    let (cx, cy) = center;
+    let area = canvas.area();
+
+    let glow = (-supernova.magnitude() / GLOW_MAGNITUDES).clamp(0.0, 1.0) * GLOW;
+    if glow > 0.0 {
+        let reach = GLOW_RADIUS * zoom.sqrt();
+        let rows = (cy - reach).max(0.0) as u16..((cy + reach).ceil() as u16).min(area.height);
+        let columns = (cx - reach * aspect).max(0.0) as u16..((cx + reach * aspect).ceil() as u16).min(area.width);
+        for y in rows {
+            for x in columns.clone() {
+                let dx = (x as f32 + 0.5 - cx) / aspect;
+                let dy = y as f32 + 0.5 - cy;
+                let falloff = 1.0 - dx.hypot(dy) / reach;
+                if falloff > 0.0 {
+                    canvas.wash(x, y, Paint::new(light, glow * falloff * falloff));
+                }
+            }
+        }
+    }
+
+    // Fast at first, slowing as it spreads
+    let Some(progress) = supernova.ring() else {
+        return;
+    };
+    let radius = REACH * zoom * progress.sqrt();
+    let fade = (1.0 - progress) * (1.0 - progress);
+    let glyph = if progress < 0.3 { "•" } else { "·" };
+    let around = std::f32::consts::TAU * radius * (aspect + 1.0) / 2.0;
+    let dots = ((around / SPACING) as usize).max(8);
+    for i in 0..dots {
+        let angle = i as f32 / dots as f32 * std::f32::consts::TAU;
+        let x = cx + angle.cos() * radius * aspect;
+        let y = cy + angle.sin() * radius;
+        if x >= 0.0 && y >= 0.0 {
+            canvas.glyph(x as u16, y as u16, glyph, Paint::new(light, fade));
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! Color themes.
+
+use ratatui::style::Color;
+
+/// Head and trail colors for one meteor composition.
+#[derive(Clone, Copy, Debug)]
+pub struct MeteorTint {
+    pub name: &'static str,
+    pub head: Color,
+    pub trail: Color,
+    /// Relative likelihood of a meteor getting this tint
+    pub weight: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Theme {
+    pub name: &'static str,
+    pub background: Color,
+    /// Star colors from faintest to brightest
+    pub stars: [Color; 5],
+    pub satellite: Color,
+    pub meteors: &'static [MeteorTint],
+}
+
+const STARS: [Color; 5] = [
+    Color::Rgb(100, 100, 120),
+    Color::Rgb(150, 150, 180),
+    Color::Rgb(200, 200, 220),
+    Color::Rgb(230, 230, 250),
+    Color::Rgb(255, 255, 255),
+];
+
+const SATELLITE: Color = Color::Rgb(235, 235, 255);
+
+// Sodium (orange) dominates, magnesium (green) and iron/calcium (blue-white)
+// are less common. The green leans blue and the blue-white bluer, just enough
+// to keep all three apart for the color-blind
+const REALISTIC_METEORS: &[MeteorTint] = &[
+    MeteorTint {
+        name: "orange",
+        head: Color::Rgb(255, 200, 100),
+        trail: Color::Rgb(200, 150, 50),
+        weight: 55,
+    },
+    MeteorTint {
+        name: "green",
+        head: Color::Rgb(160, 255, 180),
+        trail: Color::Rgb(80, 190, 100),
+        weight: 25,
+    },
+    MeteorTint {
+        name: "blue-white",
+        head: Color::Rgb(220, 230, 255),
+        trail: Color::Rgb(130, 160, 220),
+        weight: 20,
+    },
+];
+
+const CLASSIC_METEORS: &[MeteorTint] = &[MeteorTint {
+    name: "gold",
+    head: Color::Rgb(255, 200, 100),
+    trail: Color::Rgb(200, 150, 50),
+    weight: 1,
+}];
+
+// Red-green deficiencies keep the blue-yellow axis, so meteors are split
+// along it and the satellite is pushed towards blue
+const RED_GREEN_SAFE_METEORS: &[MeteorTint] = &[
+    MeteorTint {
+        name: "amber",
+        head: Color::Rgb(240, 170, 40),
+        trail: Color::Rgb(170, 110, 20),
+        weight: 55,
+    },
+    MeteorTint {
+        name: "sky blue",
+        head: Color::Rgb(90, 180, 240),
+        trail: Color::Rgb(40, 110, 170),
+        weight: 45,
+    },
+];
+
+// Tritanopia keeps the red-green axis
+const TRITAN_SAFE_METEORS: &[MeteorTint] = &[
+    MeteorTint {
+        name: "vermilion",
+        head: Color::Rgb(240, 100, 60),
+        trail: Color::Rgb(170, 60, 30),
+        weight: 55,
+    },
+    MeteorTint {
+        name: "teal",
+        head: Color::Rgb(60, 200, 190),
+        trail: Color::Rgb(30, 130, 120),
+        weight: 45,
+    },
+];
+
+// Saturated, fully distinct colors for maximum legibility
+const HIGH_CONTRAST_METEORS: &[MeteorTint] = &[
+    MeteorTint {
+        name: "yellow",
+        head: Color::Rgb(255, 255, 0),
+        trail: Color::Rgb(255, 200, 0),
+        weight: 60,
+    },
+    MeteorTint {
+        name: "cyan",
+        head: Color::Rgb(0, 255, 255),
+        trail: Color::Rgb(0, 200, 255),
+        weight: 40,
+    },
+];
+
+pub const THEMES: &[Theme] = &[
+    Theme {
+        name: "night",
+        background: Color::Rgb(10, 10, 30),
+        stars: STARS,
+        satellite: SATELLITE,
+        meteors: REALISTIC_METEORS,
+    },
+    Theme {
+        name: "classic",
+        background: Color::Rgb(10, 10, 30),
+        stars: STARS,
+        satellite: SATELLITE,
+        meteors: CLASSIC_METEORS,
+    },
+    Theme {
+        name: "deutan-safe",
+        background: Color::Rgb(10, 10, 30),
+        stars: STARS,
+        satellite: Color::Rgb(240, 240, 240),
+        meteors: RED_GREEN_SAFE_METEORS,
+    },
+    Theme {
+        name: "protan-safe",
+        background: Color::Rgb(10, 10, 30),
+        stars: STARS,
+        satellite: Color::Rgb(240, 240, 240),
+        meteors: RED_GREEN_SAFE_METEORS,
+    },
+    Theme {
+        name: "tritan-safe",
+        background: Color::Rgb(10, 10, 30),
+        stars: STARS,
+        satellite: Color::Rgb(255, 220, 120),
+        meteors: TRITAN_SAFE_METEORS,
+    },
+    Theme {
+        name: "high-contrast",
+        background: Color::Rgb(0, 0, 0),
+        stars: [
+            Color::Rgb(190, 190, 190),
+            Color::Rgb(220, 220, 220),
+            Color::Rgb(255, 255, 255),
+            Color::Rgb(255, 255, 255),
+            Color::Rgb(255, 255, 255),
+        ],
+        satellite: Color::Rgb(255, 0, 255),
+        meteors: HIGH_CONTRAST_METEORS,
+    },
+];
+
+impl Theme {
+    pub fn by_name(name: &str) -> Option<Theme> {
+        THEMES.iter().find(|t| t.name == name).copied()
+    }
+
+    /// The meteor tint a roll in `0.0..1.0` lands on, each tint taking a
+    /// share of the range according to the palette weights.
+    pub fn meteor(&self, roll: f32) -> MeteorTint {
+        let total: u32 = self.meteors.iter().map(|t| t.weight).sum();
+        let mut roll = ((roll * total as f32) as u32).min(total.saturating_sub(1));
+        for tint in self.meteors {
+            if roll < tint.weight {
+                return *tint;
+            }
+            roll -= tint.weight;
+        }
+        self.meteors[0]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        THEMES[0]
+    }
+}
+
+/// Saved by name, as themes are built in.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Theme {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Theme {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        let name = String::deserialize(deserializer)?;
+        Self::by_name(&name).ok_or_else(|| D::Error::custom(format!("unknown theme `{}`", name)))
+    }
+}
@@ -0,0 +1,69 @@
+//! Guided tour: a looping scene that visits each landmark in turn, like a
+//! small planetarium show.
+
+use crate::render::camera::{Camera, CameraPath, Keyframe};
+use crate::render::captions::{Caption, Cue, Position};
+use crate::render::easing::Easing;
+use crate::render::scene::Scene;
+use crate::simulation::landmarks::{Kind, Landmark};
+
+// Seconds spent gliding between stops, holding on each, and resting on the
+// wide view before the tour starts over
+const TRAVEL: f32 = 6.0;
+const HOLD: f32 = 8.0;
+const REST: f32 = 6.0;
+
+/// How far to zoom in on a landmark so it fills the view without cropping.
+fn zoom_for(kind: &Kind) -> f32 {
+    match kind {
+        Kind::Asterism(_) => 1.8,
+        _ => 3.0,
+    }
+}
+
+/// A scene panning from the wide view to each of `landmarks` and back,
+/// captioning each stop. `center` is the point the camera zooms around.
+pub fn scene(landmarks: &[Landmark], center: (f32, f32)) -> Scene {
+    let wide = Camera::default();
+    let mut keyframes = vec![Keyframe {
+        time: 0.0,
+        camera: wide,
+        easing: Easing::Linear,
+    }];
+    let mut captions = Vec::new();
+    let mut time = REST;
+    let mut glide = |camera: Camera, time: &mut f32| {
+        keyframes.push(Keyframe {
+            time: *time,
+            camera: keyframes.last().map_or(wide, |k| k.camera),
+            easing: Easing::Linear,
+        });
+        *time += TRAVEL;
+        keyframes.push(Keyframe {
+            time: *time,
+            camera,
+            easing: Easing::InOut,
+        });
+    };
+    for landmark in landmarks {
+        let camera = Camera {
+            pan_x: landmark.x - center.0,
+            pan_y: landmark.y - center.1,
+            zoom: zoom_for(&landmark.kind),
+        };
+        glide(camera, &mut time);
+        captions.push(Caption {
+            cue: Cue::At(time),
+            duration: HOLD,
+            position: Position::Top,
+            text: landmark.description(),
+        });
+        time += HOLD;
+    }
+    glide(wide, &mut time);
+    Scene {
+        camera: CameraPath::new(keyframes),
+        captions,
+        period: Some(time + REST),
+    }
+}
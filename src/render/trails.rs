@@ -0,0 +1,36 @@
+//! Drawing for the smoke trains bright meteors leave behind.
+
+use crate::render::color::Grade;
+use crate::render::compositor::{Compositor, Paint};
+use crate::simulation::trails::TrailLayer;
+use ratatui::style::Color;
+
+const SMOKE_COLOR: Color = Color::Rgb(110, 105, 120);
+
+/// Draw `trails`, `project`ing each point of sky to the screen cell it
+/// shows in.
+pub fn render(
+    trails: &TrailLayer,
+    canvas: &mut Compositor,
+    grade: Grade,
+    project: impl Fn(f32, f32) -> Option<(u16, u16)>,
+) {
+    let smoke = grade.apply(SMOKE_COLOR);
+    for train in trails.iter() {
+        let life = train.age / train.max_age;
+        // Start faint and fade the rest of the way into the sky
+        let paint = Paint::new(smoke, 0.7 * (1.0 - life));
+        // The wave grows as the train ages and distorts
+        let amplitude = life * 1.5;
+
+        for (i, point) in train.points.iter().enumerate() {
+            let wave = (train.wave_phase + i as f32 * 0.4 + train.age * 0.02).sin();
+            let Some((x, y)) = project(point.x + 0.5, point.y + wave * amplitude + 0.5) else {
+                continue;
+            };
+
+            let glyph = if wave.abs() > 0.5 { "~" } else { "·" };
+            canvas.glyph(x, y, glyph, paint);
+        }
+    }
+}
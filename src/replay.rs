@@ -0,0 +1,369 @@
+//! `nk replay`: play back an asciicast recording with a scrubber bar.
+//!
+//! The recording's escape sequences are applied to an in-memory screen, so
+//! any moment can be reached by replaying output from the nearest earlier
+//! checkpoint, a snapshot of the screen taken every few seconds.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+};
+use std::fs;
+use std::io;
+
+// Seconds of recording between checkpoints
+const CHECKPOINT_INTERVAL: f32 = 10.0;
+
+/// How far the arrow keys jump, in seconds of recording.
+pub const SEEK_STEP: f32 = 5.0;
+
+const SPEEDS: &[f32] = &[0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+const NORMAL_SPEED: usize = 2;
+
+const BAR: Color = Color::Rgb(20, 22, 34);
+const TEXT: Color = Color::Rgb(210, 210, 230);
+const TRACK: Color = Color::Rgb(80, 86, 120);
+const PLAYED: Color = Color::Rgb(150, 170, 230);
+
+/// A terminal screen built up from escape sequences.
+#[derive(Clone)]
+struct Screen {
+    buffer: Buffer,
+    cursor: (u16, u16),
+    fg: Color,
+    bg: Color,
+}
+
+impl Screen {
+    fn new(width: u16, height: u16) -> Self {
+        Screen {
+            buffer: Buffer::empty(Rect::new(0, 0, width, height)),
+            cursor: (0, 0),
+            fg: Color::Reset,
+            bg: Color::Reset,
+        }
+    }
+
+    /// Apply terminal output. Only what nk's own recordings use is
+    /// understood: cursor moves, clearing and colors; anything else is
+    /// skipped.
+    fn write(&mut self, data: &str) {
+        let mut chars = data.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => {
+                    if chars.next_if_eq(&'[').is_none() {
+                        continue;
+                    }
+                    let mut params = String::new();
+                    for c in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&c) {
+                            self.control(c, &params);
+                            break;
+                        }
+                        params.push(c);
+                    }
+                }
+                '\r' => self.cursor.0 = 0,
+                '\n' => self.cursor.1 = self.cursor.1.saturating_add(1),
+                c if c.is_control() => {}
+                c => {
+                    let (x, y) = self.cursor;
+                    if let Some(cell) = self.buffer.cell_mut((x, y)) {
+                        cell.set_char(c).set_fg(self.fg).set_bg(self.bg);
+                    }
+                    self.cursor.0 = x.saturating_add(1);
+                }
+            }
+        }
+    }
+
+    /// Carry out the control sequence ending in `command`.
+    fn control(&mut self, command: char, params: &str) {
+        let numbers: Vec<u16> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+        let number = |i: usize| numbers.get(i).copied().unwrap_or(0);
+        match command {
+            'H' => self.cursor = (number(1).saturating_sub(1), number(0).saturating_sub(1)),
+            'J' if number(0) == 2 => self.buffer.reset(),
+            'm' => self.style(&numbers),
+            _ => {}
+        }
+    }
+
+    /// Apply SGR parameters.
+    fn style(&mut self, params: &[u16]) {
+        let mut params = params.iter().copied();
+        while let Some(param) = params.next() {
+            match param {
+                0 => (self.fg, self.bg) = (Color::Reset, Color::Reset),
+                38 | 48 => {
+                    let color = match params.next() {
+                        Some(2) => {
+                            let mut channel = || params.next().unwrap_or(0) as u8;
+                            Color::Rgb(channel(), channel(), channel())
+                        }
+                        Some(5) => Color::Indexed(params.next().unwrap_or(0) as u8),
+                        _ => continue,
+                    };
+                    if param == 38 {
+                        self.fg = color;
+                    } else {
+                        self.bg = color;
+                    }
+                }
+                30..=37 => self.fg = Color::Indexed((param - 30) as u8),
+                90..=97 => self.fg = Color::Indexed((param - 90 + 8) as u8),
+                40..=47 => self.bg = Color::Indexed((param - 40) as u8),
+                100..=107 => self.bg = Color::Indexed((param - 100 + 8) as u8),
+                39 => self.fg = Color::Reset,
+                49 => self.bg = Color::Reset,
+                _ => {}
+            }
+        }
+    }
+}
+
+pub struct Replay {
+    /// Output events, as seconds into the recording and what was written
+    events: Vec<(f32, String)>,
+    /// Snapshots of the screen with that many events applied
+    checkpoints: Vec<(usize, Screen)>,
+    screen: Screen,
+    /// Events applied to `screen` so far
+    applied: usize,
+    position: f32,
+    duration: f32,
+    speed: usize,
+    pub paused: bool,
+}
+
+impl Replay {
+    /// Load the asciicast at `path`.
+    pub fn open(path: &str) -> io::Result<Self> {
+        let invalid =
+            |what: &str| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path, what));
+        let text = fs::read_to_string(path)?;
+        let mut lines = text.lines();
+        let header = lines.next().ok_or_else(|| invalid("empty recording"))?;
+        let width =
+            header_number(header, "width").ok_or_else(|| invalid("no width in the header"))?;
+        let height =
+            header_number(header, "height").ok_or_else(|| invalid("no height in the header"))?;
+        let mut events = Vec::new();
+        for line in lines.filter(|l| !l.trim().is_empty()) {
+            let (time, data) =
+                parse_event(line).ok_or_else(|| invalid("not an asciicast v2 recording"))?;
+            if let Some(data) = data {
+                events.push((time, data));
+            }
+        }
+        Ok(Replay::new(width, height, events))
+    }
+
+    fn new(width: u16, height: u16, events: Vec<(f32, String)>) -> Self {
+        // Play the whole recording through once, snapshotting as we go
+        let mut screen = Screen::new(width, height);
+        let mut checkpoints = vec![(0, screen.clone())];
+        let mut next_checkpoint = CHECKPOINT_INTERVAL;
+        for (i, (time, data)) in events.iter().enumerate() {
+            if *time >= next_checkpoint {
+                checkpoints.push((i, screen.clone()));
+                next_checkpoint = time + CHECKPOINT_INTERVAL;
+            }
+            screen.write(data);
+        }
+        let duration = events.last().map_or(0.0, |&(time, _)| time);
+        let mut replay = Replay {
+            events,
+            checkpoints,
+            screen: Screen::new(width, height),
+            applied: 0,
+            position: 0.0,
+            duration,
+            speed: NORMAL_SPEED,
+            paused: false,
+        };
+        replay.catch_up();
+        replay
+    }
+
+    /// Play on by `seconds` of real time at the current speed, pausing at
+    /// the end.
+    pub fn advance(&mut self, seconds: f32) {
+        if self.paused {
+            return;
+        }
+        self.position = (self.position + seconds * SPEEDS[self.speed]).min(self.duration);
+        self.catch_up();
+        if self.position >= self.duration {
+            self.paused = true;
+        }
+    }
+
+    /// Jump `seconds` forward, or backward if negative.
+    pub fn seek(&mut self, seconds: f32) {
+        self.seek_to(self.position + seconds);
+    }
+
+    /// Jump to `seconds` into the recording.
+    pub fn seek_to(&mut self, seconds: f32) {
+        let target = seconds.clamp(0.0, self.duration);
+        // The last checkpoint taken before the target
+        let (index, screen) = self
+            .checkpoints
+            .iter()
+            .rev()
+            .find(|(i, _)| *i == 0 || self.events[i - 1].0 <= target)
+            .unwrap_or(&self.checkpoints[0]);
+        if target < self.position || *index > self.applied {
+            self.screen = screen.clone();
+            self.applied = *index;
+        }
+        self.position = target;
+        self.catch_up();
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = self.speed.saturating_sub(1);
+    }
+
+    pub fn toggle_pause(&mut self) {
+        if self.paused && self.position >= self.duration {
+            self.seek_to(0.0);
+        }
+        self.paused = !self.paused;
+    }
+
+    /// Apply every event up to the current position.
+    fn catch_up(&mut self) {
+        while let Some((time, data)) = self.events.get(self.applied) {
+            if *time > self.position {
+                break;
+            }
+            self.screen.write(data);
+            self.applied += 1;
+        }
+    }
+
+    /// Draw the recording into `area`, with the scrubber along the bottom.
+    pub fn render(&self, buf: &mut Buffer, area: Rect) {
+        let recorded = self.screen.buffer.area;
+        for y in 0..area.height.min(recorded.height) {
+            for x in 0..area.width.min(recorded.width) {
+                if let (Some(from), Some(to)) = (
+                    self.screen.buffer.cell((x, y)),
+                    buf.cell_mut((area.x + x, area.y + y)),
+                ) {
+                    *to = from.clone();
+                }
+            }
+        }
+        if area.height > 0 {
+            self.render_bar(
+                buf,
+                Rect {
+                    y: area.bottom() - 1,
+                    height: 1,
+                    ..area
+                },
+            );
+        }
+    }
+
+    fn render_bar(&self, buf: &mut Buffer, area: Rect) {
+        let state = if self.paused { "⏸" } else { "▶" };
+        let left = format!(" {} {} ", state, clock(self.position));
+        let right = format!(" {}  {}x ", clock(self.duration), SPEEDS[self.speed]);
+        buf.set_style(area, Style::default().bg(BAR).fg(TEXT));
+        buf.set_string(area.x, area.y, &left, Style::default());
+        let track_x = area.x + left.chars().count() as u16;
+        let track_width = area
+            .width
+            .saturating_sub((left.chars().count() + right.chars().count()) as u16);
+        let played = if self.duration > 0.0 {
+            (self.position / self.duration * track_width as f32).round() as u16
+        } else {
+            0
+        };
+        for i in 0..track_width {
+            let (symbol, color) = if i < played {
+                ("━", PLAYED)
+            } else {
+                ("─", TRACK)
+            };
+            let symbol = if i == played.min(track_width - 1) {
+                "●"
+            } else {
+                symbol
+            };
+            buf.set_string(track_x + i, area.y, symbol, Style::default().fg(color));
+        }
+        buf.set_string(track_x + track_width, area.y, &right, Style::default());
+    }
+}
+
+/// `seconds` as minutes and seconds, e.g. `1:05`.
+fn clock(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// The whole-number value of `key` in an asciicast header.
+fn header_number(header: &str, key: &str) -> Option<u16> {
+    let (_, rest) = header.split_once(&format!("\"{}\"", key))?;
+    let rest = rest.trim_start().strip_prefix(':')?.trim_start();
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+/// The time and, for output events, the data of one asciicast event line
+/// such as `[1.250, "o", "..."]`.
+fn parse_event(line: &str) -> Option<(f32, Option<String>)> {
+    let line = line.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let (time, rest) = line.split_once(',')?;
+    let (kind, data) = rest.trim_start().split_once(',')?;
+    let data = (kind.trim() == "\"o\"")
+        .then(|| json_string(data.trim()))
+        .flatten();
+    Some((time.trim().parse().ok()?, data))
+}
+
+/// Decode a JSON string literal.
+fn json_string(raw: &str) -> Option<String> {
+    let raw = raw.strip_prefix('"')?.strip_suffix('"')?;
+    let mut decoded = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            decoded.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => decoded.push('\n'),
+            'r' => decoded.push('\r'),
+            't' => decoded.push('\t'),
+            'b' => decoded.push('\x08'),
+            'f' => decoded.push('\x0c'),
+            'u' => {
+                let hex: String = chars.by_ref().take(4).collect();
+                let mut code = u32::from_str_radix(&hex, 16).ok()?;
+                // Characters beyond the first plane come as a surrogate pair
+                if (0xd800..0xdc00).contains(&code) {
+                    let low: String = chars.by_ref().skip(2).take(4).collect();
+                    let low = u32::from_str_radix(&low, 16).ok()?;
+                    code = 0x10000 + ((code - 0xd800) << 10) + (low.checked_sub(0xdc00)?);
+                }
+                decoded.push(char::from_u32(code)?);
+            }
+            c => decoded.push(c),
+        }
+    }
+    Some(decoded)
+}
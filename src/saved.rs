@@ -0,0 +1,116 @@
+//! Serialization of the sky's types, behind the `serde` feature.
+//!
+//! Whole simulations, their options and scenes all serialize, so a sky can
+//! be saved and restored mid-course. Wrap what's saved in [`Versioned`] so
+//! a later version of this crate can tell whether it still understands it.
+//! Built-in data such as satellites, planets, meteor showers and themes is
+//! saved by name rather than copied out.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{error::Error, fmt};
+
+/// Version of the saved format. Bumped whenever a saved type changes shape.
+pub const FORMAT_VERSION: u32 = 40;
+
+/// Saved data along with the version of the format it was saved in.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    pub version: u32,
+    pub data: T,
+}
+
+impl<T> Versioned<T> {
+    /// Wrap `data` for saving in the current format.
+    pub fn new(data: T) -> Self {
+        Versioned {
+            version: FORMAT_VERSION,
+            data,
+        }
+    }
+
+    /// The saved data, unless it came from a newer format than this crate
+    /// knows.
+    pub fn into_inner(self) -> Result<T, VersionError> {
+        if self.version > FORMAT_VERSION {
+            return Err(VersionError {
+                found: self.version,
+            });
+        }
+        Ok(self.data)
+    }
+}
+
+/// Saved data from a newer format than this crate understands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VersionError {
+    pub found: u32,
+}
+
+impl fmt::Display for VersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "saved with format version {}, but only up to {} is supported",
+            self.found, FORMAT_VERSION
+        )
+    }
+}
+
+impl Error for VersionError {}
+
+/// Built-in data that's saved by name.
+pub(crate) trait Named: Sized + 'static {
+    /// What one is called in error messages
+    const KIND: &'static str;
+
+    fn name(&self) -> &'static str;
+
+    fn all() -> &'static [Self];
+
+    fn find<E: serde::de::Error>(name: &str) -> Result<&'static Self, E> {
+        Self::all()
+            .iter()
+            .find(|item| item.name() == name)
+            .ok_or_else(|| E::custom(format!("unknown {} `{}`", Self::KIND, name)))
+    }
+}
+
+/// `#[serde(with)]` helpers for a `&'static` reference to built-in data.
+pub(crate) mod by_name {
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: Named>(
+        item: &&'static T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        item.name().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Named>(
+        deserializer: D,
+    ) -> Result<&'static T, D::Error> {
+        T::find(&String::deserialize(deserializer)?)
+    }
+}
+
+/// `#[serde(with)]` helpers for an optional `&'static` reference to built-in
+/// data.
+#[cfg(feature = "astronomy")]
+pub(crate) mod by_name_opt {
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: Named>(
+        item: &Option<&'static T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        item.map(T::name).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Named>(
+        deserializer: D,
+    ) -> Result<Option<&'static T>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|name| T::find(&name))
+            .transpose()
+    }
+}
@@ -0,0 +1,24 @@
+//! The `/` search prompt: a star or constellation typed in by name, to find
+//! it in the sky.
+
+use crate::form::{Field, Form, Item};
+
+/// The prompt, empty.
+pub fn form() -> Form {
+    let items = vec![Item {
+        key: "name",
+        label: "Name",
+        field: Field::Text(String::new()),
+    }];
+    Form::new(
+        "Find",
+        items,
+        "A star or constellation, or the start of its name.\n\
+         enter find  esc cancel",
+    )
+}
+
+/// The name typed into the prompt.
+pub fn name(form: &Form) -> &str {
+    form.text("name").trim()
+}
@@ -0,0 +1,163 @@
+//! The in-app settings menu: the sky options worth tweaking live, as a form,
+//! and saving them back to the config file.
+
+use crate::cli::SkyOptions;
+use crate::config;
+use crate::form::{Field, Form, Item};
+use crate::render::theme::{Theme, THEMES};
+use crate::simulation::density::DensityMap;
+use std::path::PathBuf;
+
+const TOGGLES: [(&str, &str); 6] = [
+    ("realism", "Realism"),
+    ("dither", "Dither gradient"),
+    ("no-flashes", "No flashes"),
+    ("event-glow", "Event glow"),
+    ("leaves", "Leaves"),
+    ("showers", "Rain showers"),
+];
+
+// Bortle classes to choose from
+const BORTLE: [&str; 9] = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+/// Config keys the menu owns, so saving replaces only these.
+const MANAGED: &[&str] = &[
+    "theme",
+    "density",
+    "bortle",
+    "pacing",
+    "meteor-rate",
+    "satellite-rate",
+    "aurora",
+    "seeing",
+    "realism",
+    "dither",
+    "no-flashes",
+    "event-glow",
+    "leaves",
+    "showers",
+];
+
+/// Whether the form item `key` changes where stars are placed, which needs a
+/// fresh sky.
+pub fn needs_rebuild(key: &str) -> bool {
+    key.starts_with("density")
+}
+
+fn toggle_value(options: &SkyOptions, key: &str) -> bool {
+    match key {
+        "realism" => options.realism,
+        "dither" => options.dither,
+        "no-flashes" => !options.flashes,
+        "event-glow" => options.event_glow,
+        "leaves" => options.leaves,
+        "showers" => options.showers,
+        _ => false,
+    }
+}
+
+pub fn form(options: &SkyOptions) -> Form {
+    let mut items = vec![
+        Item {
+            key: "theme",
+            label: "Theme",
+            field: Field::choice(THEMES.iter().map(|t| t.name).collect(), options.theme.name),
+        },
+        Item {
+            key: "density-band",
+            label: "Milky Way band",
+            field: Field::slider(options.density.band, 0.0, 3.0, 0.25),
+        },
+        Item {
+            key: "density-horizon",
+            label: "Horizon glow",
+            field: Field::slider(options.density.horizon, 0.0, 1.0, 0.1),
+        },
+        Item {
+            key: "density-poles",
+            label: "Thinning at the poles",
+            field: Field::slider(options.density.poles, 0.0, 1.0, 0.1),
+        },
+        Item {
+            key: "bortle",
+            label: "Light pollution",
+            field: Field::choice(BORTLE.to_vec(), &options.bortle.to_string()),
+        },
+        Item {
+            key: "meteor-rate",
+            label: "Meteors",
+            field: Field::slider(options.meteor_rate, 0.0, 10.0, 0.25),
+        },
+        Item {
+            key: "satellite-rate",
+            label: "Satellites",
+            field: Field::slider(options.satellite_rate, 0.0, 10.0, 0.25),
+        },
+        Item {
+            key: "aurora",
+            label: "Auroras",
+            field: Field::slider(options.aurora, 0.0, 1.0, 0.1),
+        },
+        Item {
+            key: "seeing",
+            label: "Seeing",
+            field: Field::slider(options.seeing, 0.0, 1.0, 0.1),
+        },
+        Item {
+            key: "pacing",
+            label: "Pacing",
+            field: Field::choice(vec!["realistic", "cinematic"], options.pacing.name()),
+        },
+    ];
+    items.extend(TOGGLES.iter().map(|&(key, label)| Item {
+        key,
+        label,
+        field: Field::Toggle(toggle_value(options, key)),
+    }));
+    Form::new("Settings", items, "↑↓ select  ←→ change  enter save  esc close")
+}
+
+/// Copy the form's values into `options`.
+pub fn apply(form: &Form, options: &mut SkyOptions) {
+    if let Some(theme) = Theme::by_name(form.choice("theme")) {
+        options.theme = theme;
+    }
+    options.density = DensityMap {
+        band: form.slider("density-band"),
+        horizon: form.slider("density-horizon"),
+        poles: form.slider("density-poles"),
+    };
+    options.bortle = form.choice("bortle").parse().unwrap_or(options.bortle);
+    options.meteor_rate = form.slider("meteor-rate");
+    options.satellite_rate = form.slider("satellite-rate");
+    options.aurora = form.slider("aurora");
+    options.seeing = form.slider("seeing");
+    options.pacing = form.choice("pacing").parse().unwrap_or(options.pacing);
+    options.realism = form.toggle("realism");
+    options.dither = form.toggle("dither");
+    options.flashes = !form.toggle("no-flashes");
+    options.event_glow = form.toggle("event-glow");
+    options.leaves = form.toggle("leaves");
+    options.showers = form.toggle("showers");
+}
+
+/// Save the menu's settings to the config file, leaving its other lines be.
+pub fn save(options: &SkyOptions) -> Result<PathBuf, String> {
+    let mut lines = vec![
+        format!("theme {}", options.theme.name),
+        format!("density {}", options.density),
+        format!("bortle {}", options.bortle),
+        format!("pacing {}", options.pacing.name()),
+        format!("meteor-rate {}", options.meteor_rate),
+        format!("satellite-rate {}", options.satellite_rate),
+        format!("aurora {}", options.aurora),
+        format!("seeing {}", options.seeing),
+    ];
+    lines.extend(
+        TOGGLES
+            .iter()
+            .filter(|(key, _)| toggle_value(options, key))
+            .map(|(key, _)| key.to_string()),
+    );
+    config::save(&lines, MANAGED)
+}
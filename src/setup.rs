@@ -0,0 +1,97 @@
+//! First-run setup: a short form offered when there is no config file yet,
+//! whose answers become the config file.
+
+use crate::cli::SkyOptions;
+use crate::config;
+use crate::form::{Field, Form, Item};
+use crate::render::color::ColorDepth;
+use crate::render::theme::{Theme, THEMES};
+use std::path::PathBuf;
+
+/// Config keys the setup form writes.
+const MANAGED: &[&str] = &["latitude", "theme", "ascii", "colors"];
+
+/// Whether nk has never been set up: there is nowhere the config would be
+/// read from.
+pub fn is_first_run() -> bool {
+    config::path().is_some_and(|path| !path.exists())
+}
+
+pub fn form(options: &SkyOptions) -> Form {
+    let latitude = options.latitude.map(|l| l.to_string()).unwrap_or_default();
+    let glyphs = if options.ascii { "ascii" } else { "unicode" };
+    let items = vec![
+        Item {
+            key: "latitude",
+            label: "Latitude (blank to skip)",
+            field: Field::Text(latitude),
+        },
+        Item {
+            key: "theme",
+            label: "Theme",
+            field: Field::choice(THEMES.iter().map(|t| t.name).collect(), options.theme.name),
+        },
+        Item {
+            key: "glyphs",
+            label: "Characters",
+            field: Field::choice(vec!["unicode", "ascii"], glyphs),
+        },
+        Item {
+            key: "colors",
+            label: "Colors",
+            field: Field::choice(vec!["truecolor", "256", "16"], ColorDepth::detect().name()),
+        },
+    ];
+    Form::new(
+        "Welcome to nk",
+        items,
+        "Latitude places meteor shower radiants realistically.\n\
+         Changes show behind this box as you make them.\n\
+         ↑↓ select  ←→ change  enter save  esc skip",
+    )
+}
+
+/// The latitude typed into the form: `None` if blank.
+fn latitude(form: &Form) -> Result<Option<f32>, String> {
+    let text = form.text("latitude").trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    text.parse()
+        .ok()
+        .filter(|l: &f32| (-90.0..=90.0).contains(l))
+        .map(Some)
+        .ok_or_else(|| format!("latitude must be a number from -90 to 90, not '{}'", text))
+}
+
+/// Copy the form's answers into `options`. Fails, changing nothing, while
+/// the latitude isn't a valid number.
+pub fn apply(form: &Form, options: &mut SkyOptions) -> Result<(), String> {
+    options.latitude = latitude(form)?;
+    if let Some(theme) = Theme::by_name(form.choice("theme")) {
+        options.theme = theme;
+    }
+    options.ascii = form.choice("glyphs") == "ascii";
+    options.colors = form.choice("colors").parse().unwrap_or(options.colors);
+    Ok(())
+}
+
+pub fn save(options: &SkyOptions) -> Result<PathBuf, String> {
+    let mut lines = vec![
+        format!("theme {}", options.theme.name),
+        format!("colors {}", options.colors.name()),
+    ];
+    if let Some(latitude) = options.latitude {
+        lines.insert(0, format!("latitude {}", latitude));
+    }
+    if options.ascii {
+        lines.push("ascii".to_string());
+    }
+    config::save(&lines, MANAGED)
+}
+
+/// Leave the answers out but still create the config file, so setup isn't
+/// offered again.
+pub fn skip() -> Result<PathBuf, String> {
+    config::save(&[], &[])
+}
@@ -0,0 +1,1744 @@
+//! The sky itself: every object in it and how they move from tick to tick,
+//! with nothing about how they are drawn.
+//!
+//! Positions are in cells of a `width` x `height` sky, with the horizon
+//! along the bottom edge. The sky wraps horizontally. Time is counted in
+//! ticks of [`TICKS_PER_SECOND`]; stepping by fractional ticks runs the sky
+//! at other rates.
+
+pub mod astro;
+pub mod aurora;
+pub mod clouds;
+pub mod cluster;
+pub mod comet;
+pub mod constellation;
+pub mod daily;
+#[cfg(feature = "astronomy")]
+pub mod deep_sky;
+pub mod density;
+pub mod eclipse;
+pub mod director;
+pub mod double;
+pub mod environment;
+pub mod events;
+pub mod fov;
+pub mod heatmap;
+#[cfg(feature = "astronomy")]
+pub mod iss;
+pub mod landmarks;
+pub mod leaves;
+pub mod lifecycle;
+#[cfg(feature = "astronomy")]
+pub mod meteor_showers;
+#[cfg(feature = "astronomy")]
+pub mod milky_way;
+pub mod moon;
+pub mod moonbow;
+pub mod particles;
+#[cfg(feature = "astronomy")]
+pub mod passes;
+#[cfg(feature = "astronomy")]
+pub mod planets;
+#[cfg(feature = "astronomy")]
+pub mod projection;
+pub mod query;
+pub mod rain;
+pub mod rotation;
+pub mod satellite_catalog;
+pub mod sky_model;
+pub mod spectral;
+pub mod starlink;
+#[cfg(feature = "astronomy")]
+pub mod sun;
+pub mod sparks;
+pub mod supernova;
+#[cfg(feature = "astronomy")]
+pub mod star_catalog;
+pub mod star_info;
+pub mod trails;
+pub mod variable;
+pub mod wind;
+pub mod zones;
+
+use aurora::Aurora;
+use clouds::Veil;
+use cluster::Cluster;
+use comet::Comet;
+use constellation::Figure;
+use daily::{Appearance, Appointment, Daily};
+#[cfg(feature = "astronomy")]
+use deep_sky::SkyObject;
+use density::{Band, Density, DensityMap};
+use director::{Director, Pacing, Spawn};
+use double::Companion;
+use environment::{Environment, Sample};
+use events::SkyEvent;
+use fov::Fov;
+use heatmap::Heatmap;
+use landmarks::{Kind, Landmark};
+#[cfg(feature = "astronomy")]
+use iss::{Iss, Tle};
+#[cfg(feature = "astronomy")]
+use meteor_showers::{ActiveShower, MeteorShower};
+#[cfg(feature = "astronomy")]
+use planets::SkyPlanet;
+#[cfg(feature = "astronomy")]
+use projection::Observer;
+#[cfg(feature = "astronomy")]
+use star_catalog::CatalogSky;
+use moon::Moon;
+use moonbow::Moonbow;
+use particles::ParticleSystem;
+#[cfg(feature = "astronomy")]
+use passes::{NextPass, Pass, Passes};
+use rain::Rain;
+use rotation::Rotation;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use satellite_catalog::CatalogEntry;
+use query::{Match, Region};
+use sky_model::SkyModel;
+use spectral::SpectralClass;
+use star_info::StarInfo;
+use starlink::Train;
+use lifecycle::Lifecycle;
+#[cfg(feature = "astronomy")]
+use sun::{DayCycle, Sun};
+use sparks::Sparks;
+use supernova::Supernova;
+use std::time::SystemTime;
+use trails::TrailLayer;
+use variable::Variability;
+use wind::Wind;
+use zones::Zone;
+
+/// Base simulation rate. Speeds and lifetimes are expressed per tick of this
+/// rate; running the simulation faster steps it in fractional ticks.
+pub const TICKS_PER_SECOND: u32 = 20;
+
+// Cells of sky per naked-eye star, so a sky looks as full at any size
+const CELLS_PER_STAR: usize = 20;
+// The most naked-eye stars a sky gets, however big the terminal
+const MAX_STARS: usize = 4000;
+
+/// A color as red, green and blue, for objects whose color is part of what
+/// they are rather than a matter of theme.
+pub type Rgb = (u8, u8, u8);
+
+/// What the sky holds and how busy it is.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationOptions {
+    pub density: DensityMap,
+    pub pacing: Pacing,
+    /// Shower to run at its peak, rather than whichever is active today
+    #[cfg(feature = "astronomy")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::saved::by_name_opt"))]
+    pub meteor_shower: Option<&'static MeteorShower>,
+    /// Place the real bright stars where they stand tonight, rather than
+    /// scattering random ones
+    #[cfg(feature = "astronomy")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub catalog: bool,
+    /// Observer's latitude in degrees, north positive
+    pub latitude: Option<f32>,
+    /// Observer's longitude in degrees, east positive
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub longitude: Option<f32>,
+    /// Multipliers on how often meteors and satellites appear
+    pub meteor_rate: f32,
+    pub satellite_rate: f32,
+    pub leaves: bool,
+    pub showers: bool,
+    pub moon: bool,
+    pub clouds: bool,
+    /// Strength of occasional auroras, from 0 (none) to 1
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub aurora: f32,
+    /// Place landmarks for a guided tour to visit
+    pub tour: bool,
+    /// Parts of the screen where nothing moving appears
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub avoid: Vec<Zone>,
+    /// How many times as fast as the real sky random stars turn about the
+    /// pole, 0 to hold them still
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rotation: f32,
+    /// How many times taller than wide the terminal's cells are
+    #[cfg_attr(feature = "serde", serde(default = "sky_model::default_aspect"))]
+    pub aspect: f32,
+    /// Eclipse a full moon from the start, whatever the date
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub eclipse: bool,
+    /// Set off a supernova early on, rather than leaving it to chance
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub supernova: bool,
+    /// Keep to the day's schedule of rare events rather than rolling for
+    /// them
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub daily: bool,
+    /// Run through day and night, this many times as fast as the real
+    /// clock, or 0 to stay night
+    #[cfg(feature = "astronomy")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub day_cycle: f32,
+}
+
+impl Default for SimulationOptions {
+    fn default() -> Self {
+        SimulationOptions {
+            density: DensityMap::default(),
+            pacing: Pacing::default(),
+            #[cfg(feature = "astronomy")]
+            meteor_shower: None,
+            #[cfg(feature = "astronomy")]
+            catalog: false,
+            latitude: None,
+            longitude: None,
+            meteor_rate: 1.0,
+            satellite_rate: 1.0,
+            leaves: false,
+            showers: false,
+            moon: false,
+            clouds: false,
+            aurora: 0.0,
+            tour: false,
+            avoid: Vec::new(),
+            rotation: 0.0,
+            aspect: sky_model::DEFAULT_ASPECT,
+            eclipse: false,
+            supernova: false,
+            daily: false,
+            #[cfg(feature = "astronomy")]
+            day_cycle: 0.0,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Star {
+    pub x: f32,
+    pub y: f32,
+    /// Degrees above the horizon
+    pub altitude: f32,
+    /// Apparent magnitude; only stars within the current view's limit are drawn
+    pub magnitude: f32,
+    pub brightness: u8,
+    /// What color the star burns
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub class: SpectralClass,
+    pub twinkle_speed: f32,
+    /// Index into [`Simulation::clusters`] for cluster members
+    pub cluster: Option<usize>,
+    /// How much of the star shows, from 0 to 1; it's only partly there
+    /// while fading out to make way for a new star, or in as one
+    #[cfg_attr(feature = "serde", serde(default = "full_presence"))]
+    pub presence: f32,
+    /// The cycle a variable star's light follows
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub variability: Option<Variability>,
+    /// A double star's partner
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub companion: Option<Companion>,
+}
+
+/// How turbulent the air is unless set otherwise, from 0 for rock-steady to
+/// 1 for very turbulent: enough for stars to twinkle through their full
+/// swing at their own pace.
+pub const DEFAULT_SEEING: f32 = 0.5;
+
+#[cfg(feature = "serde")]
+fn full_presence() -> f32 {
+    1.0
+}
+
+impl Star {
+    /// Twinkle rate, faster near the horizon where the light path through
+    /// the atmosphere is longest.
+    pub fn scintillation_rate(&self) -> f32 {
+        self.twinkle_speed * (1.0 + (1.0 - self.altitude / 90.0).max(0.0))
+    }
+
+    /// Where in its twinkle the star is at simulation time `time` through
+    /// air as turbulent as `seeing`, from 0 to 1. Steady air holds it near
+    /// full brightness; the more turbulent, the deeper and faster it swings.
+    pub fn twinkle(&self, time: f32, seeing: f32) -> f32 {
+        let rate = self.scintillation_rate() * 4f32.powf(seeing - DEFAULT_SEEING);
+        let depth = (seeing / DEFAULT_SEEING).min(1.0);
+        1.0 - depth * (1.0 - (time * rate).sin()) / 2.0
+    }
+
+    /// The star's magnitude `seconds` into the sky, fainter than its own
+    /// while a variable star is away from its brightest.
+    pub fn magnitude_at(&self, seconds: f32) -> f32 {
+        self.magnitude + self.variability.map_or(0.0, |variability| variability.dimming(seconds))
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShootingStar {
+    pub x: f32,
+    pub y: f32,
+    /// Velocity in cells per tick
+    pub vx: f32,
+    pub vy: f32,
+    /// Where it has been since it started, for the smoke train it leaves
+    #[cfg_attr(feature = "serde", serde(default))]
+    path: Vec<(f32, f32)>,
+    pub lifetime: f32,
+    pub max_lifetime: f32,
+    /// Where in `0.0..1.0` the meteor falls in a palette of tints, so each
+    /// front end can color it from its own
+    pub tint: f32,
+    /// Bright meteors leave a persistent smoke train behind
+    pub bright: bool,
+    /// A rare bolide: slower, with a big head burning from green to orange,
+    /// that bursts in a flash at the end of its flight
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bolide: bool,
+}
+
+// One meteor in this many is a bolide
+const BOLIDE_ODDS: u32 = 80;
+// Rows per tick a meteor's fall quickens by, bending its path down
+const GRAVITY: f32 = 0.006;
+// Fraction of its speed a meteor loses to the air each tick
+const DRAG: f32 = 0.01;
+
+impl ShootingStar {
+    fn new<R: Rng>(rng: &mut R, sky: &SkyModel) -> Self {
+        let x = rng.gen_range(0..sky.width.max(1)) as f32;
+        let y = rng.gen_range(0..(sky.horizon_y / 2).max(1)) as f32;
+        Self::at(rng, sky, x, y)
+    }
+
+    /// A meteor starting from `(x, y)`, falling at 45° across `sky`.
+    fn at<R: Rng>(rng: &mut R, sky: &SkyModel, x: f32, y: f32) -> Self {
+        Self::heading(rng, x, y, (1.0, 1.0 / sky.aspect))
+    }
+
+    /// A meteor starting from `(x, y)` and travelling along `(dx, dy)`.
+    fn heading<R: Rng>(rng: &mut R, x: f32, y: f32, (dx, dy): (f32, f32)) -> Self {
+        let speed = rng.gen_range(2.0..4.0);
+        let mut meteor = ShootingStar {
+            tint: rng.gen(),
+            x,
+            y,
+            vx: speed * dx,
+            vy: speed * dy,
+            path: vec![(x, y)],
+            lifetime: 0.0,
+            max_lifetime: rng.gen_range(15.0..30.0),
+            bright: rng.gen_range(0..4) == 0,
+            bolide: false,
+        };
+        if rng.gen_range(0..BOLIDE_ODDS) == 0 {
+            // Three to five seconds across the sky, where most take one
+            meteor.bolide = true;
+            meteor.bright = true;
+            let speed = rng.gen_range(0.4..0.7);
+            meteor.vx = speed * dx;
+            meteor.vy = speed * dy;
+            meteor.max_lifetime = rng.gen_range(60.0..100.0);
+        }
+        meteor
+    }
+
+    fn update(&mut self, dt: f32) {
+        let drag = (1.0 - DRAG).powf(dt);
+        self.vx *= drag;
+        self.vy = (self.vy + GRAVITY * dt) * drag;
+        self.x += self.vx * dt;
+        self.y += self.vy * dt;
+        self.lifetime += dt;
+        self.path.push((self.x, self.y));
+    }
+
+    /// Cells travelled per tick.
+    pub fn speed(&self) -> f32 {
+        self.vx.hypot(self.vy)
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.lifetime < self.max_lifetime
+    }
+
+    /// How far through its flight the meteor is, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        (self.lifetime / self.max_lifetime).min(1.0)
+    }
+
+    /// How brightly the meteor burns, dimming from 1 to 0 over the last
+    /// third of its flight. A bolide burns on until it bursts.
+    pub fn brightness(&self) -> f32 {
+        if self.bolide {
+            return 1.0;
+        }
+        let t = ((1.0 - self.progress()) * 3.0).min(1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+// Cells between the dots of a satellite's predicted path
+const PATH_SPACING: f32 = 2.0;
+// Most satellites crossing the sky at once
+const MAX_SATELLITES: usize = 3;
+// Altitude in km of a satellite that crosses at the usual speed
+const PASS_ALTITUDE_KM: f32 = 420.0;
+// Two passes closer than this many columns make a conjunction, and both
+// blink fast for a few seconds after
+const NEAR_MISS_CELLS: f32 = 1.5;
+const NEAR_MISS_TICKS: f32 = 3.0 * TICKS_PER_SECOND as f32;
+// Share of passes that flare, as sunlight glints off a panel, and how long
+// a flare takes to build to white and again to fade
+const FLARE_CHANCE: f64 = 0.1;
+const FLARE_TICKS: f32 = 2.0 * TICKS_PER_SECOND as f32;
+// A supernova asked for goes off this long in, once the sky has settled,
+// from a star at least this many degrees up
+const SUPERNOVA_DELAY_TICKS: f32 = 10.0 * TICKS_PER_SECOND as f32;
+const SUPERNOVA_ALTITUDE: f32 = 20.0;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Satellite {
+    pub x: f32,
+    pub y: f32,
+    /// Velocity in cells per tick along the pass
+    pub vx: f32,
+    pub vy: f32,
+    pub blink_phase: f32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::saved::by_name"))]
+    pub entry: &'static CatalogEntry,
+    /// How bright it shines on this pass, fainter than its best on most
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub magnitude: f32,
+    /// Dots along the rest of the pass, in the order they'll be reached
+    predicted: Vec<(f32, f32)>,
+    /// Ticks left blinking fast after a near miss with another satellite
+    #[cfg_attr(feature = "serde", serde(default))]
+    near_miss: f32,
+    /// Ticks since the pass began
+    #[cfg_attr(feature = "serde", serde(default))]
+    age: f32,
+    /// When in the pass a flare peaks, for the few that flare
+    #[cfg_attr(feature = "serde", serde(default))]
+    flare_at: Option<f32>,
+}
+
+impl Satellite {
+    /// Start a straight pass entering from an edge of `sky`. The
+    /// inclination ranges from nearly horizontal to polar (top-to-bottom).
+    fn new<R: Rng>(rng: &mut R, sky: &SkyModel) -> Self {
+        let (width, height) = (sky.width, sky.horizon_y);
+        let (w, h) = (width.max(1) as f32, height.max(1) as f32);
+
+        // The lower it flies, the faster it crosses
+        let entry = CatalogEntry::random(rng);
+        let speed = rng.gen_range(0.3..0.8) * (PASS_ALTITUDE_KM / entry.altitude_km).sqrt();
+        let inclination = rng.gen_range(0.0..std::f32::consts::FRAC_PI_2);
+        let vx = speed * inclination.cos() * if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+        // Cells are taller than wide, so vertical motion covers fewer of them
+        let vy = speed * inclination.sin() / sky.aspect * if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+
+        // Pick a point the pass crosses, then walk back to where it enters
+        let (px, py) = (rng.gen_range(0.0..w), rng.gen_range(0.0..h));
+        let back = |p: f32, v: f32, max: f32| match v {
+            v if v > 0.0 => p / v,
+            v if v < 0.0 => (p - max) / v,
+            _ => f32::INFINITY,
+        };
+        // Stop just inside the edge so the first frame is on screen
+        let t = back(px, vx, w).min(back(py, vy, h)) - 0.01;
+
+        let mut satellite = Satellite {
+            x: px - vx * t,
+            y: py - vy * t,
+            vx,
+            vy,
+            blink_phase: rng.gen_range(0.0..std::f32::consts::TAU),
+            entry,
+            magnitude: entry.magnitude + rng.gen_range(-0.5..1.5),
+            predicted: Vec::new(),
+            near_miss: 0.0,
+            age: 0.0,
+            flare_at: None,
+        };
+        satellite.predicted = satellite.predict_path(width, height);
+        // Somewhere in the middle of the pass, well clear of the edges
+        let crossing = satellite.seconds_remaining() * TICKS_PER_SECOND as f32;
+        satellite.flare_at = rng.gen_bool(FLARE_CHANCE).then(|| rng.gen_range(0.3..0.7) * crossing);
+        satellite
+    }
+
+    /// Dots every couple of cells from here to where the pass leaves the sky.
+    fn predict_path(&self, width: u16, height: u16) -> Vec<(f32, f32)> {
+        let speed = self.vx.hypot(self.vy);
+        if speed == 0.0 {
+            return Vec::new();
+        }
+        let (dx, dy) = (self.vx / speed * PATH_SPACING, self.vy / speed * PATH_SPACING);
+        let mut ghost = Satellite {
+            predicted: Vec::new(),
+            ..*self
+        };
+        let mut dots = Vec::new();
+        loop {
+            ghost.x += dx;
+            ghost.y += dy;
+            if !ghost.is_on_screen(width, height) {
+                return dots;
+            }
+            dots.push((ghost.x, ghost.y));
+        }
+    }
+
+    /// Seconds until the pass leaves the sky.
+    pub fn seconds_remaining(&self) -> f32 {
+        let speed = self.vx.hypot(self.vy);
+        let cells = self.path_ahead().count() as f32 * PATH_SPACING;
+        cells / speed.max(f32::EPSILON) / TICKS_PER_SECOND as f32
+    }
+
+    /// Predicted dots still ahead of the satellite, nearest first.
+    pub fn path_ahead(&self) -> impl Iterator<Item = &(f32, f32)> {
+        self.predicted
+            .iter()
+            .filter(|(x, y)| (x - self.x) * self.vx + (y - self.y) * self.vy > 0.0)
+    }
+
+    /// How far into a flare the satellite is, from 0 while it shines as
+    /// usual to 1 at its whitest.
+    pub fn flare(&self) -> f32 {
+        let Some(peak) = self.flare_at else {
+            return 0.0;
+        };
+        let t = (1.0 - (self.age - peak).abs() / FLARE_TICKS).max(0.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    /// Whether the satellite is still blinking from a near miss.
+    pub fn is_near_miss(&self) -> bool {
+        self.near_miss > 0.0
+    }
+
+    /// How close, in columns, this pass and `other` come over the next `dt`
+    /// ticks, with rows `aspect` columns tall.
+    fn closest_approach(&self, other: &Satellite, dt: f32, aspect: f32) -> f32 {
+        let (px, py) = (other.x - self.x, (other.y - self.y) * aspect);
+        let (vx, vy) = (other.vx - self.vx, (other.vy - self.vy) * aspect);
+        let t = (-(px * vx + py * vy) / (vx * vx + vy * vy).max(f32::EPSILON)).clamp(0.0, dt);
+        (px + vx * t).hypot(py + vy * t)
+    }
+
+    fn update(&mut self, dt: f32) {
+        self.x += self.vx * dt;
+        self.y += self.vy * dt;
+        let blink = if self.is_near_miss() { 0.8 } else { 0.1 };
+        self.blink_phase += blink * dt;
+        self.near_miss = (self.near_miss - dt).max(0.0);
+        self.age += dt;
+    }
+
+    /// Whether the pass is still within the `width` x `height` sky.
+    fn is_on_screen(&self, width: u16, height: u16) -> bool {
+        self.x >= 0.0 && self.y >= 0.0 && self.x < width as f32 && self.y < height as f32
+    }
+}
+
+/// The whole sky, stepped on a fixed timestep.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Simulation {
+    stars: Vec<Star>,
+    clusters: Vec<Cluster>,
+    constellations: Vec<Figure>,
+    /// Named objects a tour visits
+    landmarks: Vec<Landmark>,
+    moon: Option<Moon>,
+    veils: Vec<Veil>,
+    /// Whether a moon halo is currently showing, so each is announced once
+    halo_visible: bool,
+    shooting_stars: Vec<ShootingStar>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    sparks: Sparks,
+    satellites: Vec<Satellite>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    starlink: Option<Train>,
+    trails: TrailLayer,
+    leaves: ParticleSystem,
+    rain: Rain,
+    /// The weather outside, when the sky follows it
+    #[cfg_attr(feature = "serde", serde(default))]
+    environment: Option<Environment>,
+    moonbow: Option<Moonbow>,
+    /// How much of tonight's moon is lit, fixed when the sky was built so
+    /// stepping it never reads the clock
+    #[cfg_attr(feature = "serde", serde(default))]
+    moonlight: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    aurora: Option<Aurora>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    comet: Option<Comet>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    supernova: Option<Supernova>,
+    /// Turns the stars about the pole, in a random sky asked to
+    #[cfg_attr(feature = "serde", serde(default))]
+    rotation: Option<Rotation>,
+    /// Retires random stars and brings in new ones over a long night
+    #[cfg_attr(feature = "serde", serde(default))]
+    lifecycle: Option<Lifecycle>,
+    /// The day's schedule of rare events, in a sky of the day
+    #[cfg_attr(feature = "serde", serde(default))]
+    daily: Option<Daily>,
+    /// The real ISS, once its orbit is known
+    #[cfg(feature = "astronomy")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    iss: Option<Iss>,
+    /// Real satellites' passes, when they're being flown
+    #[cfg(feature = "astronomy")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    passes: Option<Passes>,
+    wind: Wind,
+    director: Director,
+    heatmap: Heatmap,
+    /// Where the Milky Way crosses a random sky
+    #[cfg_attr(feature = "serde", serde(default))]
+    band: Band,
+    #[cfg(feature = "astronomy")]
+    meteor_shower: Option<ActiveShower>,
+    /// The real stars, turning with the clock
+    #[cfg(feature = "astronomy")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    catalog: Option<CatalogSky>,
+    /// The clock and sun of a sky running through the day
+    #[cfg(feature = "astronomy")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    day: Option<DayCycle>,
+    /// The planets of a catalog sky, following their orbits
+    #[cfg(feature = "astronomy")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    planets: Vec<SkyPlanet>,
+    /// The showpiece deep-sky objects of a catalog sky
+    #[cfg(feature = "astronomy")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    deep_sky: Vec<SkyObject>,
+    /// Events since the last step, handed back when it finishes
+    events: Vec<SkyEvent>,
+    /// Simulation time in ticks
+    time: f32,
+    sky_model: SkyModel,
+    options: SimulationOptions,
+    /// Drives every random choice, so a sky can be replayed from its seed.
+    /// The same generator as rand's `StdRng`, named so it can be saved
+    rng: ChaCha12Rng,
+}
+
+impl Simulation {
+    /// A `width` x `height` sky whose whole course is determined by `seed`,
+    /// so two skies with the same seed, size and options stay identical
+    /// tick for tick.
+    pub fn new(width: u16, height: u16, options: SimulationOptions, seed: u64) -> Self {
+        let mut rng = ChaCha12Rng::seed_from_u64(seed);
+        let sky_model = SkyModel::new(width, height, options.aspect);
+        let star_count = (width as usize * sky_model.horizon_y as usize / CELLS_PER_STAR).min(MAX_STARS);
+        let band = Band::new(&mut rng);
+        let density = Density::new(options.density, band);
+        let catalog_limit = catalog_limit(&options);
+        // The real stars turn on their own, and a tour's landmarks must stay
+        // where its camera moves were planned
+        let mut rotation = (options.rotation > 0.0 && catalog_limit.is_none() && !options.tour)
+            .then(|| Rotation::new(&sky_model, options.latitude, options.rotation));
+
+        let mut stars: Vec<Star> = (0..star_count)
+            .filter_map(|_| naked_eye_star(&mut rng, &density, &sky_model, catalog_limit))
+            .collect();
+        // Those stars come and go over a long night, but a turning sky keeps
+        // its own record of where each one is
+        let lifecycle = rotation.is_none().then(|| Lifecycle::new(0..stars.len()));
+        // A turning sky brings more stars round from beyond the edges
+        if let Some(rotation) = &rotation {
+            let sky = (0.0, 0.0, width as f32, sky_model.horizon_y as f32);
+            for (x, y) in rotation.sweep(&mut rng, sky, stars.len()) {
+                let brightness = rng.gen_range(1..=5);
+                stars.push(Star {
+                    x,
+                    y,
+                    altitude: sky_model.altitude_at(y),
+                    magnitude: 6.5 - brightness as f32,
+                    brightness,
+                    class: SpectralClass::random(&mut rng),
+                    twinkle_speed: rng.gen_range(0.1..0.5),
+                    cluster: None,
+                    presence: 1.0,
+                    variability: None,
+                    companion: None,
+                });
+            }
+        }
+
+        // Fainter stars only show up through optics, so they are scattered
+        // over the region each preset magnifies to keep its density similar
+        let mut faintest = Fov::NakedEye.magnitude_limit();
+        for fov in [Fov::Binoculars, Fov::Telescope] {
+            let limit = fov.magnitude_limit();
+            let placed = stars.len();
+            for _ in 0..star_count {
+                if let Some((x, y, altitude)) = density.place(&sky_model, &mut rng, fov.zoom()) {
+                    stars.push(Star {
+                        x,
+                        y,
+                        altitude,
+                        magnitude: rng.gen_range(faintest..limit),
+                        brightness: rng.gen_range(1..=2),
+                        class: SpectralClass::random(&mut rng),
+                        twinkle_speed: rng.gen_range(0.1..0.5),
+                        cluster: None,
+                        presence: 1.0,
+                        variability: None,
+                        companion: None,
+                    });
+                }
+            }
+            if let Some(rotation) = &rotation {
+                let (cx, cy) = sky_model.center();
+                let (half_w, half_h) = (cx / fov.zoom(), cy / fov.zoom());
+                let view = (cx - half_w, cy - half_h, cx + half_w, cy + half_h);
+                for (x, y) in rotation.sweep(&mut rng, view, stars.len() - placed) {
+                    stars.push(Star {
+                        x,
+                        y,
+                        altitude: sky_model.altitude_at(y),
+                        magnitude: rng.gen_range(faintest..limit),
+                        brightness: rng.gen_range(1..=2),
+                        class: SpectralClass::random(&mut rng),
+                        twinkle_speed: rng.gen_range(0.1..0.5),
+                        cluster: None,
+                        presence: 1.0,
+                        variability: None,
+                        companion: None,
+                    });
+                }
+            }
+            faintest = limit;
+        }
+
+        #[cfg(feature = "astronomy")]
+        let mut planets = Vec::new();
+        #[cfg(feature = "astronomy")]
+        let mut deep_sky = Vec::new();
+        #[cfg(feature = "astronomy")]
+        let catalog = options.catalog.then(|| {
+            let observer = Observer::new(options.latitude, options.longitude);
+            let start = projection::now();
+            let first = stars.len();
+            stars.extend(star_catalog::place(&mut rng, &sky_model, &observer, start));
+            planets = planets::place(&sky_model, &observer, start);
+            deep_sky = deep_sky::place(&sky_model, &observer, start);
+            CatalogSky {
+                observer,
+                start,
+                first,
+            }
+        });
+        // The real constellations are already there in a catalog sky
+        let constellations = if catalog_limit.is_some() {
+            Vec::new()
+        } else {
+            constellation::place(&mut rng, &sky_model, &mut stars)
+        };
+
+        // Occasionally one or two open clusters, and always one on a tour
+        let cluster_count = match rng.gen_range(0..100) {
+            0..=49 => 0,
+            50..=84 => 1,
+            _ => 2,
+        };
+        let cluster_count = if options.tour { cluster_count.max(1) } else { cluster_count };
+        let moon = if options.eclipse {
+            Moon::eclipsed(&mut rng, &sky_model, options.latitude)
+        } else if options.moon || options.tour {
+            Moon::rise(&mut rng, &sky_model, options.latitude)
+        } else {
+            None
+        };
+
+        let mut landmarks = Vec::new();
+        if options.tour {
+            landmarks = landmarks::place(&mut rng, &sky_model, moon.as_ref());
+            for landmark in &landmarks {
+                let Kind::Asterism(asterism) = landmark.kind else {
+                    continue;
+                };
+                for &(dx, dy) in asterism.stars {
+                    let y = landmark.y + dy;
+                    if !(0.0..sky_model.horizon_y as f32).contains(&y) {
+                        continue;
+                    }
+                    stars.push(Star {
+                        x: (landmark.x + dx).rem_euclid(width.max(1) as f32),
+                        y,
+                        altitude: sky_model.altitude_at(y),
+                        magnitude: 1.5,
+                        brightness: 5,
+                        class: SpectralClass::random(&mut rng),
+                        twinkle_speed: rng.gen_range(0.1..0.5),
+                        cluster: None,
+                        presence: 1.0,
+                        variability: None,
+                        companion: None,
+                    });
+                }
+            }
+        }
+
+        let mut clusters = Vec::new();
+        for _ in 0..cluster_count {
+            let Some((x, y, _)) = sky_model.place(&mut rng) else {
+                break;
+            };
+            let mut cluster = Cluster::new(&mut rng);
+            if options.tour && clusters.is_empty() {
+                // Held still so the looping tour keeps finding it
+                cluster.drift = 0.0;
+                landmarks.push(Landmark {
+                    kind: Kind::Cluster,
+                    x,
+                    y,
+                });
+            }
+            for member in cluster.members(&mut rng, sky_model.aspect) {
+                let y = y + member.dy;
+                // Members near the horizon would otherwise spill below it
+                if !(0.0..sky_model.horizon_y as f32).contains(&y) {
+                    continue;
+                }
+                stars.push(Star {
+                    x: x + member.dx,
+                    y,
+                    altitude: sky_model.altitude_at(y),
+                    magnitude: member.magnitude,
+                    brightness: (6.5 - member.magnitude).round().clamp(1.0, 5.0) as u8,
+                    class: SpectralClass::random(&mut rng),
+                    twinkle_speed: rng.gen_range(0.1..0.5),
+                    cluster: Some(clusters.len()),
+                    presence: 1.0,
+                    variability: None,
+                    companion: None,
+                });
+            }
+            clusters.push(cluster);
+        }
+
+        // A shower's radiant sits well up in the sky while it's active. With
+        // a known latitude it can't climb higher than it really does, and a
+        // radiant that never rises shows no meteors at all
+        #[cfg(feature = "astronomy")]
+        let shower = match options.meteor_shower {
+            Some(shower) => Some((shower, shower.peak_rate())),
+            None => MeteorShower::active_today(),
+        };
+        #[cfg(feature = "astronomy")]
+        let meteor_shower = shower.and_then(|(shower, hourly_rate)| {
+            let fraction: f32 = rng.gen_range(0.5..1.0);
+            let x = rng.gen_range(0.2..0.8) * width as f32;
+            let highest = options.latitude.map_or(70.0, |lat| shower.culmination(lat));
+            let altitude = fraction * highest;
+            (altitude >= 5.0).then(|| ActiveShower {
+                shower,
+                hourly_rate,
+                radiant: (x, sky_model.row_for_altitude(altitude)),
+            })
+        });
+
+        if let Some(rotation) = &mut rotation {
+            rotation.hold(&mut stars, &sky_model);
+        }
+
+        Simulation {
+            stars,
+            clusters,
+            constellations,
+            landmarks,
+            moon,
+            veils: Vec::new(),
+            halo_visible: false,
+            shooting_stars: Vec::new(),
+            sparks: Sparks::default(),
+            satellites: Vec::new(),
+            starlink: None,
+            trails: TrailLayer::default(),
+            leaves: ParticleSystem::default(),
+            rain: Rain::default(),
+            environment: None,
+            moonbow: None,
+            moonlight: astro::MoonPhase::now().illumination() as f32,
+            aurora: None,
+            comet: None,
+            supernova: None,
+            rotation,
+            lifecycle,
+            daily: options.daily.then(|| Daily::new(astro::julian_day(SystemTime::now()))),
+            #[cfg(feature = "astronomy")]
+            iss: None,
+            #[cfg(feature = "astronomy")]
+            passes: None,
+            wind: Wind::new(&mut rng),
+            director: Director::new(options.pacing),
+            heatmap: Heatmap::new(width, sky_model.horizon_y),
+            band,
+            #[cfg(feature = "astronomy")]
+            meteor_shower,
+            #[cfg(feature = "astronomy")]
+            catalog,
+            #[cfg(feature = "astronomy")]
+            day: (options.day_cycle > 0.0).then(|| {
+                let observer = Observer::new(options.latitude, options.longitude);
+                DayCycle::new(&sky_model, observer, options.day_cycle)
+            }),
+            #[cfg(feature = "astronomy")]
+            planets,
+            #[cfg(feature = "astronomy")]
+            deep_sky,
+            events: Vec::new(),
+            time: 0.0,
+            sky_model,
+            options,
+            rng,
+        }
+    }
+
+    pub fn options(&self) -> &SimulationOptions {
+        &self.options
+    }
+
+    /// Take on changed options from the next step. The stars, moon and
+    /// landmarks were placed when the sky was made and stay where they are.
+    pub fn set_options(&mut self, options: SimulationOptions) {
+        self.director.set_pacing(options.pacing);
+        self.options = options;
+    }
+
+    pub fn sky_model(&self) -> &SkyModel {
+        &self.sky_model
+    }
+
+    pub fn width(&self) -> u16 {
+        self.sky_model.width
+    }
+
+    pub fn stars(&self) -> &[Star] {
+        &self.stars
+    }
+
+    /// The stars above the horizon in `region`.
+    pub fn stars_in(&self, region: Region) -> impl Iterator<Item = &Star> {
+        self.stars
+            .iter()
+            .filter(move |star| star.altitude > 0.0 && region.contains(star.x, star.y))
+    }
+
+    /// The `n` brightest stars above the horizon, brightest first.
+    pub fn brightest_stars(&self, n: usize) -> Vec<&Star> {
+        let mut stars: Vec<&Star> = self.stars.iter().filter(|star| star.altitude > 0.0).collect();
+        stars.sort_by(|a, b| a.magnitude.total_cmp(&b.magnitude));
+        stars.truncate(n);
+        stars
+    }
+
+    /// What's known about the star at `index` in [`Simulation::stars`]: a
+    /// real star's name and distance, and the constellation it's part of.
+    pub fn star_info(&self, index: usize) -> Option<StarInfo> {
+        let star = self.stars.get(index)?;
+        #[cfg_attr(not(feature = "astronomy"), allow(unused_mut))]
+        let mut info = StarInfo {
+            name: None,
+            constellation: self
+                .constellations
+                .iter()
+                .find(|figure| figure.stars.contains(&index))
+                .map(|figure| figure.constellation.name),
+            magnitude: star.magnitude,
+            class: star.class,
+            distance: None,
+            variability: star.variability,
+            companion: star.companion,
+        };
+        #[cfg(feature = "astronomy")]
+        if let Some(catalog) = &self.catalog {
+            if let Some(entry) = index.checked_sub(catalog.first).and_then(|i| star_catalog::CATALOG.get(i)) {
+                info.name = Some(entry.name);
+                if let Some(details) = star_info::details(entry.name) {
+                    info.constellation = Some(details.constellation);
+                    info.distance = Some(details.distance);
+                }
+            }
+        }
+        Some(info)
+    }
+
+    /// The star or constellation in the sky called `name`, or whose name
+    /// starts with it, ignoring case. A random sky has only its
+    /// constellations to find; a catalog sky has its real stars too, and
+    /// the constellations they belong to.
+    pub fn find(&self, name: &str) -> Option<Match> {
+        let figures = self.constellations.iter().map(|figure| Match {
+            name: figure.constellation.name,
+            stars: figure.stars.clone(),
+        });
+        #[cfg(feature = "astronomy")]
+        if let Some(catalog) = &self.catalog {
+            let named = star_catalog::CATALOG.iter().enumerate().map(|(i, entry)| Match {
+                name: entry.name,
+                stars: vec![catalog.first + i],
+            });
+            let mut constellations: Vec<Match> = Vec::new();
+            for (i, entry) in star_catalog::CATALOG.iter().enumerate() {
+                let Some(details) = star_info::details(entry.name) else {
+                    continue;
+                };
+                match constellations.iter_mut().find(|m| m.name == details.constellation) {
+                    Some(found) => found.stars.push(catalog.first + i),
+                    None => constellations.push(Match {
+                        name: details.constellation,
+                        stars: vec![catalog.first + i],
+                    }),
+                }
+            }
+            return query::best(name, figures.chain(named).chain(constellations));
+        }
+        query::best(name, figures)
+    }
+
+    pub fn clusters(&self) -> &[Cluster] {
+        &self.clusters
+    }
+
+    pub fn constellations(&self) -> &[Figure] {
+        &self.constellations
+    }
+
+    /// The constellations of the zodiac among those in the sky.
+    pub fn zodiac(&self) -> impl Iterator<Item = &Figure> {
+        self.constellations.iter().filter(|figure| figure.constellation.zodiac_sign().is_some())
+    }
+
+    pub fn landmarks(&self) -> &[Landmark] {
+        &self.landmarks
+    }
+
+    pub fn moon(&self) -> Option<&Moon> {
+        self.moon.as_ref()
+    }
+
+    pub fn veils(&self) -> &[Veil] {
+        &self.veils
+    }
+
+    pub fn shooting_stars(&self) -> &[ShootingStar] {
+        &self.shooting_stars
+    }
+
+    /// The meteors falling now.
+    pub fn meteors(&self) -> impl Iterator<Item = &ShootingStar> {
+        self.shooting_stars.iter().filter(|meteor| meteor.is_alive())
+    }
+
+    /// The meteors falling now in `region`.
+    pub fn meteors_in(&self, region: Region) -> impl Iterator<Item = &ShootingStar> {
+        self.meteors().filter(move |meteor| region.contains(meteor.x, meteor.y))
+    }
+
+    pub fn satellites(&self) -> &[Satellite] {
+        &self.satellites
+    }
+
+    /// The satellites passing through `region`.
+    pub fn satellites_in(&self, region: Region) -> impl Iterator<Item = &Satellite> {
+        self.satellites
+            .iter()
+            .filter(move |satellite| region.contains(satellite.x, satellite.y))
+    }
+
+    /// A Starlink train crossing the sky, if one is.
+    pub fn starlink(&self) -> Option<&Train> {
+        self.starlink.as_ref()
+    }
+
+    /// Smoke trains left by bright meteors.
+    pub fn trails(&self) -> &TrailLayer {
+        &self.trails
+    }
+
+    pub fn sparks(&self) -> &Sparks {
+        &self.sparks
+    }
+
+    pub fn leaves(&self) -> &ParticleSystem {
+        &self.leaves
+    }
+
+    pub fn raindrops(&self) -> &ParticleSystem {
+        &self.rain.drops
+    }
+
+    pub fn snowflakes(&self) -> &ParticleSystem {
+        &self.rain.flakes
+    }
+
+    pub fn moonbow(&self) -> Option<&Moonbow> {
+        self.moonbow.as_ref()
+    }
+
+    pub fn aurora(&self) -> Option<&Aurora> {
+        self.aurora.as_ref()
+    }
+
+    pub fn comet(&self) -> Option<&Comet> {
+        self.comet.as_ref()
+    }
+
+    /// A star blowing up, if one is.
+    pub fn supernova(&self) -> Option<&Supernova> {
+        self.supernova.as_ref()
+    }
+
+    /// Horizontal wind in cells per tick, positive rightwards.
+    pub fn wind(&self) -> f32 {
+        self.wind.velocity
+    }
+
+    /// Where meteors and satellites have been so far.
+    pub fn heatmap(&self) -> &Heatmap {
+        &self.heatmap
+    }
+
+    /// The real planets, in a catalog sky. Those below the horizon have set.
+    #[cfg(feature = "astronomy")]
+    pub fn planets(&self) -> &[SkyPlanet] {
+        &self.planets
+    }
+
+    /// The showpiece deep-sky objects, in a catalog sky. Those below the
+    /// horizon have set.
+    #[cfg(feature = "astronomy")]
+    pub fn deep_sky(&self) -> &[SkyObject] {
+        &self.deep_sky
+    }
+
+    /// The sun, in a sky running through the day.
+    #[cfg(feature = "astronomy")]
+    pub fn sun(&self) -> Option<&Sun> {
+        self.day.as_ref().map(DayCycle::sun)
+    }
+
+    /// Where a catalog sky is seen from.
+    #[cfg(feature = "astronomy")]
+    pub fn observer(&self) -> Option<&Observer> {
+        self.catalog.as_ref().map(|catalog| &catalog.observer)
+    }
+
+    /// The real ISS, when it's being tracked. It's only in the sky while
+    /// its altitude is above 0.
+    #[cfg(feature = "astronomy")]
+    pub fn iss(&self) -> Option<&Iss> {
+        self.iss.as_ref()
+    }
+
+    #[cfg(feature = "astronomy")]
+    pub fn tracks_iss(&self) -> bool {
+        self.iss.is_some()
+    }
+
+    /// Follow the real ISS from now on, along the orbit `tle` describes, as
+    /// seen from the configured place. Returns whether the orbit could be
+    /// followed.
+    #[cfg(feature = "astronomy")]
+    pub fn track_iss(&mut self, tle: &Tle) -> bool {
+        let (observer, start) = match &self.catalog {
+            Some(catalog) => (catalog.observer, catalog.start),
+            None => (
+                Observer::new(self.options.latitude, self.options.longitude),
+                projection::now() - self.seconds() as f64 / 86_400.0,
+            ),
+        };
+        let seconds = self.seconds();
+        self.iss = Iss::new(tle, observer, start);
+        if let Some(iss) = &mut self.iss {
+            iss.follow(&self.sky_model, seconds);
+        }
+        self.iss.is_some()
+    }
+
+    /// What's known of the next visible pass of the real satellite called
+    /// `name`, from the ISS's orbit while it's tracked or the predicted
+    /// passes while they're flown.
+    #[cfg(feature = "astronomy")]
+    pub fn next_pass(&self, name: &str) -> NextPass {
+        let seconds = self.seconds();
+        match (&self.iss, &self.passes) {
+            (Some(iss), _) if name.starts_with("ISS") => iss.next_pass(seconds),
+            (_, Some(passes)) => passes.next(name, seconds),
+            _ => NextPass::Unknown,
+        }
+    }
+
+    /// Real satellites' passes, when they're being flown.
+    #[cfg(feature = "astronomy")]
+    pub fn passes(&self) -> Option<&Passes> {
+        self.passes.as_ref()
+    }
+
+    /// Fly `passes` from now on, on a clock starting at the real time and
+    /// running `speed` times as fast, as seen from the configured place.
+    /// Passes already being flown keep their clock, and these follow them.
+    #[cfg(feature = "astronomy")]
+    pub fn fly_passes(&mut self, passes: Vec<Pass>, speed: f32) {
+        if let Some(flying) = &mut self.passes {
+            flying.extend(passes);
+            return;
+        }
+        let observer = match &self.catalog {
+            Some(catalog) => catalog.observer,
+            None => Observer::new(self.options.latitude, self.options.longitude),
+        };
+        let seconds = self.seconds();
+        let mut passes = Passes::new(passes, observer, projection::now(), seconds, speed);
+        passes.follow(&self.sky_model, seconds);
+        self.passes = Some(passes);
+    }
+
+    #[cfg(feature = "astronomy")]
+    pub fn meteor_shower(&self) -> Option<&ActiveShower> {
+        self.meteor_shower.as_ref()
+    }
+
+    /// Simulation time in ticks.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Simulation time in seconds.
+    pub fn seconds(&self) -> f32 {
+        self.time / TICKS_PER_SECOND as f32
+    }
+
+    /// How strongly thin cloud over a bright moon shows a halo right now.
+    pub fn halo_strength(&self) -> f32 {
+        let Some(moon) = &self.moon else {
+            return 0.0;
+        };
+        let cover = clouds::coverage(&self.veils, moon.x, moon.y);
+        // A dim crescent doesn't light a visible halo
+        cover * ((moon.brightness() - 0.3) / 0.4).clamp(0.0, 1.0)
+    }
+
+    /// How strongly the Milky Way shows at the sky point `(x, y)`, from 0
+    /// away from it to 1 along its brightest stretch. In a catalog sky it
+    /// follows the real galactic plane, turning with the stars.
+    pub fn milky_way(&self, x: f32, y: f32) -> f32 {
+        if y >= self.sky_model.horizon_y as f32 {
+            return 0.0;
+        }
+        #[cfg(feature = "astronomy")]
+        if let Some(catalog) = &self.catalog {
+            let jd = catalog.start + self.seconds() as f64 / 86_400.0;
+            let (altitude, azimuth) = catalog.observer.direction(&self.sky_model, x, y);
+            let (ra, dec) = catalog.observer.equatorial(altitude, azimuth, jd);
+            return milky_way::strength(ra, dec);
+        }
+        let (x, y) = match &self.rotation {
+            Some(rotation) => rotation.unturned(x, y),
+            None => (x, y),
+        };
+        self.band.strength(&self.sky_model, x, y)
+    }
+
+    /// Whether `(x, y)` lies in a zone kept clear of moving things.
+    pub fn is_avoided(&self, x: f32, y: f32) -> bool {
+        let (width, height) = (self.sky_model.width, self.sky_model.horizon_y);
+        self.options.avoid.iter().any(|zone| zone.contains(width, height, x, y))
+    }
+
+    /// Launch a meteor from the sky point `(x, y)`, if it's above the
+    /// horizon.
+    pub fn launch_meteor(&mut self, x: f32, y: f32) {
+        if y < self.sky_model.horizon_y as f32 && !self.is_avoided(x, y) {
+            let meteor = ShootingStar::at(&mut self.rng, &self.sky_model, x, y);
+            self.shooting_stars.push(meteor);
+        }
+    }
+
+    pub fn follows_weather(&self) -> bool {
+        self.environment.is_some()
+    }
+
+    /// Follow the weather outside from now on: rain or snow falls as hard
+    /// as `sample` says and the wind blows as it does, easing over from
+    /// whatever was reported before. Random showers stop.
+    pub fn report_weather(&mut self, sample: Sample) {
+        match &mut self.environment {
+            Some(environment) => environment.report(sample),
+            None => self.environment = Some(Environment::new(sample)),
+        }
+    }
+
+    /// Start an aurora at the configured strength, or a moderate one if
+    /// none is configured. Returns whether one started; there's only ever
+    /// one at a time.
+    pub fn summon_aurora(&mut self) -> bool {
+        if self.aurora.is_some() {
+            return false;
+        }
+        let intensity = if self.options.aurora > 0.0 {
+            self.options.aurora
+        } else {
+            aurora::DEFAULT_INTENSITY
+        };
+        self.aurora = Some(Aurora::new(&mut self.rng, intensity));
+        self.emit(SkyEvent::Aurora);
+        true
+    }
+
+    /// Send a Starlink train across the sky. Returns whether one started;
+    /// there's only ever one at a time.
+    pub fn launch_starlink(&mut self) -> bool {
+        if self.starlink.is_some() {
+            return false;
+        }
+        let train = Train::new(&mut self.rng, &self.sky_model);
+        self.starlink = Some(train);
+        self.emit(SkyEvent::StarlinkTrain);
+        true
+    }
+
+    /// Bring a comet in somewhere clear of the avoided zones, if a few
+    /// tries find it. Returns whether one came; there's only ever one at a
+    /// time.
+    fn launch_comet(&mut self) -> bool {
+        if self.comet.is_some() {
+            return false;
+        }
+        for _ in 0..8 {
+            let comet = Comet::new(&mut self.rng, &self.sky_model);
+            if !self.is_avoided(comet.x, comet.y) {
+                self.comet = Some(comet);
+                self.emit(SkyEvent::Comet);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Blow up a star too faint to see, well up in the sky and clear of the
+    /// avoided zones. Returns whether one went; there's only ever one at a
+    /// time.
+    pub fn explode_star(&mut self) -> bool {
+        if self.supernova.is_some() {
+            return false;
+        }
+        let faint = Fov::NakedEye.magnitude_limit();
+        let candidates: Vec<usize> = (0..self.stars.len())
+            .filter(|&i| {
+                let star = &self.stars[i];
+                star.magnitude > faint
+                    && star.altitude > SUPERNOVA_ALTITUDE
+                    && star.presence == 1.0
+                    && star.cluster.is_none()
+                    && star.variability.is_none()
+                    && !self.is_avoided(star.x, star.y)
+            })
+            .collect();
+        if candidates.is_empty() {
+            return false;
+        }
+        let index = candidates[self.rng.gen_range(0..candidates.len())];
+        self.supernova = Some(Supernova::new(index, &self.stars[index]));
+        self.emit(SkyEvent::Supernova);
+        true
+    }
+
+    /// Bring on the day's `appointment`. Its details are drawn from its own
+    /// seed, so it looks the same in everyone's sky, and the sky's own
+    /// generator is left where it was.
+    fn keep(&mut self, appointment: Appointment) {
+        let rng = std::mem::replace(&mut self.rng, ChaCha12Rng::seed_from_u64(appointment.seed));
+        match appointment.appearance {
+            Appearance::Comet => {
+                self.launch_comet();
+            }
+            Appearance::Aurora => {
+                self.summon_aurora();
+            }
+            Appearance::StarlinkTrain => {
+                self.launch_starlink();
+            }
+            Appearance::Fireball => {
+                let mut meteor = ShootingStar::new(&mut self.rng, &self.sky_model);
+                meteor.bright = true;
+                if !self.is_avoided(meteor.x, meteor.y) {
+                    self.shooting_stars.push(meteor);
+                }
+            }
+        }
+        self.rng = rng;
+    }
+
+    fn emit(&mut self, event: SkyEvent) {
+        self.director.note_interesting();
+        self.events.push(event);
+    }
+
+    // The real satellites come over on the real clock, whatever the seed,
+    // so they're reported without moving the director's pacing
+    #[cfg(feature = "astronomy")]
+    fn report(&mut self, event: SkyEvent) {
+        self.events.push(event);
+    }
+
+    /// Advance the sky by `dt` ticks, returning what notable happened on
+    /// the way.
+    pub fn update(&mut self, dt: f32) -> Vec<SkyEvent> {
+        self.time += dt;
+        self.director.update(dt);
+        let width = self.sky_model.width;
+
+        // The sun keeps to the day's clock
+        #[cfg(feature = "astronomy")]
+        if let Some(day) = &mut self.day {
+            day.update(&self.sky_model, self.time / TICKS_PER_SECOND as f32);
+        }
+
+        // The real stars wheel across the sky with the hours
+        #[cfg(feature = "astronomy")]
+        if let Some(catalog) = &self.catalog {
+            let jd = match &self.day {
+                Some(day) => day.jd(self.seconds()),
+                None => catalog.start + self.seconds() as f64 / 86_400.0,
+            };
+            let stars = &mut self.stars[catalog.first..catalog.first + star_catalog::CATALOG.len()];
+            star_catalog::follow(stars, &self.sky_model, &catalog.observer, jd);
+            planets::follow(&mut self.planets, &self.sky_model, &catalog.observer, jd);
+            deep_sky::follow(&mut self.deep_sky, &self.sky_model, &catalog.observer, jd);
+        }
+
+        // Clusters drift as a group, unless the whole sky is turning
+        if let Some(rotation) = &mut self.rotation {
+            rotation.update(dt, &mut self.stars, &self.sky_model);
+        } else {
+            for star in &mut self.stars {
+                if let Some(i) = star.cluster {
+                    let drift = self.clusters[i].drift;
+                    star.x = (star.x + drift * dt).rem_euclid(width.max(1) as f32);
+                }
+            }
+        }
+
+        // Now and then a star fades out, and another fades in elsewhere
+        if let Some(lifecycle) = &mut self.lifecycle {
+            let density = Density::new(self.options.density, self.band);
+            let limit = catalog_limit(&self.options);
+            let sky = &self.sky_model;
+            lifecycle.update(dt, &mut self.stars, &mut self.rng, |rng| {
+                naked_eye_star(rng, &density, sky, limit)
+            });
+        }
+
+        // While a shower is on, every meteor streams from its radiant
+        #[cfg(feature = "astronomy")]
+        let sporadic = self.meteor_shower.is_none();
+        #[cfg(not(feature = "astronomy"))]
+        let sporadic = true;
+
+        // Spawn shooting stars randomly (2% chance per tick)
+        let chance = 0.02 * dt * self.options.meteor_rate;
+        if sporadic && self.director.roll(&mut self.rng, Spawn::Meteor, chance) {
+            let meteor = ShootingStar::new(&mut self.rng, &self.sky_model);
+            if !self.is_avoided(meteor.x, meteor.y) {
+                self.shooting_stars.push(meteor);
+            }
+        }
+
+        // Shower meteors instead, in proportion to the shower's hourly rate;
+        // several times the usual number at a strong shower's peak
+        #[cfg(feature = "astronomy")]
+        if let Some(shower) = &self.meteor_shower {
+            let rate = shower.hourly_rate / meteor_showers::SPORADIC_RATE;
+            let chance = 0.02 * dt * self.options.meteor_rate * rate;
+            if self.director.roll(&mut self.rng, Spawn::ShowerMeteor, chance) {
+                let ((x, y), heading) = shower.meteor_path(&mut self.rng, self.sky_model.aspect);
+                let meteor = ShootingStar::heading(&mut self.rng, x, y, heading);
+                if !self.is_avoided(x, y) {
+                    self.shooting_stars.push(meteor);
+                }
+            }
+        }
+
+        // Update and remove dead shooting stars, which shed sparks as they go
+        self.sparks.update(dt);
+        for star in &mut self.shooting_stars {
+            let from = (star.x, star.y);
+            star.update(dt);
+            self.heatmap.record_segment(from, (star.x, star.y));
+            self.sparks.shed(&mut self.rng, star, dt);
+        }
+        let horizon = self.sky_model.horizon_y as f32;
+        let in_sight = |s: &ShootingStar| s.x >= 0.0 && s.y >= 0.0 && s.x < width as f32 && s.y < horizon;
+        let visible = |s: &ShootingStar| s.is_alive() && in_sight(s);
+        for star in self.shooting_stars.iter().filter(|s| s.bright && !visible(s)) {
+            self.trails.spawn(&mut self.rng, &star.path);
+        }
+        // A bolide that burns out in sight goes off in a burst
+        let burst = self.shooting_stars.iter().any(|s| s.bolide && !s.is_alive() && in_sight(s));
+        self.shooting_stars.retain(visible);
+        if self.shooting_stars.iter().any(|s| s.bright && s.lifetime <= dt) {
+            self.emit(SkyEvent::Fireball);
+        }
+        if burst {
+            self.emit(SkyEvent::Bolide);
+        }
+
+        let outside = self.environment.as_mut().map(|environment| {
+            environment.update(dt);
+            environment.current()
+        });
+        match outside {
+            Some(outside) => self.wind.follow(outside.wind, dt),
+            None => self.wind.update(&mut self.rng, dt),
+        }
+        self.trails.update(self.wind.velocity, dt);
+
+        if self.options.leaves {
+            leaves::spawn(&mut self.leaves, &mut self.rng, width, dt);
+        }
+        let ground = self.sky_model.horizon_y as f32;
+        self.leaves.update(dt, self.wind.velocity, width, ground);
+
+        if let Some(outside) = outside {
+            self.rain.follow(outside.precipitation, outside.snow);
+        } else if self.options.showers {
+            // Roughly one shower every ten minutes
+            if !self.rain.is_raining()
+                && self
+                    .director
+                    .roll(&mut self.rng, Spawn::Shower, dt / (600.0 * TICKS_PER_SECOND as f32))
+            {
+                self.rain.start(&mut self.rng);
+            }
+        }
+        if outside.is_some() || self.options.showers {
+            let wind = self.wind.velocity;
+            let snow = self.rain.is_snow();
+            let shower_ended = self.rain.update(&mut self.rng, dt, wind, width, ground);
+            // Sometimes a bright moon lights a bow in the departing rain
+            if shower_ended
+                && !snow
+                && self.moonlight > 0.6
+                && self.director.roll(&mut self.rng, Spawn::Moonbow, 0.5)
+            {
+                self.moonbow = Some(Moonbow::new(60.0 * TICKS_PER_SECOND as f32));
+                self.emit(SkyEvent::Moonbow);
+            }
+        }
+        if let Some(moonbow) = &mut self.moonbow {
+            moonbow.update(dt);
+            if moonbow.is_done() {
+                self.moonbow = None;
+            }
+        }
+
+        // A sky of the day keeps to its schedule instead of rolling for
+        // auroras, comets and Starlink trains
+        let seconds = self.seconds();
+        while let Some(appointment) = self.daily.as_mut().and_then(|daily| daily.next_due(seconds)) {
+            self.keep(appointment);
+        }
+        let rolls = self.daily.is_none();
+
+        // An aurora every half hour or so, when there are to be any
+        if rolls
+            && self.options.aurora > 0.0
+            && self.aurora.is_none()
+            && self
+                .director
+                .roll(&mut self.rng, Spawn::Aurora, dt / (1800.0 * TICKS_PER_SECOND as f32))
+        {
+            self.aurora = Some(Aurora::new(&mut self.rng, self.options.aurora));
+            self.emit(SkyEvent::Aurora);
+        }
+        if let Some(aurora) = &mut self.aurora {
+            aurora.update(dt);
+            if aurora.is_done() {
+                self.aurora = None;
+            }
+        }
+
+        // A comet every few hours
+        if rolls
+            && self.comet.is_none()
+            && self
+                .director
+                .roll(&mut self.rng, Spawn::Comet, dt / (3.0 * 3600.0 * TICKS_PER_SECOND as f32))
+        {
+            self.launch_comet();
+        }
+        if let Some(comet) = &mut self.comet {
+            comet.update(dt);
+            if comet.is_done() {
+                self.comet = None;
+            }
+        }
+
+        // Once in a long while a star blows up, or a little way in when
+        // one's been asked for
+        let asked = self.options.supernova
+            && self.time - dt < SUPERNOVA_DELAY_TICKS
+            && self.time >= SUPERNOVA_DELAY_TICKS;
+        let chance = dt / (24.0 * 3600.0 * TICKS_PER_SECOND as f32);
+        let rolled = rolls && self.supernova.is_none() && self.director.roll(&mut self.rng, Spawn::Supernova, chance);
+        if asked || rolled {
+            self.explode_star();
+        }
+        if let Some(supernova) = &mut self.supernova {
+            supernova.update(dt);
+            let star = &mut self.stars[supernova.star];
+            star.magnitude = supernova.magnitude();
+            star.brightness = supernova.brightness();
+            if supernova.is_done() {
+                self.supernova = None;
+            }
+        }
+
+        // A thin veil of high cloud every few minutes
+        if self.options.clouds
+            && self
+                .director
+                .roll(&mut self.rng, Spawn::Veil, dt / (240.0 * TICKS_PER_SECOND as f32))
+        {
+            let veil = Veil::new(&mut self.rng, width, self.sky_model.horizon_y);
+            self.veils.push(veil);
+        }
+        for veil in &mut self.veils {
+            veil.update(dt, self.wind.velocity);
+        }
+        self.veils.retain(|v| !v.is_done());
+        // An eclipse's shadow creeps over the moon
+        if let Some(moon) = &mut self.moon {
+            moon.update(dt);
+        }
+        let halo = self.halo_strength();
+        if halo > 0.3 && !self.halo_visible {
+            self.halo_visible = true;
+            self.emit(SkyEvent::MoonHalo);
+        } else if halo < 0.05 {
+            self.halo_visible = false;
+        }
+
+        // Spawn satellites rarely (1 in 300 per tick, a few at once)
+        let chance = dt / 300.0 * self.options.satellite_rate;
+        if self.satellites.len() < MAX_SATELLITES && self.director.roll(&mut self.rng, Spawn::Satellite, chance) {
+            let satellite = Satellite::new(&mut self.rng, &self.sky_model);
+            if !self.is_avoided(satellite.x, satellite.y) {
+                self.satellites.push(satellite);
+                self.emit(SkyEvent::SatellitePass);
+            }
+        }
+
+        // Now and then, a fresh batch of Starlinks in a line
+        let chance = dt / (3600.0 * TICKS_PER_SECOND as f32) * self.options.satellite_rate;
+        if rolls && self.starlink.is_none() && self.director.roll(&mut self.rng, Spawn::StarlinkTrain, chance) {
+            self.launch_starlink();
+        }
+        if let Some(train) = &mut self.starlink {
+            train.update(dt);
+            for member in train.visible(width, self.sky_model.horizon_y) {
+                self.heatmap.record(member.x, member.y);
+            }
+            if train.is_done() {
+                self.starlink = None;
+            }
+        }
+
+        // The real ISS goes over on its own schedule
+        #[cfg(feature = "astronomy")]
+        if let Some(iss) = &mut self.iss {
+            let was_up = iss.altitude > 0.0;
+            iss.follow(&self.sky_model, self.time / TICKS_PER_SECOND as f32);
+            if iss.altitude > 0.0 && !was_up {
+                self.report(SkyEvent::IssPass);
+            }
+        }
+        #[cfg(feature = "astronomy")]
+        if let Some(passes) = &mut self.passes {
+            if passes.follow(&self.sky_model, self.time / TICKS_PER_SECOND as f32) > 0 {
+                self.report(SkyEvent::PredictedPass);
+            }
+        }
+
+        // Passes that cross close by each other make a conjunction
+        for i in 0..self.satellites.len() {
+            for j in i + 1..self.satellites.len() {
+                let (a, b) = (&self.satellites[i], &self.satellites[j]);
+                if a.is_near_miss() || b.is_near_miss() || a.closest_approach(b, dt, self.sky_model.aspect) > NEAR_MISS_CELLS {
+                    continue;
+                }
+                self.satellites[i].near_miss = NEAR_MISS_TICKS;
+                self.satellites[j].near_miss = NEAR_MISS_TICKS;
+                self.emit(SkyEvent::Conjunction);
+            }
+        }
+
+        // Update satellites and remove those that have crossed the screen
+        for satellite in &mut self.satellites {
+            satellite.update(dt);
+            self.heatmap.record(satellite.x, satellite.y);
+        }
+        self.satellites
+            .retain(|s| s.is_on_screen(width, self.sky_model.horizon_y));
+
+        std::mem::take(&mut self.events)
+    }
+}
+
+/// The faintest magnitude a catalog sky takes from its catalog, leaving
+/// random stars to fill in behind them. Other skies are all random stars.
+#[cfg_attr(not(feature = "astronomy"), allow(unused_variables))]
+fn catalog_limit(options: &SimulationOptions) -> Option<f32> {
+    #[cfg(feature = "astronomy")]
+    if options.catalog {
+        return Some(star_catalog::FAINTEST);
+    }
+    None
+}
+
+/// A random star of the kind seen with the naked eye, placed by `density`,
+/// or `None` if no place was found. Below a catalog's `catalog_limit` it's
+/// only ever fainter than the real stars.
+fn naked_eye_star<R: Rng>(
+    rng: &mut R,
+    density: &Density,
+    sky: &SkyModel,
+    catalog_limit: Option<f32>,
+) -> Option<Star> {
+    let (x, y, altitude) = density.place(sky, rng, 1.0)?;
+    let (magnitude, brightness) = match catalog_limit {
+        Some(limit) => {
+            let magnitude = rng.gen_range(limit..6.5);
+            (magnitude, (6.5 - magnitude).round().clamp(1.0, 5.0) as u8)
+        }
+        None => {
+            let brightness = rng.gen_range(1..=5);
+            (6.5 - brightness as f32, brightness)
+        }
+    };
+    Some(Star {
+        x,
+        y,
+        altitude,
+        magnitude,
+        brightness,
+        class: SpectralClass::random(rng),
+        twinkle_speed: rng.gen_range(0.1..0.5),
+        cluster: None,
+        presence: 1.0,
+        variability: variable::random(rng),
+        companion: double::random(rng, magnitude),
+    })
+}
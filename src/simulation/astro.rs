@@ -0,0 +1,161 @@
+//! Lightweight ephemeris helpers shared by the sky renderer and the status line.
+//!
+//! Everything here is a pure function of a Julian day and, where it matters,
+//! a place, so it can be checked against an almanac.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Mean length of a lunation in days
+const SYNODIC_MONTH: f64 = 29.530588853;
+// Julian day of the Unix epoch (1970-01-01 00:00 UTC)
+const JD_UNIX_EPOCH: f64 = 2440587.5;
+// Julian day of a known new moon (2000-01-06 18:14 UTC)
+const JD_REFERENCE_NEW_MOON: f64 = 2451550.26;
+// Julian day of the J2000.0 epoch
+const J2000: f64 = 2451545.0;
+// Sunrise and sunset come when the top of the sun's disc, lifted by
+// refraction, touches the horizon: its centre is this far below
+const SUNRISE_ALTITUDE: f64 = -0.833;
+
+/// Julian day for a point in time.
+pub fn julian_day(time: SystemTime) -> f64 {
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs_f64(),
+        Err(e) => -e.duration().as_secs_f64(),
+    };
+    JD_UNIX_EPOCH + secs / 86_400.0
+}
+
+/// The point in time at Julian day `jd`.
+pub fn time_of(jd: f64) -> SystemTime {
+    let secs = (jd - JD_UNIX_EPOCH) * 86_400.0;
+    if secs >= 0.0 {
+        UNIX_EPOCH + Duration::from_secs_f64(secs)
+    } else {
+        UNIX_EPOCH - Duration::from_secs_f64(-secs)
+    }
+}
+
+/// Local sidereal time in degrees at Julian day `jd` and `longitude`
+/// (degrees, east positive).
+pub fn sidereal(jd: f64, longitude: f64) -> f64 {
+    let days = jd - J2000;
+    (280.46061837 + 360.98564736629 * days + longitude).rem_euclid(360.0)
+}
+
+/// Right ascension (hours) and declination (degrees) of the sun at Julian
+/// day `jd`, good to about a hundredth of a degree this century.
+pub fn sun(jd: f64) -> (f64, f64) {
+    let days = jd - J2000;
+    let mean_longitude = 280.460 + 0.9856474 * days;
+    let anomaly = (357.528 + 0.9856003 * days).to_radians();
+    let longitude = (mean_longitude + 1.915 * anomaly.sin() + 0.020 * (2.0 * anomaly).sin()).to_radians();
+    let obliquity = (23.439 - 0.0000004 * days).to_radians();
+    let ra = (obliquity.cos() * longitude.sin()).atan2(longitude.cos());
+    let dec = (obliquity.sin() * longitude.sin()).asin();
+    (ra.to_degrees().rem_euclid(360.0) / 15.0, dec.to_degrees())
+}
+
+/// Degrees the sun's centre stands above the horizon at Julian day `jd`,
+/// seen from `latitude` and `longitude` (degrees, north and east positive).
+pub fn sun_altitude(jd: f64, latitude: f64, longitude: f64) -> f64 {
+    let (ra, dec) = sun(jd);
+    let hour_angle = (sidereal(jd, longitude) - ra * 15.0).to_radians();
+    let (lat, dec) = (latitude.to_radians(), dec.to_radians());
+    (lat.sin() * dec.sin() + lat.cos() * dec.cos() * hour_angle.cos())
+        .asin()
+        .to_degrees()
+}
+
+/// How dark the sky has got with the sun below the horizon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Twilight {
+    Day,
+    /// Sun less than 6° down: bright enough to read outside
+    Civil,
+    /// Less than 12° down: the horizon still shows against the sky
+    Nautical,
+    /// Less than 18° down: the faintest stars are still washed out
+    Astronomical,
+    Night,
+}
+
+impl Twilight {
+    /// The twilight with the sun's centre at `sun_altitude` degrees.
+    pub fn at(sun_altitude: f64) -> Self {
+        match sun_altitude {
+            a if a > SUNRISE_ALTITUDE => Twilight::Day,
+            a if a > -6.0 => Twilight::Civil,
+            a if a > -12.0 => Twilight::Nautical,
+            a if a > -18.0 => Twilight::Astronomical,
+            _ => Twilight::Night,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Twilight::Day => "day",
+            Twilight::Civil => "civil twilight",
+            Twilight::Nautical => "nautical twilight",
+            Twilight::Astronomical => "astronomical twilight",
+            Twilight::Night => "night",
+        }
+    }
+}
+
+/// Position of the moon within its synodic cycle.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoonPhase {
+    /// Days since the last new moon
+    pub age_days: f64,
+}
+
+impl MoonPhase {
+    pub fn at(jd: f64) -> Self {
+        let age_days = (jd - JD_REFERENCE_NEW_MOON).rem_euclid(SYNODIC_MONTH);
+        MoonPhase { age_days }
+    }
+
+    pub fn now() -> Self {
+        Self::at(julian_day(SystemTime::now()))
+    }
+
+    pub fn full() -> Self {
+        MoonPhase {
+            age_days: SYNODIC_MONTH / 2.0,
+        }
+    }
+
+    /// Cycle position in `0.0..1.0` (0 = new, 0.5 = full).
+    pub fn fraction(&self) -> f64 {
+        self.age_days / SYNODIC_MONTH
+    }
+
+    /// Illuminated fraction of the disc in `0.0..=1.0`.
+    pub fn illumination(&self) -> f64 {
+        (1.0 - (self.fraction() * std::f64::consts::TAU).cos()) / 2.0
+    }
+
+    /// Index into the eight named phases, starting at new moon.
+    fn octant(&self) -> usize {
+        ((self.fraction() * 8.0 + 0.5).floor() as usize) % 8
+    }
+
+    pub fn name(&self) -> &'static str {
+        [
+            "new moon",
+            "waxing crescent",
+            "first quarter",
+            "waxing gibbous",
+            "full moon",
+            "waning gibbous",
+            "last quarter",
+            "waning crescent",
+        ][self.octant()]
+    }
+
+    pub fn glyph(&self) -> &'static str {
+        ["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"][self.octant()]
+    }
+}
@@ -0,0 +1,94 @@
+//! The aurora: curtains of light rippling across the high sky, now and then
+//! or when called up.
+
+use crate::simulation::TICKS_PER_SECOND;
+use rand::Rng;
+use std::f32::consts::TAU;
+
+/// Strength of an aurora called up when none is configured.
+pub const DEFAULT_INTENSITY: f32 = 0.6;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Curtain {
+    /// Centre, as a fraction of the sky's width
+    x: f32,
+    /// Half-width, as a fraction of the sky's width
+    width: f32,
+    /// Fractions of the sky's width moved per tick
+    drift: f32,
+    /// Folds across the sky's width
+    folds: f32,
+    /// How quickly the folds ripple along, radians per tick
+    ripple: f32,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aurora {
+    curtains: Vec<Curtain>,
+    age: f32,
+    duration: f32,
+    /// How bright it gets at its height, from 0 to 1
+    intensity: f32,
+}
+
+impl Aurora {
+    pub fn new<R: Rng>(rng: &mut R, intensity: f32) -> Self {
+        let curtains = (0..rng.gen_range(2..=4))
+            .map(|_| Curtain {
+                x: rng.gen_range(0.0..1.0),
+                width: rng.gen_range(0.08..0.2),
+                drift: rng.gen_range(-0.0004..0.0004),
+                folds: rng.gen_range(12.0..30.0),
+                ripple: rng.gen_range(0.03..0.08),
+            })
+            .collect();
+        Aurora {
+            curtains,
+            age: 0.0,
+            duration: rng.gen_range(90.0..240.0) * TICKS_PER_SECOND as f32,
+            intensity: intensity.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.age += dt;
+        for curtain in &mut self.curtains {
+            curtain.x = (curtain.x + curtain.drift * dt).rem_euclid(1.0);
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.age >= self.duration
+    }
+
+    /// Brightens over the first fifth and fades over the last.
+    pub fn strength(&self) -> f32 {
+        let t = self.age / self.duration;
+        let envelope = (t * 5.0).min((1.0 - t) * 5.0).clamp(0.0, 1.0);
+        envelope * self.intensity
+    }
+
+    /// How brightly the curtains hang at `u` across the sky, as a fraction
+    /// of its width, from 0 to 1.
+    pub fn brightness(&self, u: f32) -> f32 {
+        let total: f32 = self
+            .curtains
+            .iter()
+            .map(|curtain| {
+                // The sky wraps, so measure the short way round
+                let dx = (u - curtain.x + 0.5).rem_euclid(1.0) - 0.5;
+                let body = (-(dx / curtain.width).powi(2)).exp();
+                let fold = (u * curtain.folds * TAU + self.age * curtain.ripple).sin();
+                body * (0.55 + 0.45 * fold)
+            })
+            .sum();
+        total.min(1.0)
+    }
+
+    /// How far down the curtains reach at `u`, as a fraction of the height
+    /// they hang in.
+    pub fn hem(&self, u: f32) -> f32 {
+        0.8 + 0.15 * (u * 3.0 * TAU + self.age * 0.02).sin()
+    }
+}
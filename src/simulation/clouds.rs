@@ -0,0 +1,67 @@
+//! Thin, high cloud veils that drift across on the wind. They barely dim
+//! the stars, but spread a bright moon's light into halos.
+
+use rand::Rng;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Veil {
+    x: f32,
+    y: f32,
+    /// Half-width and half-height in cells
+    rx: f32,
+    ry: f32,
+    density: f32,
+    /// Width of the sky, which the veil wraps around
+    span: f32,
+    /// Own drift on top of the wind, in cells per tick
+    speed: f32,
+    age: f32,
+    duration: f32,
+}
+
+impl Veil {
+    pub fn new<R: Rng>(rng: &mut R, width: u16, horizon: u16) -> Self {
+        let speed = rng.gen_range(0.005..0.02);
+        Veil {
+            x: rng.gen_range(0.0..width.max(1) as f32),
+            y: rng.gen_range(0.0..horizon.max(1) as f32 * 0.7),
+            rx: width as f32 * rng.gen_range(0.15..0.35),
+            ry: horizon as f32 * rng.gen_range(0.15..0.3),
+            density: rng.gen_range(0.5..1.0),
+            span: width.max(1) as f32,
+            speed: if rng.gen_bool(0.5) { speed } else { -speed },
+            age: 0.0,
+            duration: rng.gen_range(120.0..300.0) * crate::simulation::TICKS_PER_SECOND as f32,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32, wind: f32) {
+        self.age += dt;
+        self.x = (self.x + (self.speed + wind) * dt).rem_euclid(self.span);
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.age >= self.duration
+    }
+
+    /// Thicken over the first fifth of the veil's life and thin over the
+    /// last.
+    fn envelope(&self) -> f32 {
+        let t = self.age / self.duration;
+        (t * 5.0).min((1.0 - t) * 5.0).clamp(0.0, 1.0)
+    }
+
+    /// How thickly the veil covers the sky point `(x, y)`, in `0.0..=1.0`.
+    fn coverage(&self, x: f32, y: f32) -> f32 {
+        let dx = (x - self.x + self.span / 2.0).rem_euclid(self.span) - self.span / 2.0;
+        let d = (dx / self.rx).powi(2) + ((y - self.y) / self.ry).powi(2);
+        // Soft edges rather than a hard ellipse
+        let falloff = (1.0 - d).max(0.0).powi(2);
+        falloff * self.density * self.envelope()
+    }
+}
+
+/// Combined cover of every veil at the sky point `(x, y)`.
+pub fn coverage(veils: &[Veil], x: f32, y: f32) -> f32 {
+    veils.iter().map(|v| v.coverage(x, y)).sum::<f32>().min(1.0)
+}
@@ -0,0 +1,53 @@
+//! Open star clusters: tight, slightly blue groups that share a drift.
+
+use rand::Rng;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cluster {
+    /// Shared horizontal motion of every member in cells per tick
+    pub drift: f32,
+}
+
+/// One member star as an offset from the cluster centre.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Member {
+    pub dx: f32,
+    pub dy: f32,
+    pub magnitude: f32,
+}
+
+impl Cluster {
+    pub fn new<R: Rng>(rng: &mut R) -> Self {
+        let speed = rng.gen_range(0.0005..0.0015);
+        Cluster {
+            drift: if rng.gen_bool(0.5) { speed } else { -speed },
+        }
+    }
+
+    /// Members visible to the naked eye plus fainter ones that only resolve
+    /// through binoculars or a telescope, packed closer to the core. Rows
+    /// are `aspect` columns tall.
+    pub fn members<R: Rng>(&self, rng: &mut R, aspect: f32) -> Vec<Member> {
+        let bright = rng.gen_range(5..=12);
+        let faint = rng.gen_range(10..=25);
+        let mut members = Vec::with_capacity(bright + faint);
+        for i in 0..bright + faint {
+            let is_faint = i >= bright;
+            // Round in the sky, so squashed into fewer rows than columns
+            let rx = if is_faint { 1.5 } else { 3.0 };
+            let ry = rx / aspect;
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let r = rng.gen::<f32>().sqrt();
+            members.push(Member {
+                dx: angle.cos() * r * rx,
+                dy: angle.sin() * r * ry,
+                magnitude: if is_faint {
+                    rng.gen_range(6.5..11.0)
+                } else {
+                    rng.gen_range(3.0..6.0)
+                },
+            });
+        }
+        members
+    }
+}
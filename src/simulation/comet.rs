@@ -0,0 +1,92 @@
+//! Comets: rare visitors that creep across the sky over several minutes,
+//! trailing a long tail that curves away from the sun.
+
+use crate::simulation::sky_model::SkyModel;
+use crate::simulation::TICKS_PER_SECOND;
+use rand::Rng;
+
+// Which way the sun lies, below the horizon, as a direction across the sky
+// with rows running down. Every tail points the other way.
+const SUN: (f32, f32) = (0.5, 0.87);
+// Steps along the tail per cell of its length
+const TAIL_STEPS: f32 = 2.0;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Comet {
+    pub x: f32,
+    pub y: f32,
+    /// Velocity in cells per tick
+    vx: f32,
+    vy: f32,
+    age: f32,
+    duration: f32,
+    /// How long the tail grows, in rows
+    length: f32,
+    /// How far the tail bends back along the comet's path at its tip, as a
+    /// fraction of its length
+    curve: f32,
+    /// The sky's cell aspect: a tail reaches this many times as many
+    /// columns as rows for the same length
+    #[cfg_attr(feature = "serde", serde(default = "crate::simulation::sky_model::default_aspect"))]
+    aspect: f32,
+}
+
+impl Comet {
+    /// A comet somewhere in the middle of the sky, staying three to six
+    /// minutes.
+    pub fn new<R: Rng>(rng: &mut R, sky: &SkyModel) -> Self {
+        let x = rng.gen_range(0.0..sky.width.max(1) as f32);
+        let y = rng.gen_range(0.35..0.7) * sky.horizon_y.max(1) as f32;
+        let duration = rng.gen_range(180.0..360.0) * TICKS_PER_SECOND as f32;
+        // Drifting a handful of cells over its whole stay
+        let distance = rng.gen_range(6.0..16.0);
+        let (sin, cos) = rng.gen_range(0.0..std::f32::consts::TAU).sin_cos();
+        Comet {
+            x,
+            y,
+            vx: distance * cos / duration,
+            vy: distance * sin / duration / sky.aspect,
+            age: 0.0,
+            duration,
+            length: rng.gen_range(5.0..10.0),
+            curve: rng.gen_range(0.15..0.4),
+            aspect: sky.aspect,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.x += self.vx * dt;
+        self.y += self.vy * dt;
+        self.age += dt;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.age >= self.duration
+    }
+
+    /// Brightens over the first quarter and fades over the last.
+    pub fn strength(&self) -> f32 {
+        let t = self.age / self.duration;
+        let envelope = (t * 4.0).min((1.0 - t) * 4.0).clamp(0.0, 1.0);
+        envelope * envelope * (3.0 - 2.0 * envelope)
+    }
+
+    /// Points along the tail from the head out, each with how far along
+    /// the tail it is from 0 to 1. The tail straightens away from the sun
+    /// and bends back along the path the comet came by.
+    pub fn tail(&self) -> impl Iterator<Item = (f32, f32, f32)> + '_ {
+        // The tail grows as the comet brightens
+        let length = self.length * self.strength().sqrt();
+        let speed = self.vx.hypot(self.vy).max(f32::EPSILON);
+        let behind = (-self.vx / speed, -self.vy / speed);
+        let steps = (length * TAIL_STEPS) as usize;
+        (1..=steps).map(move |i| {
+            let s = i as f32 / TAIL_STEPS;
+            let t = s / length;
+            let bend = self.curve * length * t * t;
+            let dx = -SUN.0 * s + behind.0 * bend;
+            let dy = -SUN.1 * s + behind.1 * bend;
+            (self.x + dx * self.aspect, self.y + dy, t)
+        })
+    }
+}
@@ -0,0 +1,490 @@
+//! Real constellations, set among the random stars so the sky has familiar
+//! shapes in it.
+//!
+//! Each constellation's stars are given as offsets from its centre in rows,
+//! across and down, with north up. Cells are narrower than they are tall,
+//! so offsets across are stretched by the sky's aspect when placed.
+
+use crate::simulation::sky_model::SkyModel;
+use crate::simulation::spectral::SpectralClass::{self, *};
+use crate::simulation::Star;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// Figures are drawn for a sky this many rows tall, and scaled to others
+const REFERENCE_HEIGHT: f32 = 24.0;
+// Sky area in cells per constellation shown, so a bigger sky shows more
+const AREA_PER_FIGURE: usize = 800;
+const MAX_FIGURES: usize = 6;
+// Spots tried for each constellation before giving up on it
+const ATTEMPTS: usize = 12;
+// The signs of the zodiac, by the constellations the sun passes through
+const ZODIAC: [(&str, &str); 12] = [
+    ("Aries", "♈"),
+    ("Taurus", "♉"),
+    ("Gemini", "♊"),
+    ("Cancer", "♋"),
+    ("Leo", "♌"),
+    ("Virgo", "♍"),
+    ("Libra", "♎"),
+    ("Scorpius", "♏"),
+    ("Sagittarius", "♐"),
+    ("Capricornus", "♑"),
+    ("Aquarius", "♒"),
+    ("Pisces", "♓"),
+];
+
+pub struct Constellation {
+    pub name: &'static str,
+    /// Offsets, magnitudes and spectral classes of the member stars
+    pub stars: &'static [(f32, f32, f32, SpectralClass)],
+    /// Pairs of indices into `stars` joined in the stick figure
+    pub lines: &'static [(usize, usize)],
+}
+
+impl Constellation {
+    /// The glyph of its zodiac sign, if it's one of the constellations of
+    /// the zodiac.
+    pub fn zodiac_sign(&self) -> Option<&'static str> {
+        ZODIAC.iter().find(|&&(name, _)| name == self.name).map(|&(_, sign)| sign)
+    }
+}
+
+pub const CONSTELLATIONS: &[Constellation] = &[
+    Constellation {
+        name: "Orion",
+        stars: &[
+            (-5.0, -4.0, 0.5, M), // Betelgeuse
+            (4.0, -3.5, 1.6, B),  // Bellatrix
+            (-1.5, 0.3, 1.8, O),  // Alnitak
+            (0.0, 0.0, 1.7, B),   // Alnilam
+            (1.5, -0.3, 2.2, O),  // Mintaka
+            (-4.0, 4.5, 2.1, B),  // Saiph
+            (5.0, 4.0, 0.1, B),   // Rigel
+            (0.0, -6.0, 3.4, O),  // Meissa
+        ],
+        lines: &[(0, 2), (1, 4), (2, 3), (3, 4), (2, 5), (4, 6), (0, 7), (1, 7)],
+    },
+    Constellation {
+        name: "Ursa Major",
+        stars: &[
+            (-5.0, -1.0, 1.9, B), // Alkaid
+            (-3.0, -0.8, 2.2, A), // Mizar
+            (-1.5, -0.3, 1.8, A), // Alioth
+            (0.0, 0.3, 3.3, A),   // Megrez
+            (0.5, 1.8, 2.4, A),   // Phecda
+            (3.5, 1.6, 2.4, A),   // Merak
+            (3.8, -0.2, 1.8, K),  // Dubhe
+        ],
+        lines: &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6), (6, 3)],
+    },
+    Constellation {
+        name: "Ursa Minor",
+        stars: &[
+            (-4.5, -0.5, 2.0, F), // Polaris
+            (-3.0, -0.2, 4.4, A), // Yildun
+            (-1.5, 0.3, 4.2, G),
+            (0.0, 0.5, 4.3, A),
+            (1.0, 1.5, 5.0, F),
+            (2.5, 1.2, 3.0, A), // Pherkad
+            (2.0, 0.0, 2.1, K), // Kochab
+        ],
+        lines: &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6), (6, 3)],
+    },
+    Constellation {
+        name: "Cassiopeia",
+        stars: &[
+            (-4.0, -0.8, 2.3, F), // Caph
+            (-2.0, 0.8, 2.2, K),  // Schedar
+            (0.0, -0.3, 2.5, B),
+            (2.0, 0.8, 2.7, A), // Ruchbah
+            (4.0, -0.8, 3.4, B),
+        ],
+        lines: &[(0, 1), (1, 2), (2, 3), (3, 4)],
+    },
+    Constellation {
+        name: "Cygnus",
+        stars: &[
+            (0.0, -5.0, 1.3, A), // Deneb
+            (0.0, -1.0, 2.2, F), // Sadr
+            (0.0, 5.5, 3.1, K),  // Albireo
+            (0.0, 2.5, 3.9, K),
+            (-3.0, -0.5, 2.5, K), // Gienah
+            (2.5, -2.0, 2.9, B),
+            (-4.5, 1.0, 3.2, G),
+            (4.0, -3.0, 3.8, A),
+        ],
+        lines: &[(0, 1), (1, 3), (3, 2), (1, 4), (4, 6), (1, 5), (5, 7)],
+    },
+    Constellation {
+        name: "Lyra",
+        stars: &[
+            (0.0, -2.5, 0.0, A), // Vega
+            (1.5, -3.0, 4.7, A),
+            (1.0, -1.2, 4.4, A),
+            (1.8, 1.2, 4.3, M),
+            (0.2, 1.6, 3.2, B), // Sulafat
+            (-0.6, -0.4, 3.5, B), // Sheliak
+        ],
+        lines: &[(0, 1), (0, 2), (2, 3), (3, 4), (4, 5), (5, 2)],
+    },
+    Constellation {
+        name: "Aquila",
+        stars: &[
+            (0.0, 0.0, 0.8, A),   // Altair
+            (-0.8, -1.0, 2.7, K), // Tarazed
+            (0.8, 1.0, 3.7, G),   // Alshain
+            (-4.0, -3.0, 3.0, A),
+            (-2.0, 2.0, 3.4, B),
+            (-4.0, 5.0, 3.4, B),
+            (4.0, 3.0, 3.2, F),
+        ],
+        lines: &[(3, 1), (1, 0), (0, 2), (2, 6), (0, 4), (4, 5)],
+    },
+    Constellation {
+        name: "Scorpius",
+        stars: &[
+            (0.0, 0.0, 1.0, M), // Antares
+            (-1.5, -1.0, 2.9, B),
+            (1.0, 1.0, 2.8, B),
+            (-4.0, -2.0, 2.3, B), // Dschubba
+            (-3.5, -4.0, 2.6, B), // Acrab
+            (-5.0, 0.0, 2.9, B),
+            (2.0, 3.0, 2.3, K),
+            (2.5, 5.0, 3.0, B),
+            (2.0, 7.0, 3.6, K),
+            (1.0, 8.5, 3.3, F),
+            (-1.0, 9.5, 1.9, F), // Sargas
+            (-4.0, 9.5, 3.0, F),
+            (-5.0, 8.5, 2.4, B),
+            (-6.0, 7.5, 1.6, B), // Shaula
+        ],
+        lines: &[
+            (4, 3),
+            (3, 5),
+            (3, 1),
+            (1, 0),
+            (0, 2),
+            (2, 6),
+            (6, 7),
+            (7, 8),
+            (8, 9),
+            (9, 10),
+            (10, 11),
+            (11, 12),
+            (12, 13),
+        ],
+    },
+    Constellation {
+        name: "Leo",
+        stars: &[
+            (2.5, 3.0, 1.4, B), // Regulus
+            (2.5, 0.5, 3.5, A),
+            (2.0, -1.5, 2.0, K), // Algieba
+            (2.5, -3.5, 3.4, F),
+            (3.5, -4.5, 3.9, K),
+            (4.5, -3.5, 3.0, G),
+            (-1.5, -1.0, 2.6, A), // Zosma
+            (-1.5, 1.5, 3.3, A),  // Chertan
+            (-4.5, 1.0, 2.1, A),  // Denebola
+        ],
+        lines: &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (2, 6), (6, 8), (8, 7), (7, 0)],
+    },
+    Constellation {
+        name: "Gemini",
+        stars: &[
+            (-2.0, -5.0, 1.6, A), // Castor
+            (1.5, -4.5, 1.1, K),  // Pollux
+            (-2.5, -2.0, 4.4, K),
+            (-4.0, 1.0, 3.0, G), // Mebsuta
+            (-5.5, 4.0, 2.9, M), // Tejat
+            (1.0, -0.5, 3.5, F), // Wasat
+            (-0.5, 2.5, 3.8, G), // Mekbuda
+            (-2.0, 5.5, 1.9, A), // Alhena
+        ],
+        lines: &[(0, 1), (0, 2), (2, 3), (3, 4), (1, 5), (5, 6), (6, 7)],
+    },
+    Constellation {
+        name: "Taurus",
+        stars: &[
+            (-1.5, -1.0, 0.9, K), // Aldebaran
+            (0.0, 0.0, 3.4, K),
+            (1.5, 1.0, 3.6, K),
+            (0.5, -1.5, 3.8, K),
+            (-0.5, -2.5, 3.5, A),
+            (-6.0, -5.0, 1.7, B), // Elnath
+            (-6.5, -1.5, 3.0, B),
+        ],
+        lines: &[(2, 1), (1, 0), (0, 6), (2, 3), (3, 4), (4, 5)],
+    },
+    Constellation {
+        name: "Canis Major",
+        stars: &[
+            (0.0, 0.0, -1.5, A), // Sirius
+            (3.0, -0.5, 2.0, B), // Mirzam
+            (-1.5, -2.5, 4.4, K),
+            (-2.0, 4.0, 1.8, F), // Wezen
+            (1.0, 6.0, 1.5, B),  // Adhara
+            (-5.0, 5.0, 2.4, B), // Aludra
+        ],
+        lines: &[(1, 0), (0, 2), (0, 3), (3, 4), (3, 5)],
+    },
+    Constellation {
+        name: "Sagittarius",
+        stars: &[
+            (0.0, 2.0, 1.8, B),  // Kaus Australis
+            (0.0, 0.0, 2.7, K),  // Kaus Media
+            (1.0, -2.0, 2.8, K), // Kaus Borealis
+            (-3.0, -0.5, 3.2, B),
+            (-6.0, -1.5, 2.0, B), // Nunki
+            (-7.0, 1.0, 3.3, K),
+            (-4.0, 2.0, 2.6, A), // Ascella
+            (3.0, 0.5, 3.0, K),  // Alnasl
+        ],
+        lines: &[
+            (7, 1),
+            (7, 0),
+            (0, 6),
+            (6, 3),
+            (3, 1),
+            (1, 0),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+        ],
+    },
+    Constellation {
+        name: "Crux",
+        stars: &[
+            (0.0, 3.0, 0.8, B),  // Acrux
+            (0.0, -3.0, 1.6, M), // Gacrux
+            (2.5, -0.5, 1.3, B), // Mimosa
+            (-2.5, -1.0, 2.8, B),
+        ],
+        lines: &[(0, 1), (2, 3)],
+    },
+    Constellation {
+        name: "Pegasus",
+        stars: &[
+            (1.5, 2.0, 2.5, B),   // Markab
+            (1.5, -3.0, 2.4, M),  // Scheat
+            (-2.0, -3.0, 2.1, B), // Alpheratz
+            (-2.0, 2.0, 2.8, B),  // Algenib
+            (3.5, 3.0, 3.4, B),
+            (6.0, 4.0, 2.4, K), // Enif
+            (3.5, -5.0, 2.9, G),
+        ],
+        lines: &[(0, 1), (1, 2), (2, 3), (3, 0), (0, 4), (4, 5), (1, 6)],
+    },
+    Constellation {
+        name: "Andromeda",
+        stars: &[
+            (4.0, 1.0, 2.1, B), // Alpheratz
+            (1.0, 0.0, 3.3, K),
+            (-2.0, -1.0, 2.1, M), // Mirach
+            (-6.0, -2.5, 2.2, K), // Almach
+            (-1.5, -2.5, 3.9, A),
+            (-1.0, -4.5, 4.5, B),
+        ],
+        lines: &[(0, 1), (1, 2), (2, 3), (2, 4), (4, 5)],
+    },
+    Constellation {
+        name: "Perseus",
+        stars: &[
+            (0.0, 0.0, 1.8, F), // Mirfak
+            (2.0, 3.0, 2.1, B), // Algol
+            (-2.0, 1.5, 3.0, B),
+            (-3.0, 4.0, 2.9, B),
+            (1.0, -2.0, 2.9, G),
+            (2.0, -4.0, 3.8, K),
+            (-5.0, 5.0, 2.9, B),
+            (3.0, 4.5, 3.4, M),
+        ],
+        lines: &[(5, 4), (4, 0), (0, 2), (2, 3), (3, 6), (0, 1), (1, 7)],
+    },
+    Constellation {
+        name: "Boötes",
+        stars: &[
+            (0.0, 4.0, -0.1, K), // Arcturus
+            (2.0, 0.0, 2.4, K),  // Izar
+            (2.5, -3.0, 3.5, G),
+            (0.5, -5.0, 3.5, G), // Nekkar
+            (-1.5, -3.5, 3.0, A),
+            (-1.0, 0.0, 3.6, K),
+            (-2.0, 4.5, 2.7, G), // Muphrid
+        ],
+        lines: &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0), (0, 6)],
+    },
+    Constellation {
+        name: "Corona Borealis",
+        stars: &[
+            (4.0, -1.0, 4.1, A),
+            (2.5, 0.5, 3.7, B),
+            (0.0, 1.2, 2.2, A), // Alphecca
+            (-2.0, 1.0, 3.8, A),
+            (-3.5, 0.0, 4.6, G),
+            (-4.5, -1.5, 4.1, K),
+        ],
+        lines: &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5)],
+    },
+    Constellation {
+        name: "Auriga",
+        stars: &[
+            (0.0, -3.0, 0.1, G), // Capella
+            (4.0, -2.0, 1.9, A), // Menkalinan
+            (4.0, 2.0, 2.6, A),
+            (1.0, 4.0, 1.7, B), // Elnath
+            (-3.0, 2.0, 2.7, K),
+            (-2.0, -2.0, 3.0, F),
+            (-2.0, -0.5, 3.2, B),
+        ],
+        lines: &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (0, 5), (5, 6)],
+    },
+    Constellation {
+        name: "Draco",
+        stars: &[
+            (-4.0, 1.0, 2.2, K),  // Eltanin
+            (-4.0, -1.0, 2.8, G), // Rastaban
+            (-5.0, -0.5, 4.9, A),
+            (-5.0, 1.5, 3.8, K),
+            (-2.5, 3.0, 3.1, G),
+            (-0.5, 2.0, 3.2, B),
+            (0.5, -0.5, 2.7, G),
+            (2.0, -1.0, 4.0, A),
+            (3.5, -2.5, 3.3, K),
+            (4.5, 0.0, 3.7, A), // Thuban
+            (5.5, -3.0, 3.9, B),
+            (6.0, -5.0, 3.8, M),
+        ],
+        lines: &[
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 0),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (6, 7),
+            (7, 8),
+            (8, 9),
+            (9, 10),
+            (10, 11),
+        ],
+    },
+    Constellation {
+        name: "Virgo",
+        stars: &[
+            (0.0, 4.0, 1.0, B), // Spica
+            (-1.0, 1.0, 4.4, A),
+            (2.0, 0.0, 2.7, F), // Porrima
+            (0.0, -2.0, 3.4, M),
+            (-2.0, -4.0, 2.8, G), // Vindemiatrix
+            (5.0, 1.0, 3.9, F),
+            (8.0, 0.0, 3.6, F),
+            (-3.0, 1.5, 3.4, A),
+        ],
+        lines: &[(6, 5), (5, 2), (2, 1), (1, 0), (2, 3), (3, 4), (1, 7)],
+    },
+    Constellation {
+        name: "Hercules",
+        stars: &[
+            (-1.0, -1.0, 3.2, G),
+            (1.5, -1.5, 3.5, K),
+            (1.0, 1.0, 2.8, F),
+            (-1.5, 1.0, 3.9, A),
+            (3.0, 4.0, 2.8, G), // Kornephoros
+            (-3.0, 4.0, 3.1, A),
+            (-2.0, -5.0, 3.8, K),
+            (2.0, -4.5, 4.2, B),
+        ],
+        lines: &[(0, 1), (1, 2), (2, 3), (3, 0), (2, 4), (3, 5), (0, 6), (1, 7)],
+    },
+];
+
+/// A constellation set into the sky.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Figure {
+    #[cfg_attr(feature = "serde", serde(with = "crate::saved::by_name"))]
+    pub constellation: &'static Constellation,
+    /// Where each of its stars went in the sky's stars
+    pub stars: Vec<usize>,
+}
+
+/// A few constellations, more for a bigger sky, scattered so they don't
+/// overlap. Their stars are added to `stars`.
+pub fn place<R: Rng>(rng: &mut R, sky: &SkyModel, stars: &mut Vec<Star>) -> Vec<Figure> {
+    let height = sky.horizon_y as f32;
+    let span = sky.width.max(1) as f32;
+    let scale = (height / REFERENCE_HEIGHT).clamp(0.5, 1.5);
+    let count = (sky.width as usize * sky.horizon_y as usize / AREA_PER_FIGURE).clamp(1, MAX_FIGURES);
+
+    // Bounds already taken, as (centre x, half width, top, bottom)
+    let mut taken: Vec<(f32, f32, f32, f32)> = Vec::new();
+    let mut figures = Vec::new();
+    for constellation in CONSTELLATIONS.choose_multiple(rng, count) {
+        // The figure's extent around its centre, in cells
+        let (mut left, mut right, mut top, mut bottom) = (0.0f32, 0.0f32, 0.0f32, 0.0f32);
+        for &(dx, dy, _, _) in constellation.stars {
+            left = left.min(dx * sky.aspect * scale);
+            right = right.max(dx * sky.aspect * scale);
+            top = top.min(dy * scale);
+            bottom = bottom.max(dy * scale);
+        }
+        let half_width = left.abs().max(right);
+        let spot = (0..ATTEMPTS).find_map(|_| {
+            let (x, y, _) = sky.place(rng)?;
+            let fits = y + top >= 0.0 && y + bottom < height;
+            let clear = taken.iter().all(|&(tx, thw, ttop, tbottom)| {
+                let apart = (x - tx).rem_euclid(span).min((tx - x).rem_euclid(span));
+                apart > half_width + thw || y + bottom < ttop || y + top > tbottom
+            });
+            (fits && clear).then_some((x, y))
+        });
+        let Some((x, y)) = spot else {
+            continue;
+        };
+        taken.push((x, half_width, y + top, y + bottom));
+
+        let members = constellation
+            .stars
+            .iter()
+            .map(|&(dx, dy, magnitude, class)| {
+                let y = y + dy * scale;
+                stars.push(Star {
+                    x: (x + dx * sky.aspect * scale).rem_euclid(span),
+                    y,
+                    altitude: sky.altitude_at(y),
+                    magnitude,
+                    brightness: (6.5 - magnitude).round().clamp(1.0, 5.0) as u8,
+                    class,
+                    twinkle_speed: rng.gen_range(0.1..0.5),
+                    cluster: None,
+                    presence: 1.0,
+                    variability: None,
+                    companion: None,
+                });
+                stars.len() - 1
+            })
+            .collect();
+        figures.push(Figure {
+            constellation,
+            stars: members,
+        });
+    }
+    figures
+}
+
+#[cfg(feature = "serde")]
+impl crate::saved::Named for Constellation {
+    const KIND: &'static str = "constellation";
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn all() -> &'static [Self] {
+        CONSTELLATIONS
+    }
+}
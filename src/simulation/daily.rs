@@ -0,0 +1,105 @@
+//! The sky of the day: the same sky for everyone on a given UTC date, with
+//! the same rare events at the same moments, so there's only one comet of
+//! the day to have caught or missed.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+// Mixed into the day's seed for its schedule, so the schedule isn't drawn
+// from the same stream as the sky itself
+const SCHEDULE_SALT: u64 = 0x6e6b_6461_696c_7900;
+// How many of each kind of event a day brings, spread over its 24 hours
+const APPOINTMENTS: [(Appearance, usize); 4] = [
+    (Appearance::Comet, 1),
+    (Appearance::Aurora, 1),
+    (Appearance::StarlinkTrain, 3),
+    (Appearance::Fireball, 6),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Appearance {
+    Comet,
+    Aurora,
+    StarlinkTrain,
+    Fireball,
+}
+
+/// One of the day's events.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Appointment {
+    pub appearance: Appearance,
+    /// When it's due, as a Julian day
+    pub jd: f64,
+    /// Where it appears and how it looks are drawn from this, so it's the
+    /// same for everyone
+    pub seed: u64,
+}
+
+/// The UTC date containing Julian day `jd`, as a day count.
+pub fn day(jd: f64) -> i64 {
+    (jd + 0.5).floor() as i64
+}
+
+/// The seed of the sky for the UTC date containing Julian day `jd`.
+pub fn seed(jd: f64) -> u64 {
+    day(jd) as u64
+}
+
+/// The appointments for `day`, earliest first.
+pub fn schedule(day: i64) -> Vec<Appointment> {
+    let mut rng = ChaCha12Rng::seed_from_u64(day as u64 ^ SCHEDULE_SALT);
+    let midnight = day as f64 - 0.5;
+    let mut appointments: Vec<Appointment> = APPOINTMENTS
+        .iter()
+        .flat_map(|&(appearance, count)| std::iter::repeat(appearance).take(count))
+        .map(|appearance| Appointment {
+            appearance,
+            jd: midnight + rng.gen::<f64>(),
+            seed: rng.gen(),
+        })
+        .collect();
+    appointments.sort_by(|a, b| a.jd.total_cmp(&b.jd));
+    appointments
+}
+
+/// Keeps a sky to the day's schedule as its clock runs, going on to the
+/// next day's at midnight.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Daily {
+    /// Julian day when the sky started
+    start: f64,
+    day: i64,
+    appointments: Vec<Appointment>,
+    /// Index of the first appointment not yet kept
+    next: usize,
+}
+
+impl Daily {
+    /// Follow the schedule from Julian day `start`, skipping what's
+    /// already past.
+    pub fn new(start: f64) -> Self {
+        let appointments = schedule(day(start));
+        let next = appointments.iter().take_while(|a| a.jd <= start).count();
+        Daily {
+            start,
+            day: day(start),
+            appointments,
+            next,
+        }
+    }
+
+    /// The next appointment due by `seconds` into the sky, if any.
+    pub fn next_due(&mut self, seconds: f32) -> Option<Appointment> {
+        let now = self.start + seconds as f64 / 86_400.0;
+        if self.next == self.appointments.len() && day(now) > self.day {
+            self.day += 1;
+            self.appointments = schedule(self.day);
+            self.next = 0;
+        }
+        let appointment = *self.appointments.get(self.next).filter(|a| a.jd <= now)?;
+        self.next += 1;
+        Some(appointment)
+    }
+}
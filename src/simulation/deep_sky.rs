@@ -0,0 +1,120 @@
+//! The showpiece Messier objects: galaxies, nebulae and star clusters bright
+//! or big enough to find from a dark site, where they stand in a catalog
+//! sky. Positions are J2000, like the star catalog's.
+
+use crate::simulation::projection::Observer;
+use crate::simulation::sky_model::SkyModel;
+use DeepSkyKind::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeepSkyKind {
+    Galaxy,
+    Nebula,
+    PlanetaryNebula,
+    OpenCluster,
+    GlobularCluster,
+}
+
+impl DeepSkyKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            DeepSkyKind::Galaxy => "galaxy",
+            DeepSkyKind::Nebula => "nebula",
+            DeepSkyKind::PlanetaryNebula => "planetary nebula",
+            DeepSkyKind::OpenCluster => "open cluster",
+            DeepSkyKind::GlobularCluster => "globular cluster",
+        }
+    }
+}
+
+pub struct DeepSkyObject {
+    pub name: &'static str,
+    /// Its number in Messier's catalog
+    pub messier: u8,
+    pub kind: DeepSkyKind,
+    /// Right ascension in hours
+    pub ra: f64,
+    /// Declination in degrees
+    pub dec: f64,
+    pub magnitude: f32,
+    /// Apparent size across, in arcminutes
+    pub size: f32,
+    pub constellation: &'static str,
+    /// In light years
+    pub distance: f32,
+}
+
+pub const MESSIER: &[DeepSkyObject] = &[
+    DeepSkyObject { name: "Crab Nebula", messier: 1, kind: Nebula, ra: 5.575, dec: 22.01, magnitude: 8.4, size: 7.0, constellation: "Taurus", distance: 6_500.0 },
+    DeepSkyObject { name: "Messier 3", messier: 3, kind: GlobularCluster, ra: 13.703, dec: 28.38, magnitude: 6.2, size: 18.0, constellation: "Canes Venatici", distance: 33_900.0 },
+    DeepSkyObject { name: "Messier 4", messier: 4, kind: GlobularCluster, ra: 16.393, dec: -26.53, magnitude: 5.6, size: 36.0, constellation: "Scorpius", distance: 7_200.0 },
+    DeepSkyObject { name: "Ptolemy Cluster", messier: 7, kind: OpenCluster, ra: 17.898, dec: -34.82, magnitude: 3.3, size: 80.0, constellation: "Scorpius", distance: 980.0 },
+    DeepSkyObject { name: "Lagoon Nebula", messier: 8, kind: Nebula, ra: 18.063, dec: -24.38, magnitude: 6.0, size: 90.0, constellation: "Sagittarius", distance: 4_100.0 },
+    DeepSkyObject { name: "Wild Duck Cluster", messier: 11, kind: OpenCluster, ra: 18.851, dec: -6.27, magnitude: 6.3, size: 14.0, constellation: "Scutum", distance: 6_200.0 },
+    DeepSkyObject { name: "Great Globular Cluster in Hercules", messier: 13, kind: GlobularCluster, ra: 16.695, dec: 36.46, magnitude: 5.8, size: 20.0, constellation: "Hercules", distance: 22_200.0 },
+    DeepSkyObject { name: "Eagle Nebula", messier: 16, kind: Nebula, ra: 18.313, dec: -13.78, magnitude: 6.0, size: 35.0, constellation: "Serpens", distance: 7_000.0 },
+    DeepSkyObject { name: "Omega Nebula", messier: 17, kind: Nebula, ra: 18.346, dec: -16.18, magnitude: 6.0, size: 11.0, constellation: "Sagittarius", distance: 5_500.0 },
+    DeepSkyObject { name: "Trifid Nebula", messier: 20, kind: Nebula, ra: 18.045, dec: -23.03, magnitude: 6.3, size: 28.0, constellation: "Sagittarius", distance: 5_200.0 },
+    DeepSkyObject { name: "Messier 22", messier: 22, kind: GlobularCluster, ra: 18.607, dec: -23.90, magnitude: 5.1, size: 32.0, constellation: "Sagittarius", distance: 10_600.0 },
+    DeepSkyObject { name: "Dumbbell Nebula", messier: 27, kind: PlanetaryNebula, ra: 19.993, dec: 22.72, magnitude: 7.5, size: 8.0, constellation: "Vulpecula", distance: 1_360.0 },
+    DeepSkyObject { name: "Andromeda Galaxy", messier: 31, kind: Galaxy, ra: 0.712, dec: 41.27, magnitude: 3.4, size: 190.0, constellation: "Andromeda", distance: 2_500_000.0 },
+    DeepSkyObject { name: "Triangulum Galaxy", messier: 33, kind: Galaxy, ra: 1.564, dec: 30.66, magnitude: 5.7, size: 70.0, constellation: "Triangulum", distance: 2_730_000.0 },
+    DeepSkyObject { name: "Messier 35", messier: 35, kind: OpenCluster, ra: 6.149, dec: 24.33, magnitude: 5.3, size: 28.0, constellation: "Gemini", distance: 2_800.0 },
+    DeepSkyObject { name: "Orion Nebula", messier: 42, kind: Nebula, ra: 5.588, dec: -5.39, magnitude: 4.0, size: 85.0, constellation: "Orion", distance: 1_344.0 },
+    DeepSkyObject { name: "Beehive Cluster", messier: 44, kind: OpenCluster, ra: 8.667, dec: 19.67, magnitude: 3.7, size: 95.0, constellation: "Cancer", distance: 577.0 },
+    DeepSkyObject { name: "Pleiades", messier: 45, kind: OpenCluster, ra: 3.790, dec: 24.12, magnitude: 1.6, size: 110.0, constellation: "Taurus", distance: 444.0 },
+    DeepSkyObject { name: "Whirlpool Galaxy", messier: 51, kind: Galaxy, ra: 13.498, dec: 47.20, magnitude: 8.4, size: 11.0, constellation: "Canes Venatici", distance: 23_000_000.0 },
+    DeepSkyObject { name: "Ring Nebula", messier: 57, kind: PlanetaryNebula, ra: 18.893, dec: 33.03, magnitude: 8.8, size: 1.4, constellation: "Lyra", distance: 2_300.0 },
+    DeepSkyObject { name: "Bode's Galaxy", messier: 81, kind: Galaxy, ra: 9.926, dec: 69.07, magnitude: 6.9, size: 27.0, constellation: "Ursa Major", distance: 11_800_000.0 },
+    DeepSkyObject { name: "Cigar Galaxy", messier: 82, kind: Galaxy, ra: 9.931, dec: 69.68, magnitude: 8.4, size: 11.0, constellation: "Ursa Major", distance: 11_500_000.0 },
+    DeepSkyObject { name: "Pinwheel Galaxy", messier: 101, kind: Galaxy, ra: 14.053, dec: 54.35, magnitude: 7.9, size: 29.0, constellation: "Ursa Major", distance: 20_900_000.0 },
+    DeepSkyObject { name: "Sombrero Galaxy", messier: 104, kind: Galaxy, ra: 12.667, dec: -11.62, magnitude: 8.0, size: 9.0, constellation: "Virgo", distance: 31_100_000.0 },
+];
+
+/// A deep-sky object where it stands in the sky.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SkyObject {
+    #[cfg_attr(feature = "serde", serde(with = "crate::saved::by_name"))]
+    pub object: &'static DeepSkyObject,
+    pub x: f32,
+    pub y: f32,
+    /// Degrees above the horizon; negative while it's set
+    pub altitude: f32,
+}
+
+/// Every object where it stands at Julian day `jd`, set ones included.
+pub fn place(sky: &SkyModel, observer: &Observer, jd: f64) -> Vec<SkyObject> {
+    let mut objects: Vec<SkyObject> = MESSIER
+        .iter()
+        .map(|object| SkyObject {
+            object,
+            x: 0.0,
+            y: 0.0,
+            altitude: 0.0,
+        })
+        .collect();
+    follow(&mut objects, sky, observer, jd);
+    objects
+}
+
+/// Move `objects` to where they stand at Julian day `jd`.
+pub fn follow(objects: &mut [SkyObject], sky: &SkyModel, observer: &Observer, jd: f64) {
+    for sky_object in objects {
+        let (altitude, azimuth) = observer.horizontal(sky_object.object.ra, sky_object.object.dec, jd);
+        (sky_object.x, sky_object.y) = observer.cell(sky, altitude, azimuth);
+        sky_object.altitude = altitude as f32;
+    }
+}
+
+#[cfg(feature = "serde")]
+impl crate::saved::Named for DeepSkyObject {
+    const KIND: &'static str = "deep-sky object";
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn all() -> &'static [Self] {
+        MESSIER
+    }
+}
@@ -0,0 +1,178 @@
+//! Where stars are concentrated across the sky.
+
+use crate::simulation::sky_model::SkyModel;
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+// Half-width of the Milky Way band as a fraction of the sky
+const BAND_WIDTH: f32 = 0.12;
+// Altitude in degrees over which the horizon glow washes stars out
+const HORIZON_FALLOFF: f32 = 20.0;
+// How far from the band, as a fraction of the sky, stars thin out towards
+// its poles
+const POLE_WIDTH: f32 = 0.35;
+
+/// How strongly each feature shapes the star density, written like
+/// `band=1.5,horizon=0.6,poles=0.5`. All zero is a uniform sky.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DensityMap {
+    /// Extra density at the centre of the Milky Way band, as a multiple of
+    /// the background
+    pub band: f32,
+    /// Fraction of stars lost right at the horizon, `0.0..=1.0`
+    pub horizon: f32,
+    /// Fraction of stars lost far from the band, out at its poles,
+    /// `0.0..=1.0`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub poles: f32,
+}
+
+impl DensityMap {
+    /// The same density all over the sky.
+    pub const UNIFORM: DensityMap = DensityMap {
+        band: 0.0,
+        horizon: 0.0,
+        poles: 0.0,
+    };
+
+    /// Crowded along the Milky Way, thinning towards its poles and washed
+    /// out near the horizon, the way the real sky looks.
+    pub const MILKY_WAY: DensityMap = DensityMap {
+        band: 1.5,
+        horizon: 0.6,
+        poles: 0.5,
+    };
+
+    pub fn is_uniform(&self) -> bool {
+        self.band == 0.0 && self.horizon == 0.0 && self.poles == 0.0
+    }
+}
+
+impl Default for DensityMap {
+    fn default() -> Self {
+        DensityMap::MILKY_WAY
+    }
+}
+
+impl fmt::Display for DensityMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "band={},horizon={},poles={}", self.band, self.horizon, self.poles)
+    }
+}
+
+impl FromStr for DensityMap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uniform" => return Ok(DensityMap::UNIFORM),
+            "milky-way" => return Ok(DensityMap::MILKY_WAY),
+            _ => {}
+        }
+        let mut map = DensityMap::UNIFORM;
+        for part in s.split(',') {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value in density, got '{}'", part))?;
+            let value: f32 = value
+                .parse()
+                .ok()
+                .filter(|v: &f32| v.is_finite() && *v >= 0.0)
+                .ok_or_else(|| format!("invalid density value '{}'", value))?;
+            match key {
+                "band" => map.band = value,
+                "horizon" => map.horizon = value.min(1.0),
+                "poles" => map.poles = value.min(1.0),
+                other => return Err(format!("unknown density feature '{}'", other)),
+            }
+        }
+        Ok(map)
+    }
+}
+
+/// Where the Milky Way crosses a random sky: a straight band at a tilt.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Band {
+    /// Point the band passes through, in fractions of the sky
+    origin: (f32, f32),
+    /// Unit normal to the band
+    normal: (f32, f32),
+}
+
+impl Band {
+    pub fn new<R: Rng>(rng: &mut R) -> Self {
+        let tilt = rng.gen_range(-1.0..1.0f32);
+        Band {
+            origin: (rng.gen_range(0.3..0.7), 0.5),
+            normal: (tilt.cos(), -tilt.sin()),
+        }
+    }
+
+    /// How far into the band `(x, y)` lies, from 1 along its middle falling
+    /// away to 0 outside it.
+    pub fn strength(&self, sky: &SkyModel, x: f32, y: f32) -> f32 {
+        (-(self.distance(sky, x, y) / BAND_WIDTH).powi(2)).exp()
+    }
+
+    /// How near `(x, y)` lies to the band on the scale of the whole sky,
+    /// from 1 along it falling away to 0 out at its poles.
+    pub fn nearness(&self, sky: &SkyModel, x: f32, y: f32) -> f32 {
+        (-(self.distance(sky, x, y) / POLE_WIDTH).powi(2)).exp()
+    }
+
+    /// How far `(x, y)` lies from the middle of the band, in fractions of
+    /// the sky.
+    fn distance(&self, sky: &SkyModel, x: f32, y: f32) -> f32 {
+        let u = x / sky.width.max(1) as f32 - self.origin.0;
+        let v = y / sky.horizon_y.max(1) as f32 - self.origin.1;
+        u * self.normal.0 + v * self.normal.1
+    }
+}
+
+impl Default for Band {
+    /// Straight up through the middle of the sky.
+    fn default() -> Self {
+        Band {
+            origin: (0.5, 0.5),
+            normal: (1.0, 0.0),
+        }
+    }
+}
+
+/// A density map laid out over a particular sky.
+pub struct Density {
+    map: DensityMap,
+    band: Band,
+}
+
+impl Density {
+    pub fn new(map: DensityMap, band: Band) -> Self {
+        Density { map, band }
+    }
+
+    /// Relative density at `(x, y)` with the given `altitude`.
+    fn weight(&self, sky: &SkyModel, x: f32, y: f32, altitude: f32) -> f32 {
+        let band = 1.0 + self.map.band * self.band.strength(sky, x, y);
+        let glow = 1.0 - self.map.horizon * (-altitude / HORIZON_FALLOFF).exp();
+        let poles = 1.0 - self.map.poles * (1.0 - self.band.nearness(sky, x, y));
+        band * glow * poles
+    }
+
+    /// Like [`SkyModel::place_in_view`], but following the density map.
+    pub fn place<R: Rng>(&self, sky: &SkyModel, rng: &mut R, zoom: f32) -> Option<(f32, f32, f32)> {
+        if self.map.is_uniform() {
+            return sky.place_in_view(rng, zoom);
+        }
+        // Rejection sampling against the highest possible weight
+        let max = 1.0 + self.map.band;
+        loop {
+            let (x, y, altitude) = sky.place_in_view(rng, zoom)?;
+            if rng.gen::<f32>() * max < self.weight(sky, x, y, altitude) {
+                return Some((x, y, altitude));
+            }
+        }
+    }
+}
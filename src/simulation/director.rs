@@ -0,0 +1,167 @@
+//! Session director: paces spawns so the sky stays interesting without
+//! events piling up on top of each other.
+//!
+//! Every spawn decision goes through [`Director::roll`]. Rare spawns become
+//! gradually more likely the longer nothing interesting has happened, and
+//! each kind has a minimum gap so two of them never land back to back.
+
+use rand::Rng;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pacing {
+    /// Close to natural rates, with a gentle nudge during long lulls
+    #[default]
+    Realistic,
+    /// More frequent rare events, pushed harder after quiet spells and kept
+    /// further apart
+    Cinematic,
+}
+
+impl Pacing {
+    pub fn name(self) -> &'static str {
+        match self {
+            Pacing::Realistic => "realistic",
+            Pacing::Cinematic => "cinematic",
+        }
+    }
+}
+
+impl FromStr for Pacing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "realistic" => Ok(Pacing::Realistic),
+            "cinematic" => Ok(Pacing::Cinematic),
+            other => Err(format!("unknown pacing '{}'", other)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Spawn {
+    Meteor,
+    /// A meteor belonging to an active meteor shower
+    ShowerMeteor,
+    Satellite,
+    Shower,
+    Moonbow,
+    /// A thin cloud veil drifting in
+    Veil,
+    Aurora,
+    Comet,
+    StarlinkTrain,
+    Supernova,
+}
+
+impl Spawn {
+    const ALL: [Spawn; 10] = [
+        Spawn::Meteor,
+        Spawn::ShowerMeteor,
+        Spawn::Satellite,
+        Spawn::Shower,
+        Spawn::Moonbow,
+        Spawn::Veil,
+        Spawn::Aurora,
+        Spawn::Comet,
+        Spawn::StarlinkTrain,
+        Spawn::Supernova,
+    ];
+
+    /// Rare spawns are boosted after lulls and reset the lull when they happen.
+    fn is_rare(self) -> bool {
+        !matches!(self, Spawn::Meteor | Spawn::ShowerMeteor | Spawn::Veil)
+    }
+
+    /// Shortest allowed time between two of these, in seconds.
+    fn min_gap(self, pacing: Pacing) -> f32 {
+        match (self, pacing) {
+            (Spawn::Meteor | Spawn::ShowerMeteor, _) => 0.0,
+            (Spawn::Satellite, Pacing::Realistic) => 5.0,
+            (Spawn::Satellite, Pacing::Cinematic) => 20.0,
+            (Spawn::Shower, Pacing::Realistic) => 300.0,
+            (Spawn::Shower, Pacing::Cinematic) => 600.0,
+            (Spawn::Moonbow, Pacing::Realistic) => 1800.0,
+            (Spawn::Moonbow, Pacing::Cinematic) => 3600.0,
+            (Spawn::Veil, _) => 60.0,
+            (Spawn::Aurora, Pacing::Realistic) => 1200.0,
+            (Spawn::Aurora, Pacing::Cinematic) => 2400.0,
+            (Spawn::Comet, Pacing::Realistic) => 1800.0,
+            (Spawn::Comet, Pacing::Cinematic) => 3600.0,
+            (Spawn::StarlinkTrain, Pacing::Realistic) => 600.0,
+            (Spawn::StarlinkTrain, Pacing::Cinematic) => 1200.0,
+            (Spawn::Supernova, Pacing::Realistic) => 6.0 * 3600.0,
+            (Spawn::Supernova, Pacing::Cinematic) => 12.0 * 3600.0,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Director {
+    pacing: Pacing,
+    /// Seconds since anything interesting happened
+    quiet: f32,
+    /// Seconds since each spawn kind last fired, indexed like `Spawn::ALL`
+    since: [f32; Spawn::ALL.len()],
+}
+
+impl Director {
+    pub fn new(pacing: Pacing) -> Self {
+        Director {
+            pacing,
+            quiet: 0.0,
+            // Never fired. Finite, as some save formats have no infinity
+            since: [f32::MAX; Spawn::ALL.len()],
+        }
+    }
+
+    pub fn set_pacing(&mut self, pacing: Pacing) {
+        self.pacing = pacing;
+    }
+
+    fn index(spawn: Spawn) -> usize {
+        Spawn::ALL.iter().position(|s| *s == spawn).unwrap_or(0)
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        let seconds = dt / crate::simulation::TICKS_PER_SECOND as f32;
+        self.quiet += seconds;
+        for since in &mut self.since {
+            *since += seconds;
+        }
+    }
+
+    /// Something worth watching just happened.
+    pub fn note_interesting(&mut self) {
+        self.quiet = 0.0;
+    }
+
+    /// How much more likely rare spawns are right now.
+    fn boost(&self, spawn: Spawn) -> f32 {
+        if !spawn.is_rare() {
+            return 1.0;
+        }
+        match self.pacing {
+            Pacing::Realistic => 1.0 + (self.quiet / 600.0).min(1.0),
+            Pacing::Cinematic => 2.0 * (1.0 + (self.quiet / 60.0).min(5.0)),
+        }
+    }
+
+    /// Decide whether `spawn` happens, given a base `chance` for this step.
+    pub fn roll<R: Rng>(&mut self, rng: &mut R, spawn: Spawn, chance: f32) -> bool {
+        let i = Self::index(spawn);
+        if self.since[i] < spawn.min_gap(self.pacing) {
+            return false;
+        }
+        if rng.gen::<f32>() >= chance * self.boost(spawn) {
+            return false;
+        }
+        self.since[i] = 0.0;
+        if spawn.is_rare() {
+            self.note_interesting();
+        }
+        true
+    }
+}
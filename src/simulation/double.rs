@@ -0,0 +1,78 @@
+//! Double stars: a companion beside the star, often a different color, like
+//! Albireo's gold and blue. Real pairs sit far too close to split on a
+//! terminal, so their separations are widened, each pair still needing
+//! more magnification than the last; at wide field the two look like one.
+
+use crate::simulation::spectral::SpectralClass;
+use rand::Rng;
+
+// Share of random stars with a companion
+const DOUBLE_CHANCE: f64 = 0.03;
+
+/// A double star's fainter partner.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Companion {
+    pub magnitude: f32,
+    pub class: SpectralClass,
+    /// How far it sits from its primary, in rows at the sky's own scale;
+    /// the pair splits once magnified to a row or more apart
+    pub separation: f32,
+    /// Which way it lies from its primary on screen, in radians clockwise
+    /// from the right
+    pub angle: f32,
+}
+
+impl Companion {
+    /// Where the companion sits from its primary at `zoom`, in columns
+    /// across and rows down for rows `aspect` columns tall, if it's far
+    /// enough out to show in a cell of its own.
+    pub fn offset(&self, zoom: f32, aspect: f32) -> Option<(i32, i32)> {
+        let distance = self.separation * zoom;
+        if distance < 1.0 {
+            return None;
+        }
+        let dx = (self.angle.cos() * distance * aspect).round() as i32;
+        let dy = (self.angle.sin() * distance).round() as i32;
+        Some((dx, dy))
+    }
+
+    /// The share of the pair's light that's the companion's, beside a
+    /// primary of `magnitude`.
+    pub fn share(&self, magnitude: f32) -> f32 {
+        1.0 / (1.0 + 10f32.powf(0.4 * (self.magnitude - magnitude)))
+    }
+}
+
+/// The companions of the catalog's famous doubles, by name.
+pub fn known(name: &str) -> Option<Companion> {
+    let (magnitude, class, separation, angle) = match name {
+        // Alcor, the naked-eye test, splits with the least zoom
+        "Mizar" => (4.0, SpectralClass::A, 0.8, -0.3),
+        "Albireo" => (5.1, SpectralClass::B, 0.35, -0.9),
+        "Almach" => (4.8, SpectralClass::B, 0.2, 1.0),
+        "Acrux" => (1.3, SpectralClass::B, 0.12, 2.0),
+        "Castor" => (2.9, SpectralClass::A, 0.1, 0.5),
+        "Rigil Kentaurus" => (1.3, SpectralClass::K, 0.08, -2.5),
+        _ => return None,
+    };
+    Some(Companion {
+        magnitude,
+        class,
+        separation,
+        angle,
+    })
+}
+
+/// Now and then, a companion for a random star of `magnitude`.
+pub fn random<R: Rng>(rng: &mut R, magnitude: f32) -> Option<Companion> {
+    if !rng.gen_bool(DOUBLE_CHANCE) {
+        return None;
+    }
+    Some(Companion {
+        magnitude: magnitude + rng.gen_range(0.5..3.0),
+        class: SpectralClass::random(rng),
+        separation: rng.gen_range(0.06..0.5),
+        angle: rng.gen_range(-std::f32::consts::PI..std::f32::consts::PI),
+    })
+}
@@ -0,0 +1,99 @@
+//! Total lunar eclipses: the Earth's shadow creeping across a full moon and
+//! turning it a dim copper red while it's wholly inside.
+
+use crate::simulation::TICKS_PER_SECOND;
+
+// The total lunar eclipses to come, as the Julian day of greatest eclipse
+// (UTC) and the minutes of totality and of the whole umbral eclipse, both
+// partial phases included
+const ECLIPSES: [(f64, f32, f32); 8] = [
+    (2460748.7903, 65.0, 218.0), // 14 March 2025
+    (2460926.2576, 82.0, 209.0), // 7 September 2025
+    (2461102.9813, 58.0, 207.0), // 3 March 2026
+    (2462137.2028, 71.0, 209.0), // 31 December 2028
+    (2462313.6403, 102.0, 220.0), // 26 June 2029
+    (2462491.4458, 54.0, 213.0), // 20 December 2029
+    (2463348.1340, 66.0, 213.0), // 25 April 2032
+    (2463524.2931, 47.0, 197.0), // 18 October 2032
+];
+// Seconds of each partial phase and of totality when one is put on to show
+// off, six minutes in all
+const DEMO_PARTIAL: f32 = 120.0;
+const DEMO_TOTAL: f32 = 120.0;
+
+/// Radius of the Earth's shadow where the moon crosses it, in moon radii.
+pub const UMBRA_RADIUS: f32 = 2.7;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Eclipse {
+    /// Seconds since the shadow first touched the moon
+    elapsed: f32,
+    /// Seconds for the shadow to cover the disc, and for the disc to stay
+    /// covered
+    partial: f32,
+    total: f32,
+}
+
+impl Eclipse {
+    /// A quick eclipse starting now, for a demonstration.
+    pub fn demo() -> Self {
+        Eclipse {
+            elapsed: 0.0,
+            partial: DEMO_PARTIAL,
+            total: DEMO_TOTAL,
+        }
+    }
+
+    /// The real eclipse under way at Julian day `jd`, if there is one, as
+    /// far along as it really is.
+    pub fn at(jd: f64) -> Option<Self> {
+        ECLIPSES.iter().find_map(|&(greatest, total, umbral)| {
+            let (partial, total) = ((umbral - total) / 2.0 * 60.0, total * 60.0);
+            let elapsed = ((jd - greatest) * 86_400.0) as f32 + partial + total / 2.0;
+            (0.0..2.0 * partial + total).contains(&elapsed).then_some(Eclipse {
+                elapsed,
+                partial,
+                total,
+            })
+        })
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.elapsed += dt / TICKS_PER_SECOND as f32;
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.elapsed >= 2.0 * self.partial + self.total
+    }
+
+    /// How much of the way across the disc the shadow has crept, from 0
+    /// before it touches to 1 through totality.
+    pub fn coverage(&self) -> f32 {
+        let remaining = 2.0 * self.partial + self.total - self.elapsed;
+        (self.elapsed.min(remaining) / self.partial).clamp(0.0, 1.0)
+    }
+
+    /// How deep into the shadow the moon is, from 0 at its edge to 1 at
+    /// greatest eclipse, where the copper is darkest.
+    pub fn depth(&self) -> f32 {
+        let half = self.partial + self.total / 2.0;
+        let t = (1.0 - (self.elapsed - half).abs() / half).clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    /// Where the centre of the shadow lies, in moon radii across from the
+    /// centre of the disc. The moon moves east into it, so it comes on
+    /// from the east (left) side and drifts off to the west.
+    pub fn shadow(&self) -> f32 {
+        let (r, partial, total) = (UMBRA_RADIUS, self.partial, self.total);
+        let t = self.elapsed;
+        if t < partial {
+            -(1.0 + r) + 2.0 * t / partial
+        } else if t < partial + total {
+            (1.0 - r) + 2.0 * (r - 1.0) * (t - partial) / total.max(f32::EPSILON)
+        } else {
+            (r - 1.0) + 2.0 * (t - partial - total) / partial
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! The real weather outside, for the sky's rain and wind to follow. Reports
+//! arrive now and then, and the sky eases from one to the next rather than
+//! jumping.
+
+use crate::simulation::TICKS_PER_SECOND;
+
+// Seconds taken to ease into a new report
+const EASE_SECONDS: f32 = 600.0;
+
+/// The weather at one moment, as far as the sky goes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sample {
+    /// How hard rain or snow is falling, from 0 (dry) to 1 (a downpour)
+    pub precipitation: f32,
+    /// Whether it falls as snow
+    pub snow: bool,
+    /// Wind in km/h, positive blowing rightwards across the sky
+    pub wind: f32,
+}
+
+impl Sample {
+    /// Part way from `self` to `other`, `t` from 0 to 1. Rain turns to snow
+    /// halfway.
+    fn lerp(&self, other: &Sample, t: f32) -> Sample {
+        Sample {
+            precipitation: self.precipitation + (other.precipitation - self.precipitation) * t,
+            snow: if t < 0.5 { self.snow } else { other.snow },
+            wind: self.wind + (other.wind - self.wind) * t,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Environment {
+    from: Sample,
+    to: Sample,
+    /// Ticks since `to` was reported
+    elapsed: f32,
+}
+
+impl Environment {
+    pub fn new(sample: Sample) -> Self {
+        Environment {
+            from: sample,
+            to: sample,
+            elapsed: EASE_SECONDS * TICKS_PER_SECOND as f32,
+        }
+    }
+
+    /// Start easing towards a new report from wherever things stand now.
+    pub fn report(&mut self, sample: Sample) {
+        self.from = self.current();
+        self.to = sample;
+        self.elapsed = 0.0;
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.elapsed += dt;
+    }
+
+    /// The weather as the sky has it right now.
+    pub fn current(&self) -> Sample {
+        let t = (self.elapsed / (EASE_SECONDS * TICKS_PER_SECOND as f32)).min(1.0);
+        self.from.lerp(&self.to, t * t * (3.0 - 2.0 * t))
+    }
+}
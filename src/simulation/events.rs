@@ -0,0 +1,88 @@
+//! Notable things that happen in the sky.
+
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SkyEvent {
+    /// A bright meteor that leaves a smoke train
+    Fireball,
+    /// A slow, huge fireball bursting in a flash at the end of its flight
+    Bolide,
+    SatellitePass,
+    /// The real ISS rising over the horizon
+    IssPass,
+    /// A real satellite rising on one of its predicted visible passes
+    PredictedPass,
+    Moonbow,
+    /// A ring of light around the moon seen through thin cloud
+    MoonHalo,
+    Aurora,
+    Comet,
+    /// Two satellites passing within a whisker of each other
+    Conjunction,
+    /// A line of Starlink satellites crossing in formation
+    StarlinkTrain,
+    /// A star too faint to see flaring up brighter than any other
+    Supernova,
+}
+
+impl SkyEvent {
+    /// Name as written in scene captions and hooks, like `fireball`.
+    pub fn name(self) -> &'static str {
+        match self {
+            SkyEvent::Fireball => "fireball",
+            SkyEvent::Bolide => "bolide",
+            SkyEvent::SatellitePass => "satellite",
+            SkyEvent::IssPass => "iss",
+            SkyEvent::PredictedPass => "pass",
+            SkyEvent::Moonbow => "moonbow",
+            SkyEvent::MoonHalo => "halo",
+            SkyEvent::Aurora => "aurora",
+            SkyEvent::Comet => "comet",
+            SkyEvent::Conjunction => "conjunction",
+            SkyEvent::StarlinkTrain => "starlink",
+            SkyEvent::Supernova => "supernova",
+        }
+    }
+
+    /// Rare events are worth drawing attention to when someone only glances
+    /// at the screen now and then.
+    pub fn is_rare(self) -> bool {
+        match self {
+            SkyEvent::Fireball | SkyEvent::SatellitePass => false,
+            SkyEvent::Bolide
+            | SkyEvent::IssPass
+            | SkyEvent::PredictedPass
+            | SkyEvent::Moonbow
+            | SkyEvent::MoonHalo
+            | SkyEvent::Aurora
+            | SkyEvent::Comet
+            | SkyEvent::Conjunction
+            | SkyEvent::StarlinkTrain
+            | SkyEvent::Supernova => true,
+        }
+    }
+}
+
+impl FromStr for SkyEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fireball" => Ok(SkyEvent::Fireball),
+            "bolide" => Ok(SkyEvent::Bolide),
+            "satellite" => Ok(SkyEvent::SatellitePass),
+            "iss" => Ok(SkyEvent::IssPass),
+            "pass" => Ok(SkyEvent::PredictedPass),
+            "moonbow" => Ok(SkyEvent::Moonbow),
+            "halo" => Ok(SkyEvent::MoonHalo),
+            "aurora" => Ok(SkyEvent::Aurora),
+            "comet" => Ok(SkyEvent::Comet),
+            "conjunction" => Ok(SkyEvent::Conjunction),
+            "starlink" => Ok(SkyEvent::StarlinkTrain),
+            "supernova" => Ok(SkyEvent::Supernova),
+            other => Err(format!("unknown sky event '{}'", other)),
+        }
+    }
+}
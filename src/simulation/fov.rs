@@ -0,0 +1,58 @@
+//! Observer field-of-view presets.
+
+/// How the sky is being observed. Each preset trades field of view for
+/// reach: narrower views magnify and reveal fainter stars.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Fov {
+    #[default]
+    NakedEye,
+    Binoculars,
+    Telescope,
+}
+
+impl Fov {
+    pub fn next(self) -> Self {
+        match self {
+            Fov::NakedEye => Fov::Binoculars,
+            Fov::Binoculars => Fov::Telescope,
+            Fov::Telescope => Fov::NakedEye,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Fov::NakedEye => "naked eye",
+            Fov::Binoculars => "binoculars",
+            Fov::Telescope => "wide-field telescope",
+        }
+    }
+
+    /// Magnification applied around the centre of the view.
+    pub fn zoom(self) -> f32 {
+        match self {
+            Fov::NakedEye => 1.0,
+            Fov::Binoculars => 3.0,
+            Fov::Telescope => 8.0,
+        }
+    }
+
+    /// Faintest apparent magnitude that is still drawn.
+    pub fn magnitude_limit(self) -> f32 {
+        match self {
+            Fov::NakedEye => 6.5,
+            Fov::Binoculars => 9.0,
+            Fov::Telescope => 11.5,
+        }
+    }
+
+    /// Faintest apparent magnitude of a named star that is labelled. Fewer
+    /// stars fit in a narrower view, so more of them can be named.
+    pub fn label_limit(self) -> f32 {
+        match self {
+            Fov::NakedEye => 0.5,
+            Fov::Binoculars => 2.0,
+            Fov::Telescope => 3.5,
+        }
+    }
+}
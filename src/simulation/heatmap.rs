@@ -0,0 +1,65 @@
+//! Per-cell record of where meteors and satellites have been this session.
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Heatmap {
+    width: u16,
+    height: u16,
+    counts: Vec<u32>,
+    max: u32,
+}
+
+impl Heatmap {
+    pub fn new(width: u16, height: u16) -> Self {
+        Heatmap {
+            width,
+            height,
+            counts: vec![0; width as usize * height as usize],
+            max: 0,
+        }
+    }
+
+    /// Count a visit to the cell containing `(x, y)`.
+    pub fn record(&mut self, x: f32, y: f32) {
+        if x < 0.0 || y < 0.0 || x >= self.width as f32 || y >= self.height as f32 {
+            return;
+        }
+        let i = y as usize * self.width as usize + x as usize;
+        self.counts[i] += 1;
+        self.max = self.max.max(self.counts[i]);
+    }
+
+    /// Count every cell along the segment from `from` to `to`, for objects
+    /// that move more than one cell per tick.
+    pub fn record_segment(&mut self, from: (f32, f32), to: (f32, f32)) {
+        let steps = (to.0 - from.0)
+            .abs()
+            .max((to.1 - from.1).abs())
+            .ceil()
+            .max(1.0) as usize;
+        for i in 1..=steps {
+            let t = i as f32 / steps as f32;
+            self.record(from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t);
+        }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Visits to the cell at column `x`, row `y`.
+    pub fn count(&self, x: u16, y: u16) -> u32 {
+        if x >= self.width || y >= self.height {
+            return 0;
+        }
+        self.counts[y as usize * self.width as usize + x as usize]
+    }
+
+    /// Visits to the busiest cell.
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+}
@@ -0,0 +1,481 @@
+//! The International Space Station where it really is: its orbit read from
+//! a two-line element set (TLE), carried forward with the SGP4 model the
+//! elements are made for, and seen from the observer's place.
+
+use crate::simulation::astro;
+use crate::simulation::passes::{self, NextPass};
+use crate::simulation::projection::Observer;
+use crate::simulation::sky_model::SkyModel;
+use std::f64::consts::TAU;
+use std::str::FromStr;
+
+// The WGS-72 Earth that SGP4's elements are fitted against
+const EARTH_RADIUS_KM: f64 = 6378.135;
+// Square root of the Earth's gravitational constant, in Earth radii^1.5
+// per minute
+const XKE: f64 = 0.0743669161331734;
+const J2: f64 = 0.001082616;
+const J3: f64 = -0.00000253881;
+const J4: f64 = -0.00000165597;
+// The WGS-84 Earth that places on the ground are given on
+const WGS84_RADIUS_KM: f64 = 6378.137;
+const WGS84_FLATTENING: f64 = 1.0 / 298.257223563;
+// Days ahead the station's next pass is looked for
+const NEXT_PASS_DAYS: f64 = 1.0;
+
+/// A two-line element set: an orbit as tracked at one moment, its epoch.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tle {
+    /// Julian day of the epoch
+    pub epoch: f64,
+    /// Drag term, per Earth radius
+    pub bstar: f64,
+    /// Degrees
+    pub inclination: f64,
+    /// Right ascension of the ascending node, degrees
+    pub node: f64,
+    pub eccentricity: f64,
+    /// Argument of perigee, degrees
+    pub perigee: f64,
+    /// Mean anomaly, degrees
+    pub anomaly: f64,
+    /// Revolutions per day
+    pub mean_motion: f64,
+}
+
+impl FromStr for Tle {
+    type Err = String;
+
+    /// Parse the element set's two lines, with or without a name line
+    /// before them.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().map(str::trim_end).filter(|l| !l.is_empty()).collect();
+        let (Some(line1), Some(line2)) = (
+            lines.iter().find(|l| l.starts_with("1 ")),
+            lines.iter().find(|l| l.starts_with("2 ")),
+        ) else {
+            return Err("expected the two lines of a TLE".to_string());
+        };
+        let field = |line: &str, from: usize, to: usize| -> Result<f64, String> {
+            let text = line.get(from - 1..to).ok_or("TLE line too short")?.trim();
+            text.parse().map_err(|_| format!("invalid TLE field '{}'", text))
+        };
+        // Decimal point assumed, like "-11606-4" for -0.11606e-4
+        let assumed = |text: &str| -> Result<f64, String> {
+            let invalid = || format!("invalid TLE field '{}'", text);
+            let text = text.trim();
+            let (mantissa, exponent) = match text.rfind(['-', '+']).filter(|&i| i > 0) {
+                Some(i) => (&text[..i], text[i..].parse::<i32>().map_err(|_| invalid())?),
+                None => (text, 0),
+            };
+            let (sign, digits) = match mantissa.strip_prefix('-') {
+                Some(digits) => (-1.0, digits),
+                None => (1.0, mantissa.trim_start_matches('+')),
+            };
+            let value: f64 = format!("0.{}", digits).parse().map_err(|_| invalid())?;
+            Ok(sign * value * 10f64.powi(exponent))
+        };
+
+        let year = field(line1, 19, 20)? as i32;
+        let year = if year < 57 { 2000 + year } else { 1900 + year };
+        let day = field(line1, 21, 32)?;
+        let bstar = assumed(line1.get(53..61).ok_or("TLE line too short")?)?;
+        Ok(Tle {
+            epoch: new_year(year) + day - 1.0,
+            bstar,
+            inclination: field(line2, 9, 16)?,
+            node: field(line2, 18, 25)?,
+            eccentricity: assumed(line2.get(26..33).ok_or("TLE line too short")?)?,
+            perigee: field(line2, 35, 42)?,
+            anomaly: field(line2, 44, 51)?,
+            mean_motion: field(line2, 53, 63)?,
+        })
+    }
+}
+
+/// Julian day at the start of 1 January of `year`.
+fn new_year(year: i32) -> f64 {
+    let y = (year - 1) as f64;
+    1721425.5 + 365.0 * y + (y / 4.0).floor() - (y / 100.0).floor() + (y / 400.0).floor()
+}
+
+/// The SGP4 model for a near-Earth orbit, set up from its elements. Orbits
+/// taking over 225 minutes need the deep-space terms, which the station
+/// never does.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sgp4 {
+    epoch: f64,
+    bstar: f64,
+    inclination: f64,
+    node: f64,
+    eccentricity: f64,
+    perigee: f64,
+    anomaly: f64,
+    /// Mean motion in radians per minute, with the Kozai terms taken out
+    motion: f64,
+    /// Whether the perigee is so low that the higher drag terms are dropped
+    simple: bool,
+    aycof: f64,
+    con41: f64,
+    cc1: f64,
+    cc4: f64,
+    cc5: f64,
+    d2: f64,
+    d3: f64,
+    d4: f64,
+    delmo: f64,
+    eta: f64,
+    argpdot: f64,
+    omgcof: f64,
+    sinmao: f64,
+    t2cof: f64,
+    t3cof: f64,
+    t4cof: f64,
+    t5cof: f64,
+    x1mth2: f64,
+    x7thm1: f64,
+    mdot: f64,
+    nodedot: f64,
+    xlcof: f64,
+    xmcof: f64,
+    nodecf: f64,
+}
+
+impl Sgp4 {
+    /// Set up the model, or `None` for an orbit it can't carry: one that's
+    /// deep space, or not an orbit at all.
+    pub fn new(tle: &Tle) -> Option<Self> {
+        let e = tle.eccentricity;
+        let inclination = tle.inclination.to_radians();
+        let kozai = tle.mean_motion * TAU / 1440.0;
+        if !(0.0..1.0).contains(&e) || kozai <= 0.0 {
+            return None;
+        }
+
+        // Recover the original mean motion and semi-major axis
+        let omeosq = 1.0 - e * e;
+        let rteosq = omeosq.sqrt();
+        let (sinio, cosio) = inclination.sin_cos();
+        let cosio2 = cosio * cosio;
+        let ak = (XKE / kozai).powf(2.0 / 3.0);
+        let d1 = 0.75 * J2 * (3.0 * cosio2 - 1.0) / (rteosq * omeosq);
+        let del = d1 / (ak * ak);
+        let adel = ak * (1.0 - del * del - del * (1.0 / 3.0 + 134.0 * del * del / 81.0));
+        let del = d1 / (adel * adel);
+        let motion = kozai / (1.0 + del);
+        if TAU / motion >= 225.0 {
+            return None;
+        }
+        let ao = (XKE / motion).powf(2.0 / 3.0);
+        let po = ao * omeosq;
+        let con42 = 1.0 - 5.0 * cosio2;
+        let con41 = -con42 - cosio2 - cosio2;
+        let posq = po * po;
+        let rp = ao * (1.0 - e);
+
+        // The atmosphere's density falls off above 78 km, less steeply for
+        // perigees below 156 km
+        let simple = rp < 220.0 / EARTH_RADIUS_KM + 1.0;
+        let perigee_km = (rp - 1.0) * EARTH_RADIUS_KM;
+        let mut sfour = 78.0 / EARTH_RADIUS_KM + 1.0;
+        let mut qzms24 = ((120.0 - 78.0) / EARTH_RADIUS_KM).powi(4);
+        if perigee_km < 156.0 {
+            let s = if perigee_km < 98.0 { 20.0 } else { perigee_km - 78.0 };
+            qzms24 = ((120.0 - s) / EARTH_RADIUS_KM).powi(4);
+            sfour = s / EARTH_RADIUS_KM + 1.0;
+        }
+
+        let pinvsq = 1.0 / posq;
+        let tsi = 1.0 / (ao - sfour);
+        let eta = ao * e * tsi;
+        let etasq = eta * eta;
+        let eeta = e * eta;
+        let psisq = (1.0 - etasq).abs();
+        let coef = qzms24 * tsi.powi(4);
+        let coef1 = coef / psisq.powf(3.5);
+        let cc2 = coef1
+            * motion
+            * (ao * (1.0 + 1.5 * etasq + eeta * (4.0 + etasq))
+                + 0.375 * J2 * tsi / psisq * con41 * (8.0 + 3.0 * etasq * (8.0 + etasq)));
+        let cc1 = tle.bstar * cc2;
+        let cc3 = if e > 1.0e-4 {
+            -2.0 * coef * tsi * (J3 / J2) * motion * sinio / e
+        } else {
+            0.0
+        };
+        let x1mth2 = 1.0 - cosio2;
+        let perigee = tle.perigee.to_radians();
+        let cc4 = 2.0
+            * motion
+            * coef1
+            * ao
+            * omeosq
+            * (eta * (2.0 + 0.5 * etasq) + e * (0.5 + 2.0 * etasq)
+                - J2 * tsi / (ao * psisq)
+                    * (-3.0 * con41 * (1.0 - 2.0 * eeta + etasq * (1.5 - 0.5 * eeta))
+                        + 0.75 * x1mth2 * (2.0 * etasq - eeta * (1.0 + etasq)) * (2.0 * perigee).cos()));
+        let cc5 = 2.0 * coef1 * ao * omeosq * (1.0 + 2.75 * (etasq + eeta) + eeta * etasq);
+
+        // Secular rates from the Earth's oblateness
+        let cosio4 = cosio2 * cosio2;
+        let temp1 = 1.5 * J2 * pinvsq * motion;
+        let temp2 = 0.5 * temp1 * J2 * pinvsq;
+        let temp3 = -0.46875 * J4 * pinvsq * pinvsq * motion;
+        let mdot = motion
+            + 0.5 * temp1 * rteosq * con41
+            + 0.0625 * temp2 * rteosq * (13.0 - 78.0 * cosio2 + 137.0 * cosio4);
+        let argpdot = -0.5 * temp1 * con42
+            + 0.0625 * temp2 * (7.0 - 114.0 * cosio2 + 395.0 * cosio4)
+            + temp3 * (3.0 - 36.0 * cosio2 + 49.0 * cosio4);
+        let xhdot1 = -temp1 * cosio;
+        let nodedot = xhdot1 + (0.5 * temp2 * (4.0 - 19.0 * cosio2) + 2.0 * temp3 * (3.0 - 7.0 * cosio2)) * cosio;
+
+        let anomaly = tle.anomaly.to_radians();
+        let xmcof = if e > 1.0e-4 {
+            -2.0 / 3.0 * coef * tle.bstar / eeta
+        } else {
+            0.0
+        };
+        let xlcof_divisor = if (cosio + 1.0).abs() > 1.5e-12 { 1.0 + cosio } else { 1.5e-12 };
+
+        let (mut d2, mut d3, mut d4) = (0.0, 0.0, 0.0);
+        let (mut t3cof, mut t4cof, mut t5cof) = (0.0, 0.0, 0.0);
+        if !simple {
+            let cc1sq = cc1 * cc1;
+            d2 = 4.0 * ao * tsi * cc1sq;
+            let temp = d2 * tsi * cc1 / 3.0;
+            d3 = (17.0 * ao + sfour) * temp;
+            d4 = 0.5 * temp * ao * tsi * (221.0 * ao + 31.0 * sfour) * cc1;
+            t3cof = d2 + 2.0 * cc1sq;
+            t4cof = 0.25 * (3.0 * d3 + cc1 * (12.0 * d2 + 10.0 * cc1sq));
+            t5cof = 0.2 * (3.0 * d4 + 12.0 * cc1 * d3 + 6.0 * d2 * d2 + 15.0 * cc1sq * (2.0 * d2 + cc1sq));
+        }
+
+        Some(Sgp4 {
+            epoch: tle.epoch,
+            bstar: tle.bstar,
+            inclination,
+            node: tle.node.to_radians(),
+            eccentricity: e,
+            perigee,
+            anomaly,
+            motion,
+            simple,
+            aycof: -0.5 * (J3 / J2) * sinio,
+            con41,
+            cc1,
+            cc4,
+            cc5,
+            d2,
+            d3,
+            d4,
+            delmo: (1.0 + eta * anomaly.cos()).powi(3),
+            eta,
+            argpdot,
+            omgcof: tle.bstar * cc3 * perigee.cos(),
+            sinmao: anomaly.sin(),
+            t2cof: 1.5 * cc1,
+            t3cof,
+            t4cof,
+            t5cof,
+            x1mth2,
+            x7thm1: 7.0 * cosio2 - 1.0,
+            mdot,
+            nodedot,
+            xlcof: -0.25 * (J3 / J2) * sinio * (3.0 + 5.0 * cosio) / xlcof_divisor,
+            xmcof,
+            nodecf: 3.5 * omeosq * xhdot1 * cc1,
+        })
+    }
+
+    /// Position in km at Julian day `jd`, in the true-equator mean-equinox
+    /// frame SGP4 works in, or `None` once the orbit has decayed.
+    pub fn position(&self, jd: f64) -> Option<[f64; 3]> {
+        let t = (jd - self.epoch) * 1440.0;
+
+        // Secular gravity and drag
+        let xmdf = self.anomaly + self.mdot * t;
+        let argpdf = self.perigee + self.argpdot * t;
+        let nodedf = self.node + self.nodedot * t;
+        let t2 = t * t;
+        let node = nodedf + self.nodecf * t2;
+        let mut argp = argpdf;
+        let mut mean = xmdf;
+        let mut tempa = 1.0 - self.cc1 * t;
+        let mut tempe = self.bstar * self.cc4 * t;
+        let mut templ = self.t2cof * t2;
+        if !self.simple {
+            let delomg = self.omgcof * t;
+            let delm = self.xmcof * ((1.0 + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            mean = xmdf + delomg + delm;
+            argp = argpdf - delomg - delm;
+            let (t3, t4) = (t2 * t, t2 * t2);
+            tempa -= self.d2 * t2 + self.d3 * t3 + self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mean.sin() - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+        let am = (XKE / self.motion).powf(2.0 / 3.0) * tempa * tempa;
+        let e = (self.eccentricity - tempe).max(1.0e-6);
+        if e >= 1.0 || am < 0.95 {
+            return None;
+        }
+        mean += self.motion * templ;
+        let xlm = (mean + argp + node).rem_euclid(TAU);
+        let node = node.rem_euclid(TAU);
+        let argp = argp.rem_euclid(TAU);
+        let mean = (xlm - argp - node).rem_euclid(TAU);
+
+        // Long-period periodics
+        let axnl = e * argp.cos();
+        let temp = 1.0 / (am * (1.0 - e * e));
+        let aynl = e * argp.sin() + temp * self.aycof;
+        let xl = mean + argp + node + temp * self.xlcof * axnl;
+
+        // Kepler's equation
+        let u = (xl - node).rem_euclid(TAU);
+        let mut eo1 = u;
+        for _ in 0..10 {
+            let (sin, cos) = eo1.sin_cos();
+            let step = (u - aynl * cos + axnl * sin - eo1) / (1.0 - cos * axnl - sin * aynl);
+            eo1 += step.clamp(-0.95, 0.95);
+            if step.abs() < 1.0e-12 {
+                break;
+            }
+        }
+        let (sineo1, coseo1) = eo1.sin_cos();
+
+        // Short-period periodics
+        let ecose = axnl * coseo1 + aynl * sineo1;
+        let esine = axnl * sineo1 - aynl * coseo1;
+        let el2 = axnl * axnl + aynl * aynl;
+        let pl = am * (1.0 - el2);
+        if pl < 0.0 {
+            return None;
+        }
+        let rl = am * (1.0 - ecose);
+        let betal = (1.0 - el2).sqrt();
+        let temp = esine / (1.0 + betal);
+        let sinu = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu = am / rl * (coseo1 - axnl + aynl * temp);
+        let su = sinu.atan2(cosu);
+        let sin2u = 2.0 * cosu * sinu;
+        let cos2u = 1.0 - 2.0 * sinu * sinu;
+        let temp1 = 0.5 * J2 / pl;
+        let temp2 = temp1 / pl;
+        let (sinip, cosip) = self.inclination.sin_cos();
+        let mrt = rl * (1.0 - 1.5 * temp2 * betal * self.con41) + 0.5 * temp1 * self.x1mth2 * cos2u;
+        if mrt < 1.0 {
+            return None;
+        }
+        let su = su - 0.25 * temp2 * self.x7thm1 * sin2u;
+        let xnode = node + 1.5 * temp2 * cosip * sin2u;
+        let xinc = self.inclination + 1.5 * temp2 * cosip * sinip * cos2u;
+
+        let (sinsu, cossu) = su.sin_cos();
+        let (snod, cnod) = xnode.sin_cos();
+        let (sini, cosi) = xinc.sin_cos();
+        let (xmx, xmy) = (-snod * cosi, cnod * cosi);
+        let r = mrt * EARTH_RADIUS_KM;
+        Some([
+            r * (xmx * sinsu + cnod * cossu),
+            r * (xmy * sinsu + snod * cossu),
+            r * sini * sinsu,
+        ])
+    }
+}
+
+/// Altitude and azimuth in degrees, azimuth east of north, of a satellite
+/// at `position` (km, as from [`Sgp4::position`]) at Julian day `jd`, seen
+/// from `observer` at sea level.
+pub fn look(position: [f64; 3], observer: &Observer, jd: f64) -> (f64, f64) {
+    // Turn with the Earth under the stars
+    let (sg, cg) = astro::sidereal(jd, 0.0).to_radians().sin_cos();
+    let [x, y, z] = position;
+    let fixed = [cg * x + sg * y, -sg * x + cg * y, z];
+
+    let (sl, cl) = observer.latitude.to_radians().sin_cos();
+    let (so, co) = observer.longitude.to_radians().sin_cos();
+    let e2 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+    let n = WGS84_RADIUS_KM / (1.0 - e2 * sl * sl).sqrt();
+    let site = [n * cl * co, n * cl * so, n * (1.0 - e2) * sl];
+    let [rx, ry, rz] = [fixed[0] - site[0], fixed[1] - site[1], fixed[2] - site[2]];
+
+    let south = sl * co * rx + sl * so * ry - cl * rz;
+    let east = -so * rx + co * ry;
+    let up = cl * co * rx + cl * so * ry + sl * rz;
+    let range = (rx * rx + ry * ry + rz * rz).sqrt();
+    let altitude = (up / range).asin().to_degrees();
+    let azimuth = east.atan2(-south).to_degrees().rem_euclid(360.0);
+    (altitude, azimuth)
+}
+
+/// Whether a satellite at `position` is in sunlight at Julian day `jd`,
+/// rather than in the Earth's shadow.
+pub fn is_sunlit(position: [f64; 3], jd: f64) -> bool {
+    let (ra, dec) = astro::sun(jd);
+    let (sr, cr) = (ra * 15.0).to_radians().sin_cos();
+    let (sd, cd) = dec.to_radians().sin_cos();
+    let sun = [cd * cr, cd * sr, sd];
+    let along: f64 = position.iter().zip(sun).map(|(p, s)| p * s).sum();
+    if along >= 0.0 {
+        return true;
+    }
+    let across = position.iter().zip(sun).map(|(p, s)| (p - along * s).powi(2)).sum::<f64>();
+    across.sqrt() > EARTH_RADIUS_KM
+}
+
+/// The station where it stands in the sky.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Iss {
+    pub x: f32,
+    pub y: f32,
+    /// Degrees above the horizon; negative while it's below
+    pub altitude: f32,
+    /// Lit by the sun, and so shining, rather than in the Earth's shadow
+    pub sunlit: bool,
+    orbit: Sgp4,
+    observer: Observer,
+    /// Julian day when the sky started
+    start: f64,
+}
+
+impl Iss {
+    /// The station on the orbit `tle` describes, seen by `observer` in a
+    /// sky that started at Julian day `start`, or `None` if SGP4 can't carry
+    /// the orbit.
+    pub fn new(tle: &Tle, observer: Observer, start: f64) -> Option<Self> {
+        Some(Iss {
+            x: 0.0,
+            y: 0.0,
+            altitude: -90.0,
+            sunlit: false,
+            orbit: Sgp4::new(tle)?,
+            observer,
+            start,
+        })
+    }
+
+    /// Move to where the station stands `seconds` after the sky started.
+    pub fn follow(&mut self, sky: &SkyModel, seconds: f32) {
+        let jd = self.start + seconds as f64 / 86_400.0;
+        let Some(position) = self.orbit.position(jd) else {
+            self.altitude = -90.0;
+            return;
+        };
+        let (altitude, azimuth) = look(position, &self.observer, jd);
+        (self.x, self.y) = self.observer.cell(sky, altitude, azimuth);
+        self.altitude = altitude as f32;
+        self.sunlit = is_sunlit(position, jd);
+    }
+
+    /// When the station next rises on a visible pass, after `seconds` into
+    /// the sky.
+    pub fn next_pass(&self, seconds: f32) -> NextPass {
+        let jd = self.start + seconds as f64 / 86_400.0;
+        passes::visible(&self.orbit, &self.observer, jd, NEXT_PASS_DAYS)
+            .first()
+            .map_or(NextPass::NoneSoon, |&(rise, _)| NextPass::At(rise))
+    }
+}
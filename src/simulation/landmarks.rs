@@ -0,0 +1,159 @@
+//! Named objects worth pointing out: the moon, a planet, a recognisable
+//! asterism and deep-sky objects.
+
+use crate::simulation::astro::MoonPhase;
+use crate::simulation::moon::Moon;
+use crate::simulation::sky_model::SkyModel;
+use crate::simulation::Rgb;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+pub struct Planet {
+    pub name: &'static str,
+    pub color: Rgb,
+    pub blurb: &'static str,
+}
+
+pub const PLANETS: &[Planet] = &[
+    Planet {
+        name: "Mercury",
+        color: (225, 215, 200),
+        blurb: "the smallest planet, never far from twilight",
+    },
+    Planet {
+        name: "Venus",
+        color: (255, 250, 225),
+        blurb: "brightest of the planets, wrapped in cloud",
+    },
+    Planet {
+        name: "Mars",
+        color: (255, 150, 100),
+        blurb: "the red planet, rusted iron dust",
+    },
+    Planet {
+        name: "Jupiter",
+        color: (255, 235, 200),
+        blurb: "the largest planet, with four bright moons",
+    },
+    Planet {
+        name: "Saturn",
+        color: (240, 220, 160),
+        blurb: "ringed, and light enough to float",
+    },
+];
+
+/// A pattern of bright stars, as cell offsets from its centre.
+pub struct Asterism {
+    pub name: &'static str,
+    pub blurb: &'static str,
+    pub stars: &'static [(f32, f32)],
+}
+
+pub const ASTERISMS: &[Asterism] = &[
+    Asterism {
+        name: "Cassiopeia",
+        blurb: "the W-shaped queen",
+        stars: &[(-8.0, -1.0), (-4.0, 1.5), (0.0, -0.5), (4.0, 1.5), (8.0, -1.5)],
+    },
+    Asterism {
+        name: "The Big Dipper",
+        blurb: "seven stars of the Great Bear",
+        stars: &[
+            (-10.0, -2.0),
+            (-6.0, -1.5),
+            (-3.0, -0.5),
+            (0.0, 0.5),
+            (1.0, 2.5),
+            (6.0, 2.5),
+            (6.5, 0.5),
+        ],
+    },
+    Asterism {
+        name: "Orion's Belt",
+        blurb: "three stars in a row, the hunter's belt",
+        stars: &[(-3.0, 1.0), (0.0, 0.0), (3.0, -1.0)],
+    },
+];
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Kind {
+    Moon(MoonPhase),
+    #[cfg_attr(feature = "serde", serde(with = "crate::saved::by_name"))]
+    Planet(&'static Planet),
+    #[cfg_attr(feature = "serde", serde(with = "crate::saved::by_name"))]
+    Asterism(&'static Asterism),
+    Cluster,
+}
+
+/// A named object and where its centre sits in the sky.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Landmark {
+    pub kind: Kind,
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Landmark {
+    /// One line introducing the object, for captions.
+    pub fn description(&self) -> String {
+        match &self.kind {
+            Kind::Moon(phase) => format!(
+                "The Moon: {}, {:.0}% lit",
+                phase.name(),
+                phase.illumination() * 100.0
+            ),
+            Kind::Planet(planet) => format!("{}: {}", planet.name, planet.blurb),
+            Kind::Asterism(asterism) => format!("{}: {}", asterism.name, asterism.blurb),
+            Kind::Cluster => "An open cluster: young stars born together".to_string(),
+        }
+    }
+}
+
+/// The moon if it's up, a planet and an asterism, scattered over the sky.
+/// Asterism stars still need adding to the sky's stars.
+pub fn place<R: Rng>(rng: &mut R, sky: &SkyModel, moon: Option<&Moon>) -> Vec<Landmark> {
+    let mut landmarks: Vec<Landmark> = moon
+        .map(|moon| Landmark {
+            kind: Kind::Moon(moon.phase),
+            x: moon.x,
+            y: moon.y,
+        })
+        .into_iter()
+        .collect();
+    let kinds = [
+        PLANETS.choose(rng).map(Kind::Planet),
+        ASTERISMS.choose(rng).map(Kind::Asterism),
+    ];
+    for kind in kinds.into_iter().flatten() {
+        if let Some((x, y, _)) = sky.place(rng) {
+            landmarks.push(Landmark { kind, x, y });
+        }
+    }
+    landmarks
+}
+
+#[cfg(feature = "serde")]
+impl crate::saved::Named for Planet {
+    const KIND: &'static str = "planet";
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn all() -> &'static [Self] {
+        PLANETS
+    }
+}
+
+#[cfg(feature = "serde")]
+impl crate::saved::Named for Asterism {
+    const KIND: &'static str = "asterism";
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn all() -> &'static [Self] {
+        ASTERISMS
+    }
+}
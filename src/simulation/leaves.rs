@@ -0,0 +1,36 @@
+//! Autumn leaves tumbling down in front of the sky.
+
+use crate::simulation::particles::{Particle, ParticleSystem};
+use crate::simulation::Rgb;
+use rand::Rng;
+
+const COLORS: &[Rgb] = &[
+    (200, 90, 30),
+    (170, 50, 30),
+    (220, 160, 40),
+    (130, 80, 40),
+];
+
+const MAX_LEAVES: usize = 40;
+
+/// Maybe start a new leaf at the top of the sky.
+pub fn spawn<R: Rng>(system: &mut ParticleSystem, rng: &mut R, width: u16, dt: f32) {
+    if width == 0 || system.len() >= MAX_LEAVES || rng.gen::<f32>() >= 0.05 * dt {
+        return;
+    }
+    system.spawn(Particle {
+        x: rng.gen_range(0.0..width as f32),
+        y: 0.0,
+        vx: 0.0,
+        vy: rng.gen_range(0.05..0.12),
+        wind_response: rng.gen_range(1.5..3.0),
+        sway: rng.gen_range(0.05..0.15),
+        phase: rng.gen_range(0.0..std::f32::consts::TAU),
+        frame_rate: rng.gen_range(0.1..0.25),
+        color: COLORS[rng.gen_range(0..COLORS.len())],
+        // Settle briefly before fading away
+        rest: rng.gen_range(2.0..4.0) * crate::simulation::TICKS_PER_SECOND as f32,
+        age: 0.0,
+        landed_at: None,
+    });
+}
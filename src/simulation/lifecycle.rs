@@ -0,0 +1,94 @@
+//! Stars coming and going over a long night: once the sky has been up a
+//! while, now and then one of its random stars fades out and a new one fades
+//! in somewhere else, so a sky left up for hours slowly changes instead of
+//! staying exactly as it started.
+
+use crate::simulation::{Star, TICKS_PER_SECOND};
+use rand::Rng;
+use std::ops::Range;
+
+// How long the sky holds still before its stars start coming and going
+const SETTLE_TICKS: f32 = 30.0 * 60.0 * TICKS_PER_SECOND as f32;
+// Average time between one star starting to fade out and the next
+const MEAN_GAP_TICKS: f32 = 3.0 * 60.0 * TICKS_PER_SECOND as f32;
+// How long a star takes to fade out, and its replacement to fade in
+const FADE_TICKS: f32 = 90.0 * TICKS_PER_SECOND as f32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Phase {
+    Retiring,
+    Rising,
+}
+
+/// A star on its way out, or its replacement on the way in.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Change {
+    star: usize,
+    phase: Phase,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lifecycle {
+    /// The stars that come and go, which no figure or cluster is built from
+    stars: Range<usize>,
+    /// Ticks until the next star starts to fade out
+    next: f32,
+    changing: Vec<Change>,
+}
+
+impl Lifecycle {
+    /// Retire and replace stars from the indices in `stars`.
+    pub fn new(stars: Range<usize>) -> Self {
+        Lifecycle {
+            stars,
+            next: SETTLE_TICKS,
+            changing: Vec::new(),
+        }
+    }
+
+    /// Fade stars out and in over `dt` ticks. A star that's gone is swapped
+    /// for one from `replace`, which fades in where that puts it; if it
+    /// can't find a place, the old star comes back.
+    pub fn update<R: Rng>(
+        &mut self,
+        dt: f32,
+        stars: &mut [Star],
+        rng: &mut R,
+        mut replace: impl FnMut(&mut R) -> Option<Star>,
+    ) {
+        self.next -= dt;
+        if self.next <= 0.0 && !self.stars.is_empty() {
+            self.next += rng.gen_range(0.5..1.5) * MEAN_GAP_TICKS;
+            let star = rng.gen_range(self.stars.clone());
+            if self.changing.iter().all(|change| change.star != star) {
+                self.changing.push(Change {
+                    star,
+                    phase: Phase::Retiring,
+                });
+            }
+        }
+
+        let step = dt / FADE_TICKS;
+        self.changing.retain_mut(|change| {
+            let star = &mut stars[change.star];
+            match change.phase {
+                Phase::Retiring => {
+                    star.presence = (star.presence - step).max(0.0);
+                    if star.presence == 0.0 {
+                        if let Some(new) = replace(rng) {
+                            *star = Star { presence: 0.0, ..new };
+                        }
+                        change.phase = Phase::Rising;
+                    }
+                    true
+                }
+                Phase::Rising => {
+                    star.presence = (star.presence + step).min(1.0);
+                    star.presence < 1.0
+                }
+            }
+        });
+    }
+}
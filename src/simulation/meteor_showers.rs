@@ -0,0 +1,114 @@
+//! Annual meteor showers and their radiants.
+
+use chrono::{Datelike, Local};
+use rand::Rng;
+
+pub struct MeteorShower {
+    pub name: &'static str,
+    /// Day of the year of peak activity
+    peak_day: u32,
+    /// Days either side of the peak over which activity falls off
+    spread_days: f32,
+    /// Zenithal hourly rate at the peak
+    zhr: f32,
+    /// Declination of the radiant in degrees
+    declination: f32,
+}
+
+// Peak dates drift by a day or so from year to year; these are typical
+const SHOWERS: &[MeteorShower] = &[
+    MeteorShower { name: "Quadrantids", peak_day: 3, spread_days: 1.0, zhr: 110.0, declination: 49.0 },
+    MeteorShower { name: "Lyrids", peak_day: 112, spread_days: 2.0, zhr: 18.0, declination: 34.0 },
+    MeteorShower { name: "Eta Aquariids", peak_day: 126, spread_days: 5.0, zhr: 50.0, declination: -1.0 },
+    MeteorShower { name: "Delta Aquariids", peak_day: 211, spread_days: 6.0, zhr: 25.0, declination: -16.0 },
+    MeteorShower { name: "Perseids", peak_day: 224, spread_days: 5.0, zhr: 100.0, declination: 58.0 },
+    MeteorShower { name: "Draconids", peak_day: 281, spread_days: 1.0, zhr: 10.0, declination: 54.0 },
+    MeteorShower { name: "Orionids", peak_day: 294, spread_days: 4.0, zhr: 20.0, declination: 16.0 },
+    MeteorShower { name: "Taurids", peak_day: 309, spread_days: 10.0, zhr: 10.0, declination: 14.0 },
+    MeteorShower { name: "Leonids", peak_day: 321, spread_days: 2.0, zhr: 15.0, declination: 22.0 },
+    MeteorShower { name: "Geminids", peak_day: 348, spread_days: 3.0, zhr: 150.0, declination: 33.0 },
+    MeteorShower { name: "Ursids", peak_day: 356, spread_days: 1.0, zhr: 10.0, declination: 75.0 },
+];
+
+/// Hourly rate of the sporadic meteors seen on any night, which shower
+/// rates are measured against.
+pub const SPORADIC_RATE: f32 = 20.0;
+
+// Fraction of peak activity below which a shower is lost among background
+// meteors
+const MIN_PROFILE: f32 = 0.2;
+
+impl MeteorShower {
+    pub fn by_name(name: &str) -> Option<&'static MeteorShower> {
+        SHOWERS.iter().find(|s| s.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Fraction of peak activity on `day_of_year`.
+    fn profile(&self, day_of_year: u32) -> f32 {
+        let diff = (day_of_year as f32 - self.peak_day as f32).abs();
+        let diff = diff.min(365.0 - diff);
+        (-(diff / self.spread_days).powi(2)).exp()
+    }
+
+    /// Meteors per hour on `day_of_year`.
+    pub fn hourly_rate(&self, day_of_year: u32) -> f32 {
+        self.profile(day_of_year) * self.zhr
+    }
+
+    /// Highest altitude in degrees the radiant reaches from `latitude`.
+    /// Negative if it never rises.
+    pub fn culmination(&self, latitude: f32) -> f32 {
+        90.0 - (latitude - self.declination).abs()
+    }
+
+    /// Meteors per hour at the peak.
+    pub fn peak_rate(&self) -> f32 {
+        self.zhr
+    }
+
+    /// The most active shower today with its hourly rate, if any is under way.
+    pub fn active_today() -> Option<(&'static MeteorShower, f32)> {
+        let today = Local::now().ordinal();
+        SHOWERS
+            .iter()
+            .filter(|s| s.profile(today) >= MIN_PROFILE)
+            .map(|s| (s, s.hourly_rate(today)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+/// A shower under way tonight and where its radiant sits on screen.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActiveShower {
+    #[cfg_attr(feature = "serde", serde(with = "crate::saved::by_name"))]
+    pub shower: &'static MeteorShower,
+    pub hourly_rate: f32,
+    pub radiant: (f32, f32),
+}
+
+impl ActiveShower {
+    /// Where a new shower meteor appears and the direction it travels.
+    /// Meteors are rarely seen right at the radiant; they light up a little
+    /// way out and streak directly away from it. Rows are `aspect` columns
+    /// tall.
+    pub fn meteor_path<R: Rng>(&self, rng: &mut R, aspect: f32) -> ((f32, f32), (f32, f32)) {
+        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+        let (dx, dy) = (angle.cos(), angle.sin() / aspect);
+        let distance = rng.gen_range(3.0..12.0);
+        let (rx, ry) = self.radiant;
+        ((rx + dx * distance, ry + dy * distance), (dx, dy))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl crate::saved::Named for MeteorShower {
+    const KIND: &'static str = "meteor shower";
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn all() -> &'static [Self] {
+        SHOWERS
+    }
+}
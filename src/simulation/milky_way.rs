@@ -0,0 +1,36 @@
+//! Where the real Milky Way runs: along the galactic plane, brightest
+//! towards the galaxy's centre in Sagittarius.
+
+// North galactic pole, J2000, degrees
+const POLE_RA: f64 = 192.85948;
+const POLE_DEC: f64 = 27.12825;
+// Galactic longitude of the north celestial pole, degrees
+const NORTH_POLE_LONGITUDE: f64 = 122.93192;
+// Half-width of the band in degrees of galactic latitude
+const WIDTH: f64 = 12.0;
+// How much fainter the band is opposite the galactic centre
+const ANTICENTRE_FADE: f64 = 0.5;
+
+/// Galactic latitude and longitude in degrees of the point at right
+/// ascension `ra` (hours) and declination `dec` (degrees).
+pub fn galactic(ra: f64, dec: f64) -> (f64, f64) {
+    let (pole_ra, pole_dec) = (POLE_RA.to_radians(), POLE_DEC.to_radians());
+    let dec = dec.to_radians();
+    let offset = (ra * 15.0).to_radians() - pole_ra;
+    let latitude =
+        (dec.sin() * pole_dec.sin() + dec.cos() * pole_dec.cos() * offset.cos()).asin();
+    let longitude = NORTH_POLE_LONGITUDE
+        - (dec.cos() * offset.sin())
+            .atan2(dec.sin() * pole_dec.cos() - dec.cos() * pole_dec.sin() * offset.cos())
+            .to_degrees();
+    (latitude.to_degrees(), longitude.rem_euclid(360.0))
+}
+
+/// How strongly the Milky Way shows at `ra` and `dec`, from 0 well away
+/// from it to 1 in the middle of the band towards the galactic centre.
+pub fn strength(ra: f64, dec: f64) -> f32 {
+    let (latitude, longitude) = galactic(ra, dec);
+    let band = (-(latitude / WIDTH).powi(2)).exp();
+    let towards_centre = (1.0 + longitude.to_radians().cos()) / 2.0;
+    (band * (1.0 - ANTICENTRE_FADE * (1.0 - towards_centre))) as f32
+}
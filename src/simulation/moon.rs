@@ -0,0 +1,106 @@
+//! The moon, at tonight's real phase.
+
+use crate::simulation::astro::{self, MoonPhase};
+use crate::simulation::eclipse::Eclipse;
+use crate::simulation::sky_model::SkyModel;
+use rand::Rng;
+use std::time::SystemTime;
+
+// Below this illuminated fraction the moon is lost in the twilight
+const MIN_ILLUMINATION: f64 = 0.05;
+// Magnitudes of starlight a full moon's glare drowns out right beside it,
+// and how far in rows the glare reaches
+const GLARE_MAGNITUDES: f32 = 4.0;
+const GLARE_RADIUS: f32 = 10.0;
+// Past this illuminated fraction the moon starts lighting the sky around
+// it, fully once it's full; and how far in rows that light reaches
+const SKYLIGHT_PHASE: f32 = 0.4;
+const SKYLIGHT_RADIUS: f32 = 16.0;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Moon {
+    pub phase: MoonPhase,
+    pub x: f32,
+    pub y: f32,
+    /// Seen from south of the equator, facing north, where the moon looks
+    /// upside down to a northerner: it waxes from the left
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub southern: bool,
+    /// The sky's cell aspect, to measure the glare round
+    #[cfg_attr(feature = "serde", serde(default = "crate::simulation::sky_model::default_aspect"))]
+    aspect: f32,
+    /// The Earth's shadow crossing it, during an eclipse
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub eclipse: Option<Eclipse>,
+}
+
+impl Moon {
+    /// Tonight's moon somewhere in the upper sky, as seen from `latitude`,
+    /// or `None` when it's too close to new to see. On the night of an
+    /// eclipse, it's eclipsed just as the real one is.
+    pub fn rise<R: Rng>(rng: &mut R, sky: &SkyModel, latitude: Option<f32>) -> Option<Moon> {
+        let jd = astro::julian_day(SystemTime::now());
+        Self::place(rng, sky, latitude, MoonPhase::at(jd), Eclipse::at(jd))
+    }
+
+    /// A full moon going into an eclipse straight away, to show one off.
+    pub fn eclipsed<R: Rng>(rng: &mut R, sky: &SkyModel, latitude: Option<f32>) -> Option<Moon> {
+        Self::place(rng, sky, latitude, MoonPhase::full(), Some(Eclipse::demo()))
+    }
+
+    fn place<R: Rng>(
+        rng: &mut R,
+        sky: &SkyModel,
+        latitude: Option<f32>,
+        phase: MoonPhase,
+        eclipse: Option<Eclipse>,
+    ) -> Option<Moon> {
+        let x = rng.gen_range(0.1..0.9) * sky.width as f32;
+        let altitude = rng.gen_range(25.0..65.0);
+        (phase.illumination() > MIN_ILLUMINATION).then(|| Moon {
+            phase,
+            x,
+            y: sky.row_for_altitude(altitude),
+            southern: latitude.is_some_and(|lat| lat < 0.0),
+            aspect: sky.aspect,
+            eclipse,
+        })
+    }
+
+    /// Strength of the moonlight: 0 at new moon, 1 at full. An eclipsed
+    /// moon gives barely any.
+    pub fn brightness(&self) -> f32 {
+        let shadow = self.eclipse.map_or(0.0, |eclipse| 0.95 * eclipse.coverage());
+        self.phase.illumination() as f32 * (1.0 - shadow)
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        if let Some(eclipse) = &mut self.eclipse {
+            eclipse.update(dt);
+        }
+        if self.eclipse.is_some_and(|eclipse| eclipse.is_over()) {
+            self.eclipse = None;
+        }
+    }
+
+    /// Magnitudes of starlight lost in the moon's glare at `(x, y)`, so faint
+    /// stars near a bright moon are washed out.
+    pub fn glare(&self, x: f32, y: f32) -> f32 {
+        let falloff = (1.0 - self.distance(x, y) / GLARE_RADIUS).max(0.0);
+        GLARE_MAGNITUDES * self.brightness() * falloff * falloff
+    }
+
+    /// How much the moon lights the sky at `(x, y)`, from 0 to 1 right
+    /// beside a full moon. A crescent's light is lost; a gibbous or full
+    /// moon's brightens the sky around it.
+    pub fn skylight(&self, x: f32, y: f32) -> f32 {
+        let bright = ((self.brightness() - SKYLIGHT_PHASE) / (1.0 - SKYLIGHT_PHASE)).clamp(0.0, 1.0);
+        let falloff = (1.0 - self.distance(x, y) / SKYLIGHT_RADIUS).max(0.0);
+        bright * falloff * falloff
+    }
+
+    /// Distance in rows from the moon to `(x, y)`, measured round.
+    fn distance(&self, x: f32, y: f32) -> f32 {
+        ((x - self.x) / self.aspect).hypot(y - self.y)
+    }
+}
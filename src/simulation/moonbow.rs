@@ -0,0 +1,31 @@
+//! A faint lunar rainbow that can follow a shower when the moon is bright.
+
+// Moonbows are far fainter than daytime rainbows
+const MAX_STRENGTH: f32 = 0.3;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Moonbow {
+    age: f32,
+    duration: f32,
+}
+
+impl Moonbow {
+    pub fn new(duration: f32) -> Self {
+        Moonbow { age: 0.0, duration }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.age += dt;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.age >= self.duration
+    }
+
+    /// Ease in over the first fifth and out over the last.
+    pub fn strength(&self) -> f32 {
+        let t = self.age / self.duration;
+        let envelope = (t * 5.0).min((1.0 - t) * 5.0).clamp(0.0, 1.0);
+        envelope * MAX_STRENGTH
+    }
+}
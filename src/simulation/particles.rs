@@ -0,0 +1,89 @@
+//! Small particle engine for foreground effects that drift with the wind.
+
+use crate::simulation::Rgb;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Particle {
+    pub x: f32,
+    pub y: f32,
+    /// Own velocity in cells per tick, before wind
+    pub vx: f32,
+    pub vy: f32,
+    /// How strongly the wind pushes this particle
+    pub wind_response: f32,
+    /// Side-to-side flutter amplitude in cells per tick
+    pub sway: f32,
+    pub phase: f32,
+    /// Animation frames advanced per tick
+    pub frame_rate: f32,
+    pub color: Rgb,
+    /// Ticks to linger after landing, fading out meanwhile
+    pub rest: f32,
+    pub age: f32,
+    pub landed_at: Option<f32>,
+}
+
+impl Particle {
+    fn alive(&self) -> bool {
+        self.landed_at
+            .map_or(true, |landed| self.age - landed < self.rest)
+    }
+
+    /// Fraction faded towards the background, `0.0` until landed.
+    pub fn fade(&self) -> f32 {
+        match self.landed_at {
+            Some(landed) if self.rest > 0.0 => ((self.age - landed) / self.rest).clamp(0.0, 1.0),
+            Some(_) => 1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Animation frames shown so far. Landed particles stop animating.
+    pub fn frame(&self) -> usize {
+        (self.landed_at.unwrap_or(self.age) * self.frame_rate) as usize
+    }
+}
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+}
+
+impl ParticleSystem {
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.particles.iter()
+    }
+
+    pub fn spawn(&mut self, particle: Particle) {
+        self.particles.push(particle);
+    }
+
+    /// Step every particle; those reaching `floor` come to rest there.
+    pub fn update(&mut self, dt: f32, wind: f32, width: u16, floor: f32) {
+        for p in &mut self.particles {
+            p.age += dt;
+            if p.landed_at.is_some() {
+                continue;
+            }
+            p.phase += 0.15 * dt;
+            p.x += (p.vx + wind * p.wind_response + p.phase.sin() * p.sway) * dt;
+            p.y += p.vy * dt;
+            if p.y >= floor - 1.0 {
+                p.y = floor - 1.0;
+                p.landed_at = Some(p.age);
+            }
+        }
+        let width = width as f32;
+        self.particles
+            .retain(|p| p.alive() && p.x > -2.0 && p.x < width + 2.0);
+    }
+}
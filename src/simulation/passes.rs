@@ -0,0 +1,203 @@
+//! Real satellites going over: visible passes predicted from the bright
+//! satellites' orbital elements for the observer's place, then flown where
+//! and when they really happen, or on a clock running faster than the real
+//! one so a night's worth come by in a few minutes.
+
+use crate::simulation::astro;
+use crate::simulation::iss::{self, Sgp4, Tle};
+use crate::simulation::projection::Observer;
+use crate::simulation::sky_model::SkyModel;
+
+// Days between the positions checked while looking for passes
+const STEP: f64 = 30.0 / 86_400.0;
+// Degrees a pass must climb to be worth watching for
+const MIN_ALTITUDE: f64 = 10.0;
+// Degrees the sun must be below the horizon for a lit satellite to show
+const DARK: f64 = -6.0;
+// The longest name kept for a satellite, as long as CelesTrak's run
+const MAX_NAME: usize = 24;
+
+/// Every element set in `text`, each named by the line before its own two,
+/// as in CelesTrak's lists. Sets that don't parse are skipped. Names are
+/// drawn on the sky, so only their printable characters are kept, and no
+/// more than 24 of them.
+pub fn elements(text: &str) -> Vec<(String, Tle)> {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).filter(|line| !line.is_empty()).collect();
+    lines
+        .windows(3)
+        .filter(|set| set[1].starts_with("1 ") && set[2].starts_with("2 "))
+        .filter_map(|set| {
+            let tle = format!("{}\n{}", set[1], set[2]).parse().ok()?;
+            let name: String = set[0].trim().chars().filter(|c| !c.is_control()).take(MAX_NAME).collect();
+            Some((name.trim_end().to_string(), tle))
+        })
+        .collect()
+}
+
+/// One satellite going over, lit by the sun against a dark sky for some of
+/// the time it's up.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pass {
+    pub name: String,
+    orbit: Sgp4,
+    /// Julian days it rises and sets
+    pub rise: f64,
+    pub set: f64,
+}
+
+/// What's known of a satellite's next visible pass.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NextPass {
+    /// There are no orbital elements for it to go on
+    Unknown,
+    /// None comes in the time predicted
+    NoneSoon,
+    /// It rises at this Julian day
+    At(f64),
+}
+
+/// The passes worth watching of the satellite on `orbit` from `observer`
+/// over the `days` after Julian day `from`, as the Julian days each rises
+/// and sets.
+pub fn visible(orbit: &Sgp4, observer: &Observer, from: f64, days: f64) -> Vec<(f64, f64)> {
+    let steps = (days / STEP).ceil() as usize;
+    let mut passes = Vec::new();
+    let mut rise = None;
+    let mut seen = false;
+    for step in 0..=steps {
+        let jd = from + step as f64 * STEP;
+        let Some(position) = orbit.position(jd) else {
+            break;
+        };
+        let (altitude, _) = iss::look(position, observer, jd);
+        if altitude > 0.0 {
+            rise.get_or_insert(jd);
+            if !seen && altitude >= MIN_ALTITUDE && iss::is_sunlit(position, jd) {
+                seen = astro::sun_altitude(jd, observer.latitude, observer.longitude) < DARK;
+            }
+        } else if let Some(rise) = rise.take() {
+            if seen {
+                passes.push((rise, jd));
+            }
+            seen = false;
+        }
+    }
+    passes
+}
+
+/// The passes worth watching from `observer` over the `days` after Julian
+/// day `from`, soonest first.
+pub fn predict(elements: &[(String, Tle)], observer: &Observer, from: f64, days: f64) -> Vec<Pass> {
+    let mut passes = Vec::new();
+    for (name, tle) in elements {
+        let Some(orbit) = Sgp4::new(tle) else {
+            continue;
+        };
+        passes.extend(visible(&orbit, observer, from, days).into_iter().map(|(rise, set)| Pass {
+            name: name.clone(),
+            orbit: orbit.clone(),
+            rise,
+            set,
+        }));
+    }
+    passes.sort_by(|a, b| a.rise.total_cmp(&b.rise));
+    passes
+}
+
+/// A satellite on one of the passes, where it stands now.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Passing {
+    pub name: String,
+    pub x: f32,
+    pub y: f32,
+    /// Degrees above the horizon
+    pub altitude: f32,
+    /// Lit by the sun, and so shining, rather than in the Earth's shadow
+    pub sunlit: bool,
+}
+
+/// The passes still to come, on a clock of their own.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Passes {
+    observer: Observer,
+    /// Julian day on the passes' clock at sky time `anchor` seconds
+    start: f64,
+    anchor: f32,
+    /// How many times as fast as the real clock the passes come round
+    speed: f32,
+    upcoming: Vec<Pass>,
+    passing: Vec<Passing>,
+}
+
+impl Passes {
+    /// Fly `passes` as seen by `observer`, with the clock at Julian day
+    /// `start` `anchor` seconds into the sky and running `speed` times as
+    /// fast as the real one from there.
+    pub fn new(passes: Vec<Pass>, observer: Observer, start: f64, anchor: f32, speed: f32) -> Self {
+        Passes {
+            observer,
+            start,
+            anchor,
+            speed,
+            upcoming: passes,
+            passing: Vec::new(),
+        }
+    }
+
+    /// Fly `passes` too, once those already to come have gone over.
+    pub fn extend(&mut self, passes: Vec<Pass>) {
+        let last = self.upcoming.last().map_or(f64::NEG_INFINITY, |pass| pass.rise);
+        self.upcoming.extend(passes.into_iter().filter(|pass| pass.rise > last));
+    }
+
+    /// The Julian day by this clock `seconds` into the sky.
+    pub fn jd(&self, seconds: f32) -> f64 {
+        self.start + (seconds - self.anchor) as f64 * self.speed as f64 / 86_400.0
+    }
+
+    /// When the satellite whose name starts with `name` next rises, after
+    /// `seconds` into the sky, by the real clock.
+    pub fn next(&self, name: &str, seconds: f32) -> NextPass {
+        let jd = self.jd(seconds);
+        self.upcoming
+            .iter()
+            .find(|pass| pass.rise > jd && pass.name.starts_with(name))
+            .map_or(NextPass::NoneSoon, |pass| NextPass::At(pass.rise))
+    }
+
+    /// The satellites up now.
+    pub fn passing(&self) -> &[Passing] {
+        &self.passing
+    }
+
+    /// Move each satellite up `seconds` into the sky to where it stands,
+    /// returning how many have just risen.
+    pub fn follow(&mut self, sky: &SkyModel, seconds: f32) -> usize {
+        let jd = self.jd(seconds);
+        let was_up = std::mem::take(&mut self.passing);
+        self.upcoming.retain(|pass| pass.set > jd);
+        self.passing = self
+            .upcoming
+            .iter()
+            .filter(|pass| pass.rise <= jd)
+            .filter_map(|pass| {
+                let position = pass.orbit.position(jd)?;
+                let (altitude, azimuth) = iss::look(position, &self.observer, jd);
+                let (x, y) = self.observer.cell(sky, altitude, azimuth);
+                (altitude > 0.0).then(|| Passing {
+                    name: pass.name.clone(),
+                    x,
+                    y,
+                    altitude: altitude as f32,
+                    sunlit: iss::is_sunlit(position, jd),
+                })
+            })
+            .collect();
+        // Counted by name, since one may set in the same step another rises
+        self.passing
+            .iter()
+            .filter(|now| !was_up.iter().any(|then| then.name == now.name))
+            .count()
+    }
+}
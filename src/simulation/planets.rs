@@ -0,0 +1,205 @@
+//! Where the naked-eye planets really are, from their orbits.
+//!
+//! Each orbit is given by JPL's approximate Keplerian elements, good to a
+//! fraction of a degree between 1800 and 2050, which is far finer than a
+//! terminal cell. Positions come out as right ascension and declination, so
+//! planets go through the same [`projection`](crate::simulation::projection)
+//! as the catalog stars.
+
+use crate::simulation::landmarks::{Planet, PLANETS};
+use crate::simulation::projection::Observer;
+use crate::simulation::sky_model::SkyModel;
+
+// Julian day of the J2000.0 epoch
+const J2000: f64 = 2451545.0;
+// Tilt of the Earth's axis against the ecliptic, degrees
+const OBLIQUITY: f64 = 23.43928;
+
+/// Orbital elements at J2000, each followed by its change per century.
+struct Elements {
+    /// Semi-major axis, AU
+    a: (f64, f64),
+    /// Eccentricity
+    e: (f64, f64),
+    /// Inclination, degrees
+    i: (f64, f64),
+    /// Mean longitude, degrees
+    l: (f64, f64),
+    /// Longitude of perihelion, degrees
+    perihelion: (f64, f64),
+    /// Longitude of the ascending node, degrees
+    node: (f64, f64),
+}
+
+/// A planet and the orbit it follows.
+pub struct Orbit {
+    pub planet: &'static Planet,
+    elements: Elements,
+    /// Magnitude at 1 AU from both the sun and the Earth
+    absolute: f32,
+}
+
+pub const ORBITS: &[Orbit] = &[
+    Orbit {
+        planet: &PLANETS[0],
+        elements: Elements {
+            a: (0.38709927, 0.00000037),
+            e: (0.20563593, 0.00001906),
+            i: (7.00497902, -0.00594749),
+            l: (252.25032350, 149472.67411175),
+            perihelion: (77.45779628, 0.16047689),
+            node: (48.33076593, -0.12534081),
+        },
+        absolute: -0.42,
+    },
+    Orbit {
+        planet: &PLANETS[1],
+        elements: Elements {
+            a: (0.72333566, 0.00000390),
+            e: (0.00677672, -0.00004107),
+            i: (3.39467605, -0.00078890),
+            l: (181.97909950, 58517.81538729),
+            perihelion: (131.60246718, 0.00268329),
+            node: (76.67984255, -0.27769418),
+        },
+        absolute: -4.40,
+    },
+    Orbit {
+        planet: &PLANETS[2],
+        elements: Elements {
+            a: (1.52371034, 0.00001847),
+            e: (0.09339410, 0.00007882),
+            i: (1.84969142, -0.00813131),
+            l: (-4.55343205, 19140.30268499),
+            perihelion: (-23.94362959, 0.44441088),
+            node: (49.55953891, -0.29257343),
+        },
+        absolute: -1.52,
+    },
+    Orbit {
+        planet: &PLANETS[3],
+        elements: Elements {
+            a: (5.20288700, -0.00011607),
+            e: (0.04838624, -0.00013253),
+            i: (1.30439695, -0.00183714),
+            l: (34.39644051, 3034.74612775),
+            perihelion: (14.72847983, 0.21252668),
+            node: (100.47390909, 0.20469106),
+        },
+        absolute: -9.40,
+    },
+    Orbit {
+        planet: &PLANETS[4],
+        elements: Elements {
+            a: (9.53667594, -0.00125060),
+            e: (0.05386179, -0.00050991),
+            i: (2.48599187, 0.00193609),
+            l: (49.95424423, 1222.49362201),
+            perihelion: (92.59887831, -0.41897216),
+            node: (113.66242448, -0.28867794),
+        },
+        absolute: -8.88,
+    },
+];
+
+// The Earth-Moon barycentre, which the planets are seen from
+const EARTH: Elements = Elements {
+    a: (1.00000261, 0.00000562),
+    e: (0.01671123, -0.00004392),
+    i: (-0.00001531, -0.01294668),
+    l: (100.46457166, 35999.37244981),
+    perihelion: (102.93768193, 0.32327364),
+    node: (0.0, 0.0),
+};
+
+impl Elements {
+    /// Heliocentric ecliptic position in AU, `centuries` after J2000.
+    fn position(&self, centuries: f64) -> [f64; 3] {
+        let at = |(value, rate): (f64, f64)| value + rate * centuries;
+        let (a, e) = (at(self.a), at(self.e));
+        let (i, node) = (at(self.i).to_radians(), at(self.node).to_radians());
+        let perihelion = at(self.perihelion);
+        let argument = (perihelion - at(self.node)).to_radians();
+        let mean_anomaly = (at(self.l) - perihelion).rem_euclid(360.0).to_radians();
+
+        // Kepler's equation, by Newton's method
+        let mut eccentric = mean_anomaly + e * mean_anomaly.sin();
+        for _ in 0..6 {
+            eccentric -= (eccentric - e * eccentric.sin() - mean_anomaly) / (1.0 - e * eccentric.cos());
+        }
+        let x = a * (eccentric.cos() - e);
+        let y = a * (1.0 - e * e).sqrt() * eccentric.sin();
+
+        let (sw, cw) = argument.sin_cos();
+        let (sn, cn) = node.sin_cos();
+        let (si, ci) = i.sin_cos();
+        [
+            (cw * cn - sw * sn * ci) * x + (-sw * cn - cw * sn * ci) * y,
+            (cw * sn + sw * cn * ci) * x + (-sw * sn + cw * cn * ci) * y,
+            sw * si * x + cw * si * y,
+        ]
+    }
+}
+
+impl Orbit {
+    /// Right ascension (hours), declination (degrees) and magnitude as seen
+    /// from the Earth at Julian day `jd`. The magnitude leaves out the
+    /// planet's phase, so Mercury and Venus come out a little bright when
+    /// they're crescents.
+    pub fn apparent(&self, jd: f64) -> (f64, f64, f32) {
+        let centuries = (jd - J2000) / 36525.0;
+        let planet = self.elements.position(centuries);
+        let earth = EARTH.position(centuries);
+        let [x, y, z] = [planet[0] - earth[0], planet[1] - earth[1], planet[2] - earth[2]];
+
+        let (se, ce) = OBLIQUITY.to_radians().sin_cos();
+        let (ye, ze) = (y * ce - z * se, y * se + z * ce);
+        let ra = ye.atan2(x).to_degrees().rem_euclid(360.0) / 15.0;
+        let dec = ze.atan2(x.hypot(ye)).to_degrees();
+
+        let sun = planet.iter().map(|c| c * c).sum::<f64>().sqrt();
+        let distance = (x * x + y * y + z * z).sqrt();
+        let magnitude = self.absolute + 5.0 * (sun * distance).log10() as f32;
+        (ra, dec, magnitude)
+    }
+}
+
+/// A planet where it stands in the sky.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SkyPlanet {
+    #[cfg_attr(feature = "serde", serde(with = "crate::saved::by_name"))]
+    pub planet: &'static Planet,
+    pub x: f32,
+    pub y: f32,
+    /// Degrees above the horizon; negative once it has set
+    pub altitude: f32,
+    pub magnitude: f32,
+}
+
+/// Every planet where it stands at Julian day `jd`, in orbit order, set
+/// ones included.
+pub fn place(sky: &SkyModel, observer: &Observer, jd: f64) -> Vec<SkyPlanet> {
+    let mut planets: Vec<SkyPlanet> = ORBITS
+        .iter()
+        .map(|orbit| SkyPlanet {
+            planet: orbit.planet,
+            x: 0.0,
+            y: 0.0,
+            altitude: 0.0,
+            magnitude: 0.0,
+        })
+        .collect();
+    follow(&mut planets, sky, observer, jd);
+    planets
+}
+
+/// Move `planets` to where they stand at Julian day `jd`.
+pub fn follow(planets: &mut [SkyPlanet], sky: &SkyModel, observer: &Observer, jd: f64) {
+    for (planet, orbit) in planets.iter_mut().zip(ORBITS) {
+        let (ra, dec, magnitude) = orbit.apparent(jd);
+        let (altitude, azimuth) = observer.horizontal(ra, dec, jd);
+        (planet.x, planet.y) = observer.cell(sky, altitude, azimuth);
+        planet.altitude = altitude as f32;
+        planet.magnitude = magnitude;
+    }
+}
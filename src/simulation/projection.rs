@@ -0,0 +1,95 @@
+//! Where the real sky stands: from right ascension and declination to
+//! altitude and azimuth for an observer at a place and time, and from there
+//! to the sky's cells.
+//!
+//! The whole horizon is spread across the sky's width, so the sky wraps
+//! just as it does for everything else, with the point facing the equator
+//! in the middle.
+
+use crate::simulation::astro;
+use crate::simulation::sky_model::SkyModel;
+use chrono::Local;
+use std::time::SystemTime;
+
+// Latitude assumed when none is given
+const DEFAULT_LATITUDE: f64 = 45.0;
+
+/// Where the sky is seen from.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Observer {
+    /// Degrees, north positive
+    pub latitude: f64,
+    /// Degrees, east positive
+    pub longitude: f64,
+}
+
+impl Observer {
+    /// An observer at `latitude` and `longitude`, where given. Without a
+    /// longitude the clock's time zone stands in for it, which is rarely
+    /// more than a few degrees out.
+    pub fn new(latitude: Option<f32>, longitude: Option<f32>) -> Self {
+        let zone = Local::now().offset().local_minus_utc() as f64 / 240.0;
+        Observer {
+            latitude: latitude.map_or(DEFAULT_LATITUDE, f64::from),
+            longitude: longitude.map_or(zone, f64::from),
+        }
+    }
+
+    /// Local sidereal time in degrees at Julian day `jd`.
+    pub fn sidereal(&self, jd: f64) -> f64 {
+        astro::sidereal(jd, self.longitude)
+    }
+
+    /// Altitude and azimuth in degrees, azimuth east of north, of the point
+    /// at right ascension `ra` (hours) and declination `dec` (degrees) at
+    /// Julian day `jd`.
+    pub fn horizontal(&self, ra: f64, dec: f64, jd: f64) -> (f64, f64) {
+        let hour_angle = (self.sidereal(jd) - ra * 15.0).to_radians();
+        let (lat, dec) = (self.latitude.to_radians(), dec.to_radians());
+        let altitude = (lat.sin() * dec.sin() + lat.cos() * dec.cos() * hour_angle.cos()).asin();
+        let azimuth = hour_angle
+            .sin()
+            .atan2(hour_angle.cos() * lat.sin() - dec.tan() * lat.cos())
+            .to_degrees()
+            + 180.0;
+        (altitude.to_degrees(), azimuth.rem_euclid(360.0))
+    }
+
+    /// Right ascension (hours) and declination (degrees) of the point at
+    /// `altitude` and `azimuth` at Julian day `jd`; the reverse of
+    /// [`Observer::horizontal`].
+    pub fn equatorial(&self, altitude: f64, azimuth: f64, jd: f64) -> (f64, f64) {
+        let lat = self.latitude.to_radians();
+        let (sa, ca) = altitude.to_radians().sin_cos();
+        let (sz, cz) = azimuth.to_radians().sin_cos();
+        let dec = (lat.sin() * sa + lat.cos() * ca * cz).asin();
+        let hour_angle = (-sz * ca).atan2(sa * lat.cos() - lat.sin() * ca * cz);
+        let ra = (self.sidereal(jd) - hour_angle.to_degrees()).rem_euclid(360.0) / 15.0;
+        (ra, dec.to_degrees())
+    }
+
+    /// The sky position `(x, y)` of a point at `altitude` and `azimuth`.
+    /// Points below the horizon fall beneath the sky's bottom edge.
+    pub fn cell(&self, sky: &SkyModel, altitude: f64, azimuth: f64) -> (f32, f32) {
+        // Face the equator, where most of the sky turns past
+        let facing = if self.latitude < 0.0 { 0.0 } else { 180.0 };
+        let width = sky.width.max(1) as f64;
+        let x = (((azimuth - facing) / 360.0 + 0.5) * width).rem_euclid(width);
+        (x as f32, sky.row_for_altitude(altitude as f32))
+    }
+
+    /// Altitude and azimuth of the sky position `(x, y)`; the reverse of
+    /// [`Observer::cell`].
+    pub fn direction(&self, sky: &SkyModel, x: f32, y: f32) -> (f64, f64) {
+        let facing = if self.latitude < 0.0 { 0.0 } else { 180.0 };
+        let width = sky.width.max(1) as f64;
+        let azimuth = ((x as f64 / width - 0.5) * 360.0 + facing).rem_euclid(360.0);
+        (sky.altitude_at(y) as f64, azimuth)
+    }
+}
+
+/// Julian day right now.
+pub fn now() -> f64 {
+    astro::julian_day(SystemTime::now())
+}
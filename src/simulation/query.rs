@@ -0,0 +1,52 @@
+//! Finding things in the sky by place or by name, for front ends that build
+//! their own overlays and interactions on a simulation.
+
+/// A rectangle of the sky in cells: its top left corner and its size. The
+/// sky wraps around horizontally but a region doesn't, so one reaching past
+/// the right edge doesn't pick up anything at the left.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Region {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Region {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Region { x, y, width, height }
+    }
+
+    /// Whether the sky point `(x, y)` lies in the region.
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+}
+
+/// A star or constellation found by name: its full name and the stars it's
+/// made of, as indices into [`crate::simulation::Simulation::stars`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match {
+    pub name: &'static str,
+    pub stars: Vec<usize>,
+}
+
+/// The candidate `query` names, ignoring case: one named exactly that, or
+/// failing that the first whose name starts with it.
+pub fn best(query: &str, candidates: impl IntoIterator<Item = Match>) -> Option<Match> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    let mut prefix = None;
+    for candidate in candidates {
+        let name = candidate.name.to_lowercase();
+        if name == query {
+            return Some(candidate);
+        }
+        if prefix.is_none() && name.starts_with(&query) {
+            prefix = Some(candidate);
+        }
+    }
+    prefix
+}
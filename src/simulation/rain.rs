@@ -0,0 +1,124 @@
+//! Passing rain showers, or rain and snow following the weather outside.
+
+use crate::simulation::particles::{Particle, ParticleSystem};
+use crate::simulation::Rgb;
+use rand::Rng;
+
+const DROP_COLOR: Rgb = (90, 110, 150);
+const FLAKE_COLOR: Rgb = (215, 220, 235);
+// How hard a passing shower falls, on the scale of the weather outside
+const SHOWER_INTENSITY: f32 = 0.5;
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rain {
+    /// Ticks left in the current shower, if one is falling
+    remaining: Option<f32>,
+    pub drops: ParticleSystem,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub flakes: ParticleSystem,
+    /// How hard it falls and whether as snow, while following the weather
+    /// outside rather than in showers
+    #[cfg_attr(feature = "serde", serde(default))]
+    following: Option<(f32, bool)>,
+}
+
+impl Rain {
+    pub fn is_raining(&self) -> bool {
+        self.remaining.is_some()
+    }
+
+    /// Whether what's falling is snow.
+    pub fn is_snow(&self) -> bool {
+        self.following.is_some_and(|(_, snow)| snow)
+    }
+
+    /// Start a one to three minute shower.
+    pub fn start<R: Rng>(&mut self, rng: &mut R) {
+        self.following = None;
+        self.remaining = Some(rng.gen_range(60.0..180.0) * crate::simulation::TICKS_PER_SECOND as f32);
+    }
+
+    /// Fall as hard as `intensity`, from 0 to 1, for as long as it's above
+    /// zero, instead of in showers. Once it's zero the rain ends on the
+    /// next step.
+    pub fn follow(&mut self, intensity: f32, snow: bool) {
+        self.following = Some((intensity, snow));
+        self.remaining = if intensity > 0.0 {
+            // Finite, as some save formats have no infinity
+            Some(f32::MAX)
+        } else {
+            self.remaining.map(|_| 0.0)
+        };
+    }
+
+    /// Step the shower; returns `true` on the tick a shower ends.
+    pub fn update<R: Rng>(
+        &mut self,
+        rng: &mut R,
+        dt: f32,
+        wind: f32,
+        width: u16,
+        ground: f32,
+    ) -> bool {
+        let mut ended = false;
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= dt;
+            if *remaining <= 0.0 {
+                self.remaining = None;
+                ended = true;
+            }
+        }
+
+        if self.is_raining() && width > 0 {
+            let (intensity, snow) = self.following.unwrap_or((SHOWER_INTENSITY, false));
+            for _ in 0..((width / 12).max(1)) {
+                if rng.gen::<f32>() < intensity * dt {
+                    if snow {
+                        self.flakes.spawn(flake(rng, width));
+                    } else {
+                        self.drops.spawn(drop(rng, width));
+                    }
+                }
+            }
+        }
+        self.drops.update(dt, wind, width, ground);
+        self.flakes.update(dt, wind, width, ground);
+        ended
+    }
+}
+
+fn drop<R: Rng>(rng: &mut R, width: u16) -> Particle {
+    Particle {
+        x: rng.gen_range(0.0..width as f32),
+        y: 0.0,
+        vx: 0.0,
+        vy: rng.gen_range(1.0..1.6),
+        wind_response: 4.0,
+        sway: 0.0,
+        phase: 0.0,
+        frame_rate: 0.0,
+        color: DROP_COLOR,
+        rest: 0.0,
+        age: 0.0,
+        landed_at: None,
+    }
+}
+
+fn flake<R: Rng>(rng: &mut R, width: u16) -> Particle {
+    Particle {
+        x: rng.gen_range(0.0..width as f32),
+        y: 0.0,
+        vx: 0.0,
+        vy: rng.gen_range(0.12..0.25),
+        wind_response: 6.0,
+        sway: rng.gen_range(0.03..0.08),
+        phase: rng.gen_range(0.0..std::f32::consts::TAU),
+        frame_rate: 0.0,
+        color: FLAKE_COLOR,
+        // Settle on the ground a moment before melting away
+        rest: rng.gen_range(1.0..2.0) * crate::simulation::TICKS_PER_SECOND as f32,
+        age: 0.0,
+        landed_at: None,
+    }
+}
@@ -0,0 +1,145 @@
+//! The slow turn of a random sky's stars about the celestial pole, like the
+//! arcs of a long exposure. A catalog sky's real stars turn by the true
+//! sidereal time instead.
+
+use crate::simulation::sky_model::SkyModel;
+use crate::simulation::{Star, TICKS_PER_SECOND};
+use rand::Rng;
+use std::f32::consts::{PI, TAU};
+
+// Seconds for the stars to go once round the pole: a sidereal day
+const SIDEREAL_DAY: f32 = 86_164.1;
+// Altitude of the pole when the latitude isn't known, near the top of the
+// sky
+const DEFAULT_POLE_ALTITUDE: f32 = 70.0;
+// Where stars turned past the sides or the top of the sky are held, out of
+// sight like those that have set
+const OUT_OF_SIGHT: f32 = -1.0;
+
+/// A point's place about the pole, measured in rows out from it and radians
+/// anticlockwise from the right.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polar {
+    pub radius: f32,
+    pub angle: f32,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rotation {
+    /// Where the pole sits in the sky
+    pole: (f32, f32),
+    /// The sky's cell aspect: a column is this much less far from the pole
+    /// than a row
+    #[cfg_attr(feature = "serde", serde(default = "crate::simulation::sky_model::default_aspect"))]
+    aspect: f32,
+    /// Radians turned per tick, anticlockwise when positive
+    rate: f32,
+    /// Radians turned so far
+    angle: f32,
+    /// Each star's place about the pole before any turning
+    stars: Vec<Polar>,
+}
+
+impl Rotation {
+    /// Turning `speed` times as fast as the real sky. The pole sits as high
+    /// as `latitude` puts it: Polaris, with the stars wheeling anticlockwise
+    /// about it, or south of the equator the south pole, with them wheeling
+    /// clockwise.
+    pub fn new(sky: &SkyModel, latitude: Option<f32>, speed: f32) -> Self {
+        let altitude = latitude.map_or(DEFAULT_POLE_ALTITUDE, f32::abs);
+        let sense = if latitude.unwrap_or(0.0) < 0.0 { -1.0 } else { 1.0 };
+        Rotation {
+            pole: (sky.width as f32 / 2.0, sky.row_for_altitude(altitude)),
+            aspect: sky.aspect,
+            rate: sense * speed * TAU / SIDEREAL_DAY / TICKS_PER_SECOND as f32,
+            angle: 0.0,
+            stars: Vec::new(),
+        }
+    }
+
+    /// Where the sky point `(x, y)` lies about the pole.
+    pub fn polar(&self, x: f32, y: f32) -> Polar {
+        let dx = (x - self.pole.0) / self.aspect;
+        let dy = self.pole.1 - y;
+        Polar {
+            radius: dx.hypot(dy),
+            angle: dy.atan2(dx),
+        }
+    }
+
+    /// Where a point placed at `polar` before any turning has turned to.
+    pub fn position(&self, polar: Polar) -> (f32, f32) {
+        let (sin, cos) = (polar.angle + self.angle).sin_cos();
+        (
+            self.pole.0 + polar.radius * cos * self.aspect,
+            self.pole.1 - polar.radius * sin,
+        )
+    }
+
+    /// Where the point now at `(x, y)` was before any turning, for things
+    /// fixed among the stars like the Milky Way; the reverse of
+    /// [`Rotation::position`].
+    pub fn unturned(&self, x: f32, y: f32) -> (f32, f32) {
+        let polar = self.polar(x, y);
+        let (sin, cos) = (polar.angle - self.angle).sin_cos();
+        (
+            self.pole.0 + polar.radius * cos * self.aspect,
+            self.pole.1 - polar.radius * sin,
+        )
+    }
+
+    /// Random points over the ring the region from `(left, top)` to
+    /// `(right, bottom)` sweeps through as the sky turns, outside the region
+    /// itself, as densely as `count` points spread over it.
+    pub fn sweep<R: Rng>(&self, rng: &mut R, region: (f32, f32, f32, f32), count: usize) -> Vec<(f32, f32)> {
+        let (left, top, right, bottom) = region;
+        let area = (right - left) * (bottom - top);
+        if count == 0 || area <= 0.0 {
+            return Vec::new();
+        }
+        let inside = |x: f32, y: f32| (left..right).contains(&x) && (top..bottom).contains(&y);
+        let nearest = self.polar(self.pole.0.clamp(left, right), self.pole.1.clamp(top, bottom));
+        let farthest = [(left, top), (right, top), (left, bottom), (right, bottom)]
+            .into_iter()
+            .map(|(x, y)| self.polar(x, y).radius)
+            .fold(0.0, f32::max);
+        let (near, far) = (nearest.radius.powi(2), farthest.powi(2));
+        let ring = PI * (far - near) * self.aspect;
+        let total = (count as f32 * ring / area) as usize;
+        (0..total)
+            .map(|_| {
+                self.position(Polar {
+                    radius: rng.gen_range(near..=far).sqrt(),
+                    angle: rng.gen_range(0.0..TAU),
+                })
+            })
+            .filter(|&(x, y)| !inside(x, y))
+            .collect()
+    }
+
+    /// Hold `stars` where they stand now, to turn from there.
+    pub fn hold(&mut self, stars: &mut [Star], sky: &SkyModel) {
+        self.stars = stars.iter().map(|star| self.polar(star.x, star.y)).collect();
+        self.turn(stars, sky);
+    }
+
+    pub fn update(&mut self, dt: f32, stars: &mut [Star], sky: &SkyModel) {
+        self.angle = (self.angle + self.rate * dt).rem_euclid(TAU);
+        self.turn(stars, sky);
+    }
+
+    /// Move each star to where it has turned to.
+    fn turn(&self, stars: &mut [Star], sky: &SkyModel) {
+        for (star, &polar) in stars.iter_mut().zip(&self.stars) {
+            let (x, y) = self.position(polar);
+            star.x = x;
+            star.y = y;
+            star.altitude = if (0.0..sky.width as f32).contains(&x) && y >= 0.0 {
+                sky.altitude_at(y)
+            } else {
+                OUT_OF_SIGHT
+            };
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Well-known satellites bright enough to spot with the naked eye.
+//!
+//! There is no TLE data to propagate, so each entry carries only a typical
+//! orbital altitude, which is enough for speed and period.
+
+use rand::Rng;
+
+// Earth's gravitational parameter (km³/s²) and mean radius (km)
+const GM: f32 = 398_600.4;
+const EARTH_RADIUS: f32 = 6371.0;
+
+#[derive(Debug)]
+pub struct CatalogEntry {
+    pub name: &'static str,
+    /// Typical altitude above the surface in km
+    pub altitude_km: f32,
+    /// Magnitude on a good pass, high in a dark sky
+    pub magnitude: f32,
+}
+
+// Launched by the dozen, and seen in long trains soon after
+const STARLINK: CatalogEntry = CatalogEntry { name: "STARLINK", altitude_km: 550.0, magnitude: 4.0 };
+
+const CATALOG: &[CatalogEntry] = &[
+    CatalogEntry { name: "ISS (ZARYA)", altitude_km: 420.0, magnitude: -3.0 },
+    CatalogEntry { name: "CSS (TIANHE)", altitude_km: 390.0, magnitude: -1.5 },
+    CatalogEntry { name: "HST", altitude_km: 530.0, magnitude: 1.5 },
+    STARLINK,
+    CatalogEntry { name: "SL-16 R/B", altitude_km: 840.0, magnitude: 2.0 },
+    CatalogEntry { name: "ENVISAT", altitude_km: 765.0, magnitude: 2.5 },
+    CatalogEntry { name: "NOAA 19", altitude_km: 870.0, magnitude: 4.5 },
+];
+
+impl CatalogEntry {
+    pub fn random<R: Rng>(rng: &mut R) -> &'static CatalogEntry {
+        &CATALOG[rng.gen_range(0..CATALOG.len())]
+    }
+
+    pub fn starlink() -> &'static CatalogEntry {
+        &STARLINK
+    }
+
+    fn orbit_radius(&self) -> f32 {
+        EARTH_RADIUS + self.altitude_km
+    }
+
+    /// Speed along a circular orbit in km/s.
+    pub fn speed_km_s(&self) -> f32 {
+        (GM / self.orbit_radius()).sqrt()
+    }
+
+    /// Time for one orbit in minutes.
+    pub fn period_minutes(&self) -> f32 {
+        std::f32::consts::TAU * (self.orbit_radius().powi(3) / GM).sqrt() / 60.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl crate::saved::Named for CatalogEntry {
+    const KIND: &'static str = "satellite";
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn all() -> &'static [Self] {
+        CATALOG
+    }
+}
@@ -0,0 +1,73 @@
+//! Mapping between screen cells and sky altitude.
+//!
+//! The sky occupies the rows above the horizon line. The top edge is the
+//! zenith and altitude falls linearly to 0° at the horizon. Positions are
+//! continuous, so `y = 2.5` is the middle of row 2.
+
+use rand::Rng;
+
+/// How many times taller than wide a terminal cell usually is.
+pub const DEFAULT_ASPECT: f32 = 2.0;
+
+#[cfg(feature = "serde")]
+pub(crate) fn default_aspect() -> f32 {
+    DEFAULT_ASPECT
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SkyModel {
+    pub width: u16,
+    /// First row that belongs to the ground rather than the sky
+    pub horizon_y: u16,
+    /// How many times taller than wide a cell is, so a row spans this many
+    /// columns' worth of sky and circles come out round
+    #[cfg_attr(feature = "serde", serde(default = "default_aspect"))]
+    pub aspect: f32,
+}
+
+impl SkyModel {
+    pub fn new(width: u16, height: u16, aspect: f32) -> Self {
+        SkyModel {
+            width,
+            horizon_y: height,
+            aspect,
+        }
+    }
+
+    /// Altitude in degrees at vertical position `y`.
+    pub fn altitude_at(&self, y: f32) -> f32 {
+        if self.horizon_y == 0 {
+            return 0.0;
+        }
+        90.0 * (self.horizon_y as f32 - y) / self.horizon_y as f32
+    }
+
+    /// Vertical position at which an object at `altitude` appears.
+    pub fn row_for_altitude(&self, altitude: f32) -> f32 {
+        self.horizon_y as f32 * (1.0 - altitude / 90.0)
+    }
+
+    /// Centre of the sky, the point views are magnified around.
+    pub fn center(&self) -> (f32, f32) {
+        (self.width as f32 / 2.0, self.horizon_y as f32 / 2.0)
+    }
+
+    /// Pick a random point above the horizon, returning `(x, y, altitude)`.
+    pub fn place<R: Rng>(&self, rng: &mut R) -> Option<(f32, f32, f32)> {
+        self.place_in_view(rng, 1.0)
+    }
+
+    /// Like [`place`](Self::place) but restricted to the central region that
+    /// stays visible when magnified by `zoom`.
+    pub fn place_in_view<R: Rng>(&self, rng: &mut R, zoom: f32) -> Option<(f32, f32, f32)> {
+        if self.width == 0 || self.horizon_y == 0 {
+            return None;
+        }
+        let (cx, cy) = self.center();
+        let (half_w, half_h) = (cx / zoom, cy / zoom);
+        let x = rng.gen_range(cx - half_w..cx + half_w);
+        let y = rng.gen_range(cy - half_h..cy + half_h);
+        Some((x, y, self.altitude_at(y)))
+    }
+}
@@ -0,0 +1,87 @@
+//! Sparks shed by meteors as they burn up: short-lived specks that scatter
+//! from behind the head, slow and fade, so each meteor draws its own
+//! flickering tail.
+
+use crate::simulation::ShootingStar;
+use rand::Rng;
+
+// Sparks a meteor sheds per tick, and a bolide
+const SPARKS_PER_TICK: f32 = 1.5;
+const BOLIDE_SPARKS_PER_TICK: f32 = 4.0;
+// Fraction of its speed a spark keeps each tick
+const DRAG: f32 = 0.85;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spark {
+    pub x: f32,
+    pub y: f32,
+    vx: f32,
+    vy: f32,
+    /// Age and lifespan in ticks
+    pub age: f32,
+    pub max_age: f32,
+    /// The meteor's place in the palette of tints
+    pub tint: f32,
+    /// How far through a bolide's flight it was struck off, for its color
+    pub burn: Option<f32>,
+    /// How brightly the meteor burned as it was struck off
+    pub brightness: f32,
+}
+
+impl Spark {
+    /// How far it has faded, from 0 as it's struck to 1 as it goes out.
+    pub fn fade(&self) -> f32 {
+        (self.age / self.max_age).min(1.0)
+    }
+}
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sparks {
+    sparks: Vec<Spark>,
+}
+
+impl Sparks {
+    pub fn iter(&self) -> impl Iterator<Item = &Spark> {
+        self.sparks.iter()
+    }
+
+    /// Shed sparks along the stretch `meteor` covered over the last `dt`
+    /// ticks, thrown back the way it came and a little to either side.
+    pub fn shed<R: Rng>(&mut self, rng: &mut R, meteor: &ShootingStar, dt: f32) {
+        let rate = if meteor.bolide { BOLIDE_SPARKS_PER_TICK } else { SPARKS_PER_TICK };
+        let count = (rate * dt + rng.gen::<f32>()) as usize;
+        let speed = meteor.speed().max(f32::EPSILON);
+        // Across the meteor's path, a cell of scatter per unit
+        let (across_x, across_y) = (-meteor.vy / speed, meteor.vx / speed);
+        for _ in 0..count {
+            let back = rng.gen_range(0.0..dt.max(f32::EPSILON));
+            let drift = rng.gen_range(0.05..0.25);
+            let scatter = rng.gen_range(-0.15..0.15);
+            self.sparks.push(Spark {
+                x: meteor.x - meteor.vx * back,
+                y: meteor.y - meteor.vy * back,
+                vx: -meteor.vx * drift + across_x * scatter,
+                vy: -meteor.vy * drift + across_y * scatter,
+                age: 0.0,
+                // A third to two thirds of a second at the default tick rate
+                max_age: rng.gen_range(6.0..14.0),
+                tint: meteor.tint,
+                burn: meteor.bolide.then(|| meteor.progress()),
+                brightness: meteor.brightness(),
+            });
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        let drag = DRAG.powf(dt);
+        for spark in &mut self.sparks {
+            spark.x += spark.vx * dt;
+            spark.y += spark.vy * dt;
+            spark.vx *= drag;
+            spark.vy *= drag;
+            spark.age += dt;
+        }
+        self.sparks.retain(|spark| spark.age < spark.max_age);
+    }
+}
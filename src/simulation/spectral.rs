@@ -0,0 +1,61 @@
+//! Spectral classes, the temperatures that give stars their colors: hot
+//! blue-white O and B stars through the Sun's yellow-white G to cool
+//! red-orange M stars.
+
+use crate::simulation::Rgb;
+use rand::Rng;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpectralClass {
+    O,
+    B,
+    A,
+    F,
+    #[default]
+    G,
+    K,
+    M,
+}
+
+// Share of the naked-eye stars in each class, in percent, from the Yale
+// Bright Star Catalogue. Most stars are faint M dwarfs, but luminous hot
+// stars and giants are seen much further off, so they fill the night sky.
+const NAKED_EYE: [(SpectralClass, f32); 7] = [
+    (SpectralClass::O, 0.6),
+    (SpectralClass::B, 18.6),
+    (SpectralClass::A, 21.9),
+    (SpectralClass::F, 13.2),
+    (SpectralClass::G, 13.5),
+    (SpectralClass::K, 26.8),
+    (SpectralClass::M, 5.4),
+];
+
+impl SpectralClass {
+    /// A class for a random star, as common as it is among the stars seen
+    /// by eye.
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        let total: f32 = NAKED_EYE.iter().map(|(_, share)| share).sum();
+        let mut pick = rng.gen_range(0.0..total);
+        for (class, share) in NAKED_EYE {
+            if pick < share {
+                return class;
+            }
+            pick -= share;
+        }
+        SpectralClass::K
+    }
+
+    /// The color of a star of this class at full brightness.
+    pub fn color(self) -> Rgb {
+        match self {
+            SpectralClass::O => (155, 176, 255),
+            SpectralClass::B => (170, 191, 255),
+            SpectralClass::A => (202, 215, 255),
+            SpectralClass::F => (248, 247, 255),
+            SpectralClass::G => (255, 244, 234),
+            SpectralClass::K => (255, 210, 161),
+            SpectralClass::M => (255, 204, 111),
+        }
+    }
+}
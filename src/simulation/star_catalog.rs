@@ -0,0 +1,218 @@
+//! The brightest real stars, from the Yale Bright Star Catalogue, for a sky
+//! that shows what's really overhead.
+//!
+//! Positions are J2000 right ascension and declination, turned into places
+//! in the sky by [`projection`](crate::simulation::projection).
+
+use crate::simulation::projection::Observer;
+use crate::simulation::sky_model::SkyModel;
+use crate::simulation::spectral::SpectralClass::{self, *};
+use crate::simulation::{double, variable};
+use crate::simulation::Star;
+use rand::Rng;
+
+/// Faintest magnitude in the catalog. Random stars fill in below it.
+pub const FAINTEST: f32 = 3.7;
+
+pub struct CatalogStar {
+    pub name: &'static str,
+    /// Right ascension in hours
+    pub ra: f64,
+    /// Declination in degrees
+    pub dec: f64,
+    pub magnitude: f32,
+    pub class: SpectralClass,
+}
+
+pub const CATALOG: &[CatalogStar] = &[
+    CatalogStar { name: "Sirius", ra: 6.752, dec: -16.72, magnitude: -1.46, class: A },
+    CatalogStar { name: "Canopus", ra: 6.399, dec: -52.70, magnitude: -0.74, class: F },
+    CatalogStar { name: "Rigil Kentaurus", ra: 14.660, dec: -60.83, magnitude: -0.27, class: G },
+    CatalogStar { name: "Arcturus", ra: 14.261, dec: 19.18, magnitude: -0.05, class: K },
+    CatalogStar { name: "Vega", ra: 18.616, dec: 38.78, magnitude: 0.03, class: A },
+    CatalogStar { name: "Capella", ra: 5.278, dec: 46.00, magnitude: 0.08, class: G },
+    CatalogStar { name: "Rigel", ra: 5.242, dec: -8.20, magnitude: 0.13, class: B },
+    CatalogStar { name: "Procyon", ra: 7.655, dec: 5.22, magnitude: 0.34, class: F },
+    CatalogStar { name: "Achernar", ra: 1.629, dec: -57.24, magnitude: 0.46, class: B },
+    CatalogStar { name: "Betelgeuse", ra: 5.919, dec: 7.41, magnitude: 0.50, class: M },
+    CatalogStar { name: "Hadar", ra: 14.064, dec: -60.37, magnitude: 0.61, class: B },
+    CatalogStar { name: "Altair", ra: 19.846, dec: 8.87, magnitude: 0.76, class: A },
+    CatalogStar { name: "Acrux", ra: 12.443, dec: -63.10, magnitude: 0.76, class: B },
+    CatalogStar { name: "Aldebaran", ra: 4.599, dec: 16.51, magnitude: 0.86, class: K },
+    CatalogStar { name: "Antares", ra: 16.490, dec: -26.43, magnitude: 0.96, class: M },
+    CatalogStar { name: "Spica", ra: 13.420, dec: -11.16, magnitude: 0.97, class: B },
+    CatalogStar { name: "Pollux", ra: 7.755, dec: 28.03, magnitude: 1.14, class: K },
+    CatalogStar { name: "Fomalhaut", ra: 22.961, dec: -29.62, magnitude: 1.16, class: A },
+    CatalogStar { name: "Deneb", ra: 20.690, dec: 45.28, magnitude: 1.25, class: A },
+    CatalogStar { name: "Mimosa", ra: 12.795, dec: -59.69, magnitude: 1.25, class: B },
+    CatalogStar { name: "Regulus", ra: 10.140, dec: 11.97, magnitude: 1.35, class: B },
+    CatalogStar { name: "Adhara", ra: 6.977, dec: -28.97, magnitude: 1.50, class: B },
+    CatalogStar { name: "Castor", ra: 7.577, dec: 31.89, magnitude: 1.58, class: A },
+    CatalogStar { name: "Shaula", ra: 17.560, dec: -37.10, magnitude: 1.62, class: B },
+    CatalogStar { name: "Gacrux", ra: 12.519, dec: -57.11, magnitude: 1.64, class: M },
+    CatalogStar { name: "Bellatrix", ra: 5.419, dec: 6.35, magnitude: 1.64, class: B },
+    CatalogStar { name: "Elnath", ra: 5.438, dec: 28.61, magnitude: 1.65, class: B },
+    CatalogStar { name: "Miaplacidus", ra: 9.220, dec: -69.72, magnitude: 1.67, class: A },
+    CatalogStar { name: "Alnilam", ra: 5.604, dec: -1.20, magnitude: 1.69, class: B },
+    CatalogStar { name: "Regor", ra: 8.159, dec: -47.34, magnitude: 1.83, class: O },
+    CatalogStar { name: "Alnair", ra: 22.137, dec: -46.96, magnitude: 1.73, class: B },
+    CatalogStar { name: "Alnitak", ra: 5.679, dec: -1.94, magnitude: 1.77, class: O },
+    CatalogStar { name: "Alioth", ra: 12.900, dec: 55.96, magnitude: 1.77, class: A },
+    CatalogStar { name: "Dubhe", ra: 11.062, dec: 61.75, magnitude: 1.79, class: K },
+    CatalogStar { name: "Mirfak", ra: 3.405, dec: 49.86, magnitude: 1.79, class: F },
+    CatalogStar { name: "Wezen", ra: 7.140, dec: -26.39, magnitude: 1.84, class: F },
+    CatalogStar { name: "Kaus Australis", ra: 18.403, dec: -34.38, magnitude: 1.85, class: B },
+    CatalogStar { name: "Sargas", ra: 17.622, dec: -43.00, magnitude: 1.86, class: F },
+    CatalogStar { name: "Avior", ra: 8.375, dec: -59.51, magnitude: 1.86, class: K },
+    CatalogStar { name: "Alkaid", ra: 13.792, dec: 49.31, magnitude: 1.86, class: B },
+    CatalogStar { name: "Menkalinan", ra: 5.992, dec: 44.95, magnitude: 1.90, class: A },
+    CatalogStar { name: "Atria", ra: 16.811, dec: -69.03, magnitude: 1.91, class: K },
+    CatalogStar { name: "Alhena", ra: 6.629, dec: 16.40, magnitude: 1.92, class: A },
+    CatalogStar { name: "Peacock", ra: 20.427, dec: -56.74, magnitude: 1.94, class: B },
+    CatalogStar { name: "Alsephina", ra: 8.745, dec: -54.71, magnitude: 1.96, class: A },
+    CatalogStar { name: "Mirzam", ra: 6.378, dec: -17.96, magnitude: 1.98, class: B },
+    CatalogStar { name: "Alphard", ra: 9.460, dec: -8.66, magnitude: 1.98, class: K },
+    CatalogStar { name: "Polaris", ra: 2.530, dec: 89.26, magnitude: 1.98, class: F },
+    CatalogStar { name: "Hamal", ra: 2.120, dec: 23.46, magnitude: 2.00, class: K },
+    CatalogStar { name: "Algieba", ra: 10.333, dec: 19.84, magnitude: 2.08, class: K },
+    CatalogStar { name: "Diphda", ra: 0.727, dec: -17.99, magnitude: 2.04, class: K },
+    CatalogStar { name: "Nunki", ra: 18.921, dec: -26.30, magnitude: 2.05, class: B },
+    CatalogStar { name: "Menkent", ra: 14.111, dec: -36.37, magnitude: 2.06, class: K },
+    CatalogStar { name: "Mirach", ra: 1.162, dec: 35.62, magnitude: 2.05, class: M },
+    CatalogStar { name: "Alpheratz", ra: 0.140, dec: 29.09, magnitude: 2.06, class: B },
+    CatalogStar { name: "Rasalhague", ra: 17.582, dec: 12.56, magnitude: 2.07, class: A },
+    CatalogStar { name: "Kochab", ra: 14.845, dec: 74.16, magnitude: 2.08, class: K },
+    CatalogStar { name: "Tiaki", ra: 22.711, dec: -46.88, magnitude: 2.07, class: M },
+    CatalogStar { name: "Saiph", ra: 5.796, dec: -9.67, magnitude: 2.09, class: B },
+    CatalogStar { name: "Almach", ra: 2.065, dec: 42.33, magnitude: 2.10, class: K },
+    CatalogStar { name: "Algol", ra: 3.136, dec: 40.96, magnitude: 2.12, class: B },
+    CatalogStar { name: "Denebola", ra: 11.818, dec: 14.57, magnitude: 2.13, class: A },
+    CatalogStar { name: "Muhlifain", ra: 12.692, dec: -48.96, magnitude: 2.17, class: A },
+    CatalogStar { name: "Naos", ra: 8.060, dec: -40.00, magnitude: 2.21, class: O },
+    CatalogStar { name: "Aspidiske", ra: 9.285, dec: -59.28, magnitude: 2.21, class: A },
+    CatalogStar { name: "Suhail", ra: 9.133, dec: -43.43, magnitude: 2.21, class: K },
+    CatalogStar { name: "Alphecca", ra: 15.578, dec: 26.71, magnitude: 2.23, class: A },
+    CatalogStar { name: "Mizar", ra: 13.399, dec: 54.93, magnitude: 2.23, class: A },
+    CatalogStar { name: "Sadr", ra: 20.371, dec: 40.26, magnitude: 2.23, class: F },
+    CatalogStar { name: "Mintaka", ra: 5.533, dec: -0.30, magnitude: 2.23, class: O },
+    CatalogStar { name: "Schedar", ra: 0.675, dec: 56.54, magnitude: 2.24, class: K },
+    CatalogStar { name: "Eltanin", ra: 17.943, dec: 51.49, magnitude: 2.24, class: K },
+    CatalogStar { name: "Caph", ra: 0.153, dec: 59.15, magnitude: 2.28, class: F },
+    CatalogStar { name: "Dschubba", ra: 16.006, dec: -22.62, magnitude: 2.29, class: B },
+    CatalogStar { name: "Larawag", ra: 16.836, dec: -34.29, magnitude: 2.29, class: K },
+    CatalogStar { name: "Epsilon Centauri", ra: 13.665, dec: -53.47, magnitude: 2.30, class: B },
+    CatalogStar { name: "Alpha Lupi", ra: 14.699, dec: -47.39, magnitude: 2.30, class: B },
+    CatalogStar { name: "Eta Centauri", ra: 14.592, dec: -42.16, magnitude: 2.31, class: B },
+    CatalogStar { name: "Merak", ra: 11.031, dec: 56.38, magnitude: 2.37, class: A },
+    CatalogStar { name: "Izar", ra: 14.750, dec: 27.07, magnitude: 2.37, class: K },
+    CatalogStar { name: "Enif", ra: 21.736, dec: 9.88, magnitude: 2.39, class: K },
+    CatalogStar { name: "Girtab", ra: 17.708, dec: -39.03, magnitude: 2.39, class: B },
+    CatalogStar { name: "Ankaa", ra: 0.438, dec: -42.31, magnitude: 2.40, class: K },
+    CatalogStar { name: "Scheat", ra: 23.063, dec: 28.08, magnitude: 2.42, class: M },
+    CatalogStar { name: "Sabik", ra: 17.173, dec: -15.72, magnitude: 2.43, class: A },
+    CatalogStar { name: "Phecda", ra: 11.897, dec: 53.69, magnitude: 2.44, class: A },
+    CatalogStar { name: "Aludra", ra: 7.401, dec: -29.30, magnitude: 2.45, class: B },
+    CatalogStar { name: "Alderamin", ra: 21.310, dec: 62.59, magnitude: 2.45, class: A },
+    CatalogStar { name: "Navi", ra: 0.945, dec: 60.72, magnitude: 2.47, class: B },
+    CatalogStar { name: "Markab", ra: 23.079, dec: 15.21, magnitude: 2.48, class: B },
+    CatalogStar { name: "Aljanah", ra: 20.770, dec: 33.97, magnitude: 2.48, class: K },
+    CatalogStar { name: "Delta Centauri", ra: 12.140, dec: -50.72, magnitude: 2.52, class: B },
+    CatalogStar { name: "Menkar", ra: 3.038, dec: 4.09, magnitude: 2.54, class: M },
+    CatalogStar { name: "Zeta Centauri", ra: 13.926, dec: -47.29, magnitude: 2.55, class: B },
+    CatalogStar { name: "Zosma", ra: 11.235, dec: 20.52, magnitude: 2.56, class: A },
+    CatalogStar { name: "Arneb", ra: 5.546, dec: -17.82, magnitude: 2.58, class: F },
+    CatalogStar { name: "Gienah", ra: 12.263, dec: -17.54, magnitude: 2.59, class: B },
+    CatalogStar { name: "Ascella", ra: 19.044, dec: -29.88, magnitude: 2.60, class: A },
+    CatalogStar { name: "Zubeneschamali", ra: 15.283, dec: -9.38, magnitude: 2.61, class: B },
+    CatalogStar { name: "Acrab", ra: 16.091, dec: -19.81, magnitude: 2.62, class: B },
+    CatalogStar { name: "Unukalhai", ra: 15.738, dec: 6.43, magnitude: 2.63, class: K },
+    CatalogStar { name: "Sheratan", ra: 1.911, dec: 20.81, magnitude: 2.64, class: A },
+    CatalogStar { name: "Kraz", ra: 12.573, dec: -23.40, magnitude: 2.65, class: G },
+    CatalogStar { name: "Phact", ra: 5.661, dec: -34.07, magnitude: 2.65, class: B },
+    CatalogStar { name: "Ruchbah", ra: 1.430, dec: 60.24, magnitude: 2.68, class: A },
+    CatalogStar { name: "Muphrid", ra: 13.911, dec: 18.40, magnitude: 2.68, class: G },
+    CatalogStar { name: "Beta Lupi", ra: 14.976, dec: -43.13, magnitude: 2.68, class: B },
+    CatalogStar { name: "Lesath", ra: 17.512, dec: -37.30, magnitude: 2.70, class: B },
+    CatalogStar { name: "Kaus Media", ra: 18.350, dec: -29.83, magnitude: 2.70, class: K },
+    CatalogStar { name: "Tarazed", ra: 19.771, dec: 10.61, magnitude: 2.72, class: K },
+    CatalogStar { name: "Porrima", ra: 12.694, dec: -1.45, magnitude: 2.74, class: F },
+    CatalogStar { name: "Zubenelgenubi", ra: 14.848, dec: -16.04, magnitude: 2.75, class: A },
+    CatalogStar { name: "Hatysa", ra: 5.590, dec: -5.91, magnitude: 2.77, class: O },
+    CatalogStar { name: "Kornephoros", ra: 16.504, dec: 21.49, magnitude: 2.78, class: G },
+    CatalogStar { name: "Rastaban", ra: 17.507, dec: 52.30, magnitude: 2.79, class: G },
+    CatalogStar { name: "Imai", ra: 12.252, dec: -58.75, magnitude: 2.79, class: B },
+    CatalogStar { name: "Kaus Borealis", ra: 18.466, dec: -25.42, magnitude: 2.81, class: K },
+    CatalogStar { name: "Algenib", ra: 0.221, dec: 15.18, magnitude: 2.83, class: B },
+    CatalogStar { name: "Vindemiatrix", ra: 13.036, dec: 10.96, magnitude: 2.83, class: G },
+    CatalogStar { name: "Beta Arae", ra: 17.422, dec: -55.53, magnitude: 2.85, class: K },
+    CatalogStar { name: "Beta Trianguli Australis", ra: 15.919, dec: -63.43, magnitude: 2.85, class: F },
+    CatalogStar { name: "Deneb Algedi", ra: 21.784, dec: -16.13, magnitude: 2.85, class: A },
+    CatalogStar { name: "Gamma Trianguli Australis", ra: 15.315, dec: -68.68, magnitude: 2.87, class: A },
+    CatalogStar { name: "Alcyone", ra: 3.791, dec: 24.11, magnitude: 2.87, class: B },
+    CatalogStar { name: "Tejat", ra: 6.383, dec: 22.51, magnitude: 2.87, class: M },
+    CatalogStar { name: "Sadalsuud", ra: 21.526, dec: -5.57, magnitude: 2.90, class: G },
+    CatalogStar { name: "Cor Caroli", ra: 12.934, dec: 38.32, magnitude: 2.90, class: A },
+    CatalogStar { name: "Algorab", ra: 12.498, dec: -16.52, magnitude: 2.94, class: B },
+    CatalogStar { name: "Sadalmelik", ra: 22.096, dec: -0.32, magnitude: 2.95, class: G },
+    CatalogStar { name: "Alpha Arae", ra: 17.531, dec: -49.88, magnitude: 2.95, class: B },
+    CatalogStar { name: "Mebsuta", ra: 6.732, dec: 25.13, magnitude: 2.98, class: G },
+    CatalogStar { name: "Alnasl", ra: 18.097, dec: -30.42, magnitude: 2.98, class: K },
+    CatalogStar { name: "Mira", ra: 2.322, dec: -2.98, magnitude: 3.04, class: M },
+    CatalogStar { name: "Seginus", ra: 14.535, dec: 38.31, magnitude: 3.03, class: A },
+    CatalogStar { name: "Albireo", ra: 19.512, dec: 27.96, magnitude: 3.05, class: K },
+    CatalogStar { name: "Pherkad", ra: 15.345, dec: 71.83, magnitude: 3.05, class: A },
+    CatalogStar { name: "Rasalgethi", ra: 17.244, dec: 14.39, magnitude: 3.08, class: M },
+    CatalogStar { name: "Sulafat", ra: 18.982, dec: 32.69, magnitude: 3.26, class: B },
+    CatalogStar { name: "Megrez", ra: 12.257, dec: 57.03, magnitude: 3.31, class: A },
+    CatalogStar { name: "Chertan", ra: 11.237, dec: 15.43, magnitude: 3.33, class: A },
+    CatalogStar { name: "Meissa", ra: 5.585, dec: 9.93, magnitude: 3.33, class: O },
+    CatalogStar { name: "Segin", ra: 1.907, dec: 63.67, magnitude: 3.37, class: B },
+    CatalogStar { name: "Nekkar", ra: 15.032, dec: 40.39, magnitude: 3.49, class: G },
+    CatalogStar { name: "Sheliak", ra: 18.835, dec: 33.36, magnitude: 3.52, class: B },
+    CatalogStar { name: "Wasat", ra: 7.335, dec: 21.98, magnitude: 3.53, class: F },
+    CatalogStar { name: "Thuban", ra: 14.073, dec: 64.38, magnitude: 3.65, class: A },
+    CatalogStar { name: "Alshain", ra: 19.922, dec: 6.41, magnitude: 3.71, class: G },
+];
+
+/// A catalog sky turning overhead.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CatalogSky {
+    pub observer: Observer,
+    /// Julian day at simulation time 0
+    pub start: f64,
+    /// Where the catalog's stars begin among the sky's stars, in catalog
+    /// order
+    pub first: usize,
+}
+
+/// The whole catalog as stars, in catalog order, where they stand at Julian
+/// day `jd`. Those below the horizon are kept beneath the sky, ready to rise.
+pub fn place<R: Rng>(rng: &mut R, sky: &SkyModel, observer: &Observer, jd: f64) -> Vec<Star> {
+    let mut stars: Vec<Star> = CATALOG
+        .iter()
+        .map(|entry| Star {
+            x: 0.0,
+            y: 0.0,
+            altitude: 0.0,
+            magnitude: entry.magnitude,
+            brightness: (6.5 - entry.magnitude).round().clamp(1.0, 5.0) as u8,
+            class: entry.class,
+            twinkle_speed: rng.gen_range(0.1..0.5),
+            cluster: None,
+            presence: 1.0,
+            variability: variable::known(rng, entry.name),
+            companion: double::known(entry.name),
+        })
+        .collect();
+    follow(&mut stars, sky, observer, jd);
+    stars
+}
+
+/// Move the catalog's `stars` to where they stand at Julian day `jd`.
+pub fn follow(stars: &mut [Star], sky: &SkyModel, observer: &Observer, jd: f64) {
+    for (star, entry) in stars.iter_mut().zip(CATALOG) {
+        let (altitude, azimuth) = observer.horizontal(entry.ra, entry.dec, jd);
+        (star.x, star.y) = observer.cell(sky, altitude, azimuth);
+        star.altitude = altitude as f32;
+    }
+}
@@ -0,0 +1,194 @@
+//! What's known about a star, for showing when it's pointed at: the real
+//! bright stars' constellations and distances, and for the rest whatever
+//! the sky knows about them.
+
+use crate::simulation::double::Companion;
+use crate::simulation::spectral::SpectralClass;
+use crate::simulation::variable::Variability;
+
+/// The constellation and distance of a named star of the catalog.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Details {
+    pub constellation: &'static str,
+    /// In light years
+    pub distance: f32,
+}
+
+/// A star, described.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StarInfo {
+    /// A real star's proper name
+    pub name: Option<&'static str>,
+    pub constellation: Option<&'static str>,
+    pub magnitude: f32,
+    pub class: SpectralClass,
+    /// In light years, where known
+    pub distance: Option<f32>,
+    /// How its light changes, for a variable star
+    pub variability: Option<Variability>,
+    /// A double star's partner
+    pub companion: Option<Companion>,
+}
+
+// The catalog's stars by name, with their constellations and distances in
+// light years
+const DETAILS: &[(&str, &str, f32)] = &[
+    ("Sirius", "Canis Major", 8.6),
+    ("Canopus", "Carina", 310.0),
+    ("Rigil Kentaurus", "Centaurus", 4.4),
+    ("Arcturus", "Boötes", 37.0),
+    ("Vega", "Lyra", 25.0),
+    ("Capella", "Auriga", 43.0),
+    ("Rigel", "Orion", 860.0),
+    ("Procyon", "Canis Minor", 11.5),
+    ("Achernar", "Eridanus", 139.0),
+    ("Betelgeuse", "Orion", 548.0),
+    ("Hadar", "Centaurus", 390.0),
+    ("Altair", "Aquila", 16.7),
+    ("Acrux", "Crux", 320.0),
+    ("Aldebaran", "Taurus", 65.0),
+    ("Antares", "Scorpius", 550.0),
+    ("Spica", "Virgo", 250.0),
+    ("Pollux", "Gemini", 34.0),
+    ("Fomalhaut", "Piscis Austrinus", 25.0),
+    ("Deneb", "Cygnus", 2600.0),
+    ("Mimosa", "Crux", 280.0),
+    ("Regulus", "Leo", 79.0),
+    ("Adhara", "Canis Major", 430.0),
+    ("Castor", "Gemini", 51.0),
+    ("Shaula", "Scorpius", 570.0),
+    ("Gacrux", "Crux", 88.0),
+    ("Bellatrix", "Orion", 250.0),
+    ("Elnath", "Taurus", 134.0),
+    ("Miaplacidus", "Carina", 113.0),
+    ("Alnilam", "Orion", 2000.0),
+    ("Regor", "Vela", 1100.0),
+    ("Alnair", "Grus", 101.0),
+    ("Alnitak", "Orion", 1260.0),
+    ("Alioth", "Ursa Major", 83.0),
+    ("Dubhe", "Ursa Major", 123.0),
+    ("Mirfak", "Perseus", 510.0),
+    ("Wezen", "Canis Major", 1600.0),
+    ("Kaus Australis", "Sagittarius", 143.0),
+    ("Sargas", "Scorpius", 300.0),
+    ("Avior", "Carina", 630.0),
+    ("Alkaid", "Ursa Major", 104.0),
+    ("Menkalinan", "Auriga", 81.0),
+    ("Atria", "Triangulum Australe", 390.0),
+    ("Alhena", "Gemini", 109.0),
+    ("Peacock", "Pavo", 180.0),
+    ("Alsephina", "Vela", 80.0),
+    ("Mirzam", "Canis Major", 490.0),
+    ("Alphard", "Hydra", 180.0),
+    ("Polaris", "Ursa Minor", 430.0),
+    ("Hamal", "Aries", 66.0),
+    ("Algieba", "Leo", 130.0),
+    ("Diphda", "Cetus", 96.0),
+    ("Nunki", "Sagittarius", 228.0),
+    ("Menkent", "Centaurus", 59.0),
+    ("Mirach", "Andromeda", 197.0),
+    ("Alpheratz", "Andromeda", 97.0),
+    ("Rasalhague", "Ophiuchus", 49.0),
+    ("Kochab", "Ursa Minor", 131.0),
+    ("Tiaki", "Grus", 170.0),
+    ("Saiph", "Orion", 650.0),
+    ("Almach", "Andromeda", 350.0),
+    ("Algol", "Perseus", 90.0),
+    ("Denebola", "Leo", 36.0),
+    ("Muhlifain", "Centaurus", 130.0),
+    ("Naos", "Puppis", 1080.0),
+    ("Aspidiske", "Carina", 690.0),
+    ("Suhail", "Vela", 545.0),
+    ("Alphecca", "Corona Borealis", 75.0),
+    ("Mizar", "Ursa Major", 83.0),
+    ("Sadr", "Cygnus", 1800.0),
+    ("Mintaka", "Orion", 1200.0),
+    ("Schedar", "Cassiopeia", 228.0),
+    ("Eltanin", "Draco", 154.0),
+    ("Caph", "Cassiopeia", 55.0),
+    ("Dschubba", "Scorpius", 400.0),
+    ("Larawag", "Scorpius", 64.0),
+    ("Epsilon Centauri", "Centaurus", 430.0),
+    ("Alpha Lupi", "Lupus", 460.0),
+    ("Eta Centauri", "Centaurus", 310.0),
+    ("Merak", "Ursa Major", 79.0),
+    ("Izar", "Boötes", 202.0),
+    ("Enif", "Pegasus", 690.0),
+    ("Girtab", "Scorpius", 480.0),
+    ("Ankaa", "Phoenix", 85.0),
+    ("Scheat", "Pegasus", 196.0),
+    ("Sabik", "Ophiuchus", 88.0),
+    ("Phecda", "Ursa Major", 83.0),
+    ("Aludra", "Canis Major", 2000.0),
+    ("Alderamin", "Cepheus", 49.0),
+    ("Navi", "Cassiopeia", 550.0),
+    ("Markab", "Pegasus", 133.0),
+    ("Aljanah", "Cygnus", 72.0),
+    ("Delta Centauri", "Centaurus", 395.0),
+    ("Menkar", "Cetus", 250.0),
+    ("Zeta Centauri", "Centaurus", 380.0),
+    ("Zosma", "Leo", 58.0),
+    ("Arneb", "Lepus", 2200.0),
+    ("Gienah", "Corvus", 154.0),
+    ("Ascella", "Sagittarius", 89.0),
+    ("Zubeneschamali", "Libra", 185.0),
+    ("Acrab", "Scorpius", 400.0),
+    ("Unukalhai", "Serpens", 74.0),
+    ("Sheratan", "Aries", 59.0),
+    ("Kraz", "Corvus", 300.0),
+    ("Phact", "Columba", 260.0),
+    ("Ruchbah", "Cassiopeia", 99.0),
+    ("Muphrid", "Boötes", 37.0),
+    ("Beta Lupi", "Lupus", 383.0),
+    ("Lesath", "Scorpius", 580.0),
+    ("Kaus Media", "Sagittarius", 350.0),
+    ("Tarazed", "Aquila", 460.0),
+    ("Porrima", "Virgo", 38.0),
+    ("Zubenelgenubi", "Libra", 76.0),
+    ("Hatysa", "Orion", 1300.0),
+    ("Kornephoros", "Hercules", 139.0),
+    ("Rastaban", "Draco", 380.0),
+    ("Imai", "Crux", 360.0),
+    ("Kaus Borealis", "Sagittarius", 78.0),
+    ("Algenib", "Pegasus", 390.0),
+    ("Vindemiatrix", "Virgo", 110.0),
+    ("Beta Arae", "Ara", 600.0),
+    ("Beta Trianguli Australis", "Triangulum Australe", 40.0),
+    ("Deneb Algedi", "Capricornus", 39.0),
+    ("Gamma Trianguli Australis", "Triangulum Australe", 180.0),
+    ("Alcyone", "Taurus", 440.0),
+    ("Tejat", "Gemini", 230.0),
+    ("Sadalsuud", "Aquarius", 540.0),
+    ("Cor Caroli", "Canes Venatici", 110.0),
+    ("Algorab", "Corvus", 87.0),
+    ("Sadalmelik", "Aquarius", 520.0),
+    ("Alpha Arae", "Ara", 270.0),
+    ("Mebsuta", "Gemini", 840.0),
+    ("Alnasl", "Sagittarius", 96.0),
+    ("Mira", "Cetus", 300.0),
+    ("Seginus", "Boötes", 85.0),
+    ("Albireo", "Cygnus", 430.0),
+    ("Pherkad", "Ursa Minor", 487.0),
+    ("Rasalgethi", "Hercules", 360.0),
+    ("Sulafat", "Lyra", 620.0),
+    ("Megrez", "Ursa Major", 58.0),
+    ("Chertan", "Leo", 165.0),
+    ("Meissa", "Orion", 1100.0),
+    ("Segin", "Cassiopeia", 410.0),
+    ("Nekkar", "Boötes", 219.0),
+    ("Sheliak", "Lyra", 960.0),
+    ("Wasat", "Gemini", 60.0),
+    ("Thuban", "Draco", 303.0),
+    ("Alshain", "Aquila", 45.0),
+];
+
+/// The constellation and distance of the catalog star called `name`.
+pub fn details(name: &str) -> Option<Details> {
+    DETAILS
+        .iter()
+        .find(|&&(star, _, _)| star == name)
+        .map(|&(_, constellation, distance)| Details {
+            constellation,
+            distance,
+        })
+}
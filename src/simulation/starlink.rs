@@ -0,0 +1,74 @@
+//! Starlink trains: a batch of satellites fresh from launch, still strung
+//! out in a line along their orbit, crossing the sky one after another.
+
+use crate::simulation::satellite_catalog::CatalogEntry;
+use crate::simulation::sky_model::SkyModel;
+use crate::simulation::{Satellite, TICKS_PER_SECOND};
+use rand::Rng;
+
+// Fewest and most satellites in a train
+const MIN_MEMBERS: usize = 10;
+const MAX_MEMBERS: usize = 30;
+// Cells between neighbours along the line
+const SPACING: f32 = 2.0;
+// Seconds from the leader appearing to the last of the line leaving
+const MIN_CROSSING: f32 = 20.0;
+const MAX_CROSSING: f32 = 30.0;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Train {
+    /// The leader first, each following one `SPACING` further back
+    members: Vec<Satellite>,
+    /// Ticks until the last member leaves the sky
+    remaining: f32,
+}
+
+impl Train {
+    /// A train entering from a screen edge like a single pass, slowed so
+    /// the whole line takes 20 to 30 seconds to go over.
+    pub fn new<R: Rng>(rng: &mut R, sky: &SkyModel) -> Self {
+        let mut leader = Satellite::new(rng, sky);
+        leader.entry = CatalogEntry::starlink();
+        leader.magnitude = leader.entry.magnitude;
+        leader.flare_at = None;
+        let count = rng.gen_range(MIN_MEMBERS..=MAX_MEMBERS);
+        let crossing = rng.gen_range(MIN_CROSSING..MAX_CROSSING) * TICKS_PER_SECOND as f32;
+
+        // The leader's pass plus the length of the line behind it
+        let speed = leader.vx.hypot(leader.vy);
+        let (ux, uy) = (leader.vx / speed, leader.vy / speed);
+        let distance = (leader.path_ahead().count() + 1) as f32 * super::PATH_SPACING
+            + (count - 1) as f32 * SPACING;
+        leader.vx = ux * distance / crossing;
+        leader.vy = uy * distance / crossing;
+
+        let members = (0..count)
+            .map(|i| Satellite {
+                x: leader.x - ux * SPACING * i as f32,
+                y: leader.y - uy * SPACING * i as f32,
+                predicted: Vec::new(),
+                ..leader
+            })
+            .collect();
+        Train {
+            members,
+            remaining: crossing,
+        }
+    }
+
+    /// The members now over the `width` x `height` sky, leader first.
+    pub fn visible(&self, width: u16, height: u16) -> impl Iterator<Item = &Satellite> {
+        self.members.iter().filter(move |s| s.is_on_screen(width, height))
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for member in &mut self.members {
+            member.update(dt);
+        }
+        self.remaining -= dt;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.remaining <= 0.0
+    }
+}
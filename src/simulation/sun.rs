@@ -0,0 +1,85 @@
+//! The sun, for a sky that runs through the whole day: a clock, real or
+//! sped up, and where the sun stands by it, which decides how much daylight
+//! or twilight there is.
+
+use crate::simulation::astro;
+use crate::simulation::projection::{self, Observer};
+use crate::simulation::sky_model::SkyModel;
+
+// Degrees the sun is below the horizon once twilight is over
+const NIGHTFALL: f32 = 18.0;
+// Degrees the sun climbs after rising before it's full day
+const FULL_DAY: f32 = 10.0;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sun {
+    pub x: f32,
+    pub y: f32,
+    /// Degrees above the horizon
+    pub altitude: f32,
+}
+
+impl Sun {
+    /// How far through twilight it is, from 0 at night to 1 as the sun
+    /// rises.
+    pub fn twilight(&self) -> f32 {
+        ((self.altitude + NIGHTFALL) / NIGHTFALL).clamp(0.0, 1.0)
+    }
+
+    /// How far into the day it is, from 0 as the sun rises to 1 once it's
+    /// well up.
+    pub fn daylight(&self) -> f32 {
+        (self.altitude / FULL_DAY).clamp(0.0, 1.0)
+    }
+}
+
+/// Keeps the time of day in a sky that runs through it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DayCycle {
+    observer: Observer,
+    /// Julian day at simulation time 0
+    start: f64,
+    /// How many times as fast as the real clock the day goes by
+    speed: f32,
+    sun: Sun,
+}
+
+impl DayCycle {
+    /// A day starting now for `observer`, going `speed` times as fast as
+    /// the real one.
+    pub fn new(sky: &SkyModel, observer: Observer, speed: f32) -> Self {
+        let start = projection::now();
+        DayCycle {
+            observer,
+            start,
+            speed,
+            sun: place(sky, &observer, start),
+        }
+    }
+
+    /// The Julian day by this clock `seconds` into the sky.
+    pub fn jd(&self, seconds: f32) -> f64 {
+        self.start + seconds as f64 * self.speed as f64 / 86_400.0
+    }
+
+    pub fn sun(&self) -> &Sun {
+        &self.sun
+    }
+
+    /// Move the sun to where it stands `seconds` into the sky.
+    pub fn update(&mut self, sky: &SkyModel, seconds: f32) {
+        self.sun = place(sky, &self.observer, self.jd(seconds));
+    }
+}
+
+fn place(sky: &SkyModel, observer: &Observer, jd: f64) -> Sun {
+    let (ra, dec) = astro::sun(jd);
+    let (altitude, azimuth) = observer.horizontal(ra, dec, jd);
+    let (x, y) = observer.cell(sky, altitude, azimuth);
+    Sun {
+        x,
+        y,
+        altitude: altitude as f32,
+    }
+}
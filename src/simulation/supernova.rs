@@ -0,0 +1,71 @@
+//! Supernovae: a star too faint to see flaring within seconds to outshine
+//! everything but the moon, a shell of light racing out from it, then
+//! fading back over several minutes.
+
+use crate::simulation::{Star, TICKS_PER_SECOND};
+
+// Magnitude at its brightest
+const PEAK: f32 = -4.0;
+// How long it takes to flare up, and to fade back
+const RISE_TICKS: f32 = 2.0 * TICKS_PER_SECOND as f32;
+const FADE_TICKS: f32 = 5.0 * 60.0 * TICKS_PER_SECOND as f32;
+// How long the ring of light takes to spread out and vanish
+const RING_TICKS: f32 = 30.0 * TICKS_PER_SECOND as f32;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Supernova {
+    /// Index of the star that's blown up
+    pub star: usize,
+    /// The star's own magnitude and brightness, which it fades back to
+    magnitude: f32,
+    brightness: u8,
+    age: f32,
+}
+
+impl Supernova {
+    /// Blow up star number `index`, which is `star`.
+    pub fn new(index: usize, star: &Star) -> Self {
+        Supernova {
+            star: index,
+            magnitude: star.magnitude,
+            brightness: star.brightness,
+            age: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.age += dt;
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.age >= RISE_TICKS + FADE_TICKS
+    }
+
+    /// The star's magnitude now: up to the peak in a rush, then back down
+    /// evenly in magnitude, so its light dies away quickly at first and
+    /// slower after, as a real supernova's does.
+    pub fn magnitude(&self) -> f32 {
+        if self.age < RISE_TICKS {
+            let t = self.age / RISE_TICKS;
+            self.magnitude + (PEAK - self.magnitude) * t * (2.0 - t)
+        } else {
+            let t = ((self.age - RISE_TICKS) / FADE_TICKS).min(1.0);
+            PEAK + (self.magnitude - PEAK) * t
+        }
+    }
+
+    /// Brightness on the 1 to 5 scale random stars are drawn by, going with
+    /// [`Supernova::magnitude`].
+    pub fn brightness(&self) -> u8 {
+        if self.is_done() {
+            return self.brightness;
+        }
+        ((6.5 - self.magnitude()).round().clamp(1.0, 5.0) as u8).max(self.brightness)
+    }
+
+    /// How far the ring of light has spread, from 0 to 1, while it's
+    /// still showing.
+    pub fn ring(&self) -> Option<f32> {
+        (self.age < RING_TICKS).then(|| self.age / RING_TICKS)
+    }
+}
@@ -0,0 +1,76 @@
+//! Decaying trail layer: persistent smoke trains left behind by bright meteors.
+
+use rand::Rng;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrainPoint {
+    pub x: f32,
+    pub y: f32,
+    /// Per-point drift multiplier so the train shears apart in the wind
+    drift: f32,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmokeTrain {
+    pub points: Vec<TrainPoint>,
+    /// Age and lifespan in ticks
+    pub age: f32,
+    pub max_age: f32,
+    pub wave_phase: f32,
+}
+
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrailLayer {
+    trains: Vec<SmokeTrain>,
+}
+
+impl TrailLayer {
+    pub fn iter(&self) -> impl Iterator<Item = &SmokeTrain> {
+        self.trains.iter()
+    }
+
+    /// Leave a smoke train along `path`, through each point a meteor
+    /// passed in turn.
+    pub fn spawn<R: Rng>(&mut self, rng: &mut R, path: &[(f32, f32)]) {
+        let mut points = Vec::new();
+        for leg in path.windows(2) {
+            let (from, to) = (leg[0], leg[1]);
+            let length = (to.0 - from.0).hypot(to.1 - from.1);
+            let steps = (length.ceil() as usize).max(1);
+            points.extend((0..steps).map(|i| {
+                let t = i as f32 / steps as f32;
+                TrainPoint {
+                    x: from.0 + (to.0 - from.0) * t,
+                    y: from.1 + (to.1 - from.1) * t,
+                    drift: rng.gen_range(0.6..1.4),
+                }
+            }));
+        }
+        if let Some(&(x, y)) = path.last() {
+            points.push(TrainPoint {
+                x,
+                y,
+                drift: rng.gen_range(0.6..1.4),
+            });
+        }
+
+        self.trains.push(SmokeTrain {
+            points,
+            age: 0.0,
+            // 20-30 seconds at the default tick rate
+            max_age: rng.gen_range(20.0..30.0) * crate::simulation::TICKS_PER_SECOND as f32,
+            wave_phase: rng.gen_range(0.0..std::f32::consts::TAU),
+        });
+    }
+
+    pub fn update(&mut self, wind: f32, dt: f32) {
+        for train in &mut self.trains {
+            train.age += dt;
+            for point in &mut train.points {
+                point.x += wind * point.drift * dt;
+            }
+        }
+        self.trains.retain(|t| t.age < t.max_age);
+    }
+}
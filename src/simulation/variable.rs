@@ -0,0 +1,96 @@
+//! Variable stars, whose light changes on a cycle of its own on top of the
+//! atmosphere's twinkle: eclipsing binaries like Algol that hold steady and
+//! then dip sharply as a companion passes in front, and pulsating giants
+//! like Mira that swell and fade slowly. Their real cycles run to days or
+//! months, sped up here to minutes so a night's watching catches them.
+
+use rand::Rng;
+use std::f32::consts::PI;
+
+// Share of an eclipsing star's cycle spent in eclipse
+const ECLIPSE: f32 = 0.1;
+// Share of a pulsating star's cycle spent brightening; it fades for longer
+const RISE: f32 = 0.4;
+// Share of random stars that vary
+const VARIABLE_CHANCE: f64 = 0.02;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Variation {
+    /// Steady but for a short, deep dip each cycle, like Algol
+    Eclipsing,
+    /// Brightening quickly and fading slowly, like Mira
+    Pulsating,
+}
+
+impl Variation {
+    pub fn name(self) -> &'static str {
+        match self {
+            Variation::Eclipsing => "eclipsing binary",
+            Variation::Pulsating => "pulsating",
+        }
+    }
+}
+
+/// How a variable star's light changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variability {
+    pub variation: Variation,
+    /// Seconds per cycle
+    pub period: f32,
+    /// Magnitudes between brightest and faintest
+    pub amplitude: f32,
+    /// Where in its cycle the star is at time 0, from 0 to 1
+    pub phase: f32,
+}
+
+impl Variability {
+    /// How many magnitudes fainter than its brightest the star is
+    /// `seconds` into the sky.
+    pub fn dimming(&self, seconds: f32) -> f32 {
+        let t = (seconds / self.period.max(f32::EPSILON) + self.phase).rem_euclid(1.0);
+        let depth = match self.variation {
+            Variation::Eclipsing if t < ECLIPSE => (PI * t / ECLIPSE).sin().powi(2),
+            Variation::Eclipsing => 0.0,
+            Variation::Pulsating if t < RISE => (1.0 + (PI * t / RISE).cos()) / 2.0,
+            Variation::Pulsating => (1.0 - (PI * (t - RISE) / (1.0 - RISE)).cos()) / 2.0,
+        };
+        self.amplitude * depth
+    }
+}
+
+/// The famous variables among the catalog's stars, by name, at a random
+/// point in their cycles.
+pub fn known<R: Rng>(rng: &mut R, name: &str) -> Option<Variability> {
+    let (variation, minutes, amplitude) = match name {
+        "Algol" => (Variation::Eclipsing, 3.0, 1.3),
+        "Mira" => (Variation::Pulsating, 10.0, 4.0),
+        "Betelgeuse" => (Variation::Pulsating, 15.0, 0.6),
+        _ => return None,
+    };
+    Some(Variability {
+        variation,
+        period: minutes * 60.0,
+        amplitude,
+        phase: rng.gen_range(0.0..1.0),
+    })
+}
+
+/// Now and then, a cycle for a random star to vary by.
+pub fn random<R: Rng>(rng: &mut R) -> Option<Variability> {
+    if !rng.gen_bool(VARIABLE_CHANCE) {
+        return None;
+    }
+    let (variation, minutes, amplitude) = if rng.gen_bool(0.5) {
+        (Variation::Eclipsing, rng.gen_range(2.0..6.0), rng.gen_range(0.5..1.5))
+    } else {
+        (Variation::Pulsating, rng.gen_range(5.0..15.0), rng.gen_range(1.0..3.0))
+    };
+    Some(Variability {
+        variation,
+        period: minutes * 60.0,
+        amplitude,
+        phase: rng.gen_range(0.0..1.0),
+    })
+}
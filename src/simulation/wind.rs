@@ -0,0 +1,40 @@
+//! A slowly wandering horizontal breeze shared by drifting effects.
+
+use rand::Rng;
+
+// Strongest gust in cells per tick
+const MAX_SPEED: f32 = 0.08;
+// Real wind, in km/h, that blows as hard as the strongest gust
+const STRONG_KMH: f32 = 40.0;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Wind {
+    /// Current horizontal velocity in cells per tick (positive = rightwards)
+    pub velocity: f32,
+    target: f32,
+}
+
+impl Wind {
+    pub fn new<R: Rng>(rng: &mut R) -> Self {
+        let velocity = rng.gen_range(-MAX_SPEED..MAX_SPEED);
+        Wind {
+            velocity,
+            target: velocity,
+        }
+    }
+
+    pub fn update<R: Rng>(&mut self, rng: &mut R, dt: f32) {
+        // Occasionally pick a new gust to ease towards
+        if rng.gen::<f32>() < dt / 400.0 {
+            self.target = rng.gen_range(-MAX_SPEED..MAX_SPEED);
+        }
+        self.velocity += (self.target - self.velocity) * (0.01 * dt).min(1.0);
+    }
+
+    /// Ease towards a real wind of `kmh`, positive rightwards, instead of
+    /// wandering. A gale blows harder than any made-up gust.
+    pub fn follow(&mut self, kmh: f32, dt: f32) {
+        self.target = (kmh / STRONG_KMH).clamp(-2.0, 2.0) * MAX_SPEED;
+        self.velocity += (self.target - self.velocity) * (0.01 * dt).min(1.0);
+    }
+}
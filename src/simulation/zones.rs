@@ -0,0 +1,53 @@
+//! Zones of the screen kept clear of moving things, so an overlay like a
+//! clock or a neighbouring tmux pane's border stays legible over the sky.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A rectangle of screen cells, written `X,Y,WxH`. Negative positions count
+/// back from the right and bottom edges, so `-20,0,20x3` hugs the top right
+/// corner of a sky of any size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Zone {
+    pub x: i32,
+    pub y: i32,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Zone {
+    /// Whether the point `(x, y)` of a `width` x `height` screen lies in the
+    /// zone.
+    pub fn contains(&self, width: u16, height: u16, x: f32, y: f32) -> bool {
+        let from = |at: i32, size: u16| if at < 0 { size as i32 + at } else { at } as f32;
+        let (left, top) = (from(self.x, width), from(self.y, height));
+        (left..left + self.width as f32).contains(&x) && (top..top + self.height as f32).contains(&y)
+    }
+}
+
+impl FromStr for Zone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected a zone like '0,0,20x3', got '{}'", s);
+        let mut parts = s.split(',').map(str::trim);
+        let (Some(x), Some(y), Some(size), None) = (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        let (width, height) = size.split_once('x').ok_or_else(invalid)?;
+        Ok(Zone {
+            x: x.parse().map_err(|_| invalid())?,
+            y: y.parse().map_err(|_| invalid())?,
+            width: width.parse().map_err(|_| invalid())?,
+            height: height.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}x{}", self.x, self.y, self.width, self.height)
+    }
+}
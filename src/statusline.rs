@@ -0,0 +1,92 @@
+//! Compact one-line sky summary for embedding in tmux/i3 status bars.
+
+use crate::cli::StatuslineOptions;
+#[cfg(feature = "astronomy")]
+use crate::simulation::{
+    astro,
+    iss::{Iss, Tle},
+    passes::NextPass,
+    projection::{self, Observer},
+};
+use crate::simulation::astro::MoonPhase;
+#[cfg(feature = "astronomy")]
+use crate::tle;
+#[cfg(feature = "weather")]
+use crate::weather::{self, Conditions};
+#[cfg(feature = "astronomy")]
+use chrono::{DateTime, Local};
+use std::{
+    io::{self, Write},
+    thread,
+};
+#[cfg(any(feature = "weather", feature = "astronomy"))]
+use std::time::{Duration, Instant};
+
+// Weather changes slowly; don't hammer the service on every tick
+#[cfg(feature = "weather")]
+const WEATHER_REFRESH: Duration = Duration::from_secs(15 * 60);
+// The ISS's elements are cached for a day; look again now and then so a
+// long-running status line picks up fresh ones
+#[cfg(feature = "astronomy")]
+const ISS_REFRESH: Duration = Duration::from_secs(6 * 60 * 60);
+
+pub fn run(opts: &StatuslineOptions) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    #[cfg(feature = "weather")]
+    let mut weather: Option<Conditions> = None;
+    #[cfg(feature = "weather")]
+    let mut last_fetch: Option<Instant> = None;
+    #[cfg(feature = "astronomy")]
+    let mut orbit: Option<Tle> = None;
+    #[cfg(feature = "astronomy")]
+    let mut last_load: Option<Instant> = None;
+
+    loop {
+        #[cfg(feature = "astronomy")]
+        if opts.iss && last_load.map_or(true, |t| t.elapsed() >= ISS_REFRESH) {
+            orbit = tle::load().or(orbit);
+            last_load = Some(Instant::now());
+        }
+
+        #[cfg(feature = "weather")]
+        if opts.weather && last_fetch.map_or(true, |t| t.elapsed() >= WEATHER_REFRESH) {
+            let report = weather::fetch(opts.location.as_deref());
+            weather = report.map(|r| r.conditions).or(weather);
+            last_fetch = Some(Instant::now());
+        }
+
+        let summary = moon_summary(MoonPhase::now());
+        #[cfg(feature = "astronomy")]
+        let summary = match orbit.as_ref().and_then(|tle| iss_summary(tle, opts)) {
+            Some(iss) => format!("{} │ {}", summary, iss),
+            None => summary,
+        };
+        #[cfg(feature = "weather")]
+        let summary = match weather {
+            Some(conditions) => format!("{} │ {}", summary, conditions.icon()),
+            None => summary,
+        };
+        writeln!(stdout, "{}", summary)?;
+        stdout.flush()?;
+
+        if opts.once {
+            return Ok(());
+        }
+        thread::sleep(opts.interval);
+    }
+}
+
+fn moon_summary(moon: MoonPhase) -> String {
+    format!("{} {:.0}%", moon.glyph(), moon.illumination() * 100.0)
+}
+
+/// When the ISS on the orbit `tle` next comes over, like `ISS 21:40`, or
+/// `None` if SGP4 can't carry the orbit.
+#[cfg(feature = "astronomy")]
+fn iss_summary(tle: &Tle, opts: &StatuslineOptions) -> Option<String> {
+    let iss = Iss::new(tle, Observer::new(opts.latitude, opts.longitude), projection::now())?;
+    Some(match iss.next_pass(0.0) {
+        NextPass::At(jd) => format!("ISS {}", DateTime::<Local>::from(astro::time_of(jd)).format("%H:%M")),
+        NextPass::NoneSoon | NextPass::Unknown => "ISS –".to_string(),
+    })
+}
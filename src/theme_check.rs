@@ -0,0 +1,23 @@
+//! `nk check-theme`: warn about object colors that collapse together under
+//! color-vision deficiencies.
+
+use crate::render::cvd;
+use crate::render::theme::Theme;
+
+/// Print a report for each theme; returns whether all of them passed.
+pub fn run(themes: &[Theme]) -> bool {
+    let mut all_ok = true;
+    for theme in themes {
+        let warnings = cvd::check(theme);
+        if warnings.is_empty() {
+            println!("{}: ok", theme.name);
+        } else {
+            all_ok = false;
+            println!("{}:", theme.name);
+            for warning in warnings {
+                println!("  {}", warning);
+            }
+        }
+    }
+    all_ok
+}
@@ -0,0 +1,129 @@
+//! Orbital elements fetched from CelesTrak through the system `curl` and
+//! cached for a day, since they're only updated a few times a day and the
+//! satellites drift off them slowly: the ISS's, and the bright satellites'
+//! that `--passes` predicts from.
+
+use nightsky_tui::simulation::iss::Tle;
+use nightsky_tui::simulation::passes::{self, Pass};
+use nightsky_tui::simulation::projection::Observer;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+const URL: &str = "https://celestrak.org/NORAD/elements/gp.php?CATNR=25544&FORMAT=tle";
+// The hundred or so satellites bright enough to see with the naked eye
+const VISUAL_URL: &str = "https://celestrak.org/NORAD/elements/gp.php?GROUP=visual&FORMAT=tle";
+// How long a cached element set is used before fetching a fresh one
+const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+// The most read from a download, many times what the visual list takes
+const MAX_BYTES: u64 = 1024 * 1024;
+/// Days of passes predicted at a time.
+pub const PASS_DAYS: f64 = 1.0;
+
+/// `$XDG_CACHE_HOME/nk/<file>`, falling back to `~/.cache/nk/<file>`.
+fn cache_path(file: &str) -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(base.join("nk").join(file))
+}
+
+/// What's at `url`, unless it's bigger than [`MAX_BYTES`].
+fn download(url: &str) -> Option<String> {
+    let mut child = Command::new("curl")
+        .args(["-sf", "-m", "10", "--max-filesize", &MAX_BYTES.to_string(), url])
+        .stdout(Stdio::piped())
+        .spawn()
+        .ok()?;
+    // Still held to the limit when the server doesn't say how big it is
+    let mut bytes = Vec::new();
+    let read = child.stdout.take()?.take(MAX_BYTES + 1).read_to_end(&mut bytes);
+    let _ = child.kill();
+    let finished = child.wait().ok()?.success();
+    if read.is_err() || !finished || bytes.len() as u64 > MAX_BYTES {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// What's at `url`, read as a `T`: the copy cached in `file` while it's
+/// fresh, otherwise newly downloaded, falling back to a stale copy when
+/// offline. Blocks for up to ten seconds.
+fn fetch<T: FromStr>(url: &str, file: &str) -> Option<T> {
+    let path = cache_path(file);
+    let cached = path.as_ref().and_then(|path| {
+        let age = fs::metadata(path).ok()?.modified().ok()?.elapsed().unwrap_or_default();
+        Some((fs::read_to_string(path).ok()?, age))
+    });
+    if let Some((text, age)) = &cached {
+        if *age < MAX_AGE {
+            if let Ok(found) = text.parse() {
+                return Some(found);
+            }
+        }
+    }
+    if let Some(text) = download(url) {
+        if let Ok(found) = text.parse() {
+            if let Some(path) = &path {
+                if let Some(dir) = path.parent() {
+                    let _ = fs::create_dir_all(dir);
+                }
+                let _ = fs::write(path, &text);
+            }
+            return Some(found);
+        }
+    }
+    cached.and_then(|(text, _)| text.parse().ok())
+}
+
+/// The ISS's elements, as [`fetch`] finds them.
+pub fn load() -> Option<Tle> {
+    fetch(URL, "iss.tle")
+}
+
+/// [`load`] on a background thread, so the sky never waits for it.
+pub fn load_in_background() -> Receiver<Option<Tle>> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = tx.send(load());
+    });
+    rx
+}
+
+/// The bright satellites' elements, one list read whole.
+struct Visual(Vec<(String, Tle)>);
+
+impl FromStr for Visual {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let elements = passes::elements(s);
+        if elements.is_empty() {
+            return Err(());
+        }
+        Ok(Visual(elements))
+    }
+}
+
+/// The bright satellites' visible passes for `observer` over the day from
+/// Julian day `from`, looked up and worked out on a background thread. The
+/// ISS is left out when it's `skip_iss`, being followed on its own.
+pub fn predict_in_background(observer: Observer, from: f64, skip_iss: bool) -> Receiver<Option<Vec<Pass>>> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let passes = fetch(VISUAL_URL, "visual.tle").map(|Visual(mut elements)| {
+            if skip_iss {
+                elements.retain(|(name, _)| !name.starts_with("ISS"));
+            }
+            passes::predict(&elements, &observer, from, PASS_DAYS)
+        });
+        let _ = tx.send(passes);
+    });
+    rx
+}
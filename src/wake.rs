@@ -0,0 +1,85 @@
+//! `nk wake`: a sunrise alarm clock. The sky stays dark and barely redraws
+//! overnight, then dawns over the half hour before the alarm.
+
+use crate::popup::Popup;
+use chrono::{DateTime, Duration as Span, Local, NaiveTime};
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// How long dawn takes, ending at the alarm.
+const DAWN: Span = Span::minutes(30);
+
+/// Frames drawn per second before dawn starts, to save power overnight.
+pub const OVERNIGHT_FPS: f32 = 1.0;
+
+/// Meteors and satellites come this many times more often by the alarm,
+/// on top of the usual rate, so the sky livens up as it dawns.
+pub const DAWN_ACTIVITY: f32 = 3.0;
+
+/// How often the bell rings again until the alarm is dismissed.
+pub const RING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Parse an alarm time such as `06:45`.
+pub fn parse_time(raw: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(raw, "%H:%M")
+        .map_err(|_| format!("invalid alarm time '{}', expected e.g. 06:45", raw))
+}
+
+pub struct Alarm {
+    at: DateTime<Local>,
+}
+
+impl Alarm {
+    /// An alarm at the next `time` of day.
+    pub fn new(time: NaiveTime) -> Self {
+        let now = Local::now();
+        let mut day = now.date_naive();
+        if time <= now.time() {
+            day = day.succ_opt().unwrap_or(day);
+        }
+        let at = day
+            .and_time(time)
+            .and_local_timezone(Local)
+            .earliest()
+            // Skipped by a clock change; go off an hour later instead
+            .unwrap_or_else(|| now + Span::hours(1));
+        Alarm { at }
+    }
+
+    /// How far dawn has come: 0 overnight, rising to 1 at the alarm.
+    pub fn dawn(&self) -> f32 {
+        let left = self.at - Local::now();
+        (1.0 - left.num_seconds() as f32 / DAWN.num_seconds() as f32).clamp(0.0, 1.0)
+    }
+
+    pub fn is_due(&self) -> bool {
+        Local::now() >= self.at
+    }
+
+    pub fn time(&self) -> String {
+        self.at.format("%H:%M").to_string()
+    }
+
+    /// The message left up once the alarm goes off, in the middle of a sky
+    /// `width` by `height`.
+    pub fn greeting(&self, width: u16, height: u16) -> Popup {
+        let lines = vec![
+            format!("it's {}", self.time()),
+            "press any key to dismiss".to_string(),
+        ];
+        Popup {
+            title: "Good morning".to_string(),
+            anchor: ((width / 2).saturating_sub(15), height / 3),
+            lines,
+            ticks_left: f32::INFINITY,
+        }
+    }
+}
+
+/// Sound the alarm. There's no audio here, so this rings the terminal bell
+/// where birdsong would play.
+pub fn ring() -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(b"\x07")?;
+    stdout.flush()
+}
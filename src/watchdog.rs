@@ -0,0 +1,63 @@
+//! Watching how long frames take to draw. A terminal that blocks, like one
+//! over a congested SSH link, can hold up a draw for seconds; rather than
+//! freeze and then rush through the backlog, the sky draws less often and
+//! less changes from frame to frame until the terminal keeps up again.
+
+use std::time::Duration;
+
+// A draw slower than this many frame intervals, and this long at least, is
+// a stall
+const STALL_FRAMES: u32 = 3;
+const MIN_STALL: Duration = Duration::from_millis(100);
+// The most times the usual interval frames are spread out to
+const MAX_BACKOFF: u32 = 8;
+// Quick draws in a row before frames come closer together again
+const RECOVERY_FRAMES: u32 = 20;
+
+pub struct Watchdog {
+    frame_interval: Duration,
+    /// How many times the usual interval frames are currently spaced
+    backoff: u32,
+    /// Quick draws since the last slow one
+    quick: u32,
+}
+
+impl Watchdog {
+    /// A watchdog for frames usually `frame_interval` apart.
+    pub fn new(frame_interval: Duration) -> Self {
+        Watchdog {
+            frame_interval,
+            backoff: 1,
+            quick: 0,
+        }
+    }
+
+    /// Note that a frame took `took` to draw. A draw slower than the frame
+    /// interval drops the frame rate, and quick ones restore it. After a
+    /// stall, returns how far the draw overran, for the simulation to skip
+    /// instead of catching up.
+    pub fn record(&mut self, took: Duration) -> Option<Duration> {
+        if took > self.frame_interval {
+            self.backoff = (self.backoff * 2).min(MAX_BACKOFF);
+            self.quick = 0;
+        } else if took < self.frame_interval / 4 && self.backoff > 1 {
+            self.quick += 1;
+            if self.quick == RECOVERY_FRAMES {
+                self.backoff /= 2;
+                self.quick = 0;
+            }
+        }
+        let stall = (self.frame_interval * STALL_FRAMES).max(MIN_STALL);
+        (took > stall).then(|| took - self.frame_interval)
+    }
+
+    /// How long to wait before the next frame.
+    pub fn interval(&self) -> Duration {
+        self.frame_interval * self.backoff
+    }
+
+    /// Whether frames are being dropped to let the terminal keep up.
+    pub fn is_struggling(&self) -> bool {
+        self.backoff > 1
+    }
+}
@@ -0,0 +1,122 @@
+//! Current weather conditions, fetched from wttr.in through the system `curl`.
+
+use std::process::Command;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Conditions {
+    Clear,
+    PartlyCloudy,
+    Cloudy,
+    Fog,
+    Rain,
+    Snow,
+    Storm,
+}
+
+impl Conditions {
+    /// Classify a free-form description such as "Light rain shower".
+    pub fn from_description(text: &str) -> Self {
+        let text = text.to_lowercase();
+        if text.contains("thunder") || text.contains("storm") {
+            Conditions::Storm
+        } else if text.contains("snow") || text.contains("sleet") || text.contains("blizzard") {
+            Conditions::Snow
+        } else if text.contains("rain") || text.contains("drizzle") || text.contains("shower") {
+            Conditions::Rain
+        } else if text.contains("fog") || text.contains("mist") || text.contains("haze") {
+            Conditions::Fog
+        } else if text.contains("partly") {
+            Conditions::PartlyCloudy
+        } else if text.contains("cloud") || text.contains("overcast") {
+            Conditions::Cloudy
+        } else {
+            Conditions::Clear
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Conditions::Clear => "✨",
+            Conditions::PartlyCloudy => "⛅",
+            Conditions::Cloudy => "☁",
+            Conditions::Fog => "🌫",
+            Conditions::Rain => "🌧",
+            Conditions::Snow => "❄",
+            Conditions::Storm => "⛈",
+        }
+    }
+}
+
+/// The weather right now.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Report {
+    pub conditions: Conditions,
+    /// Rain or snow, in millimetres
+    pub precipitation: f32,
+    /// Wind speed in km/h
+    pub wind: f32,
+    /// Which way the wind blows, degrees clockwise from north
+    pub wind_heading: f32,
+}
+
+impl Report {
+    /// Parse wttr.in's `%C|%p|%w`, like `Light rain|0.4mm|↙11km/h`.
+    /// Anything past the conditions that doesn't parse is taken as calm.
+    fn parse(text: &str) -> Report {
+        let mut fields = text.split('|').map(str::trim);
+        let conditions = Conditions::from_description(fields.next().unwrap_or_default());
+        let precipitation = fields
+            .next()
+            .and_then(|p| p.trim_end_matches("mm").parse().ok())
+            .unwrap_or(0.0);
+        let wind = fields.next().unwrap_or_default();
+        // wttr.in draws the wind as an arrow pointing the way it blows
+        let arrows = ["↑", "↗", "→", "↘", "↓", "↙", "←", "↖"];
+        let heading = arrows.iter().position(|a| wind.starts_with(a));
+        let speed = wind
+            .trim_start_matches(|c: char| !c.is_ascii_digit())
+            .trim_end_matches(|c: char| !c.is_ascii_digit())
+            .parse()
+            .unwrap_or(0.0);
+        Report {
+            conditions,
+            precipitation,
+            wind: if heading.is_some() { speed } else { 0.0 },
+            wind_heading: heading.map_or(0.0, |i| i as f32 * 45.0),
+        }
+    }
+}
+
+/// `text` as one segment of a URL's path: everything but the unreserved
+/// characters percent-encoded, byte by byte, so spaces, accents and any
+/// `?`, `#` or `/` stay part of the place's name.
+fn encode_segment(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Fetch the weather for `location` (or the IP-derived location when
+/// `None`). Returns `None` when offline or when `curl` is unavailable.
+pub fn fetch(location: Option<&str>) -> Option<Report> {
+    let url = format!("https://wttr.in/{}?format=%C|%p|%w", encode_segment(location.unwrap_or("")));
+    let output = Command::new("curl")
+        .args(["-sf", "-m", "5", &url])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(Report::parse(text))
+}
@@ -0,0 +1,186 @@
+//! The ephemeris math checked against almanac values for known dates and
+//! places, so the realism features can be trusted.
+
+use chrono::NaiveDate;
+use nightsky_tui::simulation::astro::{self, MoonPhase, Twilight};
+use std::time::{Duration, UNIX_EPOCH};
+
+// Greenwich and London, degrees north and east
+const GREENWICH: (f64, f64) = (51.4769, 0.0);
+const LONDON: (f64, f64) = (51.5072, -0.1276);
+
+/// Julian day of a UTC date and time.
+fn jd(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> f64 {
+    let time = NaiveDate::from_ymd_opt(year, month, day)
+        .and_then(|date| date.and_hms_opt(hour, minute, 0))
+        .unwrap()
+        .and_utc();
+    astro::julian_day(UNIX_EPOCH + Duration::from_secs(time.timestamp() as u64))
+}
+
+fn assert_near(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "expected {} within {}, got {}",
+        expected,
+        tolerance,
+        actual
+    );
+}
+
+#[test]
+fn julian_day_of_j2000() {
+    assert_near(jd(2000, 1, 1, 12, 0), 2451545.0, 1e-9);
+    assert_near(jd(1970, 1, 1, 0, 0), 2440587.5, 1e-9);
+}
+
+#[test]
+fn moon_is_new_at_known_new_moons() {
+    // The mean lunation drifts up to about 14 hours from the true moon, a
+    // fiftieth of the cycle
+    for when in [jd(2024, 1, 11, 11, 57), jd(1999, 8, 11, 11, 8), jd(2000, 1, 6, 18, 14)] {
+        let phase = MoonPhase::at(when);
+        let from_new = phase.fraction().min(1.0 - phase.fraction());
+        assert_near(from_new, 0.0, 0.03);
+        assert_eq!(phase.name(), "new moon");
+        assert!(phase.illumination() < 0.02);
+    }
+}
+
+#[test]
+fn moon_is_full_at_known_full_moons() {
+    for when in [jd(2024, 1, 25, 17, 54), jd(2015, 9, 28, 2, 50), jd(2025, 10, 7, 3, 47)] {
+        let phase = MoonPhase::at(when);
+        assert_near(phase.fraction(), 0.5, 0.03);
+        assert_eq!(phase.name(), "full moon");
+        assert!(phase.illumination() > 0.98);
+    }
+}
+
+#[test]
+fn moon_quarters() {
+    assert_eq!(MoonPhase::at(jd(2024, 1, 18, 3, 53)).name(), "first quarter");
+    assert_eq!(MoonPhase::at(jd(2024, 2, 2, 23, 18)).name(), "last quarter");
+}
+
+#[test]
+fn sun_at_the_equinoxes_and_solstices() {
+    // March equinox: on the equator at 0h right ascension
+    let (ra, dec) = astro::sun(jd(2024, 3, 20, 3, 6));
+    assert_near(ra.min(24.0 - ra), 0.0, 0.01);
+    assert_near(dec, 0.0, 0.02);
+    // June solstice: furthest north, at 6h
+    let (ra, dec) = astro::sun(jd(2024, 6, 20, 20, 51));
+    assert_near(ra, 6.0, 0.01);
+    assert_near(dec, 23.44, 0.02);
+    // September equinox, at 12h
+    let (ra, dec) = astro::sun(jd(2024, 9, 22, 12, 44));
+    assert_near(ra, 12.0, 0.01);
+    assert_near(dec, 0.0, 0.02);
+    // December solstice: furthest south, at 18h
+    let (ra, dec) = astro::sun(jd(2024, 12, 21, 9, 20));
+    assert_near(ra, 18.0, 0.01);
+    assert_near(dec, -23.44, 0.02);
+}
+
+#[test]
+fn sun_altitude_at_noon_and_midnight() {
+    let (lat, lon) = GREENWICH;
+    // Midsummer noon: 90° less the latitude plus the sun's declination
+    assert_near(astro::sun_altitude(jd(2024, 6, 20, 12, 2), lat, lon), 61.96, 0.1);
+    // Midsummer midnight: as far below the horizon as it ever gets
+    assert_near(astro::sun_altitude(jd(2024, 6, 21, 0, 2), lat, lon), -15.09, 0.1);
+    // Overhead on the equator at the March equinox
+    assert_near(astro::sun_altitude(jd(2024, 3, 20, 12, 7), 0.0, 0.0), 90.0, 0.3);
+}
+
+#[test]
+fn sunrise_in_london_at_midsummer() {
+    // The almanac has the sun rising at 03:43 UTC on 20 June 2024
+    let (lat, lon) = LONDON;
+    let before = astro::sun_altitude(jd(2024, 6, 20, 3, 40), lat, lon);
+    let after = astro::sun_altitude(jd(2024, 6, 20, 3, 46), lat, lon);
+    assert_eq!(Twilight::at(before), Twilight::Civil);
+    assert_eq!(Twilight::at(after), Twilight::Day);
+}
+
+#[test]
+fn twilight_stages() {
+    assert_eq!(Twilight::at(10.0), Twilight::Day);
+    assert_eq!(Twilight::at(-3.0), Twilight::Civil);
+    assert_eq!(Twilight::at(-9.0), Twilight::Nautical);
+    assert_eq!(Twilight::at(-15.0), Twilight::Astronomical);
+    assert_eq!(Twilight::at(-30.0), Twilight::Night);
+    // London never gets properly dark around midsummer
+    let (lat, lon) = LONDON;
+    let midnight = astro::sun_altitude(jd(2024, 6, 21, 0, 0), lat, lon);
+    assert_eq!(Twilight::at(midnight), Twilight::Astronomical);
+    // but does by midwinter
+    let midnight = astro::sun_altitude(jd(2024, 12, 21, 0, 0), lat, lon);
+    assert_eq!(Twilight::at(midnight), Twilight::Night);
+}
+
+#[cfg(feature = "astronomy")]
+#[test]
+fn observer_agrees_with_sun_altitude() {
+    use nightsky_tui::simulation::projection::Observer;
+
+    let (lat, lon) = GREENWICH;
+    let observer = Observer::new(Some(lat as f32), Some(lon as f32));
+    for when in [jd(2024, 6, 20, 12, 2), jd(2024, 12, 21, 15, 30), jd(2025, 3, 1, 6, 0)] {
+        let (ra, dec) = astro::sun(when);
+        let (altitude, _) = observer.horizontal(ra, dec, when);
+        assert_near(altitude, astro::sun_altitude(when, lat, lon), 1e-3);
+    }
+}
+
+#[cfg(feature = "astronomy")]
+#[test]
+fn sgp4_matches_the_reference_orbit() {
+    use nightsky_tui::simulation::iss::{Sgp4, Tle};
+
+    // Vanguard 1, the first of the published SGP4 verification cases
+    let tle: Tle = "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753\n\
+                    2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667"
+        .parse()
+        .unwrap();
+    let orbit = Sgp4::new(&tle).unwrap();
+    for (minutes, expected) in [
+        (0.0, [7022.46529266, -1400.08296755, 0.03995155]),
+        (360.0, [-7154.03120202, -3783.17682504, -3536.19412294]),
+        (720.0, [-7134.59340119, 6531.68641334, 3260.27186483]),
+    ] {
+        let position = orbit.position(tle.epoch + minutes / 1440.0).unwrap();
+        for (actual, expected) in position.into_iter().zip(expected) {
+            assert_near(actual, expected, 0.01);
+        }
+    }
+}
+
+#[cfg(feature = "astronomy")]
+#[test]
+fn satellite_names_are_kept_printable_and_short() {
+    use nightsky_tui::simulation::passes;
+
+    let text = "VANGUARD \x1b]0;owned\x07 1\r\n\
+                1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753\n\
+                2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667\n\
+                A SATELLITE WITH A NAME FAR TOO LONG TO LABEL\n\
+                1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753\n\
+                2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667\n";
+    let names: Vec<String> = passes::elements(text).into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["VANGUARD ]0;owned 1", "A SATELLITE WITH A NAME"]);
+}
+
+#[test]
+fn eclipse_on_a_known_eclipse_night() {
+    use nightsky_tui::simulation::eclipse::Eclipse;
+
+    // Greatest eclipse of 14 March 2025 was at 06:58 UTC, mid-totality
+    let eclipse = Eclipse::at(jd(2025, 3, 14, 6, 58)).unwrap();
+    assert_eq!(eclipse.coverage(), 1.0);
+    assert_near(eclipse.depth() as f64, 1.0, 0.01);
+    assert_near(eclipse.shadow() as f64, 0.0, 0.05);
+    assert!(Eclipse::at(jd(2025, 3, 14, 2, 0)).is_none());
+    assert!(Eclipse::at(jd(2025, 6, 11, 7, 0)).is_none());
+}
@@ -0,0 +1,40 @@
+//! Which sky events count as rare, and so light the border with
+//! `--event-glow`, and that every event's name reads back as itself.
+
+use nightsky_tui::simulation::events::SkyEvent;
+
+const ALL: [SkyEvent; 12] = [
+    SkyEvent::Fireball,
+    SkyEvent::Bolide,
+    SkyEvent::SatellitePass,
+    SkyEvent::IssPass,
+    SkyEvent::PredictedPass,
+    SkyEvent::Moonbow,
+    SkyEvent::MoonHalo,
+    SkyEvent::Aurora,
+    SkyEvent::Comet,
+    SkyEvent::Conjunction,
+    SkyEvent::StarlinkTrain,
+    SkyEvent::Supernova,
+];
+
+#[test]
+fn supernovae_iss_passes_and_comets_are_rare() {
+    for event in [SkyEvent::Supernova, SkyEvent::IssPass, SkyEvent::Comet] {
+        assert!(event.is_rare(), "{} should be rare", event.name());
+    }
+}
+
+#[test]
+fn everyday_events_are_not_rare() {
+    for event in [SkyEvent::Fireball, SkyEvent::SatellitePass] {
+        assert!(!event.is_rare(), "{} shouldn't be rare", event.name());
+    }
+}
+
+#[test]
+fn names_parse_back() {
+    for event in ALL {
+        assert_eq!(event.name().parse::<SkyEvent>(), Ok(event));
+    }
+}
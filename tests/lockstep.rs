@@ -0,0 +1,50 @@
+//! Two skies built from the same seed and options, as a shared sky's host
+//! and guests build theirs, stay identical step for step.
+
+use nightsky_tui::simulation::director::Pacing;
+use nightsky_tui::simulation::{Simulation, SimulationOptions, TICKS_PER_SECOND};
+
+// Long enough for showers, auroras and the rarer spawns to come round
+const MINUTES: u32 = 20;
+
+/// Where everything moving in `sim` stands.
+fn positions(sim: &Simulation) -> Vec<(f32, f32)> {
+    let meteors = sim.shooting_stars().iter().map(|meteor| (meteor.x, meteor.y));
+    let satellites = sim.satellites().iter().map(|satellite| (satellite.x, satellite.y));
+    let stars = sim.stars().iter().map(|star| (star.x, star.y));
+    meteors.chain(satellites).chain(stars).collect()
+}
+
+#[test]
+fn skies_from_the_same_seed_stay_in_step() {
+    let options = SimulationOptions {
+        pacing: Pacing::Cinematic,
+        meteor_rate: 5.0,
+        satellite_rate: 5.0,
+        leaves: true,
+        showers: true,
+        moon: true,
+        clouds: true,
+        aurora: 1.0,
+        ..SimulationOptions::default()
+    };
+    let mut host = Simulation::new(100, 30, options.clone(), 7);
+    let mut guest = Simulation::new(100, 30, options, 7);
+
+    let mut events = 0;
+    for tick in 0..MINUTES * 60 * TICKS_PER_SECOND {
+        // A click launches a meteor for everyone at once
+        if tick % 500 == 0 {
+            host.launch_meteor(40.0, 5.0);
+            guest.launch_meteor(40.0, 5.0);
+        }
+        let seen = host.update(1.0);
+        assert_eq!(seen, guest.update(1.0), "events differ at tick {}", tick);
+        events += seen.len();
+        if tick % 100 == 0 {
+            assert_eq!(positions(&host), positions(&guest), "skies differ at tick {}", tick);
+        }
+    }
+    assert!(events > 0, "nothing happened to compare");
+    assert_eq!(positions(&host), positions(&guest));
+}
@@ -0,0 +1,152 @@
+//! Satellite passes in a seeded sky: where they come in, how fast and
+//! bright they are for their altitude, and when two make a conjunction.
+
+use nightsky_tui::simulation::events::SkyEvent;
+use nightsky_tui::simulation::{Satellite, Simulation, SimulationOptions, TICKS_PER_SECOND};
+
+// The altitude a pass's speed is reckoned from, the ISS's
+const PASS_ALTITUDE_KM: f32 = 420.0;
+// How close, in columns, two passes come to make a conjunction
+const NEAR_MISS_CELLS: f32 = 1.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// A pass as it was on the step before: its velocity, which it keeps, and
+/// whether it was blinking from a near miss.
+struct Seen {
+    vx: f32,
+    vy: f32,
+    near_miss: bool,
+}
+
+impl Seen {
+    fn is(&self, satellite: &Satellite) -> bool {
+        self.vx == satellite.vx && self.vy == satellite.vy
+    }
+}
+
+/// A busy sky of satellites, stepped one tick at a time for `minutes`,
+/// with `check` shown each step's satellites before and after it and the
+/// events it brought.
+fn fly(minutes: u32, mut check: impl FnMut(&Simulation, &[Seen], &[SkyEvent])) {
+    let options = SimulationOptions {
+        satellite_rate: 10.0,
+        meteor_rate: 0.0,
+        ..SimulationOptions::default()
+    };
+    let mut sim = Simulation::new(120, 40, options, 11);
+    let mut before = Vec::new();
+    for _ in 0..minutes * 60 * TICKS_PER_SECOND {
+        let events = sim.update(1.0);
+        check(&sim, &before, &events);
+        before = sim
+            .satellites()
+            .iter()
+            .map(|satellite| Seen {
+                vx: satellite.vx,
+                vy: satellite.vy,
+                near_miss: satellite.is_near_miss(),
+            })
+            .collect();
+    }
+}
+
+/// Columns between two passes, with rows `aspect` columns tall.
+fn distance(a: &Satellite, b: &Satellite, aspect: f32) -> f32 {
+    (a.x - b.x).hypot((a.y - b.y) * aspect)
+}
+
+#[test]
+fn passes_enter_from_every_edge() {
+    let mut edges = Vec::new();
+    fly(30, |sim, before, _| {
+        let (width, height) = (sim.sky_model().width as f32, sim.sky_model().horizon_y as f32);
+        for satellite in sim.satellites().iter().filter(|now| !before.iter().any(|then| then.is(now))) {
+            // Ticks since it crossed each edge it's moving away from
+            let (x, y, vx, vy) = (satellite.x, satellite.y, satellite.vx, satellite.vy);
+            let crossed = [
+                (Edge::Left, x / vx),
+                (Edge::Right, (x - width) / vx),
+                (Edge::Top, y / vy),
+                (Edge::Bottom, (y - height) / vy),
+            ];
+            let (edge, ticks) = crossed
+                .into_iter()
+                .filter(|(_, ticks)| *ticks >= 0.0)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap();
+            // It starts just inside that edge and has made one step since
+            assert!(ticks <= 1.02, "{:?} pass first seen {} ticks in", edge, ticks);
+            edges.push(edge);
+        }
+    });
+    for edge in [Edge::Left, Edge::Right, Edge::Top, Edge::Bottom] {
+        assert!(edges.contains(&edge), "no pass entered from the {:?}", edge);
+    }
+}
+
+#[test]
+fn lower_passes_cross_faster_and_brightness_varies_about_the_best() {
+    // Each pass's name and speed in columns per tick
+    let mut passes: Vec<(&str, f32)> = Vec::new();
+    fly(30, |sim, before, _| {
+        let aspect = sim.sky_model().aspect;
+        for satellite in sim.satellites().iter().filter(|now| !before.iter().any(|then| then.is(now))) {
+            let entry = satellite.entry;
+            let speed = satellite.vx.hypot(satellite.vy * aspect);
+            // Scaled back to the ISS's altitude, it's within the usual range
+            let scaled = speed * (entry.altitude_km / PASS_ALTITUDE_KM).sqrt();
+            assert!((0.3 - 1e-4..0.8 + 1e-4).contains(&scaled), "{} at {} columns per tick", entry.name, speed);
+            let dimmer = satellite.magnitude - entry.magnitude;
+            assert!((-0.5..1.5).contains(&dimmer), "{} at magnitude {}", entry.name, satellite.magnitude);
+            passes.push((entry.name, speed));
+        }
+    });
+
+    let mean_speed = |name: &str| {
+        let speeds: Vec<f32> = passes.iter().filter(|pass| pass.0 == name).map(|pass| pass.1).collect();
+        assert!(speeds.len() >= 5, "too few {} passes", name);
+        speeds.iter().sum::<f32>() / speeds.len() as f32
+    };
+    assert!(mean_speed("ISS (ZARYA)") > mean_speed("NOAA 19"));
+}
+
+#[test]
+fn passes_within_a_column_and_a_half_make_a_conjunction() {
+    let mut conjunctions = 0;
+    fly(60, |sim, before, events| {
+        let aspect = sim.sky_model().aspect;
+        let after = sim.satellites();
+        let newly = |satellite: &&Satellite| {
+            satellite.is_near_miss() && !before.iter().any(|then| then.is(satellite) && then.near_miss)
+        };
+        let flagged: Vec<&Satellite> = after.iter().filter(newly).collect();
+        // Each pass flagged came within the threshold of another during the
+        // step, so is no further than that plus how far they closed since
+        for a in &flagged {
+            let near = flagged.iter().any(|b| {
+                let closing = (a.vx - b.vx).hypot((a.vy - b.vy) * aspect);
+                !std::ptr::eq(*a, *b) && distance(a, b, aspect) <= NEAR_MISS_CELLS + closing + 1e-3
+            });
+            assert!(near, "flagged with no pass close by");
+        }
+        // And passes left unflagged never came that close
+        let clear: Vec<&Satellite> = after.iter().filter(|satellite| !satellite.is_near_miss()).collect();
+        for (i, a) in clear.iter().enumerate() {
+            for b in &clear[i + 1..] {
+                let apart = distance(a, b, aspect);
+                assert!(apart > NEAR_MISS_CELLS, "not flagged {} columns apart", apart);
+            }
+        }
+        let announced = events.iter().filter(|event| **event == SkyEvent::Conjunction).count();
+        assert_eq!(announced * 2, flagged.len());
+        conjunctions += announced;
+    });
+    assert!(conjunctions > 0, "no conjunction to check");
+}
@@ -0,0 +1,13 @@
+//! Every built-in theme passes `nk check-theme`: its objects stay told apart
+//! under each color-vision deficiency.
+
+use nightsky_tui::render::cvd;
+use nightsky_tui::render::theme::THEMES;
+
+#[test]
+fn every_theme_passes_the_color_vision_check() {
+    for theme in THEMES {
+        let warnings = cvd::check(theme);
+        assert!(warnings.is_empty(), "{}: {}", theme.name, warnings.join("; "));
+    }
+}